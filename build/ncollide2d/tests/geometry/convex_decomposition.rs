@@ -0,0 +1,168 @@
+use na::{Isometry2, Point2};
+use ncollide2d::query::PointQuery;
+use ncollide2d::shape::{ConvexPolygon, Polyline};
+use ncollide2d::transformation;
+
+fn area(poly: &ConvexPolygon<f64>) -> f64 {
+    let pts = poly.points();
+    let mut res = 0.0;
+
+    for i in 0..pts.len() {
+        res += pts[i].coords.perp(&pts[(i + 1) % pts.len()].coords);
+    }
+
+    res.abs() / 2.0
+}
+
+fn num_covering(pieces: &[ConvexPolygon<f64>], pt: &Point2<f64>) -> usize {
+    pieces
+        .iter()
+        .filter(|p| p.contains_point(&Isometry2::identity(), pt))
+        .count()
+}
+
+fn check_partition(
+    pieces: &[ConvexPolygon<f64>],
+    expected_area: f64,
+    is_inside: impl Fn(&Point2<f64>) -> bool,
+) {
+    let total: f64 = pieces.iter().map(area).sum();
+    assert_relative_eq!(total, expected_area, epsilon = 1.0e-7);
+
+    // Sample points away from the edges of the inputs and of the pieces.
+    for i in 0..80 {
+        for j in 0..80 {
+            let pt = Point2::new(-0.4537 + i as f64 * 0.1, -0.4419 + j as f64 * 0.1);
+            let expected = if is_inside(&pt) { 1 } else { 0 };
+            assert_eq!(num_covering(pieces, &pt), expected, "{:?}", pt);
+        }
+    }
+}
+
+#[test]
+fn convex_decomposition_of_concave_polygons() {
+    // A clockwise L shape.
+    let outline = [
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 2.0),
+        Point2::new(1.0, 2.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(2.0, 0.0),
+    ];
+    let pieces = transformation::convex_decomposition(&outline, &[], 0.0);
+    assert_eq!(pieces.len(), 2);
+    check_partition(&pieces, 3.0, |pt| {
+        pt.x > 0.0 && pt.y > 0.0 && (pt.x < 1.0 && pt.y < 2.0 || pt.x < 2.0 && pt.y < 1.0)
+    });
+
+    // A comb.
+    let mut outline = vec![Point2::new(0.0, 0.0), Point2::new(7.0, 0.0)];
+
+    for i in 0..4 {
+        let x = 7.0 - i as f64 * 2.0;
+        outline.push(Point2::new(x, 3.0));
+        outline.push(Point2::new(x - 1.0, 3.0));
+        outline.push(Point2::new(x - 1.0, 1.0));
+        outline.push(Point2::new(x - 2.0, 1.0));
+    }
+
+    let _ = outline.pop();
+    let _ = outline.pop();
+    let pieces = transformation::convex_decomposition(&outline, &[], 0.0);
+    assert!(pieces.len() >= 5 && pieces.len() <= 8);
+    check_partition(&pieces, 15.0, |pt| {
+        pt.x > 0.0 && pt.x < 7.0 && pt.y > 0.0 && (pt.y < 1.0 || pt.y < 3.0 && pt.x % 2.0 < 1.0)
+    });
+}
+
+#[test]
+fn convex_decomposition_with_holes() {
+    let outline = [
+        Point2::new(0.0, 0.0),
+        Point2::new(4.0, 0.0),
+        Point2::new(4.0, 4.0),
+        Point2::new(0.0, 4.0),
+    ];
+    let hole1 = [
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(1.0, 2.0),
+    ];
+    let hole2 = [
+        Point2::new(3.5, 2.5),
+        Point2::new(2.5, 2.5),
+        Point2::new(2.5, 3.0),
+        Point2::new(3.5, 3.0),
+    ];
+    let pieces = transformation::convex_decomposition(&outline, &[&hole1, &hole2], 0.0);
+
+    check_partition(&pieces, 14.5, |pt| {
+        let in_outline = pt.x > 0.0 && pt.x < 4.0 && pt.y > 0.0 && pt.y < 4.0;
+        let in_hole1 = pt.x > 1.0 && pt.x < 2.0 && pt.y > 1.0 && pt.y < 2.0;
+        let in_hole2 = pt.x > 2.5 && pt.x < 3.5 && pt.y > 2.5 && pt.y < 3.0;
+        in_outline && !in_hole1 && !in_hole2
+    });
+}
+
+#[test]
+fn polyline_convex_decomposition_with_tolerance() {
+    // A square with a square hole, and a square with a small notch on its top edge.
+    let points = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(3.0, 0.0),
+        Point2::new(3.0, 3.0),
+        Point2::new(0.0, 3.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(1.0, 2.0),
+        Point2::new(4.0, 0.0),
+        Point2::new(7.0, 0.0),
+        Point2::new(7.0, 3.0),
+        Point2::new(5.5, 2.95),
+        Point2::new(4.0, 3.0),
+    ];
+    let indices = vec![
+        Point2::new(0, 1),
+        Point2::new(1, 2),
+        Point2::new(2, 3),
+        Point2::new(3, 0),
+        Point2::new(4, 5),
+        Point2::new(5, 6),
+        Point2::new(6, 7),
+        Point2::new(7, 4),
+        Point2::new(8, 9),
+        Point2::new(9, 10),
+        Point2::new(10, 11),
+        Point2::new(11, 12),
+    ];
+    let polyline = Polyline::new(points, Some(indices));
+    let in_holed_square = |pt: &Point2<f64>| {
+        let in_outline = pt.x > 0.0 && pt.x < 3.0 && pt.y > 0.0 && pt.y < 3.0;
+        let in_hole = pt.x > 1.0 && pt.x < 2.0 && pt.y > 1.0 && pt.y < 2.0;
+        in_outline && !in_hole
+    };
+    let in_notched_square = |pt: &Point2<f64>| {
+        let top = 3.0 - 0.05 * (1.0 - (pt.x - 5.5).abs() / 1.5);
+        pt.x > 4.0 && pt.x < 7.0 && pt.y > 0.0 && pt.y < top
+    };
+
+    let exact = transformation::polyline_convex_decomposition(&polyline, 0.0);
+    let notched_area = 9.0 - 3.0 * 0.05 / 2.0;
+    check_partition(&exact, 8.0 + notched_area, |pt| {
+        in_holed_square(pt) || in_notched_square(pt)
+    });
+    assert!(exact.iter().filter(|p| p.points()[0].x >= 4.0).count() > 1);
+
+    // The notch is small enough to be ignored.
+    let approx = transformation::polyline_convex_decomposition(&polyline, 0.1);
+    assert_eq!(approx.iter().filter(|p| p.points()[0].x >= 4.0).count(), 1);
+    assert_eq!(
+        num_covering(&approx, &Point2::new(5.5, 2.97)),
+        1,
+        "The notch is covered by the convex hull."
+    );
+    assert_eq!(num_covering(&approx, &Point2::new(1.5, 1.5)), 0);
+}
@@ -0,0 +1,36 @@
+use na::{self, Isometry2, Point2, Vector2};
+use ncollide2d::query::{PointQuery, Ray, RayCast};
+use ncollide2d::shape::Ellipsoid;
+use ncollide2d::transformation::ToPolyline;
+
+#[test]
+fn ellipse_queries() {
+    let e = Ellipsoid::new(Vector2::new(2.0f64, 1.0));
+    let m = Isometry2::new(Vector2::new(1.0, 2.0), 0.3);
+
+    let ray = Ray::new(m * Point2::new(0.0, 5.0), m * -Vector2::y());
+    let inter = e.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 4.0, epsilon = 1.0e-7);
+    assert_relative_eq!(inter.normal, m * Vector2::y(), epsilon = 1.0e-7);
+
+    // Deep inside, the projection leaves the major axis, on either side of it.
+    let proj = e.project_point(&m, &(m * Point2::new(0.5, 0.0)), false);
+    let ls_proj = m.inverse_transform_point(&proj.point);
+    assert!(proj.is_inside);
+    assert_relative_eq!(ls_proj.x, 4.0 * 0.5 / 3.0, epsilon = 1.0e-7);
+    assert_relative_eq!(ls_proj.y.abs(), (8.0f64 / 9.0).sqrt(), epsilon = 1.0e-7);
+
+    let proj = e.project_point(&m, &(m * Point2::new(4.0, 0.0)), true);
+    assert!(!proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point2::new(2.0, 0.0), epsilon = 1.0e-7);
+}
+
+#[test]
+fn ellipse_to_polyline() {
+    let e = Ellipsoid::new(Vector2::new(2.0, 1.0));
+
+    for pt in e.to_polyline(32).coords() {
+        let ls = pt.coords.component_div(e.radii());
+        assert_relative_eq!(ls.norm(), 1.0, epsilon = 1.0e-7);
+    }
+}
@@ -0,0 +1,52 @@
+use super::feature_based_contacts;
+use na::{self, Isometry2, Point2, Vector2};
+use ncollide2d::shape::{Ball, Cuboid, Polyline};
+
+#[test]
+fn polyline_contacts_without_internal_vertices() {
+    let points = (0..9)
+        .map(|i| Point2::new(i as f64 * 0.5 - 2.0, 0.0))
+        .collect();
+    let floor = Polyline::new(points, None);
+    let id = Isometry2::identity();
+    let ball = Ball::new(0.5);
+    let cuboid = Cuboid::new(Vector2::new(0.4, 0.3));
+
+    for x in &[0.02, 0.48, -0.97] {
+        for flip in &[false, true] {
+            let m2 = Isometry2::new(Vector2::new(*x, 0.4), na::zero());
+            let manifold = if *flip {
+                feature_based_contacts(&m2, &ball, &id, &floor)
+            } else {
+                feature_based_contacts(&id, &floor, &m2, &ball)
+            };
+
+            assert!(manifold.len() > 0);
+
+            for tracked in manifold.contacts() {
+                let c = &tracked.contact;
+                let normal = if *flip { -*c.normal } else { *c.normal };
+                assert_relative_eq!(normal, Vector2::y(), epsilon = 1.0e-7);
+                assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-7);
+            }
+        }
+
+        let m2 = Isometry2::new(Vector2::new(*x, 0.25), 0.2);
+        let manifold = feature_based_contacts(&id, &floor, &m2, &cuboid);
+        assert!(manifold.len() > 0);
+
+        for tracked in manifold.contacts() {
+            assert_relative_eq!(*tracked.contact.normal, Vector2::y(), epsilon = 1.0e-7);
+        }
+    }
+
+    // The actual ends of the polyline are kept.
+    let m2 = Isometry2::new(Vector2::new(2.3, 0.3), na::zero());
+    let manifold = feature_based_contacts(&id, &floor, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(
+        *c.normal,
+        Vector2::new(0.3, 0.3).normalize(),
+        epsilon = 1.0e-7
+    );
+}
@@ -0,0 +1,42 @@
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::bounding_volume::{self, KDOP};
+use ncollide2d::query::{PointQuery, Ray, RayCast};
+use ncollide2d::shape::ConvexPolygon;
+
+#[test]
+fn kdop_of_diamond_is_exact() {
+    let points = [
+        Point2::new(1.0, 0.0),
+        Point2::new(0.0, 1.0),
+        Point2::new(-1.0, 0.0),
+        Point2::new(0.0, -1.0),
+    ];
+    let diamond = ConvexPolygon::try_from_points(&points).unwrap();
+    let kdop: KDOP<f64> = bounding_volume::local_kdop(&diamond);
+    let id = Isometry2::identity();
+
+    for i in 0..100 {
+        let t = i as f64 * 0.17;
+        let origin = Point2::new(t.cos(), t.sin()) * 3.0;
+        let pt = Point2::new((t * 3.0).cos(), (t * 5.0).sin()) * 1.5;
+
+        let ray = Ray::new(
+            origin,
+            Vector2::new((t * 2.0).cos(), (t * 0.5).sin()) - origin.coords,
+        );
+        let expected = diamond.toi_and_normal_with_ray(&id, &ray, true);
+        let inter = kdop.toi_and_normal_with_ray(&id, &ray, true);
+        assert_eq!(inter.is_some(), expected.is_some());
+
+        if let (Some(inter), Some(expected)) = (inter, expected) {
+            assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-7);
+            assert_relative_eq!(inter.normal, expected.normal, epsilon = 1.0e-7);
+        }
+
+        assert_relative_eq!(
+            kdop.distance_to_point(&id, &pt, true),
+            diamond.distance_to_point(&id, &pt, true),
+            epsilon = 1.0e-7
+        );
+    }
+}
@@ -1,5 +1,48 @@
+use na::RealField;
+use ncollide2d::math::Isometry;
+use ncollide2d::pipeline::{ContactDispatcher, DefaultContactDispatcher};
+use ncollide2d::query::{ContactManifold, ContactPrediction, ContactTrackingMode};
+use ncollide2d::shape::Shape;
+
 mod ball_ball_toi;
 mod compound_penetration;
+mod convex_decomposition;
+mod ellipse;
 mod epa2;
+mod internal_edges;
+mod kdop;
+mod obb;
 mod ray_cast;
+mod scaled;
+mod sdf_shape;
 mod time_of_impact2;
+
+/// The contacts between `g1` and `g2` computed by the contact generator of the default
+/// dispatcher, without any prediction, and with feature-based contact tracking so that deeper
+/// contacts don't hide the others.
+pub fn feature_based_contacts<N: RealField>(
+    m1: &Isometry<N>,
+    g1: &dyn Shape<N>,
+    m2: &Isometry<N>,
+    g2: &dyn Shape<N>,
+) -> ContactManifold<N> {
+    let dispatcher = DefaultContactDispatcher::new();
+    let mut generator = dispatcher.get_contact_algorithm(g1, g2).unwrap();
+    let mut manifold = ContactManifold::new();
+    manifold.set_tracking_mode(ContactTrackingMode::FeatureBased);
+    let prediction = ContactPrediction::new(N::zero(), N::zero(), N::zero());
+
+    manifold.save_cache_and_clear();
+    let _ = generator.generate_contacts(
+        &dispatcher,
+        m1,
+        g1,
+        None,
+        m2,
+        g2,
+        None,
+        &prediction,
+        &mut manifold,
+    );
+    manifold
+}
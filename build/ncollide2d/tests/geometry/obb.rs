@@ -0,0 +1,57 @@
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::bounding_volume::{BoundingVolume, OBB};
+use ncollide2d::query;
+use ncollide2d::shape::{ConvexPolygon, Cuboid, Shape};
+
+#[test]
+fn obb_of_rotated_rectangle() {
+    let m = Isometry2::new(Vector2::new(1.0, -2.0), 0.6);
+    let obb = OBB::new(m, Vector2::new(3.0, 0.5));
+    let mut points = obb.vertices();
+
+    // Many points close to one side, which would attract the principal axes.
+    points.extend((0..100).map(|i| m * Point2::new(2.9, i as f64 / 100.0 - 0.5)));
+
+    let fitted = OBB::from_convex_hull(&points);
+    assert_relative_eq!(
+        fitted.half_extents().x * fitted.half_extents().y,
+        1.5,
+        epsilon = 1.0e-7
+    );
+    assert_relative_eq!(fitted.center(), obb.center(), epsilon = 1.0e-7);
+
+    let polygon = ConvexPolygon::try_from_points(&points).unwrap();
+    let polygon_obb = polygon.obb(&Isometry2::identity());
+    assert_relative_eq!(
+        polygon_obb.half_extents().x * polygon_obb.half_extents().y,
+        1.5,
+        epsilon = 1.0e-7
+    );
+    let aabb_extents = polygon.aabb(&Isometry2::identity()).extents();
+    assert!(aabb_extents.x * aabb_extents.y > 6.0 * 3.0);
+}
+
+#[test]
+fn obb_intersection_matches_distance() {
+    let c1 = Cuboid::new(Vector2::new(1.0, 0.2));
+    let c2 = Cuboid::new(Vector2::new(0.1, 1.5));
+
+    for i in 0..500 {
+        let t = i as f64;
+        let m1 = Isometry2::new(Vector2::new((t * 0.1).sin(), (t * 0.2).cos()), t * 0.37);
+        let m2 = Isometry2::new(
+            Vector2::new((t * 0.7).cos(), (t * 0.5).sin()) * 2.0,
+            t * 0.53,
+        );
+        let distance = query::distance(&m1, &c1, &m2, &c2);
+
+        if distance.abs() > 1.0e-4 {
+            assert_eq!(
+                c1.obb(&m1).intersects(&c2.obb(&m2)),
+                distance <= 0.0,
+                "{}",
+                i
+            );
+        }
+    }
+}
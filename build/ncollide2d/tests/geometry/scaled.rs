@@ -0,0 +1,25 @@
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::query::{PointQuery, Ray, RayCast};
+use ncollide2d::shape::{Cuboid, Polyline, Scaled, Shape, ShapeHandle};
+
+#[test]
+fn scaled_polyline_and_cuboid() {
+    let points = vec![Point2::new(-1.0f64, 0.0), Point2::new(1.0, 0.0)];
+    let polyline = ShapeHandle::new(Polyline::new(points, None));
+    let scaled = Scaled::new(polyline, Vector2::new(4.0, 1.0));
+    let id = Isometry2::identity();
+
+    assert!(scaled.is_composite_shape());
+    let ray = Ray::new(Point2::new(3.5, 2.0), -Vector2::y());
+    assert_relative_eq!(scaled.toi_with_ray(&id, &ray, true).unwrap(), 2.0);
+
+    let m = Isometry2::new(Vector2::new(1.0, 2.0), 0.3);
+    let cuboid = Scaled::new(Cuboid::new(Vector2::new(1.0, 1.0)), Vector2::new(2.0, 0.5));
+    let proj = cuboid.project_point(&m, &(m * Point2::new(1.5, 3.0)), true);
+    assert_relative_eq!(proj.point, m * Point2::new(1.5, 0.5), epsilon = 1.0e-7);
+
+    let ray = Ray::new(m * Point2::new(5.0, 0.2), m * -Vector2::x());
+    let inter = cuboid.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 3.0, epsilon = 1.0e-7);
+    assert_relative_eq!(inter.normal, m * Vector2::x(), epsilon = 1.0e-7);
+}
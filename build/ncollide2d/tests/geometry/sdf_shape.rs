@@ -0,0 +1,69 @@
+use na::{self, Isometry2, Point2, Vector2};
+use ncollide2d::bounding_volume::AABB;
+use ncollide2d::pipeline::{ContactDispatcher, DefaultContactDispatcher};
+use ncollide2d::query::{ContactManifold, ContactPrediction, PointQuery, Ray, RayCast};
+use ncollide2d::shape::{Ball, Cuboid, SDFShape};
+
+#[test]
+fn sdf_shape_2d_queries() {
+    let aabb = AABB::new(Point2::new(-2.0, -2.0), Point2::new(2.0, 2.0));
+    let sdf = SDFShape::from_fn(aabb, Vector2::repeat(81), |pt| pt.coords.norm() - 1.0);
+    let m = Isometry2::new(Vector2::new(1.0, 2.0), 0.3);
+
+    let pt = Point2::new(0.33, -0.71);
+    assert_relative_eq!(
+        sdf.distance_at(&pt),
+        pt.coords.norm() - 1.0,
+        epsilon = 1.0e-2
+    );
+
+    let pt = Point2::new(0.6, 1.6);
+    let proj = sdf.project_point(&m, &(m * pt), true);
+    assert!(!proj.is_inside);
+    assert_relative_eq!(
+        proj.point,
+        m * Point2::from(pt.coords.normalize()),
+        epsilon = 1.0e-2
+    );
+
+    let ray = Ray::new(m * Point2::new(-3.0, 0.3), m * Vector2::x());
+    let inter = sdf.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+    let hit = Point2::new(-(1.0f64 - 0.09).sqrt(), 0.3);
+    assert_relative_eq!(inter.toi, hit.x + 3.0, epsilon = 1.0e-2);
+    assert_relative_eq!(inter.normal, m * hit.coords, epsilon = 1.0e-2);
+}
+
+#[test]
+fn sdf_shape_2d_contacts() {
+    let aabb = AABB::new(Point2::new(-2.0, -2.0), Point2::new(2.0, 2.0));
+    let sdf = SDFShape::from_point_query(&Ball::new(1.0), aabb, Vector2::repeat(81));
+    let cuboid = Cuboid::new(Vector2::new(0.5, 0.25));
+    let m1 = Isometry2::identity();
+    let m2 = Isometry2::new(Vector2::new(0.0, 1.0), na::zero());
+
+    let dispatcher = DefaultContactDispatcher::new();
+    let mut generator = dispatcher.get_contact_algorithm(&sdf, &cuboid).unwrap();
+    let mut manifold = ContactManifold::new();
+    let prediction = ContactPrediction::new(0.0, 0.0, 0.0);
+
+    let _ = generator.generate_contacts(
+        &dispatcher,
+        &m1,
+        &sdf,
+        None,
+        &m2,
+        &cuboid,
+        None,
+        &prediction,
+        &mut manifold,
+    );
+
+    // The two bottom corners of the cuboid penetrate the disk.
+    assert_eq!(manifold.len(), 2);
+
+    for tracked in manifold.contacts() {
+        let c = &tracked.contact;
+        let expected = 1.0 - Point2::new(0.5, 0.75).coords.norm();
+        assert_relative_eq!(c.depth, expected, epsilon = 1.0e-2);
+    }
+}
@@ -7,6 +7,8 @@ extern crate rand;
 extern crate test;
 
 mod bounding_volume;
+mod broad_phase;
 mod common;
+mod partitioning;
 mod query;
 mod support_map;
@@ -0,0 +1,86 @@
+use na::{Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::broad_phase::{
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle, DBVTBroadPhase,
+    SAPBroadPhase, UniformGridBroadPhase,
+};
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+use test::Bencher;
+
+const NUM_OBJECTS: usize = 1000;
+
+struct CountingHandler(usize);
+
+impl BroadPhaseInterferenceHandler<usize> for CountingHandler {
+    fn is_interference_allowed(&mut self, a: &usize, b: &usize) -> bool {
+        a != b
+    }
+
+    fn interference_started(&mut self, _: &usize, _: &usize) {
+        self.0 += 1
+    }
+
+    fn interference_stopped(&mut self, _: &usize, _: &usize) {
+        self.0 -= 1
+    }
+}
+
+// Small objects moving on a plane.
+fn planar_aabbs(rng: &mut IsaacRng) -> (Vec<Point3<f32>>, Vec<Vector3<f32>>) {
+    let centers = (0..NUM_OBJECTS)
+        .map(|_| Point3::new(rng.gen::<f32>() * 100.0, 0.0, rng.gen::<f32>() * 100.0))
+        .collect();
+    let velocities = (0..NUM_OBJECTS)
+        .map(|_| Vector3::new(rng.gen::<f32>() - 0.5, 0.0, rng.gen::<f32>() - 0.5) * 0.1)
+        .collect();
+
+    (centers, velocities)
+}
+
+fn bench_broad_phase_update<BF>(bh: &mut Bencher, mut broad_phase: BF)
+where
+    BF: BroadPhase<f32, AABB<f32>, usize>,
+{
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let (mut centers, velocities) = planar_aabbs(&mut rng);
+    let half_extents = Vector3::repeat(0.5);
+    let mut handler = CountingHandler(0);
+
+    let handles: Vec<BroadPhaseProxyHandle> = centers
+        .iter()
+        .enumerate()
+        .map(|(i, c)| broad_phase.create_proxy(AABB::from_half_extents(*c, half_extents), i))
+        .collect();
+    broad_phase.update(&mut handler);
+
+    bh.iter(|| {
+        for ((handle, center), vel) in handles
+            .iter()
+            .zip(centers.iter_mut())
+            .zip(velocities.iter())
+        {
+            *center += vel;
+            let aabb = AABB::from_half_extents(*center, half_extents);
+            broad_phase.deferred_set_bounding_volume(*handle, aabb);
+        }
+
+        broad_phase.update(&mut handler);
+        test::black_box(handler.0);
+    })
+}
+
+#[bench]
+fn bench_dbvt_broad_phase_planar_update(bh: &mut Bencher) {
+    bench_broad_phase_update(bh, DBVTBroadPhase::new(0.01))
+}
+
+#[bench]
+fn bench_sap_broad_phase_planar_update(bh: &mut Bencher) {
+    bench_broad_phase_update(bh, SAPBroadPhase::new(0.01))
+}
+
+#[bench]
+fn bench_uniform_grid_broad_phase_planar_update(bh: &mut Bencher) {
+    bench_broad_phase_update(bh, UniformGridBroadPhase::new(1.0, 0.01))
+}
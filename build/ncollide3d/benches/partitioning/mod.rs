@@ -0,0 +1,220 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{self, AABB};
+use ncollide3d::partitioning::{BVH, BVT, QBVH};
+use ncollide3d::pipeline::{ContactDispatcher, DefaultContactDispatcher};
+use ncollide3d::query::visitors::{
+    BoundingVolumeInterferencesCollector, RayInterferencesCollector,
+};
+use ncollide3d::query::{ContactManifold, ContactPrediction, Ray, RayCast};
+use ncollide3d::shape::{Ball, TriMesh, Triangle};
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+use test::Bencher;
+
+const NUM_QUERIES: usize = 1 << 7;
+
+// A large sparse mesh with a small, densely tessellated, area near the origin.
+fn uneven_trimesh(rng: &mut IsaacRng) -> TriMesh<f32> {
+    let mut points = Vec::new();
+
+    for _ in 0..200 {
+        let center = Point3::new(rng.gen::<f32>() - 0.5, 0.0, rng.gen::<f32>() - 0.5) * 100.0;
+        points.push(center);
+        points.push(center + Vector3::new(5.0, rng.gen::<f32>(), 0.0));
+        points.push(center + Vector3::new(0.0, rng.gen::<f32>(), 5.0));
+    }
+
+    for _ in 0..5000 {
+        let center = Point3::new(rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>()) * 2.0;
+        points.push(center);
+        points.push(center + Vector3::new(0.05, 0.0, 0.0));
+        points.push(center + Vector3::new(0.0, 0.0, 0.05));
+    }
+
+    let indices = (0..points.len() / 3)
+        .map(|i| Point3::new(i * 3, i * 3 + 1, i * 3 + 2))
+        .collect();
+
+    TriMesh::new(points, indices, None)
+}
+
+fn triangle_leaves(mesh: &TriMesh<f32>) -> Vec<(usize, AABB<f32>)> {
+    mesh.faces()
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let pts = mesh.points();
+            let triangle = Triangle::new(pts[f.indices.x], pts[f.indices.y], pts[f.indices.z]);
+            (i, bounding_volume::local_aabb(&triangle))
+        })
+        .collect()
+}
+
+// Rays shot downward, mostly toward the dense area of the mesh.
+fn rays(rng: &mut IsaacRng) -> Vec<Ray<f32>> {
+    (0..NUM_QUERIES)
+        .map(|i| {
+            let scale = if i % 4 == 0 { 100.0 } else { 2.0 };
+            let origin = Point3::new(rng.gen::<f32>(), 0.0, rng.gen::<f32>()) * scale;
+            Ray::new(origin + Vector3::y() * 10.0, -Vector3::y())
+        })
+        .collect()
+}
+
+fn aabbs(rng: &mut IsaacRng) -> Vec<AABB<f32>> {
+    (0..NUM_QUERIES)
+        .map(|_| {
+            let center = Point3::new(rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>()) * 2.0;
+            AABB::from_half_extents(center, Vector3::repeat(0.1))
+        })
+        .collect()
+}
+
+fn uneven_bvt(rng: &mut IsaacRng, sah: bool) -> BVT<usize, AABB<f32>> {
+    let leaves = triangle_leaves(&uneven_trimesh(rng));
+
+    if sah {
+        BVT::new_with_sah(leaves)
+    } else {
+        BVT::new_balanced(leaves)
+    }
+}
+
+fn bench_ray_interferences(bh: &mut Bencher, rng: &mut IsaacRng, bvh: &impl BVH<usize, AABB<f32>>) {
+    let rays = rays(rng);
+    let mut buffer = Vec::new();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (NUM_QUERIES - 1);
+        buffer.clear();
+        bvh.visit(&mut RayInterferencesCollector::new(&rays[i], &mut buffer));
+        test::black_box(buffer.len());
+    })
+}
+
+fn bench_aabb_interferences(
+    bh: &mut Bencher,
+    rng: &mut IsaacRng,
+    bvh: &impl BVH<usize, AABB<f32>>,
+) {
+    let aabbs = aabbs(rng);
+    let mut buffer = Vec::new();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (NUM_QUERIES - 1);
+        buffer.clear();
+        bvh.visit(&mut BoundingVolumeInterferencesCollector::new(
+            &aabbs[i],
+            &mut buffer,
+        ));
+        test::black_box(buffer.len());
+    })
+}
+
+#[bench]
+fn bench_median_bvt_ray_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let bvt = uneven_bvt(&mut rng, false);
+    bench_ray_interferences(bh, &mut rng, &bvt)
+}
+
+#[bench]
+fn bench_sah_bvt_ray_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let bvt = uneven_bvt(&mut rng, true);
+    bench_ray_interferences(bh, &mut rng, &bvt)
+}
+
+#[bench]
+fn bench_sah_qbvh_ray_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let qbvh = QBVH::from_bvt(&uneven_bvt(&mut rng, true));
+    bench_ray_interferences(bh, &mut rng, &qbvh)
+}
+
+#[bench]
+fn bench_median_bvt_aabb_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let bvt = uneven_bvt(&mut rng, false);
+    bench_aabb_interferences(bh, &mut rng, &bvt)
+}
+
+#[bench]
+fn bench_sah_bvt_aabb_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let bvt = uneven_bvt(&mut rng, true);
+    bench_aabb_interferences(bh, &mut rng, &bvt)
+}
+
+#[bench]
+fn bench_sah_qbvh_aabb_interferences(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let qbvh = QBVH::from_bvt(&uneven_bvt(&mut rng, true));
+    bench_aabb_interferences(bh, &mut rng, &qbvh)
+}
+
+#[bench]
+fn bench_median_bvt_build(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let leaves = triangle_leaves(&uneven_trimesh(&mut rng));
+
+    bh.iter(|| test::black_box(BVT::new_balanced(leaves.clone())))
+}
+
+#[bench]
+fn bench_sah_bvt_build(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let leaves = triangle_leaves(&uneven_trimesh(&mut rng));
+
+    bh.iter(|| test::black_box(BVT::new_with_sah(leaves.clone())))
+}
+
+#[bench]
+fn bench_ray_against_uneven_trimesh(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let mesh = uneven_trimesh(&mut rng);
+    let rays = rays(&mut rng);
+    let id = Isometry3::identity();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (NUM_QUERIES - 1);
+        test::black_box(mesh.toi_with_ray(&id, &rays[i], true))
+    })
+}
+
+#[bench]
+fn bench_ball_against_uneven_trimesh_contacts(bh: &mut Bencher) {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(0);
+    let mesh = uneven_trimesh(&mut rng);
+    let ball = Ball::new(0.2f32);
+    let positions: Vec<_> = aabbs(&mut rng)
+        .iter()
+        .map(|aabb| Isometry3::new(aabb.center().coords, na::zero()))
+        .collect();
+    let id = Isometry3::identity();
+    let dispatcher = DefaultContactDispatcher::new();
+    let mut generator = dispatcher.get_contact_algorithm(&mesh, &ball).unwrap();
+    let prediction = ContactPrediction::new(0.0, 0.0, 0.0);
+    let mut manifold = ContactManifold::new();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (NUM_QUERIES - 1);
+        manifold.save_cache_and_clear();
+        let _ = generator.generate_contacts(
+            &dispatcher,
+            &id,
+            &mesh,
+            None,
+            &positions[i],
+            &ball,
+            None,
+            &prediction,
+            &mut manifold,
+        );
+        test::black_box(manifold.len());
+    })
+}
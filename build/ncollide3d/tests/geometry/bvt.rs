@@ -0,0 +1,110 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{BVH, BVT};
+use ncollide3d::query::visitors::RayInterferencesCollector;
+use ncollide3d::query::{Ray, RayCast};
+
+// Many small boxes packed near the origin and a few large ones far from it.
+fn uneven_leaves() -> Vec<(usize, AABB<f64>)> {
+    let mut res = Vec::new();
+
+    for i in 0..1000 {
+        let t = i as f64;
+        let center = Point3::new((t * 0.37).sin(), (t * 1.13).cos(), (t * 0.71).sin());
+        res.push((
+            res.len(),
+            AABB::from_half_extents(center, Vector3::repeat(0.02)),
+        ));
+    }
+
+    for i in 0..20 {
+        let t = i as f64;
+        let center = Point3::new(50.0 + t * 10.0, 0.0, -t * 5.0);
+        res.push((
+            res.len(),
+            AABB::from_half_extents(center, Vector3::repeat(3.0)),
+        ));
+    }
+
+    res
+}
+
+fn half_area(aabb: &AABB<f64>) -> f64 {
+    let e = aabb.extents();
+    e.x * e.y + e.y * e.z + e.z * e.x
+}
+
+// Checks that each node encloses its children, and returns the sum of the half areas of the
+// internal nodes and the leaves found.
+fn check_tree(
+    bvt: &BVT<usize, AABB<f64>>,
+    node: <BVT<usize, AABB<f64>> as BVH<usize, AABB<f64>>>::Node,
+    leaves: &mut Vec<usize>,
+) -> f64 {
+    let (bv, data) = bvt.content(node);
+
+    if let Some(data) = data {
+        leaves.push(*data);
+        return 0.0;
+    }
+
+    let mut cost = half_area(bv);
+
+    for i in 0..bvt.num_children(node) {
+        let child = bvt.child(i, node);
+        assert!(bv.contains(bvt.content(child).0));
+        cost += check_tree(bvt, child, leaves);
+    }
+
+    cost
+}
+
+#[test]
+fn sah_bvt_is_valid_and_cheaper() {
+    let leaves = uneven_leaves();
+    let sah = BVT::new_with_sah(leaves.clone());
+    let median = BVT::new_balanced(leaves.clone());
+
+    let mut sah_leaves = Vec::new();
+    let sah_cost = check_tree(&sah, sah.root().unwrap(), &mut sah_leaves);
+    let mut median_leaves = Vec::new();
+    let median_cost = check_tree(&median, median.root().unwrap(), &mut median_leaves);
+
+    sah_leaves.sort();
+    median_leaves.sort();
+    assert_eq!(sah_leaves, (0..leaves.len()).collect::<Vec<_>>());
+    assert_eq!(median_leaves, sah_leaves);
+    assert!(sah_cost < median_cost, "{} >= {}", sah_cost, median_cost);
+
+    // Both trees find the same ray interferences as a brute-force search.
+    for i in 0..50 {
+        let t = i as f64;
+        let origin = Point3::new((t * 0.3).cos() * 100.0, 20.0, (t * 0.3).sin() * 100.0);
+        let ray = Ray::new(origin, Point3::new(t * 0.05, 0.0, 0.0) - origin);
+        let mut expected: Vec<_> = leaves
+            .iter()
+            .filter(|(_, aabb)| aabb.intersects_ray(&Isometry3::identity(), &ray))
+            .map(|(i, _)| *i)
+            .collect();
+        expected.sort();
+
+        for bvt in &[&sah, &median] {
+            let mut found = Vec::new();
+            bvt.visit(&mut RayInterferencesCollector::new(&ray, &mut found));
+            found.sort();
+            assert_eq!(found, expected);
+        }
+    }
+}
+
+#[test]
+fn sah_bvt_with_coincident_leaves() {
+    let aabb = AABB::new(Point3::origin(), Point3::new(1.0, 1.0, 1.0));
+    let leaves: Vec<_> = (0..17).map(|i| (i, aabb.clone())).collect();
+    let bvt = BVT::new_with_sah(leaves);
+
+    let mut found = Vec::new();
+    let _ = check_tree(&bvt, bvt.root().unwrap(), &mut found);
+    found.sort();
+    assert_eq!(found, (0..17).collect::<Vec<_>>());
+}
@@ -0,0 +1,185 @@
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{BVTNodeId, BVH, BVT};
+use ncollide3d::pipeline::{
+    ContactDispatcher, DefaultContactDispatcher, DefaultProximityDispatcher, ProximityDispatcher,
+};
+use ncollide3d::query::{ContactManifold, ContactPrediction, Proximity, Ray, RayCast};
+use ncollide3d::shape::{Ball, CompositeShape, Compound, Cuboid, Shape, ShapeHandle};
+
+fn ball_at(x: f64) -> (Isometry3<f64>, ShapeHandle<f64>) {
+    (
+        Isometry3::new(Vector3::x() * x, na::zero()),
+        ShapeHandle::new(Ball::new(0.5)),
+    )
+}
+
+#[test]
+fn compound_edits() {
+    let mut compound = Compound::new(vec![ball_at(0.0), ball_at(2.0)]);
+    let id = Isometry3::identity();
+    let ray = Ray::new(Point3::new(4.0, 5.0, 0.0), -Vector3::y());
+
+    assert_eq!(compound.push_shape(ball_at(4.0).0, ball_at(4.0).1), 2);
+    assert_eq!(compound.nparts(), 3);
+    assert!(compound.aabb().contains(compound.aabb_at(2)));
+    assert_eq!(compound.toi_with_ray(&id, &ray, true), Some(4.5));
+
+    // Moving a part within its loosened bounding volume keeps it.
+    let aabb = compound.aabb_at(0).clone();
+    compound.set_shape_position(0, Isometry3::new(Vector3::x() * 0.01, na::zero()));
+    assert_eq!(*compound.aabb_at(0), aabb);
+
+    // Otherwise the bounding volume tree is refitted.
+    compound.set_shape_position(0, Isometry3::new(Vector3::x() * 10.0, na::zero()));
+    assert!(compound.aabb().maxs().x > 10.5);
+    let ray10 = Ray::new(Point3::new(10.0, 5.0, 0.0), -Vector3::y());
+    assert_eq!(compound.toi_with_ray(&id, &ray10, true), Some(4.5));
+
+    compound.set_shape_position(0, Isometry3::identity());
+    assert!(compound.aabb().maxs().x < 10.0);
+    assert!(compound.toi_with_ray(&id, &ray10, true).is_none());
+
+    // Replace a part by a different shape.
+    let revision = compound.part_revision(2);
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0)));
+    let _ = compound.replace_shape(2, ball_at(4.0).0, cuboid);
+    assert_ne!(compound.part_revision(2), revision);
+    assert_eq!(compound.toi_with_ray(&id, &ray, true), Some(4.0));
+
+    // The last part takes the index of the removed one.
+    let revision = compound.part_revision(2);
+    let (pos, _) = compound.remove_shape(0);
+    assert_eq!(pos, Isometry3::identity());
+    assert_eq!(compound.nparts(), 2);
+    assert_eq!(compound.part_revision(0), revision);
+    assert_eq!(compound.shapes()[0].0, ball_at(4.0).0);
+    assert_eq!(compound.toi_with_ray(&id, &ray, true), Some(4.0));
+
+    let ray0 = Ray::new(Point3::new(0.0, 5.0, 0.0), -Vector3::y());
+    assert!(compound.toi_with_ray(&id, &ray0, true).is_none());
+}
+
+#[test]
+fn compound_edits_invalidate_sub_detectors() {
+    let mut compound = Compound::new(vec![ball_at(0.0), ball_at(3.0)]);
+    let ball = Ball::new(0.5);
+    let id = Isometry3::identity();
+    let m2 = Isometry3::new(Vector3::new(0.0, 0.9, 0.0), na::zero());
+    let prediction = ContactPrediction::new(0.0, 0.0, 0.0);
+
+    let dispatcher = DefaultContactDispatcher::new();
+    let mut generator = dispatcher.get_contact_algorithm(&compound, &ball).unwrap();
+    let mut manifold = generator.init_manifold();
+
+    let proximity_dispatcher = DefaultProximityDispatcher::new();
+    let mut detector = proximity_dispatcher
+        .get_proximity_algorithm(&compound, &ball)
+        .unwrap();
+
+    let mut update = |compound: &Compound<f64>, manifold: &mut ContactManifold<f64>| {
+        manifold.save_cache_and_clear();
+        let _ = generator.generate_contacts(
+            &dispatcher,
+            &id,
+            compound,
+            None,
+            &m2,
+            &ball,
+            None,
+            &prediction,
+            manifold,
+        );
+        detector.update(&proximity_dispatcher, &id, compound, &m2, &ball, 0.0)
+    };
+
+    assert_eq!(
+        update(&compound, &mut manifold),
+        Some(Proximity::Intersecting)
+    );
+    assert_eq!(manifold.len(), 1);
+    assert_relative_eq!(manifold.deepest_contact().unwrap().contact.depth, 0.1);
+
+    // The contacts with the replaced part are computed from the new shape right away.
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5)));
+    let _ = compound.replace_shape(0, Isometry3::new(Vector3::y() * 0.3, na::zero()), cuboid);
+    assert_eq!(
+        update(&compound, &mut manifold),
+        Some(Proximity::Intersecting)
+    );
+    assert!(manifold.len() > 0);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.depth, 0.4, epsilon = 1.0e-7);
+    assert_relative_eq!(*c.normal, Vector3::y(), epsilon = 1.0e-7);
+
+    // The part moved to the index of the removed one is far from the ball.
+    let _ = compound.remove_shape(0);
+    assert_eq!(update(&compound, &mut manifold), Some(Proximity::Disjoint));
+    assert_eq!(manifold.len(), 0);
+
+    let _ = compound.push_shape(Isometry3::identity(), ShapeHandle::new(Ball::new(0.5)));
+    assert_eq!(
+        update(&compound, &mut manifold),
+        Some(Proximity::Intersecting)
+    );
+    assert_eq!(manifold.len(), 1);
+}
+
+// Checks that each internal node encloses its children, and returns the data of all the
+// leaves below `node`.
+fn check_bvt(bvt: &BVT<usize, AABB<f64>>, node: BVTNodeId, leaves: &mut Vec<usize>) {
+    let (bv, data) = bvt.content(node);
+
+    if let Some(i) = data {
+        leaves.push(*i);
+    } else {
+        for k in 0..bvt.num_children(node) {
+            let child = bvt.child(k, node);
+            assert!(bv.contains(bvt.content(child).0));
+            check_bvt(bvt, child, leaves);
+        }
+    }
+}
+
+#[test]
+fn compound_incremental_edits() {
+    let mut compound = Compound::new(vec![ball_at(0.0)]);
+    let id = Isometry3::identity();
+
+    for step in 0..60 {
+        if step % 3 == 2 {
+            let _ = compound.remove_shape((step * 7) % compound.nparts());
+        } else {
+            let x = (step as f64 * 1.7).sin() * 20.0;
+            let _ = compound.push_shape(ball_at(x).0, ball_at(x).1);
+        }
+
+        if step % 5 == 0 {
+            let x = (step as f64 * 0.3).cos() * 20.0;
+            compound.set_shape_position(0, ball_at(x).0);
+        }
+
+        let mut leaves = Vec::new();
+        check_bvt(compound.bvt(), compound.bvt().root().unwrap(), &mut leaves);
+        leaves.sort();
+        assert_eq!(leaves, (0..compound.nparts()).collect::<Vec<_>>());
+
+        for (i, leaf) in compound.bvt().leaves().iter().enumerate() {
+            let part = *leaf.data();
+            assert_eq!(leaf.bounding_volume(), compound.aabb_at(part));
+            assert_eq!(*compound.bvt().leaf(i).data(), part);
+        }
+
+        // Ray casts agree with a compound built from scratch.
+        let expected = Compound::new(compound.shapes().to_vec());
+
+        for k in 0..10 {
+            let x = k as f64 * 4.0 - 20.0;
+            let ray = Ray::new(Point3::new(x, 5.0, 0.0), -Vector3::y());
+            assert_eq!(
+                compound.toi_with_ray(&id, &ray, true),
+                expected.toi_with_ray(&id, &ray, true)
+            );
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{self, BoundingVolume, AABB};
+use ncollide3d::partitioning::BVH;
+use ncollide3d::procedural;
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{DeformableShape, TriMesh};
+
+fn sheet() -> TriMesh<f64> {
+    procedural::quad(4.0, 4.0, 8, 8).into()
+}
+
+// The coordinates of the vertices of `sheet` bent by a wave at time `t`.
+fn wave(sheet: &TriMesh<f64>, t: f64) -> Vec<f64> {
+    sheet
+        .points()
+        .iter()
+        .flat_map(|p| {
+            let z = (p.x + t).sin() * 0.5 + (p.y * 0.7 - t).cos() * 0.3;
+            vec![p.x, p.y, z]
+        })
+        .collect()
+}
+
+// Checks that each node of the hierarchy is the smallest AABB enclosing its children.
+fn check_tight<B: BVH<usize, AABB<f64>>>(bvh: &B, mesh: &TriMesh<f64>, node: B::Node) -> AABB<f64> {
+    let (bv, data) = bvh.content(node);
+
+    let expected = if let Some(i) = data {
+        bounding_volume::local_point_cloud_aabb(mesh.triangle_at(*i).vertices())
+    } else {
+        let mut res = check_tight(bvh, mesh, bvh.child(0, node));
+
+        for k in 1..bvh.num_children(node) {
+            res.merge(&check_tight(bvh, mesh, bvh.child(k, node)));
+        }
+
+        res
+    };
+
+    assert_eq!(*bv, expected);
+    expected
+}
+
+// Checks that each node of the hierarchy encloses its children, and each leaf its triangle.
+fn check_enclosing<B: BVH<usize, AABB<f64>>>(bvh: &B, mesh: &TriMesh<f64>, node: B::Node) {
+    let (bv, data) = bvh.content(node);
+
+    if let Some(i) = data {
+        let triangle = mesh.triangle_at(*i);
+        assert!(bv.contains(&bounding_volume::local_point_cloud_aabb(
+            triangle.vertices()
+        )));
+    } else {
+        for k in 0..bvh.num_children(node) {
+            let child = bvh.child(k, node);
+            assert!(bv.contains(bvh.content(child).0));
+            check_enclosing(bvh, mesh, child);
+        }
+    }
+}
+
+#[test]
+fn deformable_trimesh_refit() {
+    let mut mesh = sheet();
+    mesh.set_deformable(true);
+    assert!(mesh.deformable());
+
+    for step in 0..3 {
+        let coords = wave(&mesh, step as f64);
+        mesh.set_deformations(&coords);
+
+        let root = check_tight(mesh.bvt(), &mesh, mesh.bvt().root().unwrap());
+        let _ = check_tight(mesh.qbvh(), &mesh, mesh.qbvh().root().unwrap());
+        assert_eq!(*mesh.aabb(), root);
+
+        // The queries match those on a mesh built directly with the deformed vertices.
+        let indices = mesh.faces().iter().map(|f| f.indices).collect();
+        let expected = TriMesh::new(mesh.points().to_vec(), indices, None);
+
+        for i in 0..mesh.faces().len() {
+            assert_eq!(mesh.face_normal(i), expected.faces()[i].normal);
+            assert_eq!(mesh.face_side_normals(i), expected.faces()[i].side_normals);
+        }
+
+        let id = Isometry3::identity();
+
+        for k in 0..20 {
+            let t = k as f64;
+            let pt = Point3::new((t * 0.7).sin() * 1.8, (t * 1.3).cos() * 1.8, 2.0);
+            let ray = Ray::new(pt, -Vector3::z());
+
+            assert_relative_eq!(
+                mesh.toi_with_ray(&id, &ray, true).unwrap(),
+                expected.toi_with_ray(&id, &ray, true).unwrap()
+            );
+            assert_relative_eq!(
+                mesh.distance_to_point(&id, &pt, true),
+                expected.distance_to_point(&id, &pt, true)
+            );
+        }
+    }
+
+    // The normals stored on the faces are only updated on demand.
+    let coords = wave(&mesh, 0.0);
+    mesh.set_deformations(&coords);
+    assert!((0..mesh.faces().len()).any(|i| mesh.faces()[i].normal != mesh.face_normal(i)));
+
+    mesh.update_normals();
+    assert!((0..mesh.faces().len()).all(|i| mesh.faces()[i].normal == mesh.face_normal(i)));
+}
+
+#[test]
+fn deformable_mode_can_be_disabled() {
+    let mut mesh = sheet();
+    mesh.set_deformable(true);
+    mesh.set_deformations(&wave(&mesh, 0.0));
+    mesh.set_deformable(false);
+
+    // The face normals are up to date when leaving the deformable mode.
+    for i in 0..mesh.faces().len() {
+        assert_eq!(mesh.faces()[i].normal, mesh.triangle_at(i).normal());
+    }
+
+    // A small deformation, below the margin of the non-deformable mode.
+    let coords: Vec<f64> = wave(&mesh, 0.0)
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| if i % 3 == 2 { x + 0.01 } else { x })
+        .collect();
+    mesh.set_deformations(&coords);
+
+    for leaf in mesh.bvt().leaves() {
+        let triangle = mesh.triangle_at(*leaf.data());
+        let aabb = bounding_volume::local_point_cloud_aabb(triangle.vertices());
+        assert!(leaf.bounding_volume().contains(&aabb));
+    }
+
+    // A deformation above the margin of the non-deformable mode.
+    let coords = wave(&mesh, 1.0);
+    mesh.set_deformations(&coords);
+    check_enclosing(mesh.bvt(), &mesh, mesh.bvt().root().unwrap());
+    check_enclosing(mesh.qbvh(), &mesh, mesh.qbvh().root().unwrap());
+    assert_eq!(
+        mesh.qbvh().root_bounding_volume(),
+        mesh.bvt().root_bounding_volume()
+    );
+
+    let id = Isometry3::identity();
+    let indices = mesh.faces().iter().map(|f| f.indices).collect();
+    let expected = TriMesh::new(mesh.points().to_vec(), indices, None);
+
+    for k in 0..20 {
+        let t = k as f64;
+        let pt = Point3::new((t * 0.7).sin() * 1.8, (t * 1.3).cos() * 1.8, 2.0);
+        let ray = Ray::new(pt, -Vector3::z());
+
+        assert_relative_eq!(
+            mesh.toi_with_ray(&id, &ray, true).unwrap(),
+            expected.toi_with_ray(&id, &ray, true).unwrap()
+        );
+    }
+}
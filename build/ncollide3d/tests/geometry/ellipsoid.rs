@@ -0,0 +1,213 @@
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume;
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::algorithms::VoronoiSimplex;
+use ncollide3d::query::{self, PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Ball, Cuboid, Ellipsoid, Shape, ShapeHandle};
+use ncollide3d::transformation::ToTriMesh;
+
+fn ellipsoid() -> Ellipsoid<f64> {
+    Ellipsoid::new(Vector3::new(2.0, 0.5, 1.0))
+}
+
+fn on_surface(e: &Ellipsoid<f64>, pt: &Point3<f64>) -> bool {
+    relative_eq!(
+        pt.coords.component_div(e.radii()).norm_squared(),
+        1.0,
+        epsilon = 1.0e-7
+    )
+}
+
+fn sample_points() -> Vec<Point3<f64>> {
+    let mut pts = Vec::new();
+
+    for i in 0..200 {
+        let t = i as f64;
+        let scale = 0.1 + (t * 0.37).sin().abs() * 4.0;
+        pts.push(Point3::new(
+            (t * 1.3).sin() * scale,
+            (t * 0.7).cos() * scale * 0.5,
+            (t * 2.9).sin() * scale,
+        ));
+    }
+
+    pts
+}
+
+#[test]
+fn ellipsoid_bounding_volumes() {
+    let e = ellipsoid();
+    let m = Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.3, 1.2));
+    let aabb = e.aabb(&m);
+    let expected = bounding_volume::support_map_aabb(&m, &e);
+
+    assert_relative_eq!(aabb.mins(), expected.mins(), epsilon = 1.0e-7);
+    assert_relative_eq!(aabb.maxs(), expected.maxs(), epsilon = 1.0e-7);
+    let local_aabb = e.local_aabb();
+    assert_relative_eq!(*local_aabb.maxs(), Point3::new(2.0, 0.5, 1.0));
+    assert_eq!(e.bounding_sphere(&m).radius(), 2.0);
+}
+
+#[test]
+fn ellipsoid_ray_cast() {
+    let e = ellipsoid();
+    let m = Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.3, 1.2));
+
+    let ray = Ray::new(Point3::new(5.0, 0.0, 0.0), -Vector3::x());
+    let inter = e
+        .toi_and_normal_with_ray(&Isometry3::identity(), &ray, true)
+        .unwrap();
+    assert_relative_eq!(inter.toi, 3.0);
+    assert_relative_eq!(inter.normal, Vector3::x());
+
+    for pt in sample_points() {
+        let origin = m * (pt * 2.0);
+        let ray = Ray::new(
+            origin,
+            m.translation.vector + Vector3::new(0.1, 0.2, 0.0) - origin.coords,
+        );
+        let expected = query::ray_intersection_with_support_map_with_params(
+            &m,
+            &e,
+            &mut VoronoiSimplex::new(),
+            &ray,
+            true,
+        );
+
+        match (e.toi_and_normal_with_ray(&m, &ray, true), expected) {
+            (Some(inter), Some(expected)) => {
+                assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-5);
+
+                if inter.toi > 0.0 {
+                    assert_relative_eq!(inter.normal, expected.normal, epsilon = 1.0e-3);
+                    assert!(on_surface(
+                        &e,
+                        &m.inverse_transform_point(&ray.point_at(inter.toi))
+                    ));
+                }
+            }
+            (None, None) => {}
+            _ => panic!("Ray cast mismatch."),
+        }
+    }
+
+    // Hollow ellipsoid: the ray exits at the opposite side.
+    let ray = Ray::new(Point3::new(0.0, 0.25, 0.0), Vector3::y());
+    let inter = e
+        .toi_and_normal_with_ray(&Isometry3::identity(), &ray, false)
+        .unwrap();
+    assert_relative_eq!(inter.toi, 0.25);
+    assert_relative_eq!(inter.normal, -Vector3::y());
+}
+
+#[test]
+fn ellipsoid_point_projection() {
+    let e = ellipsoid();
+    let m = Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.3, 1.2));
+    let surface = e.to_trimesh((100, 100)).coords;
+
+    for pt in sample_points() {
+        let world_pt = m * pt;
+        let proj = e.project_point(&m, &world_pt, false);
+        let ls_proj = m.inverse_transform_point(&proj.point);
+
+        assert!(on_surface(&e, &ls_proj));
+        assert_eq!(proj.is_inside, e.contains_point(&m, &world_pt));
+
+        // The projection is at least as close as any point of a fine discretization.
+        let dist = na::distance(&pt, &ls_proj);
+        let min_dist = surface
+            .iter()
+            .map(|s| na::distance(&pt, s))
+            .fold(std::f64::MAX, f64::min);
+        assert!(dist <= min_dist + 1.0e-7);
+
+        // The direction to the point is normal to the surface.
+        let normal = e.local_normal_at(&ls_proj).unwrap();
+        let dir = pt - ls_proj;
+        assert_relative_eq!(dir.cross(&normal).norm(), 0.0, epsilon = 1.0e-6);
+
+        if !proj.is_inside {
+            let expected = query::point_projection_on_support_map(
+                &m,
+                &e,
+                &mut VoronoiSimplex::new(),
+                &world_pt,
+                false,
+            );
+            // GJK only converges approximately on curved surfaces.
+            assert!(
+                na::distance(&world_pt, &proj.point)
+                    <= na::distance(&world_pt, &expected.point) + 1.0e-7
+            );
+        }
+    }
+}
+
+#[test]
+fn ellipsoid_point_projection_deep_inside() {
+    let e = ellipsoid();
+    let id = Isometry3::identity();
+
+    // The projection of the center is on the smallest axis.
+    let proj = e.project_point(&id, &Point3::origin(), false);
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, Point3::new(0.0, 0.5, 0.0));
+
+    // Points on the plane orthogonal to the smallest axis, but not projected onto it.
+    let pt = Point3::new(0.3, 0.0, 0.2);
+    let proj = e.project_point(&id, &pt, false);
+    assert!(on_surface(&e, &proj.point));
+    assert!(proj.point.y > 0.4);
+    assert_relative_eq!(proj.point.x, 4.0 * 0.3 / (4.0 - 0.25), epsilon = 1.0e-7);
+    assert_relative_eq!(proj.point.z, 0.2 / (1.0 - 0.25), epsilon = 1.0e-7);
+
+    assert_eq!(e.project_point(&id, &pt, true).point, pt);
+}
+
+#[test]
+fn ellipsoid_to_trimesh() {
+    let e = ellipsoid();
+    let mesh = e.to_trimesh((20, 20));
+
+    for (pt, normal) in mesh
+        .coords
+        .iter()
+        .zip(mesh.normals.as_ref().unwrap().iter())
+    {
+        assert!(on_surface(&e, pt));
+        assert_relative_eq!(*normal, *e.local_normal_at(pt).unwrap(), epsilon = 1.0e-6);
+    }
+}
+
+#[test]
+fn ellipsoid_world_contacts() {
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut add = |pos, shape| world.add(pos, shape, groups, query, ()).0;
+
+    let e = add(Isometry3::identity(), ShapeHandle::new(ellipsoid()));
+    let others = [
+        add(
+            Isometry3::translation(2.4, 0.0, 0.0),
+            ShapeHandle::new(Ball::new(0.5)),
+        ),
+        add(
+            Isometry3::translation(0.0, 0.8, 0.0),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(0.4))),
+        ),
+        add(
+            Isometry3::translation(0.0, 0.0, 1.9),
+            ShapeHandle::new(ellipsoid()),
+        ),
+    ];
+
+    world.update();
+
+    for other in others.iter() {
+        let (_, _, _, manifold) = world.contact_pair(e, *other, true).unwrap();
+        let contact = manifold.deepest_contact().unwrap().contact;
+        assert_relative_eq!(contact.depth, 0.1, epsilon = 1.0e-6);
+    }
+}
@@ -0,0 +1,126 @@
+use super::feature_based_contacts;
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::shape::{Ball, Cuboid, Polyline, TriMesh};
+
+// A flat square floor on the plane `y = 0` made of `n * n` quads split into two triangles.
+fn floor(n: usize) -> TriMesh<f64> {
+    let mut points = Vec::new();
+    let mut indices = Vec::new();
+
+    for i in 0..=n {
+        for k in 0..=n {
+            points.push(Point3::new(
+                i as f64 - n as f64 / 2.0,
+                0.0,
+                k as f64 - n as f64 / 2.0,
+            ));
+        }
+    }
+
+    for i in 0..n {
+        for k in 0..n {
+            let a = i * (n + 1) + k;
+            let b = a + n + 1;
+            indices.push(Point3::new(a, a + 1, b));
+            indices.push(Point3::new(b, a + 1, b + 1));
+        }
+    }
+
+    TriMesh::new(points, indices, None)
+}
+
+#[test]
+fn trimesh_contacts_without_internal_edges() {
+    let floor = floor(4);
+    let id = Isometry3::identity();
+    let ball = Ball::new(0.5);
+    let cuboid = Cuboid::new(Vector3::new(0.4, 0.3, 0.7));
+
+    // Shapes sinking into the floor above internal edges and vertices.
+    let positions = [
+        Isometry3::new(Vector3::new(0.02, 0.4, 0.03), na::zero()),
+        Isometry3::new(Vector3::new(0.1, 0.45, -0.95), na::zero()),
+        Isometry3::new(Vector3::new(-0.98, 0.45, 0.5), na::zero()),
+    ];
+
+    for m2 in positions.iter() {
+        for flip in &[false, true] {
+            let ball_manifold = if *flip {
+                feature_based_contacts(m2, &ball, &id, &floor)
+            } else {
+                feature_based_contacts(&id, &floor, m2, &ball)
+            };
+
+            assert!(ball_manifold.len() > 0);
+
+            for tracked in ball_manifold.contacts() {
+                let c = &tracked.contact;
+                let normal = if *flip { -*c.normal } else { *c.normal };
+                assert_relative_eq!(normal, Vector3::y(), epsilon = 1.0e-7);
+                assert_relative_eq!(c.depth, 0.5 - m2.translation.vector.y, epsilon = 1.0e-7);
+            }
+        }
+
+        let m2 = Isometry3::new(
+            m2.translation.vector - Vector3::y() * 0.2,
+            Vector3::y() * 0.3,
+        );
+        let cuboid_manifold = feature_based_contacts(&id, &floor, &m2, &cuboid);
+        assert!(cuboid_manifold.len() > 0);
+
+        for tracked in cuboid_manifold.contacts() {
+            assert_relative_eq!(*tracked.contact.normal, Vector3::y(), epsilon = 1.0e-7);
+        }
+    }
+}
+
+#[test]
+fn trimesh_contacts_on_convex_edges() {
+    // A roof with a ridge along the `z` axis.
+    let points = vec![
+        Point3::new(-1.0, 0.0, -1.0),
+        Point3::new(-1.0, 0.0, 1.0),
+        Point3::new(0.0, 1.0, -1.0),
+        Point3::new(0.0, 1.0, 1.0),
+        Point3::new(1.0, 0.0, -1.0),
+        Point3::new(1.0, 0.0, 1.0),
+    ];
+    let indices = vec![
+        Point3::new(0, 1, 2),
+        Point3::new(2, 1, 3),
+        Point3::new(2, 3, 4),
+        Point3::new(4, 3, 5),
+    ];
+    let roof = TriMesh::new(points, indices, None);
+    let ball = Ball::new(0.5);
+    let id = Isometry3::identity();
+
+    // The ridge is an actual edge so its contact normal is preserved.
+    let m2 = Isometry3::new(Vector3::new(0.1, 1.4, 0.0), na::zero());
+    let manifold = feature_based_contacts(&id, &roof, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    let expected = Vector3::new(0.1, 0.4, 0.0);
+    assert_relative_eq!(*c.normal, expected.normalize(), epsilon = 1.0e-7);
+    assert_relative_eq!(c.depth, 0.5 - expected.norm(), epsilon = 1.0e-7);
+}
+
+#[test]
+fn polyline_contacts_without_internal_vertices() {
+    let points = (0..5)
+        .map(|i| Point3::new(i as f64 - 2.0, 0.0, 0.0))
+        .collect();
+    let polyline = Polyline::new(points, None);
+    let ball = Ball::new(0.5);
+    let id = Isometry3::identity();
+    let m2 = Isometry3::new(Vector3::new(0.05, 0.4, 0.1), na::zero());
+
+    let manifold = feature_based_contacts(&id, &polyline, &m2, &ball);
+    assert!(manifold.len() > 0);
+
+    for tracked in manifold.contacts() {
+        let c = &tracked.contact;
+        let expected = Vector3::new(0.0, 0.4, 0.1);
+        assert_relative_eq!(*c.normal, expected.normalize(), epsilon = 1.0e-7);
+        assert_relative_eq!(c.depth, 0.5 - expected.norm(), epsilon = 1.0e-7);
+    }
+}
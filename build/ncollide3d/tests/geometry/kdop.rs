@@ -0,0 +1,216 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{
+    self, BoundingVolume, HasBoundingVolume, AABB, KDOP, KDOP_NUM_AXES,
+};
+use ncollide3d::partitioning::{DBVTLeaf, BVH, DBVT};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::BoundingVolumeInterferencesCollector;
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Ball, Capsule, ConvexHull, Cuboid, TriMesh};
+
+fn rotation() -> Isometry3<f64> {
+    Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.7, 0.2))
+}
+
+fn assert_kdop_eq(kdop1: &KDOP<f64>, kdop2: &KDOP<f64>) {
+    for i in 0..KDOP_NUM_AXES {
+        assert_relative_eq!(kdop1.mins()[i], kdop2.mins()[i], epsilon = 1.0e-7);
+        assert_relative_eq!(kdop1.maxs()[i], kdop2.maxs()[i], epsilon = 1.0e-7);
+    }
+}
+
+#[test]
+fn kdop_of_shapes() {
+    let m = rotation();
+    let cuboid = Cuboid::new(Vector3::new(2.0, 0.1, 0.3));
+    let capsule = Capsule::new(1.0, 0.2);
+    let ball = Ball::new(0.5);
+    let sphere: TriMesh<f64> = procedural::sphere(1.0, 10, 10, true).into();
+    let hull = ConvexHull::try_from_points(sphere.points()).unwrap();
+
+    assert_kdop_eq(
+        &bounding_volume::kdop(&cuboid, &m),
+        &bounding_volume::support_map_kdop(&m, &cuboid),
+    );
+    assert_kdop_eq(
+        &bounding_volume::kdop(&capsule, &m),
+        &bounding_volume::support_map_kdop(&m, &capsule),
+    );
+    assert_kdop_eq(
+        &bounding_volume::kdop(&hull, &m),
+        &bounding_volume::support_map_kdop(&m, &hull),
+    );
+
+    let kdop: KDOP<f64> = ball.bounding_volume(&m);
+    assert_kdop_eq(&kdop, &bounding_volume::support_map_kdop(&m, &ball));
+    assert_eq!(kdop.aabb(), bounding_volume::aabb(&ball, &m));
+
+    let kdop: KDOP<f64> = sphere.bounding_volume(&m);
+    assert!(bounding_volume::aabb(&sphere, &m).contains(&kdop.aabb()));
+
+    for pt in sphere.points() {
+        assert!(kdop.loosened(1.0e-7).contains_local_point(&(m * pt)));
+    }
+
+    // The KDOP of a rotated cuboid is smaller than its AABB.
+    let kdop: KDOP<f64> = cuboid.bounding_volume(&m);
+    let aabb = kdop.aabb();
+    assert!(KDOP::from_aabb(&aabb).contains(&kdop));
+    assert!(!kdop.contains(&KDOP::from_aabb(&aabb)));
+}
+
+#[test]
+fn kdop_culls_better_than_aabb() {
+    // Two parallel strips of triangles along a diagonal, like two close pieces of cloth.
+    let mut points = Vec::new();
+    let mut indices = Vec::new();
+
+    for shift in [Vector3::zeros(), Vector3::new(0.3, -0.3, 0.0)].iter() {
+        for i in 0..20 {
+            let t = i as f64;
+            let base = points.len();
+            points.push(Point3::new(t, t, 0.0) + shift);
+            points.push(Point3::new(t + 1.0, t + 1.0, 0.0) + shift);
+            points.push(Point3::new(t + 0.5, t + 0.5, 0.1) + shift);
+            indices.push(Point3::new(base, base + 1, base + 2));
+        }
+    }
+
+    let strips = TriMesh::new(points, indices, None);
+
+    let kdops: Vec<KDOP<f64>> = (0..strips.faces().len())
+        .map(|i| strips.triangle_at(i).local_bounding_volume())
+        .collect();
+    let aabbs: Vec<AABB<f64>> = (0..strips.faces().len())
+        .map(|i| strips.triangle_at(i).local_bounding_volume())
+        .collect();
+
+    let mut num_kdop_pairs = 0;
+    let mut num_aabb_pairs = 0;
+
+    for i in 0..kdops.len() {
+        for j in i + 1..kdops.len() {
+            if kdops[i].intersects(&kdops[j]) {
+                num_kdop_pairs += 1;
+                assert!(aabbs[i].intersects(&aabbs[j]));
+            }
+
+            if aabbs[i].intersects(&aabbs[j]) {
+                num_aabb_pairs += 1;
+            }
+        }
+    }
+
+    // The diagonal slabs separate the two strips.
+    assert_eq!(num_kdop_pairs, 19 * 2);
+    assert_eq!(num_aabb_pairs, 19 * 2 + 20);
+
+    // The same pairs are found with a DBVT.
+    let mut dbvt = DBVT::new();
+
+    for (i, kdop) in kdops.iter().enumerate() {
+        let _ = dbvt.insert(DBVTLeaf::new(kdop.clone(), i));
+    }
+
+    let mut num_dbvt_pairs = 0;
+
+    for (i, kdop) in kdops.iter().enumerate() {
+        let mut found = Vec::new();
+        dbvt.visit(&mut BoundingVolumeInterferencesCollector::new(
+            kdop, &mut found,
+        ));
+        num_dbvt_pairs += found.into_iter().filter(|j| *j > i).count();
+    }
+
+    assert_eq!(num_dbvt_pairs, num_kdop_pairs);
+}
+
+fn unit_cube() -> KDOP<f64> {
+    KDOP::from_aabb(&AABB::new(
+        Point3::new(-1.0, -1.0, -1.0),
+        Point3::new(1.0, 1.0, 1.0),
+    ))
+}
+
+#[test]
+fn kdop_ray_cast() {
+    let kdop = unit_cube();
+    let id = Isometry3::identity();
+
+    let ray = Ray::new(Point3::new(-5.0, 0.5, 0.0), Vector3::x());
+    let inter = kdop.toi_and_normal_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 4.0);
+    assert_relative_eq!(inter.normal, -Vector3::x());
+
+    // From the inside.
+    let ray = Ray::new(Point3::origin(), Vector3::new(0.0, 0.0, 2.0));
+    assert_relative_eq!(kdop.toi_with_ray(&id, &ray, true).unwrap(), 0.0);
+    let inter = kdop.toi_and_normal_with_ray(&id, &ray, false).unwrap();
+    assert_relative_eq!(inter.toi, 0.5);
+    assert_relative_eq!(inter.normal, Vector3::z());
+
+    // The KDOP of a ball cuts its AABB corners.
+    let kdop: KDOP<f64> = Ball::new(1.0).bounding_volume(&id);
+    let dir = Vector3::new(-1.0, -1.0, 0.0);
+    let ray = Ray::new(Point3::new(5.0, 5.0, 0.0), dir);
+    let inter = kdop.toi_and_normal_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 5.0 - 1.0 / 2.0f64.sqrt());
+    assert_relative_eq!(inter.normal, -dir.normalize());
+
+    let ray = Ray::new(Point3::new(0.95, 0.95, 5.0), -Vector3::z());
+    assert!(kdop.aabb().intersects_ray(&id, &ray));
+    assert!(!kdop.intersects_ray(&id, &ray));
+
+    // Transformed.
+    let m = rotation();
+    let ray = Ray::new(m * Point3::new(-5.0, 0.5, 0.0), m * Vector3::x());
+    assert_relative_eq!(
+        unit_cube().toi_with_ray(&m, &ray, true).unwrap(),
+        4.0,
+        epsilon = 1.0e-7
+    );
+}
+
+#[test]
+fn kdop_point_projection() {
+    let kdop = unit_cube();
+    let id = Isometry3::identity();
+    let cases = [
+        (Point3::new(3.0, 0.5, 0.0), Point3::new(1.0, 0.5, 0.0)),
+        (Point3::new(3.0, 3.0, 0.0), Point3::new(1.0, 1.0, 0.0)),
+        (Point3::new(3.0, 3.0, 3.0), Point3::new(1.0, 1.0, 1.0)),
+        (Point3::new(0.9, 0.0, 0.2), Point3::new(1.0, 0.0, 0.2)),
+    ];
+
+    for (pt, expected) in cases.iter() {
+        let proj = kdop.project_point(&id, pt, false);
+        assert_relative_eq!(proj.point, *expected, epsilon = 1.0e-7);
+    }
+
+    assert!(
+        kdop.project_point(&id, &Point3::new(0.9, 0.0, 0.0), false)
+            .is_inside
+    );
+    assert!(kdop.contains_point(&id, &Point3::new(0.9, 0.0, 0.0)));
+    assert_relative_eq!(
+        kdop.distance_to_point(&id, &Point3::new(0.9, 0.0, 0.0), false),
+        -0.1,
+        epsilon = 1.0e-7
+    );
+
+    // Check the optimality of the projection on a KDOP with diagonal faces.
+    let sphere: TriMesh<f64> = procedural::sphere(2.0, 10, 10, true).into();
+    let kdop: KDOP<f64> = sphere.local_bounding_volume();
+
+    for i in 0..50 {
+        let t = i as f64;
+        let pt = Point3::new((t * 0.3).cos(), (t * 0.7).sin(), (t * 0.3).sin()) * 3.0;
+        let proj = kdop.project_point(&id, &pt, true).point;
+
+        assert!(kdop.loosened(1.0e-7).contains_local_point(&proj));
+
+        for vertex in sphere.points() {
+            assert!((pt - proj).dot(&(vertex - proj)) <= 1.0e-7);
+        }
+    }
+}
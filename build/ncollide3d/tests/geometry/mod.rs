@@ -1,10 +1,80 @@
+use na::RealField;
+use ncollide3d::math::Isometry;
+use ncollide3d::pipeline::{ContactDispatcher, DefaultContactDispatcher};
+use ncollide3d::query::{ContactManifold, ContactPrediction, ContactTrackingMode};
+use ncollide3d::shape::Shape;
+
 mod ball_ball_toi;
 mod ball_triangle_toi;
+mod bvt;
+mod compound;
 mod contact;
 mod cuboid_ray_cast;
 mod cylinder_cuboid_contact;
+mod deformable_trimesh;
+mod ellipsoid;
 mod epa3;
 mod interferences_with_ray;
+mod internal_edges;
+mod kdop;
+mod obb;
+mod polygon_triangulation;
+mod qbvh;
+mod round_shape;
+mod scaled;
+mod sdf_shape;
 mod still_objects_toi;
 mod time_of_impact3;
 mod trimesh_trimesh_toi;
+mod vhacd;
+mod voxels;
+
+/// The contacts between `g1` and `g2` computed by the contact generator of the default
+/// dispatcher, without any prediction.
+pub fn contacts<N: RealField>(
+    m1: &Isometry<N>,
+    g1: &dyn Shape<N>,
+    m2: &Isometry<N>,
+    g2: &dyn Shape<N>,
+) -> ContactManifold<N> {
+    generate_contacts(m1, g1, m2, g2, ContactManifold::new())
+}
+
+/// Same as `contacts`, but with feature-based contact tracking so that deeper contacts don't
+/// hide the others.
+pub fn feature_based_contacts<N: RealField>(
+    m1: &Isometry<N>,
+    g1: &dyn Shape<N>,
+    m2: &Isometry<N>,
+    g2: &dyn Shape<N>,
+) -> ContactManifold<N> {
+    let mut manifold = ContactManifold::new();
+    manifold.set_tracking_mode(ContactTrackingMode::FeatureBased);
+    generate_contacts(m1, g1, m2, g2, manifold)
+}
+
+fn generate_contacts<N: RealField>(
+    m1: &Isometry<N>,
+    g1: &dyn Shape<N>,
+    m2: &Isometry<N>,
+    g2: &dyn Shape<N>,
+    mut manifold: ContactManifold<N>,
+) -> ContactManifold<N> {
+    let dispatcher = DefaultContactDispatcher::new();
+    let mut generator = dispatcher.get_contact_algorithm(g1, g2).unwrap();
+    let prediction = ContactPrediction::new(N::zero(), N::zero(), N::zero());
+
+    manifold.save_cache_and_clear();
+    let _ = generator.generate_contacts(
+        &dispatcher,
+        m1,
+        g1,
+        None,
+        m2,
+        g2,
+        None,
+        &prediction,
+        &mut manifold,
+    );
+    manifold
+}
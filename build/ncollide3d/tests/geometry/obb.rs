@@ -0,0 +1,216 @@
+use na::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3};
+use ncollide3d::bounding_volume::{self, BoundingVolume, HasBoundingVolume, AABB, OBB};
+use ncollide3d::partitioning::{DBVTLeaf, BVH, BVT, DBVT};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::{
+    BoundingVolumeInterferencesCollector, RayInterferencesCollector,
+};
+use ncollide3d::query::{self, PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Cuboid, Shape, TriMesh, Triangle};
+
+fn volume(obb: &OBB<f64>) -> f64 {
+    obb.half_extents().iter().product::<f64>() * 8.0
+}
+
+fn plank_position() -> Isometry3<f64> {
+    Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.3, 0.8, -0.5))
+}
+
+fn plank_vertices() -> Vec<Point3<f64>> {
+    let obb = OBB::new(plank_position(), Vector3::new(5.0, 0.1, 0.2));
+    obb.vertices()
+}
+
+#[test]
+fn obb_of_rotated_plank() {
+    let plank = Cuboid::new(Vector3::new(5.0, 0.1, 0.2));
+    let m = plank_position();
+    let obb = plank.obb(&m);
+    let aabb = plank.aabb(&m);
+
+    assert_relative_eq!(volume(&obb), 8.0 * 5.0 * 0.1 * 0.2, epsilon = 1.0e-7);
+    assert!(aabb.extents().iter().product::<f64>() > volume(&obb) * 100.0);
+    assert!(obb.aabb().contains(&aabb) && aabb.contains(&obb.aabb()));
+
+    // Fitted from the vertices.
+    let vertices = plank_vertices();
+    let fitted = OBB::from_points(&vertices);
+    assert_relative_eq!(volume(&fitted), volume(&obb), epsilon = 1.0e-7);
+    assert_relative_eq!(fitted.center(), obb.center(), epsilon = 1.0e-7);
+
+    // Fitting from the points only is biased by their distribution, but not the hull fit.
+    let mut points = vertices.clone();
+    points.extend((0..200).map(|i| {
+        let t = i as f64 / 200.0;
+        m * Point3::new(4.9, 0.1 - t * 0.2, -0.2 + t * 0.1)
+    }));
+    let fitted = OBB::from_convex_hull(&points);
+    assert_relative_eq!(volume(&fitted), volume(&obb), epsilon = 1.0e-6);
+
+    for pt in &points {
+        assert!(fitted.loosened(1.0e-7).contains_local_point(pt));
+    }
+}
+
+#[test]
+fn obb_of_shapes() {
+    let m = plank_position();
+    let mesh: TriMesh<f64> = procedural::sphere(2.0, 10, 10, true).into();
+    let triangle = Triangle::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
+        Point3::new(1.0, 1.0, 1.0),
+    );
+    let shapes: [&dyn Shape<f64>; 2] = [&mesh, &triangle];
+
+    for shape in shapes.iter() {
+        let obb = shape.obb(&m);
+        let points: Vec<_> = if let Some(mesh) = shape.as_shape::<TriMesh<f64>>() {
+            mesh.points().to_vec()
+        } else {
+            triangle.vertices().to_vec()
+        };
+
+        for pt in &points {
+            assert!(obb.loosened(1.0e-7).contains_local_point(&(m * pt)));
+        }
+    }
+
+    // A triangle is flat.
+    let obb: OBB<f64> = bounding_volume::local_obb(&triangle);
+    assert!(obb.half_extents().min() < 1.0e-7);
+}
+
+#[test]
+fn obb_intersection_matches_distance() {
+    let c1 = Cuboid::new(Vector3::new(1.0, 0.2, 0.5));
+    let c2 = Cuboid::new(Vector3::new(0.1, 1.5, 0.3));
+
+    for i in 0..500 {
+        let t = i as f64;
+        let m1 = Isometry3::new(
+            Vector3::new((t * 0.1).sin(), (t * 0.2).cos(), (t * 0.3).sin()),
+            Vector3::new(t * 0.37, t * 0.13, t * 0.71),
+        );
+        let m2 = Isometry3::new(
+            Vector3::new((t * 0.7).cos(), (t * 0.5).sin(), (t * 0.11).cos()) * 2.0,
+            Vector3::new(t * 0.17, t * 0.53, t * 0.29),
+        );
+        let distance = query::distance(&m1, &c1, &m2, &c2);
+
+        if distance.abs() > 1.0e-4 {
+            assert_eq!(
+                c1.obb(&m1).intersects(&c2.obb(&m2)),
+                distance <= 0.0,
+                "{}",
+                i
+            );
+        }
+    }
+}
+
+#[test]
+fn obb_merge_and_contains() {
+    let obb1 = OBB::new(plank_position(), Vector3::new(5.0, 0.1, 0.2));
+    let obb2 = OBB::new(
+        Isometry3::new(Vector3::new(-1.0, 0.0, 0.0), Vector3::new(0.1, 0.0, 0.0)),
+        Vector3::new(0.5, 0.5, 0.5),
+    );
+    let merged = obb1.merged(&obb2);
+
+    assert!(merged.loosened(1.0e-7).contains(&obb1));
+    assert!(merged.loosened(1.0e-7).contains(&obb2));
+    assert!(!obb2.contains(&merged));
+    assert!(merged.loosened(1.0).contains(&merged));
+    assert!(!merged.contains(&merged.loosened(1.0)));
+}
+
+#[test]
+fn obb_ray_cast() {
+    let obb = OBB::new(
+        Isometry3::from_parts(
+            Translation3::new(0.0, 0.0, 0.0),
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), std::f64::consts::FRAC_PI_4),
+        ),
+        Vector3::new(5.0, 0.1, 0.1),
+    );
+    let id = Isometry3::identity();
+
+    // Along the diagonal of the plank.
+    let ray = Ray::new(Point3::new(10.0, 10.0, 0.0), Vector3::new(-1.0, -1.0, 0.0));
+    let toi = obb.toi_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(toi, 10.0 - 5.0 / 2.0f64.sqrt(), epsilon = 1.0e-7);
+
+    // This ray hits the AABB but not the OBB.
+    let ray = Ray::new(Point3::new(3.0, -3.0, 10.0), -Vector3::z());
+    assert!(obb.aabb().intersects_ray(&id, &ray));
+    assert!(!obb.intersects_ray(&id, &ray));
+
+    let inter = obb
+        .toi_and_normal_with_ray(
+            &id,
+            &Ray::new(Point3::new(0.0, -5.0, 0.0), Vector3::y()),
+            true,
+        )
+        .unwrap();
+    assert_relative_eq!(inter.normal, -obb.axis(1), epsilon = 1.0e-7);
+
+    assert!(obb.contains_point(&id, &Point3::new(2.0, 2.0, 0.0)));
+    assert!(!obb.contains_point(&id, &Point3::new(2.0, -2.0, 0.0)));
+}
+
+#[test]
+fn bvt_and_dbvt_with_obbs() {
+    let mesh: TriMesh<f64> = procedural::sphere(2.0, 12, 12, true).into();
+    let leaves: Vec<(usize, OBB<f64>)> = (0..mesh.faces().len())
+        .map(|i| (i, mesh.triangle_at(i).local_bounding_volume()))
+        .collect();
+    let bvt = BVT::new_balanced(leaves.clone());
+    let mut dbvt = DBVT::new();
+
+    for (i, obb) in &leaves {
+        let _ = dbvt.insert(DBVTLeaf::new(obb.clone(), *i));
+    }
+
+    for i in 0..20 {
+        let t = i as f64;
+        let dir = Vector3::new((t * 0.3).cos(), (t * 0.7).sin(), (t * 0.3).sin());
+        // Slightly shifted so it does not go through the vertices of the mesh exactly.
+        let ray = Ray::new(Point3::new(0.013, 0.021, -0.017) - dir * 5.0, dir);
+        let mut expected: Vec<_> = leaves
+            .iter()
+            .filter(|(_, obb)| obb.intersects_ray(&Isometry3::identity(), &ray))
+            .map(|(i, _)| *i)
+            .collect();
+        expected.sort();
+
+        let mut found = Vec::new();
+        bvt.visit(&mut RayInterferencesCollector::new(&ray, &mut found));
+        found.sort();
+        assert_eq!(found, expected);
+
+        found.clear();
+        dbvt.visit(&mut RayInterferencesCollector::new(&ray, &mut found));
+        found.sort();
+        assert_eq!(found, expected);
+
+        let query = OBB::from_aabb(
+            &AABB::from_half_extents(Point3::origin(), Vector3::new(2.0, 0.1, 0.1)),
+            &Isometry3::new(dir.normalize(), dir),
+        );
+        let mut expected: Vec<_> = leaves
+            .iter()
+            .filter(|(_, obb)| obb.intersects(&query))
+            .map(|(i, _)| *i)
+            .collect();
+        expected.sort();
+
+        let mut found = Vec::new();
+        dbvt.visit(&mut BoundingVolumeInterferencesCollector::new(
+            &query, &mut found,
+        ));
+        found.sort();
+        assert_eq!(found, expected);
+        assert!(!found.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use na::{Point2, Vector3};
+use ncollide3d::transformation;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+fn triangle_area(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>) -> f64 {
+    (b - a).perp(&(c - a)) / 2.0
+}
+
+fn angle_at(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>) -> f64 {
+    (a - b).angle(&(c - b))
+}
+
+#[test]
+fn polygon_triangulation_with_holes() {
+    let outline = [
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 4.0),
+        Point2::new(4.0, 4.0),
+        Point2::new(4.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(4.0, 0.0),
+    ];
+    let hole1 = [
+        Point2::new(0.5, 0.5),
+        Point2::new(1.5, 0.5),
+        Point2::new(1.5, 1.5),
+        Point2::new(0.5, 1.5),
+    ];
+    let hole2 = [
+        Point2::new(1.0, 2.5),
+        Point2::new(3.0, 3.0),
+        Point2::new(1.0, 3.5),
+    ];
+    let mesh = transformation::polygon_triangulation(&outline, &[&hole1, &hole2]);
+    let points: Vec<_> = mesh.coords.iter().map(|p| p.xy()).collect();
+    assert!(mesh.coords.iter().all(|p| p.z == 0.0));
+    assert!(mesh
+        .normals
+        .as_ref()
+        .unwrap()
+        .iter()
+        .all(|n| *n == Vector3::z()));
+
+    let triangles = mesh.indices.unwrap_unified();
+    assert_eq!(points.len(), 13);
+    assert_eq!(triangles.len(), 13 + 2 * 2 - 2);
+
+    let mut edges = HashMap::new();
+    let mut total_area = 0.0;
+
+    for t in &triangles {
+        let (a, b, c) = (
+            &points[t.x as usize],
+            &points[t.y as usize],
+            &points[t.z as usize],
+        );
+        let area = triangle_area(a, b, c);
+        assert!(area > 0.0);
+        total_area += area;
+
+        for k in 0..3 {
+            let _ = edges.insert((t[k], t[(k + 1) % 3]), t[(k + 2) % 3]);
+        }
+    }
+
+    assert_relative_eq!(total_area, 16.0 - 2.0 - 1.0 - 1.0, epsilon = 1.0e-7);
+
+    // The edges of the outline and of the holes are kept.
+    let loops = [0..6u32, 6..10, 10..13];
+
+    for l in loops.iter().cloned() {
+        for i in l.clone() {
+            let j = if i + 1 == l.end { l.start } else { i + 1 };
+            assert!(edges.contains_key(&(i, j)) != edges.contains_key(&(j, i)));
+        }
+    }
+
+    // The triangulation is locally Delaunay across the other edges.
+    for (&(a, b), &c) in &edges {
+        if let Some(&d) = edges.get(&(b, a)) {
+            let (pa, pb) = (&points[a as usize], &points[b as usize]);
+            let (pc, pd) = (&points[c as usize], &points[d as usize]);
+            assert!(angle_at(pa, pc, pb) + angle_at(pa, pd, pb) <= PI + 1.0e-7);
+        }
+    }
+}
+
+#[test]
+fn polygon_triangulation_of_a_convex_polygon_is_delaunay() {
+    // Points on an ellipse, where ear clipping alone creates a fan of thin triangles.
+    let outline: Vec<_> = (0..40)
+        .map(|i| {
+            let angle = i as f64 * PI * 2.0 / 40.0;
+            Point2::new(angle.cos() * 10.0, angle.sin())
+        })
+        .collect();
+    let triangles = transformation::polygon_triangulation_idx(&outline, &[]);
+    assert_eq!(triangles.len(), 38);
+
+    let mut edges = HashMap::new();
+
+    for t in &triangles {
+        for k in 0..3 {
+            let _ = edges.insert((t[k], t[(k + 1) % 3]), t[(k + 2) % 3]);
+        }
+    }
+
+    for (&(a, b), &c) in &edges {
+        if let Some(&d) = edges.get(&(b, a)) {
+            let (pa, pb) = (&outline[a], &outline[b]);
+            let (pc, pd) = (&outline[c], &outline[d]);
+            assert!(angle_at(pa, pc, pb) + angle_at(pa, pd, pb) <= PI + 1.0e-7);
+        }
+    }
+
+    let total_area: f64 = triangles
+        .iter()
+        .map(|t| triangle_area(&outline[t.x], &outline[t.y], &outline[t.z]))
+        .sum();
+    let expected: f64 = (0..40)
+        .map(|i| triangle_area(&Point2::origin(), &outline[i], &outline[(i + 1) % 40]))
+        .sum();
+    assert_relative_eq!(total_area, expected, epsilon = 1.0e-7);
+}
@@ -0,0 +1,174 @@
+use na::{Isometry3, Point3, Translation3, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{VisitStatus, BVH, BVT, QBVH};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::RayInterferencesCollector;
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{DeformableShape, TriMesh};
+use std::f64;
+
+fn leaves() -> Vec<(usize, AABB<f64>)> {
+    (0..500)
+        .map(|i| {
+            let t = i as f64;
+            let center = Point3::new((t * 0.37).sin(), (t * 1.13).cos(), (t * 0.71).sin()) * 10.0;
+            (i, AABB::from_half_extents(center, Vector3::repeat(0.5)))
+        })
+        .collect()
+}
+
+// Checks that each node encloses its children and returns the depth of the hierarchy.
+fn check_qbvh(qbvh: &QBVH<f64, usize>, node: usize, found: &mut Vec<usize>) -> usize {
+    let (bv, data) = qbvh.content(node);
+
+    if let Some(data) = data {
+        assert_eq!(qbvh.num_children(node), 0);
+        found.push(*data);
+        return 1;
+    }
+
+    let nchildren = qbvh.num_children(node);
+    assert!(nchildren >= 2 && nchildren <= 4);
+
+    let mut depth = 0;
+
+    for i in 0..nchildren {
+        let child = qbvh.child(i, node);
+        assert!(bv.contains(qbvh.content(child).0));
+        depth = depth.max(check_qbvh(qbvh, child, found) + 1);
+    }
+
+    depth
+}
+
+fn bvt_depth(
+    bvt: &BVT<usize, AABB<f64>>,
+    node: <BVT<usize, AABB<f64>> as BVH<usize, AABB<f64>>>::Node,
+) -> usize {
+    (0..bvt.num_children(node))
+        .map(|i| bvt_depth(bvt, bvt.child(i, node)) + 1)
+        .max()
+        .unwrap_or(1)
+}
+
+#[test]
+fn qbvh_from_bvt() {
+    let leaves = leaves();
+    let bvt = BVT::new_with_sah(leaves.clone());
+    let qbvh = QBVH::from_bvt(&bvt);
+
+    assert_eq!(qbvh.num_leaves(), leaves.len());
+    assert_eq!(qbvh.root_bounding_volume(), bvt.root_bounding_volume());
+
+    let mut found = Vec::new();
+    let depth = check_qbvh(&qbvh, qbvh.root().unwrap(), &mut found);
+    found.sort();
+    assert_eq!(found, (0..leaves.len()).collect::<Vec<_>>());
+    assert!(depth < bvt_depth(&bvt, bvt.root().unwrap()));
+
+    for i in 0..50 {
+        let t = i as f64;
+        let dir = Vector3::new((t * 0.3).cos(), (t * 0.7).sin(), (t * 0.3).sin());
+        let ray = Ray::new(Point3::origin() - dir * 20.0, dir);
+        let mut expected = Vec::new();
+        let mut result = Vec::new();
+        bvt.visit(&mut RayInterferencesCollector::new(&ray, &mut expected));
+        qbvh.visit(&mut RayInterferencesCollector::new(&ray, &mut result));
+        expected.sort();
+        result.sort();
+        assert_eq!(result, expected);
+    }
+
+    // Degenerate hierarchies.
+    let empty = QBVH::from_bvt(&BVT::<usize, AABB<f64>>::new_with_sah(Vec::new()));
+    assert!(empty.root().is_none());
+    let single = QBVH::from_bvt(&BVT::new_with_sah(leaves[..1].to_vec()));
+    assert_eq!(single.content(single.root().unwrap()).1, Some(&0));
+}
+
+#[test]
+fn qbvh_quad_traversals() {
+    let leaves = leaves();
+    let bvt = BVT::new_with_sah(leaves.clone());
+    let qbvh = QBVH::from_bvt(&bvt);
+    let id = Isometry3::identity();
+
+    for i in 0..50 {
+        let t = i as f64;
+        let dir = Vector3::new((t * 0.3).cos(), 0.0, (t * 0.3).sin());
+        let ray = Ray::new(Point3::origin() - dir * 20.0, dir);
+
+        // The leaves hit by the ray, sorted by time of impact.
+        let mut hits: Vec<_> = leaves
+            .iter()
+            .filter_map(|(i, aabb)| aabb.toi_with_ray(&id, &ray, true).map(|toi| (toi, *i)))
+            .collect();
+        hits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut found = Vec::new();
+        qbvh.quad_visit(
+            |aabbs| {
+                let tois = aabbs.toi_with_ray(&ray);
+                [
+                    tois[0] != f64::MAX,
+                    tois[1] != f64::MAX,
+                    tois[2] != f64::MAX,
+                    tois[3] != f64::MAX,
+                ]
+            },
+            |i| {
+                found.push(*i);
+                VisitStatus::Continue
+            },
+        );
+        found.sort();
+        let mut expected: Vec<_> = hits.iter().map(|hit| hit.1).collect();
+        expected.sort();
+        assert_eq!(found, expected);
+
+        let first = qbvh.quad_best_first_search(
+            |aabbs| aabbs.toi_with_ray(&ray),
+            |_, i| Some((leaves[*i].1.toi_with_ray(&id, &ray, true)?, *i)),
+        );
+        assert_eq!(first, hits.first().map(|hit| hit.1));
+
+        let pt = Point3::new(t.sin(), t.cos(), (t * 0.5).sin()) * 15.0;
+        let closest = qbvh.quad_best_first_search(
+            |aabbs| aabbs.distance_to_point(&pt),
+            |_, i| Some((leaves[*i].1.distance_to_point(&id, &pt, true), *i)),
+        );
+        let expected = leaves
+            .iter()
+            .map(|(i, aabb)| (aabb.distance_to_point(&id, &pt, true), *i))
+            .min_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(closest, expected.map(|closest| closest.1));
+    }
+}
+
+#[test]
+fn trimesh_queries_follow_deformations() {
+    let mut mesh: TriMesh<f64> = procedural::sphere(2.0, 20, 20, true).into();
+    let id = Isometry3::identity();
+    let ray = Ray::new(Point3::new(3.0, 0.1, 0.2), -Vector3::x());
+
+    let toi = mesh.toi_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(toi, 2.0, epsilon = 5.0e-2);
+    let proj = mesh.project_point(&id, &Point3::new(-3.0, 0.0, 0.0), true);
+    assert_relative_eq!(proj.point, Point3::new(-1.0, 0.0, 0.0), epsilon = 5.0e-2);
+
+    // Move all the vertices along `z`.
+    let shift = Translation3::new(0.0, 0.0, 3.0);
+    let coords: Vec<f64> = mesh
+        .points()
+        .iter()
+        .flat_map(|pt| (shift * pt).coords.iter().cloned().collect::<Vec<_>>())
+        .collect();
+    mesh.set_deformations(&coords);
+
+    let ray = Ray::new(Point3::new(3.0, 0.1, 3.2), -Vector3::x());
+    let toi = mesh.toi_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(toi, 2.0, epsilon = 5.0e-2);
+
+    let proj = mesh.project_point(&id, &Point3::new(-3.0, 0.0, 3.0), true);
+    assert_relative_eq!(proj.point, Point3::new(-1.0, 0.0, 3.0), epsilon = 5.0e-2);
+}
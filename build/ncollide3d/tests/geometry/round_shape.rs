@@ -0,0 +1,128 @@
+use super::contacts;
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Ball, Cuboid, RoundShape, Shape};
+
+fn rounded_cube() -> RoundShape<f32, Cuboid<f32>> {
+    RoundShape::new(Cuboid::new(Vector3::repeat(1.0)), 0.5)
+}
+
+#[test]
+fn round_shape_bounding_volumes() {
+    let shape = rounded_cube();
+    let aabb = shape.local_aabb();
+
+    assert_relative_eq!(*aabb.mins(), Point3::new(-1.5, -1.5, -1.5));
+    assert_relative_eq!(*aabb.maxs(), Point3::new(1.5, 1.5, 1.5));
+    assert_relative_eq!(
+        shape.local_bounding_sphere().radius(),
+        3.0f32.sqrt() + 0.5,
+        epsilon = 1.0e-6
+    );
+}
+
+#[test]
+fn round_shape_ray_cast() {
+    let shape = rounded_cube();
+    let id = Isometry3::identity();
+
+    let ray = Ray::new(Point3::new(5.0, 0.2, 0.0), -Vector3::x());
+    let toi = shape.toi_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(toi, 3.5, epsilon = 1.0e-4);
+
+    // Toward a rounded corner.
+    let dir = -Vector3::repeat(1.0).normalize();
+    let ray = Ray::new(Point3::new(3.0, 3.0, 3.0), dir);
+    let inter = shape.toi_and_normal_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 2.0 * 3.0f32.sqrt() - 0.5, epsilon = 1.0e-4);
+    assert_relative_eq!(inter.normal, -dir, epsilon = 1.0e-4);
+}
+
+#[test]
+fn round_shape_point_projection() {
+    let shape = rounded_cube();
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.3, 0.2, 0.1));
+    let diag = Vector3::repeat(1.0).normalize();
+
+    let corner = Point3::new(3.0, 3.0, 3.0);
+    let proj = shape.project_point(&m, &(m * corner), true);
+    assert!(!proj.is_inside);
+    assert_relative_eq!(
+        proj.point,
+        m * (Point3::new(1.0, 1.0, 1.0) + diag * 0.5),
+        epsilon = 1.0e-5
+    );
+
+    let rounded_part = Point3::new(1.2, 0.3, 0.0);
+    let proj = shape.project_point(&m, &(m * rounded_part), false);
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::new(1.5, 0.3, 0.0), epsilon = 1.0e-5);
+
+    let inside_core = Point3::new(0.9, 0.0, 0.0);
+    let proj = shape.project_point(&m, &(m * inside_core), false);
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::new(1.5, 0.0, 0.0), epsilon = 1.0e-5);
+    assert!(shape.contains_point(&m, &(m * inside_core)));
+}
+
+#[test]
+fn round_shape_cuboid_contacts() {
+    let shape = rounded_cube();
+    let ground = Cuboid::new(Vector3::new(5.0, 1.0, 5.0));
+    let m1 = Isometry3::identity();
+    let m2 = Isometry3::new(Vector3::y() * -2.4, na::zero());
+
+    for flip in &[false, true] {
+        let manifold = if *flip {
+            contacts(&m2, &ground, &m1, &shape)
+        } else {
+            contacts(&m1, &shape, &m2, &ground)
+        };
+
+        assert_eq!(manifold.len(), 4);
+
+        for tracked in manifold.contacts() {
+            let c = &tracked.contact;
+            let (round_pt, ground_pt) = if *flip {
+                (c.world2, c.world1)
+            } else {
+                (c.world1, c.world2)
+            };
+
+            assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-5);
+            assert_relative_eq!(round_pt.y, -1.5, epsilon = 1.0e-5);
+            assert_relative_eq!(ground_pt.y, -1.4, epsilon = 1.0e-5);
+        }
+    }
+}
+
+#[test]
+fn round_shape_ball_and_round_shape_contacts() {
+    let shape = rounded_cube();
+    let diag = Vector3::repeat(1.0).normalize();
+    let m1 = Isometry3::identity();
+
+    // A ball touching a rounded corner.
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::repeat(1.0) + diag * 0.9, na::zero());
+    let manifold = contacts(&m1, &shape, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-5);
+    assert_relative_eq!(*c.normal, diag, epsilon = 1.0e-5);
+    assert_relative_eq!(
+        c.world1,
+        Point3::new(1.0, 1.0, 1.0) + diag * 0.5,
+        epsilon = 1.0e-5
+    );
+
+    // Two rounded cubes with their edges touching.
+    let m2 = Isometry3::new(Vector3::new(2.6, 2.6, 0.0), na::zero());
+    let manifold = contacts(&m1, &shape, &m2, &shape);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    let expected_depth = 1.0 - (0.6f32 * 0.6 * 2.0).sqrt();
+
+    assert_relative_eq!(c.depth, expected_depth, epsilon = 1.0e-5);
+    assert_relative_eq!(c.normal.z, 0.0, epsilon = 1.0e-5);
+    assert_relative_eq!(c.normal.x, c.normal.y, epsilon = 1.0e-5);
+}
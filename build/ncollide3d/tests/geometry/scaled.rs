@@ -0,0 +1,274 @@
+use super::contacts;
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::procedural;
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{
+    Ball, Compound, Cuboid, Ellipsoid, Plane, Scaled, Shape, ShapeHandle, SupportMap, TriMesh,
+    Voxels,
+};
+use std::f64;
+
+// A square of half-width 1 on the plane `y = 0`.
+fn square() -> TriMesh<f64> {
+    let points = vec![
+        Point3::new(-1.0, 0.0, -1.0),
+        Point3::new(1.0, 0.0, -1.0),
+        Point3::new(1.0, 0.0, 1.0),
+        Point3::new(-1.0, 0.0, 1.0),
+    ];
+    let indices = vec![Point3::new(0, 2, 1), Point3::new(0, 3, 2)];
+    TriMesh::new(points, indices, None)
+}
+
+#[test]
+fn scaled_ball_matches_ellipsoid() {
+    let radii = Vector3::new(2.0, 0.5, 1.0);
+    let scaled = Scaled::new(Ball::new(1.0), radii);
+    let ellipsoid = Ellipsoid::new(radii);
+    let m = Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.3, 1.2));
+
+    for i in 0..50 {
+        let t = i as f64;
+        let dir = Vector3::new((t * 1.3).sin(), (t * 0.7).cos(), (t * 2.9).sin());
+        assert_relative_eq!(
+            scaled.support_point(&m, &dir),
+            ellipsoid.support_point(&m, &dir),
+            epsilon = 1.0e-7
+        );
+
+        let origin = m * Point3::from(dir * 3.0);
+        let ray = Ray::new(origin, m.translation.vector - origin.coords);
+        let inter = scaled.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+        let expected = ellipsoid.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+        assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-7);
+        assert_relative_eq!(inter.normal, expected.normal, epsilon = 1.0e-7);
+
+        let proj = scaled.project_point(&m, &origin, true);
+        let expected = ellipsoid.project_point(&m, &origin, true);
+        assert!(!proj.is_inside);
+        // GJK only converges approximately on curved surfaces.
+        let ls_proj = m.inverse_transform_point(&proj.point);
+        assert_relative_eq!(
+            ls_proj.coords.component_div(&radii).norm(),
+            1.0,
+            epsilon = 1.0e-5
+        );
+        assert_relative_eq!(
+            na::distance(&origin, &proj.point),
+            na::distance(&origin, &expected.point),
+            epsilon = 1.0e-3
+        );
+    }
+
+    let aabb = scaled.aabb(&m);
+    let expected = ellipsoid.aabb(&m);
+    assert_relative_eq!(aabb.mins(), expected.mins(), epsilon = 1.0e-7);
+    assert_relative_eq!(aabb.maxs(), expected.maxs(), epsilon = 1.0e-7);
+    assert!(scaled.contains_point(&m, &(m * Point3::new(1.9, 0.0, 0.0))));
+    assert!(!scaled.contains_point(&m, &(m * Point3::new(0.0, 0.6, 0.0))));
+}
+
+#[test]
+fn scaled_cuboid_contacts() {
+    let scaled = Scaled::new(
+        Cuboid::new(Vector3::repeat(1.0f64)),
+        Vector3::new(2.0, 0.5, 3.0),
+    );
+    let ground = Cuboid::new(Vector3::new(5.0, 1.0, 5.0));
+    let m1 = Isometry3::identity();
+    let m2 = Isometry3::new(Vector3::y() * -1.4, na::zero());
+
+    assert!(scaled.is_convex_polyhedron());
+    let aabb = scaled.local_aabb();
+    assert_relative_eq!(*aabb.maxs(), Point3::new(2.0, 0.5, 3.0));
+
+    for flip in &[false, true] {
+        let manifold = if *flip {
+            contacts(&m2, &ground, &m1, &scaled)
+        } else {
+            contacts(&m1, &scaled, &m2, &ground)
+        };
+
+        assert_eq!(manifold.len(), 4);
+
+        for tracked in manifold.contacts() {
+            let c = &tracked.contact;
+            let scaled_pt = if *flip { c.world2 } else { c.world1 };
+
+            assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-7);
+            assert_relative_eq!(scaled_pt.y, -0.5, epsilon = 1.0e-7);
+            assert_relative_eq!(scaled_pt.x.abs(), 2.0, epsilon = 1.0e-7);
+            assert_relative_eq!(scaled_pt.z.abs(), 3.0, epsilon = 1.0e-7);
+        }
+    }
+}
+
+#[test]
+fn scaled_ball_cuboid_contact() {
+    let scaled = Scaled::new(Ball::new(1.0), Vector3::new(2.0, 0.5, 1.0));
+    let ground = Cuboid::new(Vector3::new(5.0, 1.0, 5.0));
+    let m1 = Isometry3::new(Vector3::new(0.3, 0.0, 0.0), na::zero());
+    let m2 = Isometry3::new(Vector3::y() * -1.4, na::zero());
+
+    let manifold = contacts(&m1, &scaled, &m2, &ground);
+    assert_eq!(manifold.len(), 1);
+
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-6);
+    assert_relative_eq!(*c.normal, -Vector3::y(), epsilon = 1.0e-6);
+    assert_relative_eq!(c.world1, Point3::new(0.3, -0.5, 0.0), epsilon = 1.0e-5);
+}
+
+#[test]
+fn scaled_shared_trimesh() {
+    let mesh = ShapeHandle::new(square());
+    let small = Scaled::new(mesh.clone(), Vector3::repeat(1.0));
+    let large = Scaled::new(mesh, Vector3::new(3.0, 1.0, 2.0));
+    let id = Isometry3::identity();
+
+    assert!(large.is_composite_shape());
+    let aabb = large.local_aabb();
+    assert_relative_eq!(*aabb.mins(), Point3::new(-3.0, 0.0, -2.0));
+    assert_relative_eq!(*aabb.maxs(), Point3::new(3.0, 0.0, 2.0));
+
+    // Ray casts.
+    let ray = Ray::new(Point3::new(2.5, 5.0, 1.5), -Vector3::y());
+    assert!(small.toi_with_ray(&id, &ray, true).is_none());
+    let inter = large.toi_and_normal_with_ray(&id, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 5.0);
+    assert_relative_eq!(inter.normal, Vector3::y());
+
+    // Point projection.
+    let proj = large.project_point(&id, &Point3::new(2.5, 1.0, 1.5), true);
+    assert_relative_eq!(proj.point, Point3::new(2.5, 0.0, 1.5));
+
+    // Contacts with the parts of the scaled mesh.
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::new(2.5, 0.4, 1.5), na::zero());
+    let manifold = contacts(&id, &large, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-7);
+    assert_relative_eq!(*c.normal, Vector3::y(), epsilon = 1.0e-7);
+    // The ball also touches the diagonal edge of the other triangle with the same depth, so
+    // the contact point on the mesh depends on the order the triangles are visited.
+    assert_relative_eq!(c.world1.y, 0.0, epsilon = 1.0e-7);
+    assert_relative_eq!(c.world2, Point3::new(2.5, -0.1, 1.5), epsilon = 1.0e-7);
+
+    assert_eq!(contacts(&id, &small, &m2, &ball).len(), 0);
+    let m2 = Isometry3::new(Vector3::new(3.5, 0.4, 0.0), na::zero());
+    assert_eq!(contacts(&id, &large, &m2, &ball).len(), 0);
+}
+
+#[test]
+fn scaled_trimesh_barycentric_coordinates() {
+    let mesh = square();
+    let scale = Vector3::new(3.0, 1.0, 2.0);
+    let scaled = Scaled::new(mesh.clone(), scale);
+    let id = Isometry3::identity();
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::new(2.5, 0.4, -1.0), na::zero());
+
+    let manifold = contacts(&id, &scaled, &m2, &ball);
+    let tracked = manifold.deepest_contact().unwrap();
+    assert_relative_eq!(
+        tracked.contact.world1,
+        Point3::new(2.5, 0.0, -1.0),
+        epsilon = 1.0e-7
+    );
+
+    // The barycentric coordinates locate the contact point on the triangle of the inner mesh.
+    let (face, coords) = tracked.kinematic.barycentric_coordinates1().unwrap();
+    let indices = mesh.faces()[face].indices;
+    let inner_pt = mesh.points()[indices.x] * coords[0]
+        + mesh.points()[indices.y].coords * coords[1]
+        + mesh.points()[indices.z].coords * coords[2];
+    assert_relative_eq!(
+        Point3::from(inner_pt.coords.component_mul(&scale)),
+        tracked.contact.world1,
+        epsilon = 1.0e-7
+    );
+}
+
+#[test]
+fn non_uniformly_scaled_trimesh_point_projection() {
+    let cube = TriMesh::from(procedural::cuboid(&Vector3::repeat(2.0)));
+    let scaled = Scaled::new(cube, Vector3::new(10.0, 1.0, 1.0));
+    let m = Isometry3::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.4, -0.3, 1.2));
+
+    // The closest face of the scaled cube is not the image of the closest face of the cube.
+    let pt = m * Point3::new(9.0, 0.0, 0.5);
+    assert_relative_eq!(
+        scaled.distance_to_point(&m, &pt, false),
+        0.5,
+        epsilon = 1.0e-7
+    );
+    let proj = scaled.project_point(&m, &pt, false);
+    assert_relative_eq!(proj.point, m * Point3::new(9.0, 0.0, 1.0), epsilon = 1.0e-7);
+
+    let pt = m * Point3::new(10.5, 0.2, 0.0);
+    assert_relative_eq!(
+        scaled.distance_to_point(&m, &pt, false),
+        0.5,
+        epsilon = 1.0e-7
+    );
+}
+
+#[test]
+fn scaled_voxels() {
+    let cells = vec![Point3::new(0, 0, 0), Point3::new(1, 0, 0)];
+    let voxels = Voxels::with_voxels(Vector3::repeat(1.0), cells);
+    let scaled = Scaled::new(voxels, Vector3::new(2.0, 1.0, 1.0));
+    let id = Isometry3::identity();
+
+    let aabb = scaled.local_aabb();
+    assert_relative_eq!(*aabb.maxs(), Point3::new(4.0, 1.0, 1.0));
+
+    let ray = Ray::new(Point3::new(3.5, 5.0, 0.5), -Vector3::y());
+    assert_relative_eq!(scaled.toi_with_ray(&id, &ray, true).unwrap(), 4.0);
+
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::new(4.4, 0.5, 0.5), na::zero());
+    let manifold = contacts(&id, &scaled, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-7);
+    assert_relative_eq!(*c.normal, Vector3::x(), epsilon = 1.0e-7);
+    assert_relative_eq!(c.world1, Point3::new(4.0, 0.5, 0.5), epsilon = 1.0e-7);
+}
+
+fn compound_with_rotated_part() -> Compound<f64> {
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(1.0, 0.5, 0.5)));
+    let pos = Isometry3::new(Vector3::x() * 2.0, Vector3::z() * f64::consts::FRAC_PI_2);
+    Compound::new(vec![(pos, cuboid)])
+}
+
+#[test]
+fn uniformly_scaled_compound_with_rotated_parts() {
+    let scaled = Scaled::new(compound_with_rotated_part(), Vector3::repeat(2.0));
+    let id = Isometry3::identity();
+
+    assert!(scaled.is_composite_shape());
+    // The AABBs of the parts of a compound shape are slightly loosened.
+    let aabb = scaled.local_aabb();
+    assert_relative_eq!(*aabb.mins(), Point3::new(3.0, -2.0, -1.0), epsilon = 0.1);
+    assert_relative_eq!(*aabb.maxs(), Point3::new(5.0, 2.0, 1.0), epsilon = 0.1);
+
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::new(4.0, 2.4, 0.0), na::zero());
+    let manifold = contacts(&id, &scaled, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-7);
+    assert_relative_eq!(*c.normal, Vector3::y(), epsilon = 1.0e-7);
+}
+
+#[test]
+#[should_panic]
+fn non_uniformly_scaled_compound_with_rotated_parts() {
+    let _ = Scaled::new(compound_with_rotated_part(), Vector3::new(2.0, 1.0, 1.0));
+}
+
+#[test]
+#[should_panic]
+fn scaled_shared_plane() {
+    let plane = ShapeHandle::new(Plane::new(Vector3::y_axis()));
+    let _ = Scaled::new(plane, Vector3::new(2.0, 1.0, 1.0));
+}
@@ -0,0 +1,154 @@
+use super::contacts;
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Ball, ConvexHull, Cuboid, SDFShape};
+
+// The signed distance field of a ball with radius 1.
+fn ball_sdf() -> SDFShape<f64> {
+    let aabb = AABB::new(Point3::new(-2.0, -2.0, -2.0), Point3::new(2.0, 2.0, 2.0));
+    SDFShape::from_point_query(&Ball::new(1.0), aabb, Vector3::repeat(41))
+}
+
+#[test]
+fn sdf_shape_distance_and_gradient() {
+    let sdf = ball_sdf();
+    assert_eq!(sdf.values().len(), 41 * 41 * 41);
+    assert_relative_eq!(*sdf.spacing(), Vector3::repeat(0.1), epsilon = 1.0e-10);
+
+    // Exact at the samples.
+    assert_relative_eq!(sdf.distance_at(&Point3::new(0.0, 1.5, 0.0)), 0.5);
+    assert_relative_eq!(sdf.distance_at(&Point3::origin()), -1.0, epsilon = 1.0e-10);
+
+    let pt = Point3::new(0.33, -0.71, 0.52);
+    assert_relative_eq!(
+        sdf.distance_at(&pt),
+        pt.coords.norm() - 1.0,
+        epsilon = 1.0e-2
+    );
+    assert_relative_eq!(
+        sdf.gradient_at(&pt).normalize(),
+        pt.coords.normalize(),
+        epsilon = 1.0e-2
+    );
+
+    // Outside of the grid.
+    assert_relative_eq!(
+        sdf.distance_at(&Point3::new(5.0, 0.0, 0.0)),
+        4.0,
+        epsilon = 1.0e-10
+    );
+    assert_relative_eq!(
+        sdf.gradient_at(&Point3::new(5.0, 0.0, 0.0)).normalize(),
+        Vector3::x(),
+        epsilon = 1.0e-10
+    );
+}
+
+#[test]
+fn sdf_shape_point_query() {
+    let sdf = ball_sdf();
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+    let dir = Vector3::new(1.0, 2.0, -0.5).normalize();
+
+    let proj = sdf.project_point(&m, &(m * Point3::from(dir * 1.7)), true);
+    assert!(!proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::from(dir), epsilon = 1.0e-2);
+
+    let pt = m * Point3::from(dir * 0.4);
+    assert!(sdf.contains_point(&m, &pt));
+    assert_eq!(sdf.project_point(&m, &pt, true).point, pt);
+    assert_eq!(sdf.distance_to_point(&m, &pt, true), 0.0);
+
+    let proj = sdf.project_point(&m, &pt, false);
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::from(dir), epsilon = 1.0e-2);
+    assert_relative_eq!(
+        sdf.distance_to_point(&m, &pt, false),
+        -0.6,
+        epsilon = 1.0e-2
+    );
+}
+
+#[test]
+fn sdf_shape_ray_cast() {
+    let sdf = ball_sdf();
+    let ball = Ball::new(1.0);
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    for i in 0..50 {
+        let t = i as f64;
+        let origin = Point3::new((t * 1.3).sin(), (t * 0.7).cos(), (t * 2.9).sin()) * 4.0;
+        let target = Point3::new((t * 0.3).cos(), (t * 1.1).sin(), (t * 1.9).sin()) * 0.5;
+        let ray = Ray::new(m * origin, m * (target - origin));
+
+        let inter = sdf.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+        let expected = ball.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+        let len = ray.dir.norm();
+        assert_relative_eq!(inter.toi * len, expected.toi * len, epsilon = 1.0e-2);
+        assert_relative_eq!(inter.normal, expected.normal, epsilon = 2.0e-2);
+    }
+
+    // From the inside.
+    let ray = Ray::new(m * Point3::new(0.2, 0.0, 0.0), m * Vector3::x());
+    assert_eq!(sdf.toi_with_ray(&m, &ray, true), Some(0.0));
+    let inter = sdf.toi_and_normal_with_ray(&m, &ray, false).unwrap();
+    assert_relative_eq!(inter.toi, 0.8, epsilon = 1.0e-2);
+    assert_relative_eq!(inter.normal, m * -Vector3::x(), epsilon = 1.0e-2);
+
+    // Missing the shape.
+    let ray = Ray::new(m * Point3::new(-3.0, 1.5, 0.0), m * Vector3::x());
+    assert!(sdf.toi_with_ray(&m, &ray, true).is_none());
+}
+
+#[test]
+fn sdf_shape_contacts() {
+    let sdf = ball_sdf();
+    let id = Isometry3::identity();
+
+    // A ball sinking into the field, on both sides of the dispatcher.
+    let ball = Ball::new(0.5);
+    let m2 = Isometry3::new(Vector3::new(0.0, 1.3, 0.0), na::zero());
+
+    for flip in &[false, true] {
+        let manifold = if *flip {
+            contacts(&m2, &ball, &id, &sdf)
+        } else {
+            contacts(&id, &sdf, &m2, &ball)
+        };
+
+        assert_eq!(manifold.len(), 1);
+        let c = &manifold.deepest_contact().unwrap().contact;
+        let normal = if *flip { -*c.normal } else { *c.normal };
+        assert_relative_eq!(normal, Vector3::y(), epsilon = 1.0e-2);
+        assert_relative_eq!(c.depth, 0.2, epsilon = 1.0e-2);
+    }
+
+    // A cube resting on the field with one of its vertices.
+    let cuboid = Cuboid::new(Vector3::repeat(0.25));
+    let m2 = Isometry3::new(Vector3::new(0.0, 1.4, 0.0), Vector3::new(0.6, 0.0, 0.6155));
+    let manifold = contacts(&id, &sdf, &m2, &cuboid);
+    assert!(manifold.len() > 0);
+
+    for tracked in manifold.contacts() {
+        let c = &tracked.contact;
+        assert!(c.depth > 0.0);
+        assert_relative_eq!(sdf.distance_at(&c.world2), -c.depth, epsilon = 1.0e-2);
+    }
+
+    // The vertices of a convex hull are sampled.
+    let points = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    ];
+    let hull = ConvexHull::try_from_points(&points).unwrap();
+    let m2 = Isometry3::new(Vector3::new(0.9, 0.0, 0.0), na::zero());
+    let manifold = contacts(&id, &sdf, &m2, &hull);
+    assert_eq!(manifold.len(), 1);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(c.world2, Point3::new(0.9, 0.0, 0.0), epsilon = 1.0e-10);
+    assert_relative_eq!(*c.normal, Vector3::x(), epsilon = 1.0e-2);
+    assert_relative_eq!(c.depth, 0.1, epsilon = 1.0e-2);
+}
@@ -0,0 +1,105 @@
+use na::{Isometry3, Point3, Translation3, Vector3};
+use ncollide3d::procedural::{self, IndexBuffer, TriMesh};
+use ncollide3d::query::PointQuery;
+use ncollide3d::shape::ConvexHull;
+use ncollide3d::transformation::{self, VHACDParameters};
+
+// A U shape made of three overlapping boxes, without removing their inner faces.
+fn u_shape() -> TriMesh<f64> {
+    let boxes = [
+        (Vector3::new(3.0, 1.0, 1.0), Vector3::new(1.5, 0.5, 0.5)),
+        (Vector3::new(1.0, 3.0, 1.0), Vector3::new(0.5, 1.5, 0.5)),
+        (Vector3::new(1.0, 3.0, 1.0), Vector3::new(2.5, 1.5, 0.5)),
+    ];
+    let mut coords = Vec::new();
+    let mut indices = Vec::new();
+
+    for (extents, center) in boxes.iter() {
+        let mut cuboid = procedural::cuboid(extents);
+        cuboid.translate_by(&Translation3::from(*center));
+        cuboid.unify_index_buffer();
+
+        let offset = coords.len() as u32;
+        coords.extend_from_slice(&cuboid.coords);
+        indices.extend(
+            cuboid
+                .indices
+                .unwrap_unified()
+                .into_iter()
+                .map(|t| Point3::new(t.x + offset, t.y + offset, t.z + offset)),
+        );
+    }
+
+    TriMesh::new(coords, None, None, Some(IndexBuffer::Unified(indices)))
+}
+
+fn num_containing(hulls: &[ConvexHull<f64>], pt: &Point3<f64>) -> usize {
+    hulls
+        .iter()
+        .filter(|h| h.contains_point(&Isometry3::identity(), pt))
+        .count()
+}
+
+#[test]
+fn vhacd_splits_concave_meshes() {
+    let params = VHACDParameters {
+        resolution: 16,
+        planes_per_axis: 8,
+        ..VHACDParameters::default()
+    };
+    let hulls = transformation::vhacd(&u_shape(), &params);
+
+    assert!(hulls.len() >= 3 && hulls.len() <= 8, "{}", hulls.len());
+
+    // The inside of the U shape is covered.
+    for pt in &[
+        Point3::new(0.5, 0.5, 0.5),
+        Point3::new(1.5, 0.5, 0.5),
+        Point3::new(2.5, 0.5, 0.5),
+        Point3::new(0.5, 2.5, 0.5),
+        Point3::new(2.5, 2.5, 0.5),
+    ] {
+        assert!(num_containing(&hulls, pt) > 0, "{:?}", pt);
+    }
+
+    // Its concave part is not.
+    for pt in &[Point3::new(1.5, 1.5, 0.5), Point3::new(1.5, 2.5, 0.5)] {
+        assert_eq!(num_containing(&hulls, pt), 0, "{:?}", pt);
+    }
+
+    // The hulls only exceed the mesh by at most one voxel.
+    let voxel_size = 3.0 / 16.0;
+
+    for hull in &hulls {
+        for pt in hull.points() {
+            assert!(pt.iter().all(|e| *e >= -voxel_size - 1.0e-7));
+            assert!(pt.iter().all(|e| *e <= 3.0 + voxel_size + 1.0e-7));
+        }
+    }
+}
+
+#[test]
+fn vhacd_limits() {
+    let params = VHACDParameters {
+        resolution: 16,
+        planes_per_axis: 8,
+        max_convex_hulls: 1,
+        ..VHACDParameters::default()
+    };
+    let hulls = transformation::vhacd(&u_shape(), &params);
+    assert_eq!(hulls.len(), 1);
+    assert_eq!(num_containing(&hulls, &Point3::new(1.5, 2.5, 0.5)), 1);
+
+    let params = VHACDParameters {
+        resolution: 16,
+        max_convex_hulls: 1,
+        max_vertices_per_hull: 12,
+        ..VHACDParameters::default()
+    };
+    let sphere = procedural::sphere(2.0, 16, 16, true);
+    let hulls = transformation::vhacd(&sphere, &params);
+    assert_eq!(hulls.len(), 1);
+    assert!(hulls[0].points().len() <= 12);
+    assert_eq!(num_containing(&hulls, &Point3::origin()), 1);
+    assert_eq!(num_containing(&hulls, &Point3::new(0.5, 0.5, 0.5)), 1);
+}
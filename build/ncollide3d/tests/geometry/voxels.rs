@@ -0,0 +1,195 @@
+use super::contacts;
+use na::{self, Isometry3, Point3, Vector3};
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Ball, Compound, Cuboid, FeatureId, Shape, ShapeHandle, Voxels};
+
+// A 3x1x3 floor of unit voxels with its top face on the plane `y = 1`.
+fn floor() -> Voxels<f64> {
+    let mut cells = Vec::new();
+
+    for i in -1..=1 {
+        for k in -1..=1 {
+            cells.push(Point3::new(i, 0, k));
+        }
+    }
+
+    Voxels::with_voxels(Vector3::repeat(1.0), cells)
+}
+
+#[test]
+fn voxels_edits() {
+    let mut voxels = Voxels::new(Vector3::new(1.0, 0.5, 2.0));
+    assert!(voxels.is_empty());
+
+    assert!(!voxels.set_voxel(&Point3::new(0, 0, 0), true));
+    assert!(voxels.set_voxel(&Point3::new(0, 0, 0), true));
+    assert!(!voxels.set_voxel(&Point3::new(-1, 3, 0), true));
+    assert_eq!(voxels.num_voxels(), 2);
+
+    let aabb = voxels.local_aabb();
+    assert_relative_eq!(*aabb.mins(), Point3::new(-1.0, 0.0, 0.0));
+    assert_relative_eq!(*aabb.maxs(), Point3::new(1.0, 2.0, 2.0));
+
+    let id = Isometry3::identity();
+    assert!(voxels.contains_point(&id, &Point3::new(-0.5, 1.75, 1.0)));
+    assert!(!voxels.contains_point(&id, &Point3::new(0.5, 1.75, 1.0)));
+    assert_eq!(
+        voxels.voxel_at_point(&Point3::new(-0.5, 1.75, 1.0)),
+        Point3::new(-1, 3, 0)
+    );
+
+    // Part indices of emptied cells are reused.
+    let part = voxels.part_id(&Point3::new(0, 0, 0)).unwrap();
+    assert!(voxels.set_voxel(&Point3::new(0, 0, 0), false));
+    assert!(!voxels.set_voxel(&Point3::new(0, 0, 0), false));
+    assert!(!voxels.is_filled(&Point3::new(0, 0, 0)));
+    assert!(!voxels.set_voxel(&Point3::new(5, 5, 5), true));
+    assert_eq!(voxels.part_id(&Point3::new(5, 5, 5)), Some(part));
+    assert_eq!(voxels.cell(part), Some(Point3::new(5, 5, 5)));
+    assert_eq!(voxels.voxels().count(), 2);
+}
+
+#[test]
+fn voxels_ray_cast() {
+    let voxels = floor();
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    // Cast on the top face.
+    let ray = Ray::new(m * Point3::new(0.5, 5.0, 0.5), m * -Vector3::y());
+    let inter = voxels.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+    let part = voxels.part_id(&Point3::new(0, 0, 0)).unwrap();
+    assert_relative_eq!(inter.toi, 4.0, epsilon = 1.0e-7);
+    assert_relative_eq!(inter.normal, m * Vector3::y(), epsilon = 1.0e-7);
+    assert_eq!(inter.feature, FeatureId::Face(part * 6 + 1));
+
+    // Cast from the side, through the boundary of two cells.
+    let ray = Ray::new(m * Point3::new(-5.0, 0.5, 0.0), m * Vector3::x());
+    let inter = voxels.toi_and_normal_with_ray(&m, &ray, true).unwrap();
+    assert_relative_eq!(inter.toi, 4.0, epsilon = 1.0e-7);
+    assert_relative_eq!(inter.normal, m * -Vector3::x(), epsilon = 1.0e-7);
+
+    // Casts from the inside.
+    let ray = Ray::new(m * Point3::new(0.5, 0.5, 0.5), m * Vector3::x());
+    assert_eq!(voxels.toi_with_ray(&m, &ray, true), Some(0.0));
+    let inter = voxels.toi_and_normal_with_ray(&m, &ray, false).unwrap();
+    let part = voxels.part_id(&Point3::new(1, 0, 0)).unwrap();
+    assert_relative_eq!(inter.toi, 1.5, epsilon = 1.0e-7);
+    assert_relative_eq!(inter.normal, m * -Vector3::x(), epsilon = 1.0e-7);
+    assert_eq!(inter.feature, FeatureId::Face(part * 6));
+
+    // Cast through a hole.
+    let mut holed = voxels.clone();
+    let _ = holed.set_voxel(&Point3::new(0, 0, 0), false);
+    let ray = Ray::new(m * Point3::new(0.5, 5.0, 0.5), m * -Vector3::y());
+    assert!(holed.toi_with_ray(&m, &ray, true).is_none());
+
+    // Compare with the equivalent compound shape.
+    let shapes = voxels
+        .voxels()
+        .map(|(cell, _)| {
+            let pos = Isometry3::new(voxels.voxel_center(&cell).coords, na::zero());
+            (pos, ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5))))
+        })
+        .collect();
+    let compound = Compound::new(shapes);
+
+    for i in 0..100 {
+        let t = i as f64;
+        let origin = Point3::new((t * 1.3).sin(), (t * 0.7).cos(), (t * 2.9).sin()) * 4.0;
+        let target = Point3::new((t * 0.3).cos() * 1.5, 0.5, (t * 1.9).sin() * 1.5);
+        let ray = Ray::new(m * origin, m * (target - origin));
+
+        let inter = voxels.toi_and_normal_with_ray(&m, &ray, true);
+        let expected = compound.toi_and_normal_with_ray(&m, &ray, true);
+        assert_eq!(inter.is_some(), expected.is_some());
+
+        if let (Some(inter), Some(expected)) = (inter, expected) {
+            assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-7);
+
+            if expected.toi > 0.0 {
+                assert_relative_eq!(inter.normal, expected.normal, epsilon = 1.0e-7);
+            }
+        }
+    }
+}
+
+#[test]
+fn voxels_point_projection() {
+    let mut cells = Vec::new();
+
+    for i in 0..5 {
+        for j in 0..5 {
+            for k in 0..5 {
+                cells.push(Point3::new(i, j, k));
+            }
+        }
+    }
+
+    let voxels = Voxels::with_voxels(Vector3::repeat(1.0), cells);
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    // From the outside.
+    let proj = voxels.project_point(&m, &(m * Point3::new(2.2, 7.0, 6.0)), true);
+    assert!(!proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::new(2.2, 5.0, 5.0), epsilon = 1.0e-7);
+
+    // From deep inside.
+    let pt = m * Point3::new(2.5, 2.2, 2.5);
+    let proj = voxels.project_point(&m, &pt, true);
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, pt);
+
+    let (proj, feature) = voxels.project_point_with_feature(&m, &pt);
+    let part = voxels.part_id(&Point3::new(2, 0, 2)).unwrap();
+    assert!(proj.is_inside);
+    assert_relative_eq!(proj.point, m * Point3::new(2.5, 0.0, 2.5), epsilon = 1.0e-7);
+    assert_eq!(feature, FeatureId::Face(part * 6 + 4));
+    assert_relative_eq!(
+        voxels.distance_to_point(&m, &pt, false),
+        -2.2,
+        epsilon = 1.0e-7
+    );
+}
+
+#[test]
+fn voxels_contacts_without_internal_edges() {
+    let mut voxels = floor();
+    let ball = Ball::new(0.5);
+    let id = Isometry3::identity();
+
+    // A ball slightly sinking into the floor, near the edges shared by several voxels.
+    let m2 = Isometry3::new(Vector3::new(0.05, 1.3, 0.02), na::zero());
+
+    for flip in &[false, true] {
+        let manifold = if *flip {
+            contacts(&m2, &ball, &id, &voxels)
+        } else {
+            contacts(&id, &voxels, &m2, &ball)
+        };
+
+        assert!(manifold.len() > 0);
+
+        for tracked in manifold.contacts() {
+            let c = &tracked.contact;
+            let normal = if *flip { -*c.normal } else { *c.normal };
+            assert_relative_eq!(normal, Vector3::y(), epsilon = 1.0e-7);
+            assert_relative_eq!(c.depth, 0.2, epsilon = 1.0e-7);
+        }
+    }
+
+    // The actual edges of the floor are kept.
+    let m2 = Isometry3::new(Vector3::new(2.2, 1.2, 0.5), na::zero());
+    let manifold = contacts(&id, &voxels, &m2, &ball);
+    let c = &manifold.deepest_contact().unwrap().contact;
+    assert_relative_eq!(
+        *c.normal,
+        Vector3::new(1.0, 1.0, 0.0).normalize(),
+        epsilon = 1.0e-7
+    );
+
+    // Edits are taken into account.
+    let m2 = Isometry3::new(Vector3::new(0.5, 1.3, 0.5), na::zero());
+    assert!(contacts(&id, &voxels, &m2, &ball).len() > 0);
+    let _ = voxels.set_voxel(&Point3::new(0, 0, 0), false);
+    assert_eq!(contacts(&id, &voxels, &m2, &ball).len(), 0);
+}
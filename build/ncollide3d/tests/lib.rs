@@ -1,5 +1,7 @@
 #[macro_use]
 extern crate approx;
+#[cfg(feature = "serde-serialize")]
+extern crate bincode;
 extern crate nalgebra as na;
 extern crate ncollide3d;
 
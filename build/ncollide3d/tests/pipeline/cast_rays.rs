@@ -0,0 +1,100 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, GeometricQueryType, RayCastMode, RayCastResults,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+#[test]
+fn cast_rays_matches_interferences_with_ray() {
+    let mut world = CollisionWorld::new(0.01);
+    let shape = ShapeHandle::new(Ball::new(0.5f32));
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    for i in 0..10 {
+        for j in 0..10 {
+            let pos = Isometry3::new(Vector3::new(i as f32, 0.0, j as f32 * 1.5), na::zero());
+            let _ = world.add(pos, shape.clone(), groups, query, ());
+        }
+    }
+
+    world.update();
+
+    let rays: Vec<_> = (0..100)
+        .map(|k| {
+            let angle = k as f32 * 0.1;
+            Ray::new(
+                Point3::new(-2.0, 0.1, 5.0),
+                Vector3::new(angle.cos(), 0.0, angle.sin()),
+            )
+        })
+        .collect();
+
+    let mut all_hits = RayCastResults::new();
+    let mut first_hits = RayCastResults::new();
+    let ray_groups = vec![groups; rays.len()];
+    world.cast_rays(&rays, &ray_groups, RayCastMode::AllHits, &mut all_hits);
+    world.cast_rays(&rays, &ray_groups, RayCastMode::FirstHit, &mut first_hits);
+
+    assert_eq!(all_hits.len(), rays.len());
+    assert_eq!(first_hits.len(), rays.len());
+    assert!(all_hits.iter().any(|hits| hits.len() > 1));
+
+    for (i, ray) in rays.iter().enumerate() {
+        let expected: Vec<_> = world.interferences_with_ray(ray, &groups).collect();
+        let hits = all_hits.hits(i);
+        assert_eq!(hits.len(), expected.len());
+        assert!(hits.windows(2).all(|w| w[0].1.toi <= w[1].1.toi));
+
+        for (handle, _, inter) in &expected {
+            assert!(hits
+                .iter()
+                .any(|(h, hit)| h == handle && hit.toi == inter.toi));
+        }
+
+        let min_toi = expected.iter().map(|e| e.2.toi).fold(None, |min, toi| {
+            Some(min.map_or(toi, |min: f32| min.min(toi)))
+        });
+        assert_eq!(first_hits.first_hit(i).map(|h| h.1.toi), min_toi);
+        assert!(first_hits.hits(i).len() <= 1);
+    }
+}
+
+#[test]
+fn cast_rays_with_per_ray_groups() {
+    let mut world = CollisionWorld::new(0.01);
+    let shape = ShapeHandle::new(Ball::new(0.5f32));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    // A row of balls alternating between groups 0 and 1.
+    for i in 0..10 {
+        let pos = Isometry3::translation(i as f32 * 2.0, 0.0, 0.0);
+        let groups = CollisionGroups::new().with_membership(&[i % 2]);
+        let _ = world.add(pos, shape.clone(), groups, query, ());
+    }
+
+    world.update();
+
+    let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::x());
+    let rays = vec![ray; 3];
+    let ray_groups = [
+        CollisionGroups::new().with_whitelist(&[0]),
+        CollisionGroups::new().with_whitelist(&[1]),
+        CollisionGroups::new(),
+    ];
+    let mut results = RayCastResults::new();
+
+    // Casting twice reuses the buffers and gives the same results.
+    for _ in 0..2 {
+        world.cast_rays(&rays, &ray_groups, RayCastMode::AllHits, &mut results);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.hits(0).len(), 5);
+        assert_eq!(results.hits(1).len(), 5);
+        assert_eq!(results.hits(2).len(), 10);
+        assert_relative_eq!(results.hits(0)[0].1.toi, 4.5);
+        assert_relative_eq!(results.hits(1)[0].1.toi, 6.5);
+        assert_relative_eq!(results.hits(2)[0].1.toi, 4.5);
+    }
+}
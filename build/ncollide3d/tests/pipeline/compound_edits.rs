@@ -0,0 +1,104 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, ContactEvent, GeometricQueryType};
+use ncollide3d::query::ContactId;
+use ncollide3d::shape::{Ball, Compound, Cuboid, ShapeHandle};
+
+#[test]
+fn compound_edited_in_place() {
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)));
+    let compound = Compound::new(vec![
+        (Isometry3::identity(), cuboid.clone()),
+        (Isometry3::translation(10.0, 0.0, 0.0), cuboid.clone()),
+    ]);
+
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let compound_handle = world
+        .add(
+            Isometry3::identity(),
+            ShapeHandle::new(compound),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+    let ball1 = world
+        .add(
+            Isometry3::translation(0.0, 0.9, 0.0),
+            ball.clone(),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+    let ball2 = world
+        .add(
+            Isometry3::translation(5.0, 0.9, 0.0),
+            ball,
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+
+    world.update();
+    assert!(world.contact_pair(compound_handle, ball2, true).is_none());
+    let ids: Vec<ContactId> = world
+        .contact_pair(compound_handle, ball1, true)
+        .unwrap()
+        .3
+        .contacts()
+        .map(|c| c.id)
+        .collect();
+    assert!(!ids.is_empty());
+    world.clear_events();
+    let shape_ptr = world
+        .collision_object(compound_handle)
+        .unwrap()
+        .shape()
+        .as_shape::<Compound<f32>>()
+        .unwrap() as *const Compound<f32>;
+
+    // Move the second part of the compound below the second ball.
+    world
+        .get_mut(compound_handle)
+        .unwrap()
+        .shape_mut()
+        .as_shape_mut::<Compound<f32>>()
+        .unwrap()
+        .set_shape_position(1, Isometry3::translation(5.0, 0.0, 0.0));
+    world.update();
+
+    // The compound is not shared, so it is edited in place.
+    let compound = world
+        .collision_object(compound_handle)
+        .unwrap()
+        .shape()
+        .as_shape::<Compound<f32>>()
+        .unwrap();
+    assert_eq!(compound as *const Compound<f32>, shape_ptr);
+    assert!(world.contact_pair(compound_handle, ball2, true).is_some());
+    assert!(world.broad_phase_aabb(compound_handle).unwrap().maxs().x > 5.0);
+
+    // The interaction with the first ball, on the unchanged part, is kept as is.
+    let new_ids: Vec<ContactId> = world
+        .contact_pair(compound_handle, ball1, true)
+        .unwrap()
+        .3
+        .contacts()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(ids, new_ids);
+
+    let events: Vec<_> = world.contact_events().iter().cloned().collect();
+    assert_eq!(events.len(), 1);
+
+    match events[0] {
+        ContactEvent::Started(h1, h2) => {
+            assert!((h1, h2) == (compound_handle, ball2) || (h1, h2) == (ball2, compound_handle))
+        }
+        ContactEvent::Stopped(..) => panic!("No contact should have stopped."),
+    }
+}
@@ -0,0 +1,183 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::procedural;
+use ncollide3d::query::TrackedContact;
+use ncollide3d::shape::{Ball, ShapeHandle, TriMesh};
+
+// The coordinates of the vertices of `points` bent by a wave at time `t`.
+fn wave(points: &[Point3<f64>], t: f64) -> Vec<f64> {
+    points
+        .iter()
+        .flat_map(|p| {
+            let z = (p.x * 0.5 + t).sin() * 0.3;
+            vec![p.x, p.y, z]
+        })
+        .collect()
+}
+
+// Checks that the barycentric coordinates of a contact on the deformed mesh match its
+// contact point, and returns the triangle they are expressed on.
+fn check_barycentric_coordinates(
+    coords: &[f64],
+    mesh: &TriMesh<f64>,
+    barycentric: Option<(usize, [f64; 3])>,
+    world_pt: &Point3<f64>,
+) -> usize {
+    let (face, bcoords) = barycentric.expect("The contact has no barycentric coordinates.");
+    let vertex = |i: usize| Vector3::new(coords[i * 3], coords[i * 3 + 1], coords[i * 3 + 2]);
+    let idx = mesh.faces()[face].indices;
+    let pt = vertex(idx.x) * bcoords[0] + vertex(idx.y) * bcoords[1] + vertex(idx.z) * bcoords[2];
+
+    assert_relative_eq!(bcoords.iter().sum::<f64>(), 1.0, epsilon = 1.0e-7);
+    assert!(bcoords.iter().all(|c| *c >= -1.0e-7));
+    assert_relative_eq!(Point3::from(pt), *world_pt, epsilon = 1.0e-7);
+    face
+}
+
+#[test]
+fn deformable_trimesh_contacts() {
+    let mut sheet: TriMesh<f64> = procedural::quad(10.0, 10.0, 10, 10).into();
+    sheet.set_deformable(true);
+    let rest_points = sheet.points().to_vec();
+
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let sheet_handle = world
+        .add(
+            Isometry3::identity(),
+            ShapeHandle::new(sheet),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+    let ball_pos = Isometry3::translation(0.3, -0.2, 0.0);
+    let ball_handle = world
+        .add(
+            ball_pos,
+            ShapeHandle::new(Ball::new(0.5)),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+
+    for step in 0..10 {
+        let t = step as f64 * 0.3;
+        let coords = wave(&rest_points, t);
+        world
+            .get_mut(sheet_handle)
+            .unwrap()
+            .set_deformations(&coords);
+
+        // Keep the ball slightly penetrating the surface.
+        let z = (0.3 * 0.5 + t).sin() * 0.3 + 0.45;
+        world.set_position(ball_handle, Isometry3::translation(0.3, -0.2, z));
+        world.update();
+
+        let (h1, _, _, manifold) = world
+            .contact_pair(sheet_handle, ball_handle, true)
+            .expect("The ball should touch the deformed sheet.");
+        let contacts: Vec<&TrackedContact<f64>> = manifold.contacts().collect();
+        assert!(!contacts.is_empty());
+
+        let sheet_obj = world.collision_object(sheet_handle).unwrap();
+        let mesh = sheet_obj.shape().as_shape::<TriMesh<f64>>().unwrap();
+
+        for tracked in contacts {
+            let c = &tracked.contact;
+            assert!(c.depth > 0.0);
+
+            if h1 == sheet_handle {
+                let bc = tracked.kinematic.barycentric_coordinates1();
+                let _ = check_barycentric_coordinates(&coords, mesh, bc, &c.world1);
+                assert!(tracked.kinematic.barycentric_coordinates2().is_none());
+            } else {
+                let bc = tracked.kinematic.barycentric_coordinates2();
+                let _ = check_barycentric_coordinates(&coords, mesh, bc, &c.world2);
+                assert!(tracked.kinematic.barycentric_coordinates1().is_none());
+            }
+        }
+    }
+}
+
+#[test]
+fn deformable_trimesh_trimesh_contacts() {
+    let mut cloth1: TriMesh<f64> = procedural::quad(2.0, 2.0, 4, 4).into();
+    let mut cloth2 = cloth1.clone();
+    cloth1.set_deformable(true);
+    cloth2.set_deformable(true);
+    let rest_points = cloth1.points().to_vec();
+
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let contacts_query = GeometricQueryType::Contacts(0.1, 0.0);
+    let handle1 = world
+        .add(
+            Isometry3::identity(),
+            ShapeHandle::new(cloth1),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+    let handle2 = world
+        .add(
+            Isometry3::identity(),
+            ShapeHandle::new(cloth2),
+            groups,
+            contacts_query,
+            (),
+        )
+        .0;
+
+    for step in 0..5 {
+        let t = step as f64 * 0.3;
+        let coords1 = wave(&rest_points, t);
+        // The second cloth is tilted so that it crosses the first one.
+        let coords2: Vec<f64> = coords1
+            .chunks(3)
+            .flat_map(|p| vec![p[0], p[1], p[2] + p[1] * 0.2 + 0.01])
+            .collect();
+
+        world.get_mut(handle1).unwrap().set_deformations(&coords1);
+        world.get_mut(handle2).unwrap().set_deformations(&coords2);
+        world.update();
+
+        let (h1, _, _, manifold) = world
+            .contact_pair(handle1, handle2, true)
+            .expect("The two cloths should be in contact.");
+        let contacts: Vec<&TrackedContact<f64>> = manifold.contacts().collect();
+        assert!(!contacts.is_empty());
+
+        let (first, second) = if h1 == handle1 {
+            (&coords1, &coords2)
+        } else {
+            (&coords2, &coords1)
+        };
+        let mesh = world
+            .collision_object(handle1)
+            .unwrap()
+            .shape()
+            .as_shape::<TriMesh<f64>>()
+            .unwrap();
+
+        for tracked in contacts {
+            let c = &tracked.contact;
+            let kinematic = &tracked.kinematic;
+            let _ = check_barycentric_coordinates(
+                first,
+                mesh,
+                kinematic.barycentric_coordinates1(),
+                &c.world1,
+            );
+            let _ = check_barycentric_coordinates(
+                second,
+                mesh,
+                kinematic.barycentric_coordinates2(),
+                &c.world2,
+            );
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use na::{DMatrix, Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::ClosestPoints;
+use ncollide3d::shape::{Ball, Compound, Cuboid, HeightField, Plane, ShapeHandle};
+
+#[test]
+fn distance_pairs_within_limit() {
+    let mut world = CollisionWorld::new(0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)));
+    let groups = CollisionGroups::new();
+
+    let (sensor, _) = world.add(
+        Isometry3::identity(),
+        ball,
+        groups,
+        GeometricQueryType::Distance(2.0),
+        (),
+    );
+    let (target, _) = world.add(
+        Isometry3::translation(2.0, 0.0, 0.0),
+        cuboid,
+        groups,
+        GeometricQueryType::Contacts(0.0, 0.0),
+        (),
+    );
+
+    world.update();
+
+    let (_, _, _, pts) = world.distance_pair(sensor, target, true).unwrap();
+    assert_relative_eq!(pts.distance().unwrap(), 1.0, epsilon = 1.0e-5);
+    assert_eq!(world.distance_events().iter().count(), 1);
+
+    // Move the target beyond the distance limit.
+    world
+        .get_mut(target)
+        .unwrap()
+        .set_position(Isometry3::translation(4.0, 0.0, 0.0));
+    world.update();
+
+    assert!(world.distance_pair(sensor, target, true).is_none());
+
+    let event = world.distance_events().iter().next().unwrap();
+    assert_eq!(event.new_status, ClosestPoints::Disjoint);
+}
+
+#[test]
+fn distance_pairs_without_algorithm_are_ignored() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Distance(10.0);
+    let plane = ShapeHandle::new(Plane::new(Vector3::y_axis()));
+    let heightfield = ShapeHandle::new(HeightField::new(
+        DMatrix::zeros(3, 3),
+        Vector3::new(2.0, 1.0, 2.0),
+    ));
+
+    let (plane1, _) = world.add(Isometry3::identity(), plane.clone(), groups, query, ());
+    let (plane2, _) = world.add(
+        Isometry3::translation(0.0, -1.0, 0.0),
+        plane,
+        groups,
+        query,
+        (),
+    );
+    let (hf, _) = world.add(
+        Isometry3::translation(0.0, -2.0, 0.0),
+        heightfield,
+        groups,
+        query,
+        (),
+    );
+    let (ball, _) = world.add(
+        Isometry3::translation(0.0, 3.0, 0.0),
+        ShapeHandle::new(Ball::new(0.5)),
+        groups,
+        query,
+        (),
+    );
+
+    world.update();
+
+    assert!(world.distance_pair(plane1, plane2, false).is_none());
+    assert!(world.distance_pair(plane1, hf, false).is_none());
+    let (_, _, _, pts) = world.distance_pair(plane1, ball, true).unwrap();
+    assert_relative_eq!(pts.distance().unwrap(), 2.5, epsilon = 1.0e-5);
+}
+
+#[test]
+fn composite_distance_pairs_without_algorithm_are_ignored() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Distance(10.0);
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)));
+    let compound = ShapeHandle::new(Compound::new(vec![
+        (Isometry3::identity(), cuboid.clone()),
+        (Isometry3::translation(2.0, 0.0, 0.0), cuboid),
+    ]));
+    let heightfield = ShapeHandle::new(HeightField::new(
+        DMatrix::zeros(3, 3),
+        Vector3::new(2.0, 1.0, 2.0),
+    ));
+
+    let (compound1, _) = world.add(Isometry3::identity(), compound.clone(), groups, query, ());
+    let (compound2, _) = world.add(
+        Isometry3::translation(0.0, 3.0, 0.0),
+        compound,
+        groups,
+        query,
+        (),
+    );
+    let (hf, _) = world.add(
+        Isometry3::translation(0.0, -2.0, 0.0),
+        heightfield,
+        groups,
+        query,
+        (),
+    );
+
+    world.update();
+
+    assert!(world.distance_pair(compound1, hf, false).is_none());
+    assert!(world.distance_pair(compound2, hf, false).is_none());
+    let (_, _, _, pts) = world.distance_pair(compound1, compound2, true).unwrap();
+    assert_relative_eq!(pts.distance().unwrap(), 2.0, epsilon = 1.0e-5);
+}
@@ -0,0 +1,67 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    BroadPhaseObject, CollisionGroups, CollisionWorld, DBVTBroadPhase, GeometricQueryType,
+    SAPBroadPhase,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+fn check_first_interference_with_ray(broad_phase: BroadPhaseObject<f32>) {
+    let mut world = CollisionWorld::with_broad_phase(broad_phase);
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.3f32)));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut odd_groups = CollisionGroups::new();
+    odd_groups.set_membership(&[1]);
+
+    for i in 0..10 {
+        let pos = Isometry3::new(Vector3::new(i as f32 * 2.0, 0.0, 0.0), na::zero());
+        let _ = world.add(pos, ball.clone(), CollisionGroups::new(), query, ());
+    }
+
+    // Let the DBVT broad phase move the first objects to its static tree.
+    for _ in 0..150 {
+        world.update();
+    }
+
+    for i in 0..10 {
+        let pos = Isometry3::new(Vector3::new(i as f32 * 2.0 + 1.0, 0.2, 0.0), na::zero());
+        let _ = world.add(pos, cuboid.clone(), odd_groups, query, ());
+    }
+
+    world.update();
+
+    let all_groups = CollisionGroups::new();
+    let mut no_odd_groups = CollisionGroups::new();
+    no_odd_groups.set_blacklist(&[1]);
+
+    for k in 0..40 {
+        let start = Point3::new(k as f32 - 10.0, 0.1, -1.0);
+        let ray = Ray::new(start, Vector3::new(1.0, 0.0, 0.05));
+
+        for groups in &[all_groups, no_odd_groups] {
+            for max_toi in &[f32::MAX, 2.5] {
+                let expected = world
+                    .interferences_with_ray(&ray, groups)
+                    .filter(|(_, _, inter)| inter.toi <= *max_toi)
+                    .map(|(handle, _, inter)| (handle, inter.toi))
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+                let first = world
+                    .first_interference_with_ray(&ray, *max_toi, groups)
+                    .map(|res| (res.handle, res.inter.toi));
+
+                assert_eq!(first.map(|f| f.1), expected.map(|e| e.1));
+            }
+        }
+    }
+}
+
+#[test]
+fn dbvt_first_interference_with_ray() {
+    check_first_interference_with_ray(Box::new(DBVTBroadPhase::new(0.01)))
+}
+
+#[test]
+fn sap_first_interference_with_ray() {
+    check_first_interference_with_ray(Box::new(SAPBroadPhase::new(0.01)))
+}
@@ -0,0 +1,105 @@
+use na::{DMatrix, Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::{self, Proximity};
+use ncollide3d::shape::{Ball, Cuboid, HeightField, ShapeHandle};
+use std::collections::HashSet;
+
+#[test]
+fn interferences_with_shape_matches_brute_force() {
+    let mut world = CollisionWorld::new(0.01);
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.3f32, 0.6, 0.2)));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut odd_groups = CollisionGroups::new();
+    odd_groups.set_membership(&[1]);
+
+    for i in 0..8 {
+        for j in 0..8 {
+            let pos = Isometry3::new(
+                Vector3::new(i as f32 * 1.2, (i * j) as f32 * 0.05, j as f32 * 1.2),
+                Vector3::new(0.0, (i + j) as f32 * 0.3, 0.0),
+            );
+
+            if (i + j) % 2 == 0 {
+                let _ = world.add(pos, ball.clone(), CollisionGroups::new(), query, ());
+            } else {
+                let _ = world.add(pos, cuboid.clone(), odd_groups, query, ());
+            }
+        }
+    }
+
+    world.update();
+
+    let mut no_odd_groups = CollisionGroups::new();
+    no_odd_groups.set_blacklist(&[1]);
+    let shape = Cuboid::new(Vector3::new(1.5f32, 0.2, 0.1));
+    let mut num_hits = 0;
+
+    for k in 0..50 {
+        let fk = k as f32;
+        let isometry = Isometry3::new(
+            Vector3::new(
+                (fk * 0.37).sin() * 5.0 + 4.0,
+                (fk * 0.11).cos(),
+                (fk * 0.71).cos() * 5.0 + 4.0,
+            ),
+            Vector3::new(fk * 0.2, fk * 0.5, 0.0),
+        );
+
+        for groups in &[CollisionGroups::new(), no_odd_groups] {
+            let expected: HashSet<_> = world
+                .collision_objects()
+                .filter(|(_, co)| co.collision_groups().can_interact_with_groups(groups))
+                .filter(|(_, co)| {
+                    query::proximity(&isometry, &shape, co.position(), co.shape().as_ref(), 0.0)
+                        == Proximity::Intersecting
+                })
+                .map(|(handle, _)| handle)
+                .collect();
+            let found: HashSet<_> = world
+                .interferences_with_shape(&shape, &isometry, groups)
+                .map(|(handle, _)| handle)
+                .collect();
+
+            assert_eq!(found, expected);
+            num_hits += found.len();
+        }
+    }
+
+    assert!(num_hits > 0);
+}
+
+#[test]
+fn interferences_with_shape_ignores_pairs_without_proximity_detector() {
+    let mut world = CollisionWorld::new(0.01);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let heightfield = HeightField::new(DMatrix::zeros(5, 5), Vector3::new(10.0f32, 1.0, 10.0));
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(heightfield),
+        groups,
+        query,
+        (),
+    );
+    let ball = world
+        .add(
+            Isometry3::translation(0.0, 0.5, 0.0),
+            ShapeHandle::new(Ball::new(0.5f32)),
+            groups,
+            query,
+            (),
+        )
+        .0;
+
+    world.update();
+
+    // The default proximity dispatcher cannot test a cuboid against a heightfield.
+    let shape = Cuboid::new(Vector3::repeat(1.0f32));
+    let found: Vec<_> = world
+        .interferences_with_shape(&shape, &Isometry3::identity(), &groups)
+        .map(|(handle, _)| handle)
+        .collect();
+
+    assert_eq!(found, vec![ball]);
+}
@@ -1,3 +1,15 @@
+mod cast_rays;
+mod compound_edits;
 mod contact_pairs;
+mod deformable_trimesh_contacts;
+mod distance_pairs;
 mod duplicate_trimesh_on_world;
+mod first_interference_with_ray;
+mod interferences_with_shape;
 mod is_send_sync;
+mod narrow_phase_determinism;
+mod nearest_object_to_point;
+mod sap_broad_phase;
+mod sweep_test;
+mod uniform_grid_broad_phase;
+mod world_snapshot;
@@ -0,0 +1,104 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, ContactEvent, GeometricQueryType, ProximityEvent,
+};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+// Records the events and contact IDs produced by a world with many moving objects, after
+// `setup` is applied to this world.
+fn run_simulation(setup: impl FnOnce(&mut CollisionWorld<f32, ()>)) -> Vec<String> {
+    let mut world = CollisionWorld::new(0.01);
+    setup(&mut world);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.4)));
+    let contacts = GeometricQueryType::Contacts(0.0, 0.0);
+    let proximity = GeometricQueryType::Proximity(0.1);
+    let mut handles = Vec::new();
+
+    for i in 0..200 {
+        let (shape, query) = if i % 3 == 0 {
+            (cuboid.clone(), proximity)
+        } else {
+            (ball.clone(), contacts)
+        };
+        let handle = world
+            .add(
+                Isometry3::identity(),
+                shape,
+                CollisionGroups::new(),
+                query,
+                (),
+            )
+            .0;
+        handles.push(handle);
+    }
+
+    let mut log = Vec::new();
+
+    for step in 0..50 {
+        let t = step as f32 * 0.1;
+
+        for (i, handle) in handles.iter().enumerate() {
+            let fi = i as f32;
+            let pos = Vector3::new(
+                (fi * 0.37 + t).sin() * 6.0,
+                (fi * 0.13 - t).cos() * 2.0,
+                (fi * 0.71 - t * 0.5).cos() * 6.0,
+            );
+            world
+                .get_mut(*handle)
+                .unwrap()
+                .set_position(Isometry3::new(pos, na::zero()));
+        }
+
+        world.update();
+
+        for event in world.contact_events().iter() {
+            match event {
+                ContactEvent::Started(a, b) => log.push(format!("started {:?} {:?}", a, b)),
+                ContactEvent::Stopped(a, b) => log.push(format!("stopped {:?} {:?}", a, b)),
+            }
+        }
+
+        for ProximityEvent {
+            collider1,
+            collider2,
+            prev_status,
+            new_status,
+        } in world.proximity_events().iter()
+        {
+            log.push(format!(
+                "proximity {:?} {:?} {:?} {:?}",
+                collider1, collider2, prev_status, new_status
+            ));
+        }
+
+        for (a, b, _, manifold) in world.contact_pairs(true) {
+            for contact in manifold.contacts() {
+                log.push(format!("contact {:?} {:?} {:?}", a, b, contact.id));
+            }
+        }
+    }
+
+    log
+}
+
+#[test]
+fn narrow_phase_events_are_deterministic() {
+    let log1 = run_simulation(|_| {});
+    let log2 = run_simulation(|_| {});
+
+    assert!(log1.iter().any(|e| e.starts_with("started")));
+    assert!(log1.iter().any(|e| e.starts_with("proximity")));
+    assert_eq!(log1, log2);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_narrow_phase_matches_the_sequential_one() {
+    let parallel = run_simulation(|_| {});
+    let sequential = run_simulation(|world| world.narrow_phase.set_parallel(false));
+
+    assert!(parallel.iter().any(|e| e.starts_with("contact")));
+    assert_eq!(parallel, sequential);
+}
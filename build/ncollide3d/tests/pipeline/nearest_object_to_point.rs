@@ -0,0 +1,75 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::PointQuery;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn nearest_object_to_point_matches_brute_force() {
+    let mut world = CollisionWorld::new(0.01);
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.3f32, 0.6, 0.2)));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut odd_groups = CollisionGroups::new();
+    odd_groups.set_membership(&[1]);
+
+    for i in 0..8 {
+        for j in 0..8 {
+            let pos = Isometry3::new(
+                Vector3::new(i as f32 * 2.0, (i * j) as f32 * 0.1, j as f32 * 2.0),
+                Vector3::new(0.0, (i + j) as f32 * 0.3, 0.0),
+            );
+
+            if (i + j) % 2 == 0 {
+                let _ = world.add(pos, ball.clone(), CollisionGroups::new(), query, ());
+            } else {
+                let _ = world.add(pos, cuboid.clone(), odd_groups, query, ());
+            }
+        }
+    }
+
+    world.update();
+
+    let mut no_odd_groups = CollisionGroups::new();
+    no_odd_groups.set_blacklist(&[1]);
+
+    for k in 0..50 {
+        let fk = k as f32;
+        let point = Point3::new(
+            (fk * 0.37).sin() * 10.0 + 7.0,
+            (fk * 0.11).cos() * 3.0,
+            (fk * 0.71).cos() * 10.0 + 7.0,
+        );
+
+        for groups in &[CollisionGroups::new(), no_odd_groups] {
+            for max_dist in &[f32::MAX, 1.0] {
+                let expected = world
+                    .collision_objects()
+                    .filter(|(_, co)| co.collision_groups().can_interact_with_groups(groups))
+                    .map(|(_, co)| co.shape().distance_to_point(co.position(), &point, true))
+                    .filter(|dist| *dist <= *max_dist)
+                    .fold(None, |min: Option<f32>, dist| {
+                        Some(min.map_or(dist, |min| min.min(dist)))
+                    });
+
+                let nearest = world.nearest_object_to_point(&point, *max_dist, groups);
+                let dist = nearest.map(|res| {
+                    assert!(res.co.collision_groups().can_interact_with_groups(groups));
+
+                    if res.proj.is_inside {
+                        0.0
+                    } else {
+                        na::distance(&point, &res.proj.point)
+                    }
+                });
+
+                match (dist, expected) {
+                    (Some(dist), Some(expected)) => {
+                        assert_relative_eq!(dist, expected, epsilon = 1.0e-5)
+                    }
+                    (None, None) => {}
+                    _ => panic!("Found {:?}, expected {:?}.", dist, expected),
+                }
+            }
+        }
+    }
+}
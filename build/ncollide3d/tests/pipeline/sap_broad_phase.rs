@@ -0,0 +1,188 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    BroadPhase, BroadPhaseInterferenceHandler, CollisionGroups, CollisionWorld, DBVTBroadPhase,
+    GeometricQueryType, SAPBroadPhase,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, ShapeHandle};
+use std::collections::HashSet;
+
+fn ball_positions(t: f32) -> Vec<Vector3<f32>> {
+    (0..50)
+        .map(|i| {
+            let fi = i as f32;
+            Vector3::new(
+                (fi * 0.37 + t).sin() * 5.0,
+                0.0,
+                (fi * 0.71 - t * 0.5).cos() * 5.0,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn sap_and_dbvt_find_the_same_pairs() {
+    let mut dbvt_world = CollisionWorld::new(0.01);
+    let mut sap_world = CollisionWorld::with_broad_phase(Box::new(SAPBroadPhase::new(0.01)));
+    let shape = ShapeHandle::new(Ball::new(0.5));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut handles = Vec::new();
+    let mut num_pairs = 0;
+
+    for pos in ball_positions(0.0) {
+        let pos = Isometry3::new(pos, na::zero());
+        let h1 = dbvt_world
+            .add(pos, shape.clone(), CollisionGroups::new(), query, ())
+            .0;
+        let h2 = sap_world
+            .add(pos, shape.clone(), CollisionGroups::new(), query, ())
+            .0;
+        handles.push((h1, h2));
+    }
+
+    for step in 0..100 {
+        let positions = ball_positions(step as f32 * 0.05);
+
+        for ((h1, h2), pos) in handles.iter().zip(positions.into_iter()) {
+            let pos = Isometry3::new(pos, na::zero());
+            dbvt_world.get_mut(*h1).unwrap().set_position(pos);
+            sap_world.get_mut(*h2).unwrap().set_position(pos);
+        }
+
+        dbvt_world.update();
+        sap_world.update();
+
+        let dbvt_pairs: HashSet<_> = dbvt_world
+            .contact_pairs(true)
+            .map(|(a, b, _, _)| (a.0.min(b.0), a.0.max(b.0)))
+            .collect();
+        let sap_pairs: HashSet<_> = sap_world
+            .contact_pairs(true)
+            .map(|(a, b, _, _)| (a.0.min(b.0), a.0.max(b.0)))
+            .collect();
+
+        assert_eq!(dbvt_pairs, sap_pairs);
+        num_pairs += sap_pairs.len();
+    }
+
+    assert!(num_pairs > 0);
+}
+
+struct NoopHandler;
+
+impl BroadPhaseInterferenceHandler<usize> for NoopHandler {
+    fn is_interference_allowed(&mut self, a: &usize, b: &usize) -> bool {
+        a != b
+    }
+
+    fn interference_started(&mut self, _: &usize, _: &usize) {}
+
+    fn interference_stopped(&mut self, _: &usize, _: &usize) {}
+}
+
+fn sorted(hits: Vec<&usize>) -> Vec<usize> {
+    let mut hits: Vec<_> = hits.into_iter().cloned().collect();
+    hits.sort();
+    hits
+}
+
+// Runs the same ray, point and bounding volume queries on both broad phases, and returns the
+// total number of interferences found.
+fn assert_same_interferences(
+    dbvt: &DBVTBroadPhase<f32, AABB<f32>, usize>,
+    sap: &SAPBroadPhase<f32, usize>,
+) -> usize {
+    let rays = [
+        Ray::new(Point3::new(-5.0, 1.0, -5.0), Vector3::new(1.0, 0.1, 1.0)),
+        Ray::new(Point3::new(30.0, 2.0, 7.0), Vector3::new(-1.0, 0.0, 0.0)),
+        Ray::new(Point3::new(4.0, 20.0, 4.0), Vector3::new(0.0, -1.0, 0.0)),
+        Ray::new(Point3::new(9.0, 1.0, 9.0), Vector3::new(0.3, -0.2, -1.0)),
+        Ray::new(Point3::new(-5.0, 100.0, -5.0), Vector3::new(1.0, 0.0, 1.0)),
+    ];
+    let points = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(4.2, 1.5, 6.1),
+        Point3::new(10.0, 3.0, 10.0),
+        Point3::new(18.0, 5.4, 0.3),
+        Point3::new(-3.0, 0.0, -3.0),
+    ];
+    let aabbs = [
+        AABB::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(3.0, 2.0, 3.0)),
+        AABB::new(Point3::new(5.0, 0.0, 5.0), Point3::new(12.0, 4.0, 7.0)),
+        AABB::new(
+            Point3::new(-10.0, -10.0, -10.0),
+            Point3::new(30.0, 30.0, 30.0),
+        ),
+        AABB::new(Point3::new(50.0, 0.0, 50.0), Point3::new(60.0, 1.0, 60.0)),
+    ];
+    let mut num_hits = 0;
+
+    for ray in &rays {
+        let mut dbvt_hits = Vec::new();
+        let mut sap_hits = Vec::new();
+        dbvt.interferences_with_ray(ray, &mut dbvt_hits);
+        sap.interferences_with_ray(ray, &mut sap_hits);
+        assert_eq!(sorted(dbvt_hits), sorted(sap_hits.clone()));
+        num_hits += sap_hits.len();
+    }
+
+    for point in &points {
+        let mut dbvt_hits = Vec::new();
+        let mut sap_hits = Vec::new();
+        dbvt.interferences_with_point(point, &mut dbvt_hits);
+        sap.interferences_with_point(point, &mut sap_hits);
+        assert_eq!(sorted(dbvt_hits), sorted(sap_hits.clone()));
+        num_hits += sap_hits.len();
+    }
+
+    for aabb in &aabbs {
+        let mut dbvt_hits = Vec::new();
+        let mut sap_hits = Vec::new();
+        dbvt.interferences_with_bounding_volume(aabb, &mut dbvt_hits);
+        sap.interferences_with_bounding_volume(aabb, &mut sap_hits);
+        assert_eq!(sorted(dbvt_hits), sorted(sap_hits.clone()));
+        num_hits += sap_hits.len();
+    }
+
+    num_hits
+}
+
+#[test]
+fn sap_and_dbvt_find_the_same_interferences() {
+    let mut dbvt = DBVTBroadPhase::new(0.0);
+    let mut sap = SAPBroadPhase::new(0.0);
+    let mut handles = Vec::new();
+
+    for i in 0..10 {
+        for j in 0..10 {
+            let center = Point3::new(i as f32 * 2.0, (i + j) as f32 * 0.3, j as f32 * 2.0);
+            let aabb = AABB::from_half_extents(center, Vector3::repeat(0.4 + j as f32 * 0.1));
+            let h1 = dbvt.create_proxy(aabb.clone(), i * 10 + j);
+            let h2 = sap.create_proxy(aabb, i * 10 + j);
+            handles.push((i * 10 + j, h1, h2));
+        }
+    }
+
+    dbvt.update(&mut NoopHandler);
+    sap.update(&mut NoopHandler);
+    assert!(assert_same_interferences(&dbvt, &sap) > 0);
+
+    // Remove every third proxy, and make sure neither broad phase reports it any more.
+    let (removed, kept): (Vec<_>, Vec<_>) = handles.into_iter().partition(|h| h.0 % 3 == 0);
+    let dbvt_removed: Vec<_> = removed.iter().map(|h| h.1).collect();
+    let sap_removed: Vec<_> = removed.iter().map(|h| h.2).collect();
+    dbvt.remove(&dbvt_removed, &mut |_, _| {});
+    sap.remove(&sap_removed, &mut |_, _| {});
+    dbvt.update(&mut NoopHandler);
+    sap.update(&mut NoopHandler);
+    assert!(assert_same_interferences(&dbvt, &sap) > 0);
+
+    let everything = AABB::new(
+        Point3::new(-100.0, -100.0, -100.0),
+        Point3::new(100.0, 100.0, 100.0),
+    );
+    let mut hits = Vec::new();
+    sap.interferences_with_bounding_volume(&everything, &mut hits);
+    assert_eq!(sorted(hits), kept.iter().map(|h| h.0).collect::<Vec<_>>());
+}
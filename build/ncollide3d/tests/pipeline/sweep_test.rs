@@ -0,0 +1,206 @@
+use na::{Isometry3, Point3, Unit, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::interpolation::ConstantVelocityRigidMotion;
+use ncollide3d::pipeline::{
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseObject, BroadPhaseProxyHandle,
+    CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, DBVTBroadPhase, GeometricQueryType,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+fn world_with_balls_along_x() -> CollisionWorld<f32, ()> {
+    world_with_balls_along_x_and_broad_phase(Box::new(DBVTBroadPhase::new(0.01)))
+}
+
+fn world_with_balls_along_x_and_broad_phase(
+    broad_phase: BroadPhaseObject<f32>,
+) -> CollisionWorld<f32, ()> {
+    let mut world = CollisionWorld::with_broad_phase(broad_phase);
+    let shape = ShapeHandle::new(Ball::new(0.5f32));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    for i in 0..10 {
+        let pos = Isometry3::new(
+            Vector3::new((9 - i) as f32 * 3.0 + 3.0, 0.0, 0.0),
+            na::zero(),
+        );
+        let _ = world.add(pos, shape.clone(), CollisionGroups::new(), query, ());
+    }
+
+    world.update();
+    world
+}
+
+#[test]
+fn sweep_test_respects_maximum_distance_and_is_sorted() {
+    let world = world_with_balls_along_x();
+    let shape = Ball::new(0.5f32);
+    let groups = CollisionGroups::new();
+    let dir = Unit::new_normalize(Vector3::x());
+    let pos = Isometry3::identity();
+
+    // The balls are at x = 3, 6, ..., 30, so the i-th one is hit after traveling 3 * i - 1.
+    let hits: Vec<_> = world
+        .sweep_test(&shape, &pos, &dir, 10.0, &groups)
+        .collect();
+    let tois: Vec<_> = hits.iter().map(|hit| hit.1.toi).collect();
+    assert_eq!(tois.len(), 3);
+
+    for (i, toi) in tois.iter().enumerate() {
+        assert_relative_eq!(*toi, (i + 1) as f32 * 3.0 - 1.0, epsilon = 1.0e-4);
+    }
+
+    let all_hits = world.sweep_test(&shape, &pos, &dir, 100.0, &groups).count();
+    assert_eq!(all_hits, 10);
+
+    let first = world
+        .first_sweep_test(&shape, &pos, &dir, 10.0, &groups)
+        .unwrap();
+    assert_eq!(first.0, hits[0].0);
+    assert_relative_eq!(first.1.toi, 2.0, epsilon = 1.0e-4);
+
+    assert!(world
+        .first_sweep_test(&shape, &pos, &dir, 1.5, &groups)
+        .is_none());
+}
+
+#[test]
+fn sweep_test_with_rotating_motion() {
+    let mut world = CollisionWorld::new(0.01);
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let groups = CollisionGroups::new();
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+    let pos = Isometry3::new(Vector3::new(0.0, 1.5, 0.0), na::zero());
+    let handle = world.add(pos, ball, groups, query, ()).0;
+    world.update();
+
+    // A long cuboid spinning around the z axis, without any translation.
+    let stick = Cuboid::new(Vector3::new(2.0f32, 0.1, 0.1));
+    let motion = ConstantVelocityRigidMotion::new(
+        0.0,
+        Isometry3::identity(),
+        Point3::origin(),
+        Vector3::zeros(),
+        Vector3::z(),
+    );
+
+    // A pure translation with a zero velocity would never hit the ball.
+    let dir = Unit::new_normalize(Vector3::x());
+    assert!(world
+        .first_sweep_test(&stick, &Isometry3::identity(), &dir, 0.0, &groups)
+        .is_none());
+
+    let hit = world
+        .first_sweep_test_with_motion(&stick, &motion, 2.0, &groups)
+        .unwrap();
+    assert_eq!(hit.0, handle);
+    // The stick touches the ball when `1.5 * cos(angle) - 0.1 = 0.5`.
+    assert_relative_eq!(hit.1.toi, 0.4f32.acos(), epsilon = 1.0e-3);
+
+    let hits: Vec<_> = world
+        .sweep_test_with_motion(&stick, &motion, 2.0, &groups)
+        .collect();
+    assert_eq!(hits.len(), 1);
+    assert_relative_eq!(hits[0].1.toi, hit.1.toi, epsilon = 1.0e-4);
+
+    assert!(world
+        .first_sweep_test_with_motion(&stick, &motion, 0.2, &groups)
+        .is_none());
+}
+
+// A broad phase relying on the default implementation of `first_interference`.
+struct CustomBroadPhase(DBVTBroadPhase<f32, AABB<f32>, CollisionObjectSlabHandle>);
+
+impl BroadPhase<f32, AABB<f32>, CollisionObjectSlabHandle> for CustomBroadPhase {
+    fn create_proxy(
+        &mut self,
+        bv: AABB<f32>,
+        data: CollisionObjectSlabHandle,
+    ) -> BroadPhaseProxyHandle {
+        self.0.create_proxy(bv, data)
+    }
+
+    fn proxy(
+        &self,
+        handle: BroadPhaseProxyHandle,
+    ) -> Option<(&AABB<f32>, &CollisionObjectSlabHandle)> {
+        self.0.proxy(handle)
+    }
+
+    fn remove(
+        &mut self,
+        handles: &[BroadPhaseProxyHandle],
+        removal_handler: &mut dyn FnMut(&CollisionObjectSlabHandle, &CollisionObjectSlabHandle),
+    ) {
+        self.0.remove(handles, removal_handler)
+    }
+
+    fn deferred_set_bounding_volume(&mut self, handle: BroadPhaseProxyHandle, bv: AABB<f32>) {
+        self.0.deferred_set_bounding_volume(handle, bv)
+    }
+
+    fn deferred_recompute_all_proximities_with(&mut self, handle: BroadPhaseProxyHandle) {
+        self.0.deferred_recompute_all_proximities_with(handle)
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        self.0.deferred_recompute_all_proximities()
+    }
+
+    fn update(
+        &mut self,
+        handler: &mut dyn BroadPhaseInterferenceHandler<CollisionObjectSlabHandle>,
+    ) {
+        self.0.update(handler)
+    }
+
+    fn interferences_with_bounding_volume<'a>(
+        &'a self,
+        bv: &AABB<f32>,
+        out: &mut Vec<&'a CollisionObjectSlabHandle>,
+    ) {
+        self.0.interferences_with_bounding_volume(bv, out)
+    }
+
+    fn interferences_with_ray<'a>(
+        &'a self,
+        ray: &Ray<f32>,
+        out: &mut Vec<&'a CollisionObjectSlabHandle>,
+    ) {
+        self.0.interferences_with_ray(ray, out)
+    }
+
+    fn interferences_with_point<'a>(
+        &'a self,
+        point: &Point3<f32>,
+        out: &mut Vec<&'a CollisionObjectSlabHandle>,
+    ) {
+        self.0.interferences_with_point(point, out)
+    }
+}
+
+#[test]
+fn sweep_test_with_custom_broad_phase() {
+    let world = world_with_balls_along_x();
+    let custom_world = world_with_balls_along_x_and_broad_phase(Box::new(CustomBroadPhase(
+        DBVTBroadPhase::new(0.01),
+    )));
+    let shape = Ball::new(0.5f32);
+    let groups = CollisionGroups::new();
+    let dir = Unit::new_normalize(Vector3::x());
+    let pos = Isometry3::identity();
+
+    let expected = world
+        .first_sweep_test(&shape, &pos, &dir, 100.0, &groups)
+        .unwrap();
+    let hit = custom_world
+        .first_sweep_test(&shape, &pos, &dir, 100.0, &groups)
+        .unwrap();
+    assert_eq!(hit.0, expected.0);
+    assert_relative_eq!(hit.1.toi, 2.0, epsilon = 1.0e-4);
+
+    let hits: Vec<_> = custom_world
+        .sweep_test(&shape, &pos, &dir, 10.0, &groups)
+        .collect();
+    assert_eq!(hits.len(), 3);
+}
@@ -0,0 +1,204 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseObject, CollisionGroups, CollisionWorld,
+    DBVTBroadPhase, GeometricQueryType, UniformGridBroadPhase,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Cuboid, Plane, ShapeHandle};
+use std::collections::HashSet;
+
+fn ball_positions(t: f32) -> Vec<Vector3<f32>> {
+    (0..50)
+        .map(|i| {
+            let fi = i as f32;
+            Vector3::new(
+                (fi * 0.37 + t).sin() * 5.0,
+                0.0,
+                (fi * 0.71 - t * 0.5).cos() * 5.0,
+            )
+        })
+        .collect()
+}
+
+// Checks that the given broad phase finds the same contact pairs as the default DBVT broad phase.
+fn assert_same_pairs_as_dbvt(broad_phase: BroadPhaseObject<f32>) {
+    let mut dbvt_world = CollisionWorld::new(0.01);
+    let mut other_world = CollisionWorld::with_broad_phase(broad_phase);
+    let shape = ShapeHandle::new(Ball::new(0.5));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut handles = Vec::new();
+    let mut num_pairs = 0;
+
+    for pos in ball_positions(0.0) {
+        let pos = Isometry3::new(pos, na::zero());
+        let h1 = dbvt_world
+            .add(pos, shape.clone(), CollisionGroups::new(), query, ())
+            .0;
+        let h2 = other_world
+            .add(pos, shape.clone(), CollisionGroups::new(), query, ())
+            .0;
+        handles.push((h1, h2));
+    }
+
+    for step in 0..100 {
+        let positions = ball_positions(step as f32 * 0.05);
+
+        for ((h1, h2), pos) in handles.iter().zip(positions.into_iter()) {
+            let pos = Isometry3::new(pos, na::zero());
+            dbvt_world.get_mut(*h1).unwrap().set_position(pos);
+            other_world.get_mut(*h2).unwrap().set_position(pos);
+        }
+
+        dbvt_world.update();
+        other_world.update();
+
+        let dbvt_pairs: HashSet<_> = dbvt_world
+            .contact_pairs(true)
+            .map(|(a, b, _, _)| (a.0.min(b.0), a.0.max(b.0)))
+            .collect();
+        let other_pairs: HashSet<_> = other_world
+            .contact_pairs(true)
+            .map(|(a, b, _, _)| (a.0.min(b.0), a.0.max(b.0)))
+            .collect();
+
+        assert_eq!(dbvt_pairs, other_pairs);
+        num_pairs += other_pairs.len();
+    }
+
+    assert!(num_pairs > 0);
+}
+
+#[test]
+fn uniform_grid_and_dbvt_find_the_same_pairs() {
+    assert_same_pairs_as_dbvt(Box::new(UniformGridBroadPhase::new(1.0, 0.01)))
+}
+
+struct NoopHandler;
+
+impl BroadPhaseInterferenceHandler<usize> for NoopHandler {
+    fn is_interference_allowed(&mut self, a: &usize, b: &usize) -> bool {
+        a != b
+    }
+
+    fn interference_started(&mut self, _: &usize, _: &usize) {}
+
+    fn interference_stopped(&mut self, _: &usize, _: &usize) {}
+}
+
+#[test]
+fn uniform_grid_and_dbvt_find_the_same_ray_interferences() {
+    let mut dbvt = DBVTBroadPhase::new(0.0);
+    let mut grid = UniformGridBroadPhase::new(1.5, 0.0);
+
+    for i in 0..10 {
+        for j in 0..10 {
+            let center = Point3::new(i as f32 * 2.0, (i + j) as f32 * 0.3, j as f32 * 2.0);
+            let aabb = AABB::from_half_extents(center, Vector3::repeat(0.4 + j as f32 * 0.1));
+            let _ = dbvt.create_proxy(aabb.clone(), i * 10 + j);
+            let _ = grid.create_proxy(aabb, i * 10 + j);
+        }
+    }
+
+    dbvt.update(&mut NoopHandler);
+    grid.update(&mut NoopHandler);
+
+    let rays = [
+        Ray::new(Point3::new(-5.0, 1.0, -5.0), Vector3::new(1.0, 0.1, 1.0)),
+        Ray::new(Point3::new(30.0, 2.0, 7.0), Vector3::new(-1.0, 0.0, 0.0)),
+        Ray::new(Point3::new(4.0, 20.0, 4.0), Vector3::new(0.0, -1.0, 0.0)),
+        Ray::new(Point3::new(9.0, 1.0, 9.0), Vector3::new(0.3, -0.2, -1.0)),
+        Ray::new(Point3::new(-5.0, 100.0, -5.0), Vector3::new(1.0, 0.0, 1.0)),
+    ];
+    let mut num_hits = 0;
+
+    for ray in &rays {
+        let mut dbvt_hits = Vec::new();
+        let mut grid_hits = Vec::new();
+        dbvt.interferences_with_ray(ray, &mut dbvt_hits);
+        grid.interferences_with_ray(ray, &mut grid_hits);
+
+        let dbvt_hits: HashSet<_> = dbvt_hits.into_iter().collect();
+        let grid_hits: HashSet<_> = grid_hits.into_iter().collect();
+        assert_eq!(dbvt_hits, grid_hits);
+        num_hits += grid_hits.len();
+    }
+
+    assert!(num_hits > 0);
+}
+
+#[test]
+fn uniform_grid_with_oversized_proxies() {
+    let mut dbvt_world = CollisionWorld::new(0.01);
+    let mut grid_world =
+        CollisionWorld::with_broad_phase(Box::new(UniformGridBroadPhase::new(1.0, 0.01)));
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let plane = ShapeHandle::new(Plane::new(Vector3::y_axis()));
+    let floor = ShapeHandle::new(Cuboid::new(Vector3::new(500.0, 0.5, 500.0)));
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let groups = CollisionGroups::new();
+
+    for world in [&mut dbvt_world, &mut grid_world].iter_mut() {
+        let _ = world.add(
+            Isometry3::translation(0.0, -0.4, 0.0),
+            plane.clone(),
+            groups,
+            query,
+            (),
+        );
+        let _ = world.add(
+            Isometry3::translation(0.0, -10.0, 0.0),
+            floor.clone(),
+            groups,
+            query,
+            (),
+        );
+
+        for pos in ball_positions(0.0) {
+            let _ = world.add(
+                Isometry3::new(pos, na::zero()),
+                ball.clone(),
+                groups,
+                query,
+                (),
+            );
+        }
+
+        // A ball touching the floor only.
+        let _ = world.add(
+            Isometry3::translation(300.0, -9.2, 300.0),
+            ball.clone(),
+            groups,
+            query,
+            (),
+        );
+        world.update();
+    }
+
+    let pairs = |world: &CollisionWorld<f32, ()>| -> HashSet<_> {
+        world
+            .contact_pairs(true)
+            .map(|(a, b, _, _)| (a.0.min(b.0), a.0.max(b.0)))
+            .collect()
+    };
+
+    let grid_pairs = pairs(&grid_world);
+    assert_eq!(pairs(&dbvt_world), grid_pairs);
+    // Every ball touches the plane or the floor.
+    assert!(grid_pairs.len() > 51);
+
+    let broad_phase = &grid_world.broad_phase;
+    let mut hits = Vec::new();
+    let ray = Ray::new(Point3::new(1000.0, 10.0, 0.0), -Vector3::y());
+    broad_phase.interferences_with_ray(&ray, &mut hits);
+    assert_eq!(hits.len(), 1);
+
+    hits.clear();
+    broad_phase.interferences_with_point(&Point3::new(-300.0, -10.0, 2.0), &mut hits);
+    assert_eq!(hits.len(), 2);
+
+    hits.clear();
+    let aabb = AABB::from_half_extents(Point3::new(300.0, -9.0, 300.0), Vector3::repeat(1.0));
+    broad_phase.interferences_with_bounding_volume(&aabb, &mut hits);
+    assert_eq!(hits.len(), 3);
+}
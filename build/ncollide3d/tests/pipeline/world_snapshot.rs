@@ -0,0 +1,341 @@
+use na::{Isometry3, Unit, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    BroadPhaseObject, CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, ContactEvent,
+    DBVTBroadPhase, GeometricQueryType, SAPBroadPhase, UniformGridBroadPhase,
+};
+use ncollide3d::query::ContactId;
+use ncollide3d::shape::{
+    Ball, Compound, Cuboid, FeatureId, RoundShape, Scaled, Shape, ShapeHandle, ShapeRegistry,
+};
+
+type Handle = CollisionObjectSlabHandle;
+type StepRecord = (
+    Vec<(Handle, Handle, Vec<ContactId>)>,
+    Vec<(bool, Handle, Handle)>,
+    Vec<(Handle, Handle, String)>,
+);
+
+fn positions(step: usize) -> Vec<Isometry3<f32>> {
+    let t = step as f32 * 0.05;
+
+    (0..30)
+        .map(|i| {
+            let fi = i as f32;
+            Isometry3::new(
+                Vector3::new(
+                    (fi * 0.37 + t).sin() * 3.0,
+                    (fi * 0.13 - t).cos() * 0.5,
+                    (fi * 0.71 - t * 0.5).cos() * 3.0,
+                ),
+                Vector3::new(fi * 0.1 + t, t * 0.3, 0.0),
+            )
+        })
+        .collect()
+}
+
+fn new_world() -> (CollisionWorld<f32, ()>, Vec<Handle>) {
+    new_world_with_broad_phase(Box::new(DBVTBroadPhase::new(0.02)))
+}
+
+fn new_world_with_broad_phase(
+    broad_phase: BroadPhaseObject<f32>,
+) -> (CollisionWorld<f32, ()>, Vec<Handle>) {
+    let mut world = CollisionWorld::with_broad_phase(broad_phase);
+    let ball = ShapeHandle::new(Ball::new(0.4f32));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.3f32, 0.5, 0.2)));
+    let contacts = GeometricQueryType::Contacts(0.0, 0.0);
+    let proximity = GeometricQueryType::Proximity(0.1);
+    let mut handles = Vec::new();
+
+    for (i, pos) in positions(0).into_iter().enumerate() {
+        let shape = if i % 2 == 0 {
+            ball.clone()
+        } else {
+            cuboid.clone()
+        };
+        let query = if i % 7 == 0 { proximity } else { contacts };
+        handles.push(world.add(pos, shape, CollisionGroups::new(), query, ()).0);
+    }
+
+    (world, handles)
+}
+
+fn step(world: &mut CollisionWorld<f32, ()>, handles: &[Handle], i: usize) -> StepRecord {
+    world.clear_events();
+
+    for (handle, pos) in handles.iter().zip(positions(i).into_iter()) {
+        world.get_mut(*handle).unwrap().set_position(pos);
+    }
+
+    world.update();
+
+    let mut contacts: Vec<_> = world
+        .contact_pairs(true)
+        .map(|(h1, h2, _, manifold)| {
+            let mut ids: Vec<_> = manifold.contacts().map(|c| c.id).collect();
+            ids.sort();
+            (h1, h2, ids)
+        })
+        .collect();
+    contacts.sort_by_key(|pair| (pair.0, pair.1));
+
+    let contact_events = world
+        .contact_events()
+        .iter()
+        .map(|e| match e {
+            ContactEvent::Started(h1, h2) => (true, *h1, *h2),
+            ContactEvent::Stopped(h1, h2) => (false, *h1, *h2),
+        })
+        .collect();
+    let proximity_events = world
+        .proximity_events()
+        .iter()
+        .map(|e| (e.collider1, e.collider2, format!("{:?}", e.new_status)))
+        .collect();
+
+    (contacts, contact_events, proximity_events)
+}
+
+fn check_restored_world_evolves_like_the_original(
+    mut world: CollisionWorld<f32, ()>,
+    handles: Vec<Handle>,
+) {
+    for i in 1..20 {
+        let _ = step(&mut world, &handles, i);
+    }
+
+    let snapshot = world.snapshot().unwrap();
+    let expected: Vec<_> = (20..60).map(|i| step(&mut world, &handles, i)).collect();
+    assert!(expected.iter().any(|record| !record.0.is_empty()));
+    assert!(expected.iter().any(|record| !record.1.is_empty()));
+
+    // Restore on a new world, and roll the original world back.
+    let mut restored = CollisionWorld::new(0.1);
+    restored.restore(&snapshot);
+    world.restore(&snapshot);
+
+    for (record, i) in expected.iter().zip(20..60) {
+        assert_eq!(step(&mut restored, &handles, i), *record);
+        assert_eq!(step(&mut world, &handles, i), *record);
+    }
+}
+
+#[test]
+fn restored_world_evolves_like_the_original() {
+    let (world, handles) = new_world();
+    check_restored_world_evolves_like_the_original(world, handles);
+}
+
+#[test]
+fn restored_world_with_sap_broad_phase() {
+    let (world, handles) = new_world_with_broad_phase(Box::new(SAPBroadPhase::new(0.02)));
+    check_restored_world_evolves_like_the_original(world, handles);
+}
+
+#[test]
+fn restored_world_with_uniform_grid_broad_phase() {
+    let broad_phase = Box::new(UniformGridBroadPhase::new(1.0, 0.02));
+    let (world, handles) = new_world_with_broad_phase(broad_phase);
+    check_restored_world_evolves_like_the_original(world, handles);
+}
+
+#[cfg(feature = "serde-serialize")]
+#[test]
+fn serialized_snapshot_keeps_the_contact_ids() {
+    use ncollide3d::pipeline::CollisionWorldSnapshot;
+
+    let (mut world, handles) = new_world();
+
+    for i in 1..20 {
+        let _ = step(&mut world, &handles, i);
+    }
+
+    let data = bincode::serialize(&world.snapshot().unwrap()).unwrap();
+    let snapshot: CollisionWorldSnapshot<f32, ()> = bincode::deserialize(&data).unwrap();
+    let mut restored = CollisionWorld::new(0.02);
+    restored.restore(&snapshot);
+
+    let contact_ids = |world: &CollisionWorld<f32, ()>| {
+        let mut ids: Vec<_> = world
+            .contact_pairs(true)
+            .map(|(h1, h2, _, manifold)| {
+                let mut ids: Vec<_> = manifold.contacts().map(|c| c.id).collect();
+                ids.sort();
+                (h1, h2, ids)
+            })
+            .collect();
+        ids.sort_by_key(|pair| (pair.0, pair.1));
+        ids
+    };
+
+    let expected = contact_ids(&world);
+    assert!(!expected.is_empty());
+    assert_eq!(contact_ids(&restored), expected);
+
+    // The contacts that persist keep their IDs, and new contacts get the same IDs as on the
+    // original world.
+    for i in 20..40 {
+        assert_eq!(
+            step(&mut restored, &handles, i),
+            step(&mut world, &handles, i)
+        );
+    }
+}
+
+// The exact contact points, normals and depths of all the contact pairs of the world.
+fn exact_contacts(world: &CollisionWorld<f32, ()>) -> Vec<(Handle, Handle, Vec<u32>)> {
+    let mut res: Vec<_> = world
+        .contact_pairs(false)
+        .map(|(h1, h2, _, manifold)| {
+            let mut bits = Vec::new();
+
+            for c in manifold.contacts() {
+                let c = &c.contact;
+                bits.extend(c.world1.iter().chain(c.world2.iter()).map(|e| e.to_bits()));
+                bits.extend(c.normal.iter().map(|e| e.to_bits()));
+                bits.push(c.depth.to_bits());
+            }
+
+            (h1, h2, bits)
+        })
+        .collect();
+    res.sort_by_key(|pair| (pair.0, pair.1));
+    res
+}
+
+#[test]
+fn restored_world_keeps_the_contact_algorithms() {
+    let (mut world, mut handles) = new_world();
+    let compound = Compound::new(vec![
+        (
+            Isometry3::translation(-0.5, 0.0, 0.0),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(0.4f32))),
+        ),
+        (
+            Isometry3::translation(0.5, 0.0, 0.0),
+            ShapeHandle::new(Ball::new(0.4f32)),
+        ),
+    ]);
+    let query = GeometricQueryType::Contacts(0.1, 0.0);
+    handles.push(
+        world
+            .add(
+                Isometry3::identity(),
+                ShapeHandle::new(compound),
+                CollisionGroups::new(),
+                query,
+                (),
+            )
+            .0,
+    );
+
+    let step_with_compound = |world: &mut CollisionWorld<f32, ()>, i: usize| {
+        let t = i as f32 * 0.05;
+        let pos = Isometry3::new(Vector3::new(t.sin(), 0.0, t.cos()), Vector3::y() * t);
+        let compound = *handles.last().unwrap();
+        world.get_mut(compound).unwrap().set_position(pos);
+        let _ = step(world, &handles[..handles.len() - 1], i);
+        exact_contacts(world)
+    };
+
+    for i in 1..20 {
+        let _ = step_with_compound(&mut world, i);
+    }
+
+    let snapshot = world.snapshot().unwrap();
+    let expected: Vec<_> = (20..40)
+        .map(|i| step_with_compound(&mut world, i))
+        .collect();
+    assert!(expected.iter().any(|contacts| !contacts.is_empty()));
+
+    let mut restored = CollisionWorld::new(0.02);
+    restored.restore(&snapshot);
+
+    for (contacts, i) in expected.iter().zip(20..40) {
+        assert_eq!(step_with_compound(&mut restored, i), *contacts);
+    }
+}
+
+#[derive(Clone)]
+struct Marker(f32);
+
+impl Shape<f32> for Marker {
+    fn aabb(&self, m: &Isometry3<f32>) -> AABB<f32> {
+        Ball::new(self.0).aabb(m)
+    }
+
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry3<f32>,
+        _: Option<&[f32]>,
+        _: &Unit<Vector3<f32>>,
+    ) -> bool {
+        false
+    }
+}
+
+#[test]
+fn shape_registry_round_trip() {
+    let compound = Compound::new(vec![
+        (Isometry3::identity(), ShapeHandle::new(Ball::new(1.0f32))),
+        (
+            Isometry3::new(Vector3::x(), na::zero()),
+            ShapeHandle::new(Marker(2.0)),
+        ),
+    ]);
+
+    let mut registry = ShapeRegistry::new();
+    assert!(registry.to_tagged_shape(&compound).is_none());
+
+    registry.register(
+        "marker",
+        |m: &Marker| m.0.to_bits().to_le_bytes().to_vec(),
+        |data| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(data);
+            Some(Marker(f32::from_bits(u32::from_le_bytes(bytes))))
+        },
+    );
+
+    let tagged = registry.to_tagged_shape(&compound).unwrap();
+    let restored = registry.from_tagged_shape(tagged).unwrap();
+    let parts = restored.as_shape::<Compound<f32>>().unwrap().shapes();
+
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].1.as_shape::<Ball<f32>>().unwrap().radius(), 1.0);
+    assert_eq!(parts[1].1.as_shape::<Marker>().unwrap().0, 2.0);
+    assert_eq!(parts[1].0, Isometry3::new(Vector3::x(), na::zero()));
+}
+
+#[test]
+fn shape_registry_round_trip_with_generic_shapes() {
+    let registry = ShapeRegistry::new();
+    let scale = Vector3::new(2.0f32, 1.0, 0.5);
+    let cuboid = Cuboid::new(Vector3::repeat(1.0f32));
+
+    let scaled = Scaled::new(cuboid.clone(), scale);
+    let tagged = registry.to_tagged_shape(&scaled).unwrap();
+    let restored = registry.from_tagged_shape(tagged).unwrap();
+    let restored = restored.as_shape::<Scaled<f32, Cuboid<f32>>>().unwrap();
+    assert_eq!(*restored.scale(), scale);
+    assert_eq!(restored.inner_shape(), &cuboid);
+
+    let shared = Scaled::new(ShapeHandle::new(Ball::new(1.0f32)), scale);
+    let tagged = registry.to_tagged_shape(&shared).unwrap();
+    let restored = registry.from_tagged_shape(tagged).unwrap();
+    let restored = restored
+        .as_shape::<Scaled<f32, ShapeHandle<f32>>>()
+        .unwrap();
+    assert_eq!(*restored.scale(), scale);
+    assert!(restored.inner_shape().is_shape::<Ball<f32>>());
+
+    let round = RoundShape::new(cuboid.clone(), 0.1);
+    let tagged = registry.to_tagged_shape(&round).unwrap();
+    let restored = registry.from_tagged_shape(tagged).unwrap();
+    assert_eq!(
+        restored.as_shape::<RoundShape<f32, Cuboid<f32>>>().unwrap(),
+        &round
+    );
+}
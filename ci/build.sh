@@ -4,5 +4,7 @@ set -ev
 
 cd $TRAVIS_BUILD_DIR/build/ncollide2d
 cargo build
+cargo build --features parallel
 cd $TRAVIS_BUILD_DIR/build/ncollide3d
 cargo build
+cargo build --features parallel
@@ -102,6 +102,15 @@ impl<N: RealField> AABB<N> {
         AABB::new(center + (-ws_half_extents), center + ws_half_extents)
     }
 
+    /// Computes the AABB bounding `self` scaled by `scale` along each coordinate axis.
+    #[inline]
+    pub fn scaled_by(&self, scale: &Vector<N>) -> Self {
+        let a = self.mins.coords.component_mul(scale);
+        let b = self.maxs.coords.component_mul(scale);
+
+        AABB::new(Point::from(na::inf(&a, &b)), Point::from(na::sup(&a, &b)))
+    }
+
     /// The smallest bounding sphere containing this AABB.
     #[inline]
     pub fn bounding_sphere(&self) -> BoundingSphere<N> {
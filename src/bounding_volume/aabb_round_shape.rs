@@ -0,0 +1,22 @@
+use crate::bounding_volume::{BoundingVolume, HasBoundingVolume, AABB};
+use crate::math::Isometry;
+use crate::shape::RoundShape;
+use na::RealField;
+
+impl<N: RealField, S: HasBoundingVolume<N, AABB<N>>> HasBoundingVolume<N, AABB<N>>
+    for RoundShape<N, S>
+{
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> AABB<N> {
+        self.inner_shape()
+            .bounding_volume(m)
+            .loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> AABB<N> {
+        self.inner_shape()
+            .local_bounding_volume()
+            .loosened(self.radius())
+    }
+}
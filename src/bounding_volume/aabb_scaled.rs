@@ -0,0 +1,27 @@
+use crate::bounding_volume::{self, HasBoundingVolume, AABB};
+use crate::math::Isometry;
+use crate::shape::{ScalableShape, Scaled};
+use na::RealField;
+
+impl<N: RealField, S: ScalableShape<N>> HasBoundingVolume<N, AABB<N>> for Scaled<N, S> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> AABB<N> {
+        if self.inner_shape().shape().is_support_map() {
+            bounding_volume::support_map_aabb(m, self)
+        } else {
+            bounding_volume::local_aabb(self).transform_by(m)
+        }
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> AABB<N> {
+        if self.inner_shape().shape().is_support_map() {
+            bounding_volume::support_map_aabb(&Isometry::identity(), self)
+        } else {
+            self.inner_shape()
+                .shape()
+                .local_aabb()
+                .scaled_by(self.scale())
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::bounding_volume;
 use crate::bounding_volume::{HasBoundingVolume, AABB};
-use crate::math::Isometry;
-use crate::shape::{Capsule, Segment};
+use crate::math::{Isometry, Point};
+use crate::shape::{Capsule, Ellipsoid, Segment};
 #[cfg(feature = "dim3")]
 use crate::shape::{Cone, Cylinder};
 use na::RealField;
@@ -47,6 +47,20 @@ impl<N: RealField> HasBoundingVolume<N, AABB<N>> for Capsule<N> {
     }
 }
 
+impl<N: RealField> HasBoundingVolume<N, AABB<N>> for Ellipsoid<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> AABB<N> {
+        bounding_volume::support_map_aabb(m, self)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> AABB<N> {
+        let half_extents = Point::from(*self.radii());
+
+        AABB::new(-half_extents, half_extents)
+    }
+}
+
 impl<N: RealField> HasBoundingVolume<N, AABB<N>> for Segment<N> {
     #[inline]
     fn bounding_volume(&self, m: &Isometry<N>) -> AABB<N> {
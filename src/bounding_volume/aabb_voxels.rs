@@ -0,0 +1,19 @@
+use crate::bounding_volume::{HasBoundingVolume, AABB};
+use crate::math::{Isometry, Point};
+use crate::shape::Voxels;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, AABB<N>> for Voxels<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> AABB<N> {
+        let aabb: AABB<N> = self.local_bounding_volume();
+        aabb.transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> AABB<N> {
+        self.root_aabb()
+            .cloned()
+            .unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+    }
+}
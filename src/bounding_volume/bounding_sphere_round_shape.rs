@@ -0,0 +1,22 @@
+use crate::bounding_volume::{BoundingSphere, BoundingVolume, HasBoundingVolume};
+use crate::math::Isometry;
+use crate::shape::RoundShape;
+use na::RealField;
+
+impl<N: RealField, S: HasBoundingVolume<N, BoundingSphere<N>>>
+    HasBoundingVolume<N, BoundingSphere<N>> for RoundShape<N, S>
+{
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> BoundingSphere<N> {
+        self.inner_shape()
+            .bounding_volume(m)
+            .loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> BoundingSphere<N> {
+        self.inner_shape()
+            .local_bounding_volume()
+            .loosened(self.radius())
+    }
+}
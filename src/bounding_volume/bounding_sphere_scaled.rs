@@ -0,0 +1,19 @@
+use crate::bounding_volume::{self, BoundingSphere, HasBoundingVolume};
+use crate::math::Isometry;
+use crate::shape::{ScalableShape, Scaled};
+use na::RealField;
+
+impl<N: RealField, S: ScalableShape<N>> HasBoundingVolume<N, BoundingSphere<N>> for Scaled<N, S> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> BoundingSphere<N> {
+        bounding_volume::local_bounding_sphere(self).transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> BoundingSphere<N> {
+        let sphere = self.inner_shape().shape().local_bounding_sphere();
+        let center = self.scale_point(sphere.center());
+
+        BoundingSphere::new(center, sphere.radius() * self.scale().amax())
+    }
+}
@@ -0,0 +1,18 @@
+use crate::bounding_volume::{self, BoundingSphere, HasBoundingVolume};
+use crate::math::Isometry;
+use crate::shape::Voxels;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, BoundingSphere<N>> for Voxels<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> BoundingSphere<N> {
+        bounding_volume::local_aabb(self)
+            .bounding_sphere()
+            .transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> BoundingSphere<N> {
+        bounding_volume::local_aabb(self).bounding_sphere()
+    }
+}
@@ -0,0 +1,244 @@
+//! Discrete Oriented Polytope.
+
+use crate::bounding_volume::{BoundingVolume, HasBoundingVolume, AABB, OBB};
+use crate::math::{Isometry, Point, Vector, DIM};
+use na::{self, RealField};
+
+/// The number of slab directions of a `KDOP`.
+#[cfg(feature = "dim2")]
+pub const KDOP_NUM_AXES: usize = 4;
+
+/// The number of slab directions of a `KDOP`.
+#[cfg(feature = "dim3")]
+pub const KDOP_NUM_AXES: usize = 9;
+
+// The first `DIM` directions are the coordinate axes, the others are not normalized.
+#[cfg(feature = "dim2")]
+const AXES: [[i8; 2]; KDOP_NUM_AXES] = [[1, 0], [0, 1], [1, 1], [1, -1]];
+
+#[cfg(feature = "dim3")]
+const AXES: [[i8; 3]; KDOP_NUM_AXES] = [
+    [1, 0, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 1, 0],
+    [1, -1, 0],
+    [1, 0, 1],
+    [1, 0, -1],
+    [0, 1, 1],
+    [0, 1, -1],
+];
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the discrete oriented polytope of a shape `g` transformed by `m`.
+///
+/// Same as `g.bounding_volume(m)`.
+#[inline]
+pub fn kdop<N, G: ?Sized>(g: &G, m: &Isometry<N>) -> KDOP<N>
+where
+    N: RealField,
+    G: HasBoundingVolume<N, KDOP<N>>,
+{
+    g.bounding_volume(m)
+}
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the discrete oriented polytope of a shape `g`.
+///
+/// Same as `g.local_bounding_volume()`.
+#[inline]
+pub fn local_kdop<N, G: ?Sized>(g: &G) -> KDOP<N>
+where
+    N: RealField,
+    G: HasBoundingVolume<N, KDOP<N>>,
+{
+    g.local_bounding_volume()
+}
+
+/// A Discrete Oriented Polytope: an 18-DOP in 3D and an 8-DOP in 2D.
+///
+/// This is the intersection of `KDOP_NUM_AXES` slabs with fixed directions: the coordinate axes,
+/// and the diagonals of each pair of coordinate axes. It bounds diagonal features much more
+/// tightly than an `AABB` while keeping its cheap intersection test and refit. However, it does
+/// not rotate well: transforming a shape requires its `KDOP` to be recomputed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct KDOP<N: RealField> {
+    mins: [N; KDOP_NUM_AXES],
+    maxs: [N; KDOP_NUM_AXES],
+}
+
+impl<N: RealField> KDOP<N> {
+    /// Creates a new KDOP from the bounds of its slabs.
+    ///
+    /// Each bound is the dot product of a point with the slab direction given by `KDOP::axis`.
+    /// Each component of `mins` must be smaller than the related component of `maxs`.
+    #[inline]
+    pub fn new(mins: [N; KDOP_NUM_AXES], maxs: [N; KDOP_NUM_AXES]) -> KDOP<N> {
+        KDOP { mins, maxs }
+    }
+
+    /// Creates a KDOP containing a single point.
+    #[inline]
+    pub fn from_point(point: &Point<N>) -> KDOP<N> {
+        let mut bounds = [N::zero(); KDOP_NUM_AXES];
+
+        for i in 0..KDOP_NUM_AXES {
+            bounds[i] = Self::axis(i).dot(&point.coords);
+        }
+
+        KDOP::new(bounds, bounds)
+    }
+
+    /// Computes the smallest KDOP containing an AABB.
+    #[inline]
+    pub fn from_aabb(aabb: &AABB<N>) -> KDOP<N> {
+        let position = Isometry::new(aabb.center().coords, na::zero());
+        KDOP::from_obb(&OBB::new(position, aabb.half_extents()))
+    }
+
+    /// Computes the smallest KDOP containing an OBB.
+    pub fn from_obb(obb: &OBB<N>) -> KDOP<N> {
+        let mut res = KDOP::from_point(&obb.center());
+
+        for i in 0..KDOP_NUM_AXES {
+            let local_axis = obb.position().inverse_transform_vector(&Self::axis(i));
+            let radius = local_axis.abs().dot(obb.half_extents());
+            res.mins[i] -= radius;
+            res.maxs[i] += radius;
+        }
+
+        res
+    }
+
+    /// The `i`-th slab direction. It is not normalized.
+    #[inline]
+    pub fn axis(i: usize) -> Vector<N> {
+        Vector::from_iterator(AXES[i].iter().map(|e| na::convert(*e as f64)))
+    }
+
+    /// The lower bounds of each slab.
+    #[inline]
+    pub fn mins(&self) -> &[N; KDOP_NUM_AXES] {
+        &self.mins
+    }
+
+    /// The upper bounds of each slab.
+    #[inline]
+    pub fn maxs(&self) -> &[N; KDOP_NUM_AXES] {
+        &self.maxs
+    }
+
+    /// The smallest AABB containing this KDOP.
+    ///
+    /// The diagonal slabs are ignored.
+    #[inline]
+    pub fn aabb(&self) -> AABB<N> {
+        let mut mins = Point::origin();
+        let mut maxs = Point::origin();
+
+        for i in 0..DIM {
+            mins[i] = self.mins[i];
+            maxs[i] = self.maxs[i];
+        }
+
+        AABB::new(mins, maxs)
+    }
+
+    /// Enlarges this KDOP so it also contains `point`.
+    #[inline]
+    pub fn take_point(&mut self, point: &Point<N>) {
+        for i in 0..KDOP_NUM_AXES {
+            let proj = Self::axis(i).dot(&point.coords);
+            self.mins[i] = self.mins[i].min(proj);
+            self.maxs[i] = self.maxs[i].max(proj);
+        }
+    }
+
+    /// Tests if `point`, expressed in the same frame as this KDOP, is inside of it.
+    #[inline]
+    pub fn contains_local_point(&self, point: &Point<N>) -> bool {
+        (0..KDOP_NUM_AXES).all(|i| {
+            let proj = Self::axis(i).dot(&point.coords);
+            proj >= self.mins[i] && proj <= self.maxs[i]
+        })
+    }
+}
+
+impl<N: RealField> BoundingVolume<N> for KDOP<N> {
+    #[inline]
+    fn center(&self) -> Point<N> {
+        self.aabb().center()
+    }
+
+    #[inline]
+    fn intersects(&self, other: &KDOP<N>) -> bool {
+        (0..KDOP_NUM_AXES).all(|i| self.mins[i] <= other.maxs[i] && self.maxs[i] >= other.mins[i])
+    }
+
+    #[inline]
+    fn contains(&self, other: &KDOP<N>) -> bool {
+        (0..KDOP_NUM_AXES).all(|i| self.mins[i] <= other.mins[i] && self.maxs[i] >= other.maxs[i])
+    }
+
+    #[inline]
+    fn merge(&mut self, other: &KDOP<N>) {
+        for i in 0..KDOP_NUM_AXES {
+            self.mins[i] = self.mins[i].min(other.mins[i]);
+            self.maxs[i] = self.maxs[i].max(other.maxs[i]);
+        }
+    }
+
+    #[inline]
+    fn merged(&self, other: &KDOP<N>) -> KDOP<N> {
+        let mut res = self.clone();
+        res.merge(other);
+        res
+    }
+
+    #[inline]
+    fn loosen(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The loosening margin must be positive."
+        );
+
+        for i in 0..KDOP_NUM_AXES {
+            let margin = Self::axis(i).norm() * amount;
+            self.mins[i] -= margin;
+            self.maxs[i] += margin;
+        }
+    }
+
+    #[inline]
+    fn loosened(&self, amount: N) -> KDOP<N> {
+        let mut res = self.clone();
+        res.loosen(amount);
+        res
+    }
+
+    #[inline]
+    fn tighten(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The tightening margin must be positive."
+        );
+
+        for i in 0..KDOP_NUM_AXES {
+            let margin = Self::axis(i).norm() * amount;
+            self.mins[i] += margin;
+            self.maxs[i] -= margin;
+            assert!(
+                self.mins[i] <= self.maxs[i],
+                "The tightening margin is to large."
+            );
+        }
+    }
+
+    #[inline]
+    fn tightened(&self, amount: N) -> KDOP<N> {
+        let mut res = self.clone();
+        res.tighten(amount);
+        res
+    }
+}
@@ -0,0 +1,34 @@
+use crate::bounding_volume::kdop::KDOP_NUM_AXES;
+use crate::bounding_volume::{HasBoundingVolume, KDOP};
+use crate::math::{Isometry, Point};
+use crate::shape::Ball;
+use na::RealField;
+
+/// Computes the KDOP of a ball with the given center and radius.
+#[inline]
+pub fn ball_kdop<N: RealField>(center: &Point<N>, radius: N) -> KDOP<N> {
+    let mut mins = [N::zero(); KDOP_NUM_AXES];
+    let mut maxs = [N::zero(); KDOP_NUM_AXES];
+
+    for i in 0..KDOP_NUM_AXES {
+        let axis = KDOP::axis(i);
+        let proj = axis.dot(&center.coords);
+        let margin = axis.norm() * radius;
+        mins[i] = proj - margin;
+        maxs[i] = proj + margin;
+    }
+
+    KDOP::new(mins, maxs)
+}
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Ball<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        ball_kdop(&Point::from(m.translation.vector), self.radius())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N> {
+        ball_kdop(&Point::origin(), self.radius())
+    }
+}
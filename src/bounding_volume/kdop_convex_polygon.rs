@@ -0,0 +1,16 @@
+use crate::bounding_volume::{self, HasBoundingVolume, KDOP};
+use crate::math::Isometry;
+use crate::shape::ConvexPolygon;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for ConvexPolygon<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::point_cloud_kdop(m, self.points().iter())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N> {
+        bounding_volume::local_point_cloud_kdop(self.points().iter())
+    }
+}
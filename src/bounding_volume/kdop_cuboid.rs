@@ -0,0 +1,11 @@
+use crate::bounding_volume::{HasBoundingVolume, KDOP, OBB};
+use crate::math::Isometry;
+use crate::shape::Cuboid;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Cuboid<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        KDOP::from_obb(&OBB::new(*m, *self.half_extents()))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::bounding_volume;
+use crate::bounding_volume::{HasBoundingVolume, KDOP};
+use crate::math::Isometry;
+use crate::shape::{Capsule, Ellipsoid, Segment};
+#[cfg(feature = "dim3")]
+use crate::shape::{Cone, Cylinder};
+use na::RealField;
+
+#[cfg(feature = "dim3")]
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Cone<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::support_map_kdop(m, self)
+    }
+}
+
+#[cfg(feature = "dim3")]
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Cylinder<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::support_map_kdop(m, self)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Capsule<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::support_map_kdop(m, self)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Ellipsoid<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::support_map_kdop(m, self)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, KDOP<N>> for Segment<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N> {
+        bounding_volume::point_cloud_kdop(m, &[*self.a(), *self.b()])
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N> {
+        bounding_volume::local_point_cloud_kdop(&[*self.a(), *self.b()])
+    }
+}
@@ -0,0 +1,62 @@
+use crate::bounding_volume::kdop::KDOP_NUM_AXES;
+use crate::bounding_volume::KDOP;
+use crate::math::{Isometry, Point};
+use crate::shape::SupportMap;
+use alga::linear::Transformation;
+use na::RealField;
+
+/// Computes the KDOP of an support mapped shape.
+pub fn support_map_kdop<N, G>(m: &Isometry<N>, i: &G) -> KDOP<N>
+where
+    N: RealField,
+    G: SupportMap<N>,
+{
+    let mut mins = [N::zero(); KDOP_NUM_AXES];
+    let mut maxs = [N::zero(); KDOP_NUM_AXES];
+
+    for k in 0..KDOP_NUM_AXES {
+        let axis = KDOP::axis(k);
+        maxs[k] = axis.dot(&i.support_point(m, &axis).coords);
+        mins[k] = axis.dot(&i.support_point(m, &-axis).coords);
+    }
+
+    KDOP::new(mins, maxs)
+}
+
+/// Computes the KDOP of a set of points transformed by `m`.
+pub fn point_cloud_kdop<'a, N: RealField, M: Transformation<Point<N>>, I>(m: &M, pts: I) -> KDOP<N>
+where
+    I: IntoIterator<Item = &'a Point<N>>,
+{
+    let mut it = pts.into_iter();
+
+    let p0 = it.next().expect(
+        "Point cloud KDOP construction: the input iterator should yield at least one point.",
+    );
+    let mut res = KDOP::from_point(&m.transform_point(p0));
+
+    for pt in it {
+        res.take_point(&m.transform_point(pt));
+    }
+
+    res
+}
+
+/// Computes the KDOP of a set of points.
+pub fn local_point_cloud_kdop<'a, N: RealField, I>(pts: I) -> KDOP<N>
+where
+    I: IntoIterator<Item = &'a Point<N>>,
+{
+    let mut it = pts.into_iter();
+
+    let p0 = it.next().expect(
+        "Point cloud KDOP construction: the input iterator should yield at least one point.",
+    );
+    let mut res = KDOP::from_point(p0);
+
+    for pt in it {
+        res.take_point(pt);
+    }
+
+    res
+}
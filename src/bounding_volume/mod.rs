@@ -17,6 +17,14 @@ pub use crate::bounding_volume::bounding_sphere_utils::{
 };
 #[doc(inline)]
 pub use crate::bounding_volume::bounding_volume::{BoundingVolume, HasBoundingVolume};
+#[doc(inline)]
+pub use crate::bounding_volume::kdop::{kdop, local_kdop, KDOP, KDOP_NUM_AXES};
+pub use crate::bounding_volume::kdop_ball::ball_kdop;
+pub use crate::bounding_volume::kdop_utils::{
+    local_point_cloud_kdop, point_cloud_kdop, support_map_kdop,
+};
+#[doc(inline)]
+pub use crate::bounding_volume::obb::{local_obb, obb, OBB};
 
 #[doc(hidden)]
 pub mod bounding_volume;
@@ -33,6 +41,9 @@ mod aabb_cuboid;
 mod aabb_heightfield;
 mod aabb_plane;
 mod aabb_polyline;
+mod aabb_round_shape;
+mod aabb_scaled;
+mod aabb_sdf_shape;
 mod aabb_shape;
 mod aabb_support_map;
 #[cfg(feature = "dim3")]
@@ -40,6 +51,8 @@ mod aabb_triangle;
 #[cfg(feature = "dim3")]
 mod aabb_trimesh;
 mod aabb_utils;
+#[cfg(feature = "dim3")]
+mod aabb_voxels;
 
 #[doc(hidden)]
 pub mod bounding_sphere;
@@ -55,9 +68,13 @@ mod bounding_sphere_convex_polygon;
 mod bounding_sphere_cuboid;
 #[cfg(feature = "dim3")]
 mod bounding_sphere_cylinder;
+mod bounding_sphere_ellipsoid;
 mod bounding_sphere_heightfield;
 mod bounding_sphere_plane;
 mod bounding_sphere_polyline;
+mod bounding_sphere_round_shape;
+mod bounding_sphere_scaled;
+mod bounding_sphere_sdf_shape;
 mod bounding_sphere_segment;
 mod bounding_sphere_shape;
 #[cfg(feature = "dim3")]
@@ -65,6 +82,49 @@ mod bounding_sphere_triangle;
 #[cfg(feature = "dim3")]
 mod bounding_sphere_trimesh;
 mod bounding_sphere_utils;
+#[cfg(feature = "dim3")]
+mod bounding_sphere_voxels;
+
+#[doc(hidden)]
+pub mod kdop;
+mod kdop_ball;
+#[cfg(feature = "dim3")]
+mod kdop_convex;
+#[cfg(feature = "dim2")]
+mod kdop_convex_polygon;
+mod kdop_cuboid;
+mod kdop_polyline;
+mod kdop_round_shape;
+mod kdop_support_map;
+#[cfg(feature = "dim3")]
+mod kdop_triangle;
+#[cfg(feature = "dim3")]
+mod kdop_trimesh;
+mod kdop_utils;
+
+#[doc(hidden)]
+pub mod obb;
+mod obb_ball;
+mod obb_compound;
+#[cfg(feature = "dim3")]
+mod obb_convex;
+#[cfg(feature = "dim2")]
+mod obb_convex_polygon;
+mod obb_cuboid;
+mod obb_heightfield;
+mod obb_plane;
+mod obb_polyline;
+mod obb_round_shape;
+mod obb_scaled;
+mod obb_sdf_shape;
+mod obb_shape;
+mod obb_support_map;
+#[cfg(feature = "dim3")]
+mod obb_triangle;
+#[cfg(feature = "dim3")]
+mod obb_trimesh;
+#[cfg(feature = "dim3")]
+mod obb_voxels;
 
 pub(crate) mod circular_cone;
 mod spatialized_normal_cone;
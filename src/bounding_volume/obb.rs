@@ -0,0 +1,424 @@
+//! Oriented Bounding Box.
+
+use crate::bounding_volume::{self, BoundingSphere, BoundingVolume, HasBoundingVolume, AABB};
+use crate::math::{Dim, Isometry, Matrix, Point, Rotation, Translation, Vector, DIM};
+use crate::transformation;
+use crate::utils::{self, IsometryOps};
+use na::{self, RealField};
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the oriented bounding box of a shape `g` transformed by `m`.
+///
+/// Same as `g.obb(m)`.
+#[inline]
+pub fn obb<N, G: ?Sized>(g: &G, m: &Isometry<N>) -> OBB<N>
+where
+    N: RealField,
+    G: HasBoundingVolume<N, OBB<N>>,
+{
+    g.bounding_volume(m)
+}
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the oriented bounding box of a shape `g`.
+///
+/// Same as `g.local_obb()`.
+#[inline]
+pub fn local_obb<N, G: ?Sized>(g: &G) -> OBB<N>
+where
+    N: RealField,
+    G: HasBoundingVolume<N, OBB<N>>,
+{
+    g.local_bounding_volume()
+}
+
+/// An Oriented Bounding Box.
+///
+/// This is a box with arbitrary orientation. It is centered at the translational part of its
+/// position, and its axes are the columns of the rotational part of its position. Compared to an
+/// `AABB`, it bounds thin rotated objects much more tightly, at the cost of a more expensive
+/// intersection test.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct OBB<N: RealField> {
+    position: Isometry<N>,
+    half_extents: Vector<N>,
+}
+
+impl<N: RealField> OBB<N> {
+    /// Creates a new OBB from its position and its half-extents along each of its local axes.
+    #[inline]
+    pub fn new(position: Isometry<N>, half_extents: Vector<N>) -> OBB<N> {
+        OBB {
+            position,
+            half_extents,
+        }
+    }
+
+    /// Creates the OBB equal to `aabb` transformed by `m`.
+    #[inline]
+    pub fn from_aabb(aabb: &AABB<N>, m: &Isometry<N>) -> OBB<N> {
+        let center = m * aabb.center();
+        OBB::new(
+            Isometry::from_parts(Translation::from(center.coords), m.rotation),
+            aabb.half_extents(),
+        )
+    }
+
+    /// Computes an OBB of a set of points, with axes aligned with the principal directions of
+    /// their covariance matrix.
+    ///
+    /// This is cheap but the result depends on the distribution of the points: dense regions
+    /// attract the axes. Use `OBB::from_convex_hull` for a tighter fit.
+    pub fn from_points(points: &[Point<N>]) -> OBB<N> {
+        let center = utils::center(points);
+        let mut cov = Matrix::zeros();
+
+        for pt in points {
+            let cpt = *pt - center;
+            cov += cpt * cpt.transpose();
+        }
+
+        let rotation = rotation_from_basis(cov.symmetric_eigen().eigenvectors);
+        OBB::from_points_with_rotation(points, &rotation)
+    }
+
+    /// Computes the smallest OBB of a set of points, with its axes oriented by `rotation`.
+    pub fn from_points_with_rotation(points: &[Point<N>], rotation: &Rotation<N>) -> OBB<N> {
+        let mut it = points.iter();
+        let p0 = rotation.inverse_transform_vector(
+            &it.next()
+                .expect("OBB construction: the point cloud should have at least one point.")
+                .coords,
+        );
+        let mut mins = p0;
+        let mut maxs = p0;
+
+        for pt in it {
+            let local = rotation.inverse_transform_vector(&pt.coords);
+            mins = na::inf(&mins, &local);
+            maxs = na::sup(&maxs, &local);
+        }
+
+        let half: N = na::convert(0.5);
+        let center = rotation * ((mins + maxs) * half);
+
+        OBB::new(
+            Isometry::from_parts(Translation::from(center), *rotation),
+            (maxs - mins) * half,
+        )
+    }
+
+    /// Computes an OBB of a set of points from their convex hull.
+    ///
+    /// In 2D, this is the minimum-area bounding rectangle: one of its sides is aligned with an
+    /// edge of the convex hull. In 3D, the axes are the principal directions of the surface of
+    /// the convex hull, which does not depend on the distribution of the points. The
+    /// axis-aligned fit is returned instead if it is smaller.
+    pub fn from_convex_hull(points: &[Point<N>]) -> OBB<N> {
+        let aabb = bounding_volume::local_point_cloud_aabb(points);
+
+        if aabb.extents().norm_squared().is_zero() {
+            return OBB::from_points(points);
+        }
+
+        Self::hull_fit(points)
+    }
+
+    #[cfg(feature = "dim2")]
+    fn hull_fit(points: &[Point<N>]) -> OBB<N> {
+        let hull: Vec<_> = transformation::convex_hull_idx(points)
+            .into_iter()
+            .map(|i| points[i])
+            .collect();
+        let mut best = OBB::from_points_with_rotation(&hull, &Rotation::identity());
+
+        for i in 0..hull.len() {
+            let edge = hull[(i + 1) % hull.len()] - hull[i];
+
+            if edge.norm_squared() > N::default_epsilon() {
+                let rotation = Rotation::rotation_between(&Vector::x(), &edge);
+                let candidate = OBB::from_points_with_rotation(&hull, &rotation);
+
+                if candidate.measure() < best.measure() {
+                    best = candidate;
+                }
+            }
+        }
+
+        best
+    }
+
+    #[cfg(feature = "dim3")]
+    fn hull_fit(points: &[Point<N>]) -> OBB<N> {
+        let hull = transformation::convex_hull(points);
+        let indices = hull.flat_indices();
+        let _3: N = na::convert(3.0);
+        let _9: N = na::convert(9.0);
+        let _12: N = na::convert(12.0);
+
+        // Area-weighted first and second order moments of the hull surface.
+        let mut area = N::zero();
+        let mut mean = Vector::zeros();
+        let mut moments = Matrix::zeros();
+
+        for tri in indices.chunks(3) {
+            let a = hull.coords[tri[0] as usize].coords;
+            let b = hull.coords[tri[1] as usize].coords;
+            let c = hull.coords[tri[2] as usize].coords;
+            let tri_area = (b - a).cross(&(c - a)).norm() * na::convert(0.5);
+            let tri_center = (a + b + c) / _3;
+
+            area += tri_area;
+            mean += tri_center * tri_area;
+            moments += (tri_center * tri_center.transpose() * _9
+                + a * a.transpose()
+                + b * b.transpose()
+                + c * c.transpose())
+                * (tri_area / _12);
+        }
+
+        if area <= N::default_epsilon() {
+            return OBB::from_points(points);
+        }
+
+        mean /= area;
+        let cov = moments / area - mean * mean.transpose();
+        let rotation = rotation_from_basis(cov.symmetric_eigen().eigenvectors);
+        let fit = OBB::from_points_with_rotation(&hull.coords, &rotation);
+        let aligned = OBB::from_points_with_rotation(&hull.coords, &Rotation::identity());
+
+        if aligned.measure() < fit.measure() {
+            aligned
+        } else {
+            fit
+        }
+    }
+
+    /// The position of this OBB: its center and its orientation.
+    #[inline]
+    pub fn position(&self) -> &Isometry<N> {
+        &self.position
+    }
+
+    /// The half extents of this OBB along each of its local axes.
+    #[inline]
+    pub fn half_extents(&self) -> &Vector<N> {
+        &self.half_extents
+    }
+
+    /// The center of this OBB.
+    #[inline]
+    pub fn center(&self) -> Point<N> {
+        Point::from(self.position.translation.vector)
+    }
+
+    /// The `i`-th axis of this OBB.
+    #[inline]
+    pub fn axis(&self, i: usize) -> Vector<N> {
+        let mut axis = Vector::zeros();
+        axis[i] = N::one();
+        self.position.rotation * axis
+    }
+
+    /// Computes the OBB bounding `self` transformed by `m`.
+    #[inline]
+    pub fn transform_by(&self, m: &Isometry<N>) -> Self {
+        OBB::new(m * self.position, self.half_extents)
+    }
+
+    /// The vertices of this OBB.
+    pub fn vertices(&self) -> Vec<Point<N>> {
+        (0..1 << DIM)
+            .map(|i| {
+                let mut corner = self.half_extents;
+
+                for k in 0..DIM {
+                    if i & (1 << k) != 0 {
+                        corner[k] = -corner[k];
+                    }
+                }
+
+                self.position * Point::from(corner)
+            })
+            .collect()
+    }
+
+    /// The smallest AABB containing this OBB.
+    #[inline]
+    pub fn aabb(&self) -> AABB<N> {
+        let half_extents = self.position.absolute_transform_vector(&self.half_extents);
+        AABB::from_half_extents(self.center(), half_extents)
+    }
+
+    /// The smallest bounding sphere containing this OBB.
+    #[inline]
+    pub fn bounding_sphere(&self) -> BoundingSphere<N> {
+        BoundingSphere::new(self.center(), self.half_extents.norm())
+    }
+
+    /// Tests if `point`, expressed in the same frame as this OBB, is inside of it.
+    #[inline]
+    pub fn contains_local_point(&self, point: &Point<N>) -> bool {
+        let local = self.position.inverse_transform_point(point);
+        (0..DIM).all(|i| local[i].abs() <= self.half_extents[i])
+    }
+
+    // The smallest OBB oriented by `rotation` containing both `self` and `other`.
+    fn merged_with_rotation(&self, other: &OBB<N>, rotation: &Rotation<N>) -> OBB<N> {
+        let frame = Isometry::from_parts(Translation::identity(), *rotation).inverse();
+        let local1 = frame * self.position;
+        let local2 = frame * other.position;
+        let half_extents1 = local1.absolute_transform_vector(&self.half_extents);
+        let half_extents2 = local2.absolute_transform_vector(&other.half_extents);
+
+        let mins = na::inf(
+            &(local1.translation.vector - half_extents1),
+            &(local2.translation.vector - half_extents2),
+        );
+        let maxs = na::sup(
+            &(local1.translation.vector + half_extents1),
+            &(local2.translation.vector + half_extents2),
+        );
+
+        let half: N = na::convert(0.5);
+        let center = rotation * ((mins + maxs) * half);
+
+        OBB::new(
+            Isometry::from_parts(Translation::from(center), *rotation),
+            (maxs - mins) * half,
+        )
+    }
+
+    // The volume of this OBB, padded so that flat boxes can still be compared.
+    fn measure(&self) -> N {
+        let pad = self.half_extents.max() * na::convert(1.0e-4);
+        self.half_extents
+            .iter()
+            .fold(N::one(), |acc, e| acc * (*e + pad))
+    }
+}
+
+// Converts the orthonormal basis given by the columns of `basis` to a rotation.
+fn rotation_from_basis<N: RealField>(mut basis: Matrix<N>) -> Rotation<N> {
+    if basis.determinant() < N::zero() {
+        let mut last = basis.column_mut(DIM - 1);
+        last.neg_mut();
+    }
+
+    Rotation::from_rotation_matrix(&na::Rotation::<N, Dim>::from_matrix_unchecked(basis))
+}
+
+impl<N: RealField> BoundingVolume<N> for OBB<N> {
+    #[inline]
+    fn center(&self) -> Point<N> {
+        self.center()
+    }
+
+    // Separating axis test.
+    fn intersects(&self, other: &OBB<N>) -> bool {
+        let dist = other.position.translation.vector - self.position.translation.vector;
+        // The axes of each OBB are the columns of its rotation matrix.
+        let axes1: Matrix<N> = self.position.rotation.to_rotation_matrix().into_inner();
+        let axes2: Matrix<N> = other.position.rotation.to_rotation_matrix().into_inner();
+
+        let separated_along = |axis: &Vector<N>| {
+            let mut radius = N::zero();
+
+            for i in 0..DIM {
+                radius += self.half_extents[i] * axes1.column(i).dot(axis).abs()
+                    + other.half_extents[i] * axes2.column(i).dot(axis).abs();
+            }
+
+            dist.dot(axis).abs() > radius
+        };
+
+        for i in 0..DIM {
+            if separated_along(&axes1.column(i).into_owned())
+                || separated_along(&axes2.column(i).into_owned())
+            {
+                return false;
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            for i in 0..DIM {
+                for j in 0..DIM {
+                    let axis = axes1.column(i).cross(&axes2.column(j));
+
+                    // Parallel axes were already tested above.
+                    if axis.norm_squared() > N::default_epsilon() && separated_along(&axis) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    #[inline]
+    fn contains(&self, other: &OBB<N>) -> bool {
+        let local = self.position.inverse() * other.position;
+        let half_extents = local.absolute_transform_vector(&other.half_extents);
+
+        (0..DIM)
+            .all(|i| local.translation.vector[i].abs() + half_extents[i] <= self.half_extents[i])
+    }
+
+    #[inline]
+    fn merge(&mut self, other: &OBB<N>) {
+        *self = self.merged(other)
+    }
+
+    // Keeps the orientation of whichever of the two OBBs gives the smallest result.
+    fn merged(&self, other: &OBB<N>) -> OBB<N> {
+        let obb1 = self.merged_with_rotation(other, &self.position.rotation);
+        let obb2 = self.merged_with_rotation(other, &other.position.rotation);
+
+        if obb2.measure() < obb1.measure() {
+            obb2
+        } else {
+            obb1
+        }
+    }
+
+    #[inline]
+    fn loosen(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The loosening margin must be positive."
+        );
+        self.half_extents += Vector::repeat(amount);
+    }
+
+    #[inline]
+    fn loosened(&self, amount: N) -> OBB<N> {
+        assert!(
+            amount >= na::zero(),
+            "The loosening margin must be positive."
+        );
+        OBB::new(self.position, self.half_extents + Vector::repeat(amount))
+    }
+
+    #[inline]
+    fn tighten(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The tightening margin must be positive."
+        );
+        self.half_extents -= Vector::repeat(amount);
+        assert!(
+            self.half_extents.iter().all(|e| *e >= N::zero()),
+            "The tightening margin is to large."
+        );
+    }
+
+    #[inline]
+    fn tightened(&self, amount: N) -> OBB<N> {
+        let mut res = self.clone();
+        res.tighten(amount);
+        res
+    }
+}
@@ -0,0 +1,16 @@
+use crate::bounding_volume::{HasBoundingVolume, OBB};
+use crate::math::{Isometry, Vector};
+use crate::shape::Ball;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Ball<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::new(*m, Vector::repeat(self.radius()))
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        OBB::new(Isometry::identity(), Vector::repeat(self.radius()))
+    }
+}
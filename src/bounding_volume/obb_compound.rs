@@ -0,0 +1,21 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::Compound;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Compound<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::local_obb(self).transform_by(m)
+    }
+
+    fn local_bounding_volume(&self) -> OBB<N> {
+        let vertices: Vec<_> = self
+            .shapes()
+            .iter()
+            .flat_map(|(delta, shape)| shape.obb(delta).vertices())
+            .collect();
+
+        OBB::from_points(&vertices)
+    }
+}
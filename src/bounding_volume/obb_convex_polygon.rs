@@ -0,0 +1,16 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::ConvexPolygon;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for ConvexPolygon<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::local_obb(self).transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        OBB::from_convex_hull(self.points())
+    }
+}
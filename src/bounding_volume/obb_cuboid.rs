@@ -0,0 +1,16 @@
+use crate::bounding_volume::{HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::Cuboid;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Cuboid<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::new(*m, *self.half_extents())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        OBB::new(Isometry::identity(), *self.half_extents())
+    }
+}
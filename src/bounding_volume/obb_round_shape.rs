@@ -0,0 +1,22 @@
+use crate::bounding_volume::{BoundingVolume, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::RoundShape;
+use na::RealField;
+
+impl<N: RealField, S: HasBoundingVolume<N, OBB<N>>> HasBoundingVolume<N, OBB<N>>
+    for RoundShape<N, S>
+{
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        self.inner_shape()
+            .bounding_volume(m)
+            .loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        self.inner_shape()
+            .local_bounding_volume()
+            .loosened(self.radius())
+    }
+}
@@ -0,0 +1,12 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::{ScalableShape, Scaled};
+use na::RealField;
+
+impl<N: RealField, S: ScalableShape<N>> HasBoundingVolume<N, OBB<N>> for Scaled<N, S> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        // A non-uniformly scaled box is no longer a box, so we start from the local AABB.
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
@@ -0,0 +1,11 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::SDFShape;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for SDFShape<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
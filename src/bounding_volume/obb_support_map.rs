@@ -0,0 +1,51 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::{Capsule, Ellipsoid, Segment};
+#[cfg(feature = "dim3")]
+use crate::shape::{Cone, Cylinder};
+use na::RealField;
+
+// These shapes are symmetric with respect to their local axes so their local AABB is also their
+// tightest OBB.
+
+#[cfg(feature = "dim3")]
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Cone<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
+
+#[cfg(feature = "dim3")]
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Cylinder<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Capsule<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Ellipsoid<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&bounding_volume::local_aabb(self), m)
+    }
+}
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Segment<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::local_obb(self).transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        OBB::from_points(&[*self.a(), *self.b()])
+    }
+}
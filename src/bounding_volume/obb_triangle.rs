@@ -0,0 +1,40 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::{Isometry, Rotation};
+use crate::shape::Triangle;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Triangle<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::local_obb(self).transform_by(m)
+    }
+
+    fn local_bounding_volume(&self) -> OBB<N> {
+        let vertices = self.vertices();
+
+        let normal = match self.normal() {
+            Some(normal) => normal,
+            None => return OBB::from_points(&vertices[..]),
+        };
+
+        // The minimum-area rectangle enclosing a triangle has one side collinear with one of
+        // its edges.
+        let mut best: Option<OBB<N>> = None;
+
+        for i in 0..3 {
+            let edge = vertices[(i + 1) % 3] - vertices[i];
+            let rotation = Rotation::face_towards(&normal, &edge);
+            let candidate = OBB::from_points_with_rotation(&vertices[..], &rotation);
+            let area = candidate.half_extents().x * candidate.half_extents().y;
+
+            if best
+                .as_ref()
+                .map_or(true, |b| area < b.half_extents().x * b.half_extents().y)
+            {
+                best = Some(candidate);
+            }
+        }
+
+        best.unwrap()
+    }
+}
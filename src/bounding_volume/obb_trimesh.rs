@@ -0,0 +1,17 @@
+use crate::bounding_volume::{self, HasBoundingVolume, OBB};
+use crate::math::Isometry;
+use crate::shape::TriMesh;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for TriMesh<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::local_obb(self).transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        // NOTE: this is not cached and grows with the number of vertices.
+        OBB::from_points(self.points())
+    }
+}
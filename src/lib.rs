@@ -60,6 +60,8 @@ extern crate bitflags;
 extern crate either;
 extern crate nalgebra as na;
 extern crate num_traits as num;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 extern crate slab;
 extern crate smallvec;
 
@@ -1,3 +1,5 @@
+use crate::bounding_volume::AABB;
+use crate::math::Vector;
 use crate::partitioning::{
     BVTNodeId, BestFirstVisitStatus, BestFirstVisitor, DBVTNodeId, SimultaneousVisitor,
     VisitStatus, Visitor, BVT, DBVT,
@@ -163,6 +165,12 @@ pub enum BVHImpl<'a, N: 'a + RealField, T: 'a, BV: 'a> {
     BVT(&'a BVT<T, BV>),
     /// A dynamic binary bounding volume tree.
     DBVT(&'a DBVT<N, T, BV>),
+    /// A static binary bounding volume tree with bounding volumes scaled along each coordinate
+    /// axis.
+    ScaledBVT(&'a BVT<T, BV>, Vector<N>),
+    /// A dynamic binary bounding volume tree with bounding volumes scaled along each coordinate
+    /// axis.
+    ScaledDBVT(&'a DBVT<N, T, BV>, Vector<N>),
 }
 
 /// The Id of a node of a BVH.
@@ -192,36 +200,92 @@ impl<'a, N: RealField, T, BV> BVHImpl<'a, N, T, BV> {
         }
     }
 
+    /// This BVH with its bounding volumes scaled by `scale` along each coordinate axis.
+    pub fn scaled(self, scale: &Vector<N>) -> Self {
+        match self {
+            BVHImpl::BVT(bvt) => BVHImpl::ScaledBVT(bvt, *scale),
+            BVHImpl::DBVT(dbvt) => BVHImpl::ScaledDBVT(dbvt, *scale),
+            BVHImpl::ScaledBVT(bvt, s) => BVHImpl::ScaledBVT(bvt, s.component_mul(scale)),
+            BVHImpl::ScaledDBVT(dbvt, s) => BVHImpl::ScaledDBVT(dbvt, s.component_mul(scale)),
+        }
+    }
+
+    // The unscaled BVH, and the scaling factor of its bounding volumes, if any.
+    fn unscaled(self) -> (Self, Option<Vector<N>>) {
+        match self {
+            BVHImpl::ScaledBVT(bvt, scale) => (BVHImpl::BVT(bvt), Some(scale)),
+            BVHImpl::ScaledDBVT(dbvt, scale) => (BVHImpl::DBVT(dbvt), Some(scale)),
+            _ => (self, None),
+        }
+    }
+}
+
+// NOTE: scaled bounding volume hierarchies are only supported with AABBs, which remain exact
+// bounding volumes after a scaling along the coordinate axes.
+impl<'a, N: RealField, T> BVHImpl<'a, N, T, AABB<N>> {
     /// Traverses this tree using a visitor.
-    pub fn visit(self, visitor: &mut impl Visitor<T, BV>) {
+    pub fn visit(self, visitor: &mut impl Visitor<T, AABB<N>>) {
+        match self.unscaled() {
+            (bvh, Some(scale)) => bvh.visit_unscaled(&mut ScaledVisitor {
+                inner: visitor,
+                scale,
+            }),
+            (bvh, None) => bvh.visit_unscaled(visitor),
+        }
+    }
+
+    fn visit_unscaled(self, visitor: &mut impl Visitor<T, AABB<N>>) {
         match self {
             BVHImpl::BVT(bvt) => bvt.visit(visitor),
             BVHImpl::DBVT(dbvt) => dbvt.visit(visitor),
+            _ => unreachable!(),
         }
     }
 
     /// Visits the bounding volume traversal tree implicitly formed with `other`.
     pub fn visit_bvtt(
         self,
-        other: BVHImpl<N, T, BV>,
-        visitor: &mut impl SimultaneousVisitor<T, BV>,
+        other: BVHImpl<N, T, AABB<N>>,
+        visitor: &mut impl SimultaneousVisitor<T, AABB<N>>,
+    ) {
+        let (bvh1, scale1) = self.unscaled();
+        let (bvh2, scale2) = other.unscaled();
+
+        if scale1.is_none() && scale2.is_none() {
+            bvh1.visit_bvtt_unscaled(bvh2, visitor)
+        } else {
+            let mut visitor = ScaledSimultaneousVisitor {
+                inner: visitor,
+                scale1,
+                scale2,
+            };
+            bvh1.visit_bvtt_unscaled(bvh2, &mut visitor)
+        }
+    }
+
+    fn visit_bvtt_unscaled(
+        self,
+        other: BVHImpl<N, T, AABB<N>>,
+        visitor: &mut impl SimultaneousVisitor<T, AABB<N>>,
     ) {
         // Note: the dispatch on each pair is split into two method to avoid
         // having to write a manually a match over each possible pair.
         match other {
             BVHImpl::BVT(bvh2) => self.visit_bvtt_dispatch(bvh2, visitor),
             BVHImpl::DBVT(bvh2) => self.visit_bvtt_dispatch(bvh2, visitor),
+            _ => unreachable!(),
         }
     }
 
     fn visit_bvtt_dispatch(
         self,
-        bvh2: &impl BVH<T, BV>,
-        visitor: &mut impl SimultaneousVisitor<T, BV>,
+        bvh2: &impl BVH<T, AABB<N>>,
+        visitor: &mut impl SimultaneousVisitor<T, AABB<N>>,
     ) {
         match self {
             BVHImpl::BVT(bvh1) => bvh1.visit_bvtt(bvh2, visitor),
             BVHImpl::DBVT(bvh1) => bvh1.visit_bvtt(bvh2, visitor),
+            _ => unreachable!(),
         }
     }
 
@@ -231,7 +295,20 @@ impl<'a, N: RealField, T, BV> BVHImpl<'a, N, T, BV> {
     /// user-defined type.
     pub fn best_first_search<BFS>(self, visitor: &mut BFS) -> Option<(BVHNodeId, BFS::Result)>
     where
-        BFS: BestFirstVisitor<N, T, BV>,
+        BFS: BestFirstVisitor<N, T, AABB<N>>,
+    {
+        match self.unscaled() {
+            (bvh, Some(scale)) => bvh.best_first_search_unscaled(&mut ScaledVisitor {
+                inner: visitor,
+                scale,
+            }),
+            (bvh, None) => bvh.best_first_search_unscaled(visitor),
+        }
+    }
+
+    fn best_first_search_unscaled<BFS>(self, visitor: &mut BFS) -> Option<(BVHNodeId, BFS::Result)>
+    where
+        BFS: BestFirstVisitor<N, T, AABB<N>>,
     {
         match self {
             BVHImpl::BVT(bvt) => bvt
@@ -240,11 +317,73 @@ impl<'a, N: RealField, T, BV> BVHImpl<'a, N, T, BV> {
             BVHImpl::DBVT(dbvt) => dbvt
                 .best_first_search(visitor)
                 .map(|res| (BVHNodeId::DBVTNodeId(res.0), res.1)),
+            _ => unreachable!(),
         }
     }
 }
 
-struct WeightedValue<N, T> {
+// Gives the scaled bounding volumes to the visitor `inner`.
+struct ScaledVisitor<'a, V: ?Sized, N: RealField> {
+    inner: &'a mut V,
+    scale: Vector<N>,
+}
+
+impl<'a, N: RealField, T, V: Visitor<T, AABB<N>>> Visitor<T, AABB<N>> for ScaledVisitor<'a, V, N> {
+    #[inline]
+    fn visit(&mut self, bv: &AABB<N>, data: Option<&T>) -> VisitStatus {
+        self.inner.visit(&bv.scaled_by(&self.scale), data)
+    }
+}
+
+impl<'a, N: RealField, T, V: BestFirstVisitor<N, T, AABB<N>>> BestFirstVisitor<N, T, AABB<N>>
+    for ScaledVisitor<'a, V, N>
+{
+    type Result = V::Result;
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best_cost_so_far: N,
+        bv: &AABB<N>,
+        data: Option<&T>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        self.inner
+            .visit(best_cost_so_far, &bv.scaled_by(&self.scale), data)
+    }
+}
+
+struct ScaledSimultaneousVisitor<'a, V: ?Sized, N: RealField> {
+    inner: &'a mut V,
+    scale1: Option<Vector<N>>,
+    scale2: Option<Vector<N>>,
+}
+
+impl<'a, N: RealField, T, V: SimultaneousVisitor<T, AABB<N>>> SimultaneousVisitor<T, AABB<N>>
+    for ScaledSimultaneousVisitor<'a, V, N>
+{
+    #[inline]
+    fn visit(
+        &mut self,
+        left_bv: &AABB<N>,
+        left_data: Option<&T>,
+        right_bv: &AABB<N>,
+        right_data: Option<&T>,
+    ) -> VisitStatus {
+        let scale = |bv: &AABB<N>, scale: &Option<Vector<N>>| match scale {
+            Some(scale) => bv.scaled_by(scale),
+            None => bv.clone(),
+        };
+
+        self.inner.visit(
+            &scale(left_bv, &self.scale1),
+            left_data,
+            &scale(right_bv, &self.scale2),
+            right_data,
+        )
+    }
+}
+
+pub(crate) struct WeightedValue<N, T> {
     pub value: T,
     pub cost: N,
 }
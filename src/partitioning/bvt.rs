@@ -1,6 +1,6 @@
 //! A read-only Bounding Volume Tree.
 
-use crate::bounding_volume::BoundingVolume;
+use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::{Point, DIM};
 use crate::partitioning::BVH;
 use crate::utils;
@@ -21,7 +21,8 @@ pub struct BVT<T, BV> {
     // that are not needed in the general case.
     deformation_timestamp: usize,
     deformation_infos: Vec<BVTDeformationInfo>,
-    // Infos for leaves are stored starting at the index self.internals.len().
+    // The parent of each leaf.
+    leaf_parents: Vec<usize>,
     parents_to_update: VecDeque<usize>,
 }
 
@@ -103,6 +104,7 @@ impl<T, BV> BVT<T, BV> {
                 leaves: Vec::new(),
                 deformation_timestamp: 1,
                 deformation_infos: Vec::new(),
+                leaf_parents: Vec::new(),
                 parents_to_update: VecDeque::new(),
             }
         } else {
@@ -119,6 +121,7 @@ impl<T, BV> BVT<T, BV> {
                 leaves,
                 deformation_timestamp: 1,
                 deformation_infos: Vec::new(),
+                leaf_parents: Vec::new(),
                 parents_to_update: VecDeque::new(),
             }
         }
@@ -163,30 +166,16 @@ impl<T, BV> BVT<T, BV> {
         self.leaves[i].bounding_volume = bv;
 
         if refit_now {
-            let mut curr = self.deformation_infos[self.internals.len() + i].parent;
+            let mut curr = self.leaf_parents[i];
 
             while curr != usize::max_value() {
-                let new_bv = match (self.internals[curr].left, self.internals[curr].right) {
-                    (BVTNodeId::Internal(i), BVTNodeId::Internal(j)) => self.internals[i]
-                        .bounding_volume
-                        .merged(&self.internals[j].bounding_volume),
-                    (BVTNodeId::Internal(i), BVTNodeId::Leaf(j)) => self.internals[i]
-                        .bounding_volume
-                        .merged(&self.leaves[j].bounding_volume),
-                    (BVTNodeId::Leaf(i), BVTNodeId::Internal(j)) => self.leaves[i]
-                        .bounding_volume
-                        .merged(&self.internals[j].bounding_volume),
-                    (BVTNodeId::Leaf(i), BVTNodeId::Leaf(j)) => self.leaves[i]
-                        .bounding_volume
-                        .merged(&self.leaves[j].bounding_volume),
-                };
+                let new_bv = Self::children_bounding_volume(&self.internals, &self.leaves, curr);
                 self.internals[curr].bounding_volume = new_bv;
                 curr = self.deformation_infos[curr].parent;
             }
         } else {
             if self.leaves.len() != 1 {
-                self.parents_to_update
-                    .push_back(self.deformation_infos[self.internals.len() + i].parent)
+                self.parents_to_update.push_back(self.leaf_parents[i])
             }
         }
     }
@@ -213,20 +202,8 @@ impl<T, BV> BVT<T, BV> {
                 // This node has not been updated yet.
                 infos.timestamp = self.deformation_timestamp;
 
-                let mut new_bv = match (self.internals[curr].left, self.internals[curr].right) {
-                    (BVTNodeId::Internal(i), BVTNodeId::Internal(j)) => self.internals[i]
-                        .bounding_volume
-                        .merged(&self.internals[j].bounding_volume),
-                    (BVTNodeId::Internal(i), BVTNodeId::Leaf(j)) => self.internals[i]
-                        .bounding_volume
-                        .merged(&self.leaves[j].bounding_volume),
-                    (BVTNodeId::Leaf(i), BVTNodeId::Internal(j)) => self.leaves[i]
-                        .bounding_volume
-                        .merged(&self.internals[j].bounding_volume),
-                    (BVTNodeId::Leaf(i), BVTNodeId::Leaf(j)) => self.leaves[i]
-                        .bounding_volume
-                        .merged(&self.leaves[j].bounding_volume),
-                };
+                let mut new_bv =
+                    Self::children_bounding_volume(&self.internals, &self.leaves, curr);
 
                 if !self.internals[curr].bounding_volume.contains(&new_bv) {
                     if !margin.is_zero() {
@@ -244,29 +221,254 @@ impl<T, BV> BVT<T, BV> {
         }
     }
 
+    /// Recomputes the bounding volumes of all the nodes of this BVT.
+    ///
+    /// The bounding volume of each leaf is replaced by `leaf_bounding_volume` applied to its data,
+    /// and then the bounding volumes of all the internal nodes are recomputed in a single
+    /// bottom-up pass. This is faster than calling `.set_leaf_bounding_volume(_, _, false)` on
+    /// each leaf followed by `.refit()` when most leaves change, e.g., when a whole mesh is
+    /// deformed. Unlike `.refit()`, this also shrinks the bounding volumes that became too large.
+    pub fn refit_bottom_up<N: RealField>(&mut self, mut leaf_bounding_volume: impl FnMut(&T) -> BV)
+    where
+        BV: BoundingVolume<N>,
+    {
+        for leaf in &mut self.leaves {
+            leaf.bounding_volume = leaf_bounding_volume(&leaf.data);
+        }
+
+        if let BVTNodeId::Internal(root) = self.root {
+            // Sort the internal nodes so that each one comes after its children.
+            let mut stack = vec![root];
+            let mut order = Vec::with_capacity(self.internals.len());
+
+            while let Some(i) = stack.pop() {
+                order.push(i);
+
+                for child in &[self.internals[i].left, self.internals[i].right] {
+                    if let BVTNodeId::Internal(j) = child {
+                        stack.push(*j)
+                    }
+                }
+            }
+
+            for i in order.into_iter().rev() {
+                self.internals[i].bounding_volume =
+                    Self::children_bounding_volume(&self.internals, &self.leaves, i);
+            }
+        }
+
+        self.parents_to_update.clear();
+    }
+
+    /// Inserts a new leaf into this BVT and returns its index.
+    ///
+    /// The new leaf is paired with the existing leaf with the closest center, and the bounding
+    /// volumes of its ancestors are enlarged to enclose it. This is much faster than building
+    /// a new BVT, but the tree may become less efficient after many insertions.
+    pub fn insert_leaf<N: RealField>(&mut self, data: T, bounding_volume: BV) -> usize
+    where
+        BV: BoundingVolume<N>,
+    {
+        self.init_deformation_infos();
+
+        let leaf = self.leaves.len();
+        let center = bounding_volume.center();
+        self.leaves.push(BVTLeaf {
+            bounding_volume,
+            data,
+        });
+        self.leaf_parents.push(usize::max_value());
+
+        if leaf == 0 {
+            self.root = BVTNodeId::Leaf(0);
+            return leaf;
+        }
+
+        // Find the closest leaf.
+        let mut sibling = self.root;
+        let mut parent = usize::max_value();
+
+        while let BVTNodeId::Internal(i) = sibling {
+            let dist = |node| na::distance_squared(&self.bounding_volume(node).center(), &center);
+            let (left, right) = (self.internals[i].left, self.internals[i].right);
+            parent = i;
+            sibling = if dist(left) < dist(right) {
+                left
+            } else {
+                right
+            };
+        }
+
+        let new_parent = self.internals.len();
+        let bv = self
+            .bounding_volume(sibling)
+            .merged(&self.leaves[leaf].bounding_volume);
+        self.internals.push(BVTInternal {
+            bounding_volume: bv,
+            left: sibling,
+            right: BVTNodeId::Leaf(leaf),
+        });
+        self.deformation_infos.push(BVTDeformationInfo {
+            parent,
+            timestamp: 0,
+        });
+        self.set_parent(sibling, new_parent);
+        self.set_parent(BVTNodeId::Leaf(leaf), new_parent);
+        self.replace_child(parent, sibling, BVTNodeId::Internal(new_parent));
+
+        let mut curr = parent;
+
+        while curr != usize::max_value() {
+            self.internals[curr]
+                .bounding_volume
+                .merge(&self.leaves[leaf].bounding_volume);
+            curr = self.deformation_infos[curr].parent;
+        }
+
+        leaf
+    }
+
+    /// Removes the `i`-th leaf of this BVT and returns it.
+    ///
+    /// The last leaf of this BVT takes the index `i`. The bounding volumes of the ancestors of
+    /// the removed leaf are recomputed to enclose exactly their remaining children.
+    pub fn remove_leaf<N: RealField>(&mut self, i: usize) -> BVTLeaf<T, BV>
+    where
+        BV: BoundingVolume<N>,
+    {
+        self.init_deformation_infos();
+
+        let parent = self.leaf_parents[i];
+
+        if parent != usize::max_value() {
+            let sibling = if self.internals[parent].left == BVTNodeId::Leaf(i) {
+                self.internals[parent].right
+            } else {
+                self.internals[parent].left
+            };
+            let grand_parent = self.deformation_infos[parent].parent;
+            self.set_parent(sibling, grand_parent);
+            self.replace_child(grand_parent, BVTNodeId::Internal(parent), sibling);
+
+            let mut curr = grand_parent;
+
+            while curr != usize::max_value() {
+                self.internals[curr].bounding_volume =
+                    Self::children_bounding_volume(&self.internals, &self.leaves, curr);
+                curr = self.deformation_infos[curr].parent;
+            }
+
+            self.remove_internal(parent);
+        }
+
+        let last = self.leaves.len() - 1;
+        let removed = self.leaves.swap_remove(i);
+        let _ = self.leaf_parents.swap_remove(i);
+
+        if i != last {
+            self.replace_child(
+                self.leaf_parents[i],
+                BVTNodeId::Leaf(last),
+                BVTNodeId::Leaf(i),
+            );
+        }
+
+        if self.leaves.is_empty() {
+            self.root = BVTNodeId::Leaf(0);
+        }
+
+        removed
+    }
+
+    /// Mutable reference to the user-data stored on the i-th leaf.
+    #[inline]
+    pub fn leaf_data_mut(&mut self, i: usize) -> &mut T {
+        &mut self.leaves[i].data
+    }
+
+    // Removes the `i`-th internal node, which must already be detached from the tree. The last
+    // internal node takes the index `i`.
+    fn remove_internal(&mut self, i: usize) {
+        let last = self.internals.len() - 1;
+        let _ = self.internals.swap_remove(i);
+        let _ = self.deformation_infos.swap_remove(i);
+        self.parents_to_update.retain(|j| *j != i);
+
+        if i != last {
+            let (left, right) = (self.internals[i].left, self.internals[i].right);
+            self.set_parent(left, i);
+            self.set_parent(right, i);
+            self.replace_child(
+                self.deformation_infos[i].parent,
+                BVTNodeId::Internal(last),
+                BVTNodeId::Internal(i),
+            );
+
+            for j in self.parents_to_update.iter_mut() {
+                if *j == last {
+                    *j = i
+                }
+            }
+        }
+    }
+
+    fn bounding_volume(&self, node: BVTNodeId) -> &BV {
+        match node {
+            BVTNodeId::Internal(i) => &self.internals[i].bounding_volume,
+            BVTNodeId::Leaf(i) => &self.leaves[i].bounding_volume,
+        }
+    }
+
+    fn set_parent(&mut self, node: BVTNodeId, parent: usize) {
+        match node {
+            BVTNodeId::Internal(i) => self.deformation_infos[i].parent = parent,
+            BVTNodeId::Leaf(i) => self.leaf_parents[i] = parent,
+        }
+    }
+
+    // Replaces the child `old` of the internal node `parent` by `new`. If `parent` is
+    // `usize::max_value()`, `old` is the root.
+    fn replace_child(&mut self, parent: usize, old: BVTNodeId, new: BVTNodeId) {
+        if parent == usize::max_value() {
+            self.root = new;
+        } else if self.internals[parent].left == old {
+            self.internals[parent].left = new;
+        } else {
+            self.internals[parent].right = new;
+        }
+    }
+
+    // The union of the bounding volumes of the children of the `i`-th internal node.
+    fn children_bounding_volume<N: RealField>(
+        internals: &[BVTInternal<BV>],
+        leaves: &[BVTLeaf<T, BV>],
+        i: usize,
+    ) -> BV
+    where
+        BV: BoundingVolume<N>,
+    {
+        let bv = |node| match node {
+            BVTNodeId::Internal(j) => &internals[j].bounding_volume,
+            BVTNodeId::Leaf(j) => &leaves[j].bounding_volume,
+        };
+
+        bv(internals[i].left).merged(bv(internals[i].right))
+    }
+
     fn init_deformation_infos(&mut self) {
-        if self.deformation_infos.is_empty() {
+        if self.leaf_parents.len() != self.leaves.len() {
             self.deformation_infos = iter::repeat(BVTDeformationInfo {
                 parent: usize::max_value(),
                 timestamp: 0,
             })
-            .take(self.internals.len() + self.leaves.len())
+            .take(self.internals.len())
             .collect();
+            self.leaf_parents = vec![usize::max_value(); self.leaves.len()];
 
-            for (i, internal) in self.internals.iter().enumerate() {
-                match internal.left {
-                    BVTNodeId::Internal(j) => self.deformation_infos[j].parent = i,
-                    BVTNodeId::Leaf(j) => {
-                        self.deformation_infos[self.internals.len() + j].parent = i
-                    }
-                }
-
-                match internal.right {
-                    BVTNodeId::Internal(j) => self.deformation_infos[j].parent = i,
-                    BVTNodeId::Leaf(j) => {
-                        self.deformation_infos[self.internals.len() + j].parent = i
-                    }
-                }
+            for i in 0..self.internals.len() {
+                let (left, right) = (self.internals[i].left, self.internals[i].right);
+                self.set_parent(left, i);
+                self.set_parent(right, i);
             }
         }
     }
@@ -400,6 +602,150 @@ impl<T, BV> BVT<T, BV> {
     }
 }
 
+/// The number of bins per axis used to evaluate the surface area heuristic.
+const SAH_NUM_BINS: usize = 16;
+
+impl<T, N: RealField> BVT<T, AABB<N>> {
+    /// Creates a `BVT` using the binned surface area heuristic.
+    ///
+    /// This is slower to build than `BVT::new_balanced` but yields trees that are
+    /// faster to traverse, especially when the leaves are unevenly distributed.
+    pub fn new_with_sah(leaves: Vec<(T, AABB<N>)>) -> BVT<T, AABB<N>> {
+        BVT::from_partitioning(leaves, &mut Self::sah_partitioning)
+    }
+
+    /// Construction function for a BVT to be used with `BVT::from_partitioning`.
+    ///
+    /// The leaves are sorted into bins along each axis depending on the center of their AABB.
+    /// The split retained is the one minimizing the surface area of each half weighted by its
+    /// number of leaves.
+    pub fn sah_partitioning(
+        _depth: usize,
+        mut leaves: Vec<(T, AABB<N>)>,
+    ) -> (AABB<N>, BinaryPartition<T, AABB<N>>) {
+        if leaves.len() == 0 {
+            panic!("Cannot build a tree without leaves.");
+        } else if leaves.len() == 1 {
+            let (b, bv) = leaves.into_iter().next().unwrap();
+            return (bv, BinaryPartition::Part(b));
+        }
+
+        let mut bounding_bounding_volume = leaves[0].1.clone();
+        let first_center = leaves[0].1.center();
+        let mut centers_bounds = AABB::new(first_center, first_center);
+
+        for (_, bv) in &leaves[1..] {
+            let center = bv.center();
+            bounding_bounding_volume.merge(bv);
+            centers_bounds.merge(&AABB::new(center, center));
+        }
+
+        // The best split as a (cost, axis, last bin of the left part) triplet.
+        let mut best: Option<(N, usize, usize)> = None;
+
+        for axis in 0..DIM {
+            let min = centers_bounds.mins()[axis];
+            let extent = centers_bounds.maxs()[axis] - min;
+
+            if extent <= N::zero() {
+                continue;
+            }
+
+            let mut bins: [Option<AABB<N>>; SAH_NUM_BINS] = Default::default();
+            let mut counts = [0; SAH_NUM_BINS];
+
+            for (_, bv) in &leaves {
+                let bin = sah_bin(bv.center()[axis], min, extent);
+                counts[bin] += 1;
+                merge_into(&mut bins[bin], bv);
+            }
+
+            // Costs of the right parts, sweeping from the last bin.
+            let mut right_costs = [N::zero(); SAH_NUM_BINS];
+            let mut right: Option<AABB<N>> = None;
+            let mut right_count = 0;
+
+            for bin in (1..SAH_NUM_BINS).rev() {
+                if let Some(bv) = &bins[bin] {
+                    merge_into(&mut right, bv);
+                    right_count += counts[bin];
+                }
+
+                if let Some(bv) = &right {
+                    right_costs[bin] = half_area(bv) * na::convert(right_count as f64);
+                }
+            }
+
+            let mut left: Option<AABB<N>> = None;
+            let mut left_count = 0;
+
+            for bin in 0..SAH_NUM_BINS - 1 {
+                if let Some(bv) = &bins[bin] {
+                    merge_into(&mut left, bv);
+                    left_count += counts[bin];
+                }
+
+                if left_count == 0 || left_count == leaves.len() {
+                    continue;
+                }
+
+                let left_cost = half_area(left.as_ref().unwrap()) * na::convert(left_count as f64);
+                let cost = left_cost + right_costs[bin + 1];
+
+                if best.map(|best| cost < best.0).unwrap_or(true) {
+                    best = Some((cost, axis, bin));
+                }
+            }
+        }
+
+        let (left, right) = match best {
+            Some((_, axis, split_bin)) => {
+                let min = centers_bounds.mins()[axis];
+                let extent = centers_bounds.maxs()[axis] - min;
+                leaves
+                    .into_iter()
+                    .partition(|(_, bv)| sah_bin(bv.center()[axis], min, extent) <= split_bin)
+            }
+            None => {
+                // All the centers coincide.
+                let right = leaves.split_off(leaves.len() / 2);
+                (leaves, right)
+            }
+        };
+
+        (
+            bounding_bounding_volume,
+            BinaryPartition::Parts(left, right),
+        )
+    }
+}
+
+fn sah_bin<N: RealField>(coord: N, min: N, extent: N) -> usize {
+    let bin = (coord - min) / extent * na::convert(SAH_NUM_BINS as f64);
+    let bin = unsafe { na::convert_unchecked::<N, f64>(bin) };
+    (bin.max(0.0) as usize).min(SAH_NUM_BINS - 1)
+}
+
+fn merge_into<N: RealField>(acc: &mut Option<AABB<N>>, aabb: &AABB<N>) {
+    match acc {
+        Some(acc) => acc.merge(aabb),
+        None => *acc = Some(aabb.clone()),
+    }
+}
+
+// Half the surface area of an AABB in 3D, or half its perimeter in 2D.
+#[cfg(feature = "dim2")]
+fn half_area<N: RealField>(aabb: &AABB<N>) -> N {
+    let extents = aabb.extents();
+    extents.x + extents.y
+}
+
+#[cfg(feature = "dim3")]
+fn half_area<N: RealField>(aabb: &AABB<N>) -> N {
+    let extents = aabb.extents();
+    extents.x * extents.y + extents.y * extents.z + extents.z * extents.x
+}
+
 impl<'a, T, BV> BVH<T, BV> for BVT<T, BV> {
     type Node = BVTNodeId;
 
@@ -7,6 +7,7 @@ use std::ops::Index;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 /// The unique identifier of a DBVT leaf.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DBVTLeafId(usize);
 
 impl DBVTLeafId {
@@ -25,12 +26,14 @@ impl DBVTLeafId {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum UpdateStatus {
     NeedsShrink,
     UpToDate,
 }
 
 #[derive(Copy, Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum DBVTInternalId {
     RightChildOf(usize),
     LeftChildOf(usize),
@@ -39,6 +42,7 @@ enum DBVTInternalId {
 
 /// The identifier of a node of the DBVT.
 #[derive(Copy, Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DBVTNodeId {
     /// Id of a leaf.
     Leaf(usize),
@@ -48,6 +52,7 @@ pub enum DBVTNodeId {
 
 /// A bounding volume hierarchy on which objects can be added or removed after construction.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DBVT<N: RealField, T, BV> {
     root: DBVTNodeId,
     leaves: Slab<DBVTLeaf<N, T, BV>>,
@@ -56,6 +61,7 @@ pub struct DBVT<N: RealField, T, BV> {
 
 /// Leaf of a Dynamic Bounding Volume Tree.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DBVTLeaf<N: RealField, T, BV> {
     /// The bounding volume of this node.
     pub bounding_volume: BV,
@@ -69,6 +75,7 @@ pub struct DBVTLeaf<N: RealField, T, BV> {
 
 /// Internal node of a DBVT. An internal node always has two children.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct DBVTInternal<N: RealField, BV> {
     /// The bounding volume of this node. It always encloses both its children bounding volumes.
     bounding_volume: BV,
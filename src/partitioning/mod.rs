@@ -3,6 +3,7 @@
 pub use self::bvh::{BVHImpl, BVH};
 pub use self::bvt::{BVTNodeId, BinaryPartition, BVT};
 pub use self::dbvt::{DBVTLeaf, DBVTLeafId, DBVTNodeId, DBVT};
+pub use self::qbvh::{QAABB, QBVH};
 pub use self::visitor::{
     BestFirstVisitStatus, BestFirstVisitor, SimultaneousVisitor, VisitStatus, Visitor,
 };
@@ -10,4 +11,5 @@ pub use self::visitor::{
 mod bvh;
 mod bvt;
 mod dbvt;
+mod qbvh;
 mod visitor;
@@ -0,0 +1,438 @@
+//! A flattened Bounding Volume Hierarchy with up to four children per node.
+
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Point, DIM};
+use crate::partitioning::bvh::WeightedValue;
+use crate::partitioning::{VisitStatus, BVH, BVT};
+use crate::query::Ray;
+use na::RealField;
+use std::collections::BinaryHeap;
+
+/// The maximum number of children of a `QBVH` node.
+const QBVH_WIDTH: usize = 4;
+
+/// The AABBs of the children of a `QBVH` node.
+///
+/// The AABBs are stored coordinate by coordinate: each array holds the same coordinate of the
+/// four AABBs. This way, the four AABBs are tested together with a few lane-wise operations
+/// that the compiler can vectorize.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct QAABB<N> {
+    mins: [[N; QBVH_WIDTH]; DIM],
+    maxs: [[N; QBVH_WIDTH]; DIM],
+}
+
+impl<N: RealField> QAABB<N> {
+    fn new() -> Self {
+        QAABB {
+            mins: [[N::zero(); QBVH_WIDTH]; DIM],
+            maxs: [[N::zero(); QBVH_WIDTH]; DIM],
+        }
+    }
+
+    fn set(&mut self, k: usize, aabb: &AABB<N>) {
+        for i in 0..DIM {
+            self.mins[i][k] = aabb.mins()[i];
+            self.maxs[i][k] = aabb.maxs()[i];
+        }
+    }
+
+    /// The `k`-th AABB.
+    pub fn aabb(&self, k: usize) -> AABB<N> {
+        let mut mins = Point::origin();
+        let mut maxs = Point::origin();
+
+        for i in 0..DIM {
+            mins[i] = self.mins[i][k];
+            maxs[i] = self.maxs[i][k];
+        }
+
+        AABB::new(mins, maxs)
+    }
+
+    /// The time of impact of a solid ray with each AABB, or `N::max_value()` if it misses it.
+    pub fn toi_with_ray(&self, ray: &Ray<N>) -> [N; QBVH_WIDTH] {
+        let mut tmin = [N::zero(); QBVH_WIDTH];
+        let mut tmax = [N::max_value(); QBVH_WIDTH];
+
+        for i in 0..DIM {
+            let origin = ray.origin[i];
+
+            let lanes = tmin
+                .iter_mut()
+                .zip(tmax.iter_mut())
+                .zip(self.mins[i].iter().zip(self.maxs[i].iter()));
+
+            if ray.dir[i].is_zero() {
+                for ((tmin, _), (min, max)) in lanes {
+                    if origin < *min || origin > *max {
+                        *tmin = N::max_value();
+                    }
+                }
+            } else {
+                let denom = N::one() / ray.dir[i];
+
+                for ((tmin, tmax), (min, max)) in lanes {
+                    let t1 = (*min - origin) * denom;
+                    let t2 = (*max - origin) * denom;
+                    *tmin = tmin.max(t1.min(t2));
+                    *tmax = tmax.min(t1.max(t2));
+                }
+            }
+        }
+
+        for (tmin, tmax) in tmin.iter_mut().zip(tmax.iter()) {
+            if *tmin > *tmax {
+                *tmin = N::max_value();
+            }
+        }
+
+        tmin
+    }
+
+    /// The distance from a point to each solid AABB.
+    pub fn distance_to_point(&self, point: &Point<N>) -> [N; QBVH_WIDTH] {
+        let mut sq_dist = [N::zero(); QBVH_WIDTH];
+
+        for i in 0..DIM {
+            let lanes = sq_dist
+                .iter_mut()
+                .zip(self.mins[i].iter().zip(self.maxs[i].iter()));
+
+            for (sq_dist, (min, max)) in lanes {
+                let d = (*min - point[i]).max(point[i] - *max).max(N::zero());
+                *sq_dist += d * d;
+            }
+        }
+
+        for sq_dist in sq_dist.iter_mut() {
+            *sq_dist = sq_dist.sqrt();
+        }
+
+        sq_dist
+    }
+
+    /// Tests if each AABB contains a point.
+    pub fn contains_point(&self, point: &Point<N>) -> [bool; QBVH_WIDTH] {
+        let mut res = [true; QBVH_WIDTH];
+
+        for i in 0..DIM {
+            let lanes = res
+                .iter_mut()
+                .zip(self.mins[i].iter().zip(self.maxs[i].iter()));
+
+            for (res, (min, max)) in lanes {
+                *res &= point[i] >= *min && point[i] <= *max;
+            }
+        }
+
+        res
+    }
+}
+
+/// A read-only, flattened, Bounding Volume Hierarchy with up to four children per node.
+///
+/// All the nodes are stored in a single array, and each node stores the AABBs of its children
+/// in a `QAABB` so that they can be tested together. Compared to a `BVT`, the resulting
+/// hierarchy is shallower and more cache-friendly to traverse, making it well suited to large
+/// static geometries queried many times.
+///
+/// The `quad_visit` and `quad_best_first_search` traversals test the children of each node
+/// together. The implementation of the `BVH` trait, which visits the nodes one by one, is
+/// provided for generic algorithms.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct QBVH<N: RealField, T> {
+    nodes: Vec<QBVHNode<N>>,
+    data: Vec<T>,
+    // The bounding volumes of the nodes and leaves, only read by the `BVH` implementation.
+    node_aabbs: Vec<AABB<N>>,
+    leaf_aabbs: Vec<AABB<N>>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+struct QBVHNode<N: RealField> {
+    aabbs: QAABB<N>,
+    // The index of each child: a node index for internal nodes, a data index for leaves.
+    children: [usize; QBVH_WIDTH],
+    // The `k`-th bit is set if the `k`-th child is a leaf.
+    leaf_mask: u8,
+    num_children: usize,
+}
+
+impl<N: RealField> QBVHNode<N> {
+    fn new() -> Self {
+        QBVHNode {
+            aabbs: QAABB::new(),
+            children: [0; QBVH_WIDTH],
+            leaf_mask: 0,
+            num_children: 0,
+        }
+    }
+
+    #[inline]
+    fn is_leaf(&self, k: usize) -> bool {
+        self.leaf_mask & (1 << k) != 0
+    }
+}
+
+impl<N: RealField, T: Clone> QBVH<N, T> {
+    /// Builds a `QBVH` with the same leaves as the given `BVT`.
+    ///
+    /// Each node of the result is obtained by merging an internal node of the `BVT` with some of
+    /// its children.
+    pub fn from_bvt(bvt: &BVT<T, AABB<N>>) -> Self {
+        let mut res = QBVH {
+            nodes: Vec::with_capacity(bvt.leaves().len() / 2 + 1),
+            data: Vec::with_capacity(bvt.leaves().len()),
+            node_aabbs: Vec::with_capacity(bvt.leaves().len() / 2 + 1),
+            leaf_aabbs: Vec::with_capacity(bvt.leaves().len()),
+        };
+
+        if let Some(root) = bvt.root() {
+            res.nodes.push(QBVHNode::new());
+            res.node_aabbs.push(bvt.content(root).0.clone());
+
+            if bvt.num_children(root) == 0 {
+                // The root is a leaf, wrap it into a node with a single child.
+                res.set_children(0, bvt, &[root]);
+            } else {
+                res.flatten(bvt, root, 0);
+            }
+        }
+
+        res
+    }
+
+    fn flatten<H: BVH<T, AABB<N>>>(&mut self, bvh: &H, node: H::Node, id: usize) {
+        let mut children: Vec<_> = (0..bvh.num_children(node))
+            .map(|i| bvh.child(i, node))
+            .collect();
+        let mut i = 0;
+
+        // Pull the children of the internal children up as long as there is room for them.
+        while i < children.len() {
+            let child = children[i];
+            let num_grandchildren = bvh.num_children(child);
+
+            if num_grandchildren != 0 && children.len() - 1 + num_grandchildren <= QBVH_WIDTH {
+                let _ = children.splice(
+                    i..i + 1,
+                    (0..num_grandchildren).map(|j| bvh.child(j, child)),
+                );
+                i += num_grandchildren;
+            } else {
+                i += 1;
+            }
+        }
+
+        self.set_children(id, bvh, &children);
+
+        for (k, child) in children.into_iter().enumerate() {
+            if !self.nodes[id].is_leaf(k) {
+                let child_id = self.nodes[id].children[k];
+                self.flatten(bvh, child, child_id);
+            }
+        }
+    }
+
+    // Registers the given children of the node `id`, allocating a new node for each internal child.
+    fn set_children<H: BVH<T, AABB<N>>>(&mut self, id: usize, bvh: &H, children: &[H::Node]) {
+        let mut node = QBVHNode::new();
+        node.num_children = children.len();
+
+        for (k, child) in children.iter().enumerate() {
+            let (aabb, data) = bvh.content(*child);
+            node.aabbs.set(k, aabb);
+
+            if let Some(data) = data {
+                node.children[k] = self.data.len();
+                node.leaf_mask |= 1 << k;
+                self.data.push(data.clone());
+                self.leaf_aabbs.push(aabb.clone());
+            } else {
+                node.children[k] = self.nodes.len();
+                self.nodes.push(QBVHNode::new());
+                self.node_aabbs.push(aabb.clone());
+            }
+        }
+
+        self.nodes[id] = node;
+    }
+}
+
+impl<N: RealField, T> QBVH<N, T> {
+    /// Recomputes the bounding volumes of all the nodes of this hierarchy.
+    ///
+    /// The bounding volume of each leaf is replaced by `leaf_bounding_volume` applied to its data,
+    /// and then the bounding volumes of all the internal nodes are recomputed in a single
+    /// bottom-up pass.
+    pub fn refit_bottom_up(&mut self, mut leaf_bounding_volume: impl FnMut(&T) -> AABB<N>) {
+        for (aabb, data) in self.leaf_aabbs.iter_mut().zip(self.data.iter()) {
+            *aabb = leaf_bounding_volume(data);
+        }
+
+        // The children of a node are always stored after it.
+        for i in (0..self.nodes.len()).rev() {
+            let node = &mut self.nodes[i];
+            let mut merged: Option<AABB<N>> = None;
+
+            for k in 0..node.num_children {
+                let child = node.children[k];
+                let aabb = if node.is_leaf(k) {
+                    &self.leaf_aabbs[child]
+                } else {
+                    &self.node_aabbs[child]
+                };
+
+                node.aabbs.set(k, aabb);
+
+                match &mut merged {
+                    Some(merged) => merged.merge(aabb),
+                    None => merged = Some(aabb.clone()),
+                }
+            }
+
+            // Internal nodes have at least one child.
+            self.node_aabbs[i] = merged.unwrap();
+        }
+    }
+
+    /// The number of leaves of this hierarchy.
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Reference to the bounding volume of the hierarchy root.
+    pub fn root_bounding_volume(&self) -> Option<&AABB<N>> {
+        self.node_aabbs.first()
+    }
+
+    /// Visits the leaves of this hierarchy, testing the children of each node together.
+    ///
+    /// The children for which `children_filter` returns `false` are not visited. The data of
+    /// each leaf reached is given to `leaf`, and the traversal is interrupted as soon as it
+    /// returns `VisitStatus::ExitEarly`.
+    pub fn quad_visit(
+        &self,
+        mut children_filter: impl FnMut(&QAABB<N>) -> [bool; QBVH_WIDTH],
+        mut leaf: impl FnMut(&T) -> VisitStatus,
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![0];
+
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            let mask = children_filter(&node.aabbs);
+
+            for (k, child) in node.children[..node.num_children].iter().enumerate() {
+                if mask[k] {
+                    if node.is_leaf(k) {
+                        if let VisitStatus::ExitEarly = leaf(&self.data[*child]) {
+                            return;
+                        }
+                    } else {
+                        stack.push(*child);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Performs a best-first search on this hierarchy, testing the children of each node together.
+    ///
+    /// The `children_costs` closure computes the costs of the children of a node, with
+    /// `N::max_value()` for the children that should not be explored. Given the smallest cost found
+    /// so far, the `leaf` closure computes the cost and result associated to the data of a leaf,
+    /// if any.
+    ///
+    /// Returns the result with the smallest cost.
+    pub fn quad_best_first_search<R>(
+        &self,
+        mut children_costs: impl FnMut(&QAABB<N>) -> [N; QBVH_WIDTH],
+        mut leaf: impl FnMut(N, &T) -> Option<(N, R)>,
+    ) -> Option<R> {
+        let mut queue: BinaryHeap<WeightedValue<N, usize>> = BinaryHeap::new();
+        let mut best_cost = N::max_value();
+        let mut best_result = None;
+
+        if !self.nodes.is_empty() {
+            queue.push(WeightedValue::new(0, N::zero()));
+        }
+
+        while let Some(entry) = queue.pop() {
+            if -entry.cost >= best_cost {
+                break; // Solution found.
+            }
+
+            let node = &self.nodes[entry.value];
+            let costs = children_costs(&node.aabbs);
+
+            for (k, child) in node.children[..node.num_children].iter().enumerate() {
+                if costs[k] < best_cost {
+                    if node.is_leaf(k) {
+                        if let Some((cost, result)) = leaf(best_cost, &self.data[*child]) {
+                            if cost < best_cost {
+                                best_cost = cost;
+                                best_result = Some(result);
+                            }
+                        }
+                    } else {
+                        queue.push(WeightedValue::new(*child, -costs[k]));
+                    }
+                }
+            }
+        }
+
+        best_result
+    }
+}
+
+// The nodes of the `BVH` implementation are numbered with the internal nodes first, followed by
+// the leaves.
+impl<N: RealField, T> BVH<T, AABB<N>> for QBVH<N, T> {
+    type Node = usize;
+
+    #[inline]
+    fn root(&self) -> Option<Self::Node> {
+        let root = self.nodes.first()?;
+
+        if root.num_children == 1 && root.is_leaf(0) {
+            Some(self.nodes.len() + root.children[0])
+        } else {
+            Some(0)
+        }
+    }
+
+    #[inline]
+    fn num_children(&self, node: Self::Node) -> usize {
+        self.nodes.get(node).map(|n| n.num_children).unwrap_or(0)
+    }
+
+    #[inline]
+    fn child(&self, i: usize, node: Self::Node) -> Self::Node {
+        let node = &self.nodes[node];
+
+        if node.is_leaf(i) {
+            self.nodes.len() + node.children[i]
+        } else {
+            node.children[i]
+        }
+    }
+
+    #[inline]
+    fn content(&self, node: Self::Node) -> (&AABB<N>, Option<&T>) {
+        if node < self.nodes.len() {
+            (&self.node_aabbs[node], None)
+        } else {
+            let leaf = node - self.nodes.len();
+            (&self.leaf_aabbs[leaf], Some(&self.data[leaf]))
+        }
+    }
+}
@@ -1,10 +1,12 @@
+use downcast_rs::Downcast;
 use na::RealField;
-use std::any::Any;
 
-use crate::math::Point;
-use crate::query::Ray;
+use crate::bounding_volume::AABB;
+use crate::math::{Point, Vector};
+use crate::query::{Ray, RayIntersection};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BroadPhaseProxyHandle(pub usize);
 
 impl BroadPhaseProxyHandle {
@@ -38,7 +40,7 @@ pub trait BroadPhaseInterferenceHandler<T> {
 }
 
 /// Trait all broad phase must implement.
-pub trait BroadPhase<N: RealField, BV, T>: Any + Sync + Send {
+pub trait BroadPhase<N: RealField, BV, T>: Downcast + Sync + Send {
     /// Tells the broad phase to add a bounding-volume at the next update.
     fn create_proxy(&mut self, bv: BV, data: T) -> BroadPhaseProxyHandle;
 
@@ -76,4 +78,105 @@ pub trait BroadPhase<N: RealField, BV, T>: Any + Sync + Send {
 
     /// Collects every object which might contain a given point.
     fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>);
+
+    /// Finds the object with the smallest cost, as computed by `object_cost`.
+    ///
+    /// The `bv_cost` closure must return a lower bound of the cost of every object with a bounding
+    /// volume contained in the given one, or `None` if none of them can have a cost at all. Both
+    /// closures are given the smallest cost found so far, and may return `None` if they cannot
+    /// beat it. Hierarchical broad phases use `bv_cost` to perform a best-first traversal.
+    ///
+    /// The default implementation ignores `bv_cost` and evaluates every object.
+    fn first_interference<'a>(
+        &'a self,
+        _bv_cost: &mut dyn FnMut(&BV, N) -> Option<N>,
+        object_cost: &mut dyn FnMut(&'a T, N) -> Option<N>,
+    ) -> Option<(&'a T, N)>
+    where
+        BV: From<AABB<N>>,
+    {
+        let everything = AABB::new(
+            Point::from(Vector::repeat(-N::max_value())),
+            Point::from(Vector::repeat(N::max_value())),
+        );
+        let mut candidates = Vec::new();
+        let mut best = None;
+        let mut best_cost = N::max_value();
+
+        self.interferences_with_bounding_volume(&BV::from(everything), &mut candidates);
+
+        for data in candidates {
+            if let Some(cost) = object_cost(data, best_cost) {
+                if cost < best_cost {
+                    best_cost = cost;
+                    best = Some((data, cost));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Finds the object with the smallest time of impact with the given ray.
+    ///
+    /// The `ray_cast` closure computes the actual intersection between the ray and the object
+    /// attached to a proxy. Its last argument is the largest time of impact still of interest,
+    /// and it should return `None` if there is no hit before it. Only hits with a time of impact
+    /// smaller than `max_toi` are considered.
+    fn first_interference_with_ray<'a>(
+        &'a self,
+        ray: &Ray<N>,
+        max_toi: N,
+        ray_cast: &mut dyn FnMut(&'a T, &Ray<N>, N) -> Option<RayIntersection<N>>,
+    ) -> Option<(&'a T, RayIntersection<N>)> {
+        let mut candidates = Vec::new();
+        let mut best = None;
+        let mut best_toi = max_toi;
+
+        self.interferences_with_ray(ray, &mut candidates);
+
+        for data in candidates {
+            if let Some(inter) = ray_cast(data, ray, best_toi) {
+                if inter.toi <= best_toi {
+                    best_toi = inter.toi;
+                    best = Some((data, inter));
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl_downcast!(BroadPhase<N, BV, T> where N: RealField, BV: 'static, T: 'static);
+
+// Evaluates the candidates by increasing bounding volume cost, until this cost exceeds the best
+// object cost found so far. Used by the broad phases that are not hierarchical.
+pub(crate) fn first_interference_among<'a, N: RealField, BV: 'a, T: 'a>(
+    candidates: impl Iterator<Item = (&'a BV, &'a T)>,
+    bv_cost: &mut dyn FnMut(&BV, N) -> Option<N>,
+    object_cost: &mut dyn FnMut(&'a T, N) -> Option<N>,
+) -> Option<(&'a T, N)> {
+    let mut sorted: Vec<_> = candidates
+        .filter_map(|(bv, data)| bv_cost(bv, N::max_value()).map(|cost| (cost, data)))
+        .collect();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let mut best = None;
+    let mut best_cost = N::max_value();
+
+    for (cost, data) in sorted {
+        if cost >= best_cost {
+            break;
+        }
+
+        if let Some(cost) = object_cost(data, best_cost) {
+            if cost < best_cost {
+                best_cost = cost;
+                best = Some((data, cost));
+            }
+        }
+    }
+
+    best
 }
@@ -1,13 +1,15 @@
 use crate::bounding_volume::BoundingVolume;
-use crate::math::Point;
-use crate::partitioning::{DBVTLeaf, DBVTLeafId, BVH, DBVT};
+use crate::math::{Isometry, Point};
+use crate::partitioning::{
+    BestFirstVisitStatus, BestFirstVisitor, DBVTLeaf, DBVTLeafId, BVH, DBVT,
+};
 use crate::pipeline::broad_phase::{
     BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle,
 };
 use crate::query::visitors::{
     BoundingVolumeInterferencesCollector, PointInterferencesCollector, RayInterferencesCollector,
 };
-use crate::query::{PointQuery, Ray, RayCast};
+use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
 use crate::utils::{DeterministicState, SortedPair};
 use na::RealField;
 use slab::Slab;
@@ -16,6 +18,7 @@ use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum ProxyStatus {
     OnStaticTree(DBVTLeafId),
     OnDynamicTree(DBVTLeafId, usize),
@@ -24,6 +27,8 @@ enum ProxyStatus {
     Deleted,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 struct DBVTBroadPhaseProxy<T> {
     data: T,
     status: ProxyStatus,
@@ -53,6 +58,8 @@ const DEACTIVATION_THRESHOLD: usize = 100;
 ///
 /// It uses two separate trees: one for static objects and which is never updated, and one for
 /// moving objects.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 pub struct DBVTBroadPhase<N: RealField, BV, T> {
     proxies: Slab<DBVTBroadPhaseProxy<T>>,
     // DBVT for moving objects.
@@ -427,4 +434,149 @@ where
             out.push(&self.proxies[l.uid()].data)
         }
     }
+
+    fn first_interference<'a>(
+        &'a self,
+        bv_cost: &mut dyn FnMut(&BV, N) -> Option<N>,
+        object_cost: &mut dyn FnMut(&'a T, N) -> Option<N>,
+    ) -> Option<(&'a T, N)> {
+        let mut visitor = BroadPhaseCostVisitor {
+            proxies: &self.proxies,
+            max_cost: N::max_value(),
+            bv_cost,
+            object_cost,
+        };
+
+        let res = self.tree.best_first_search(&mut visitor).map(|res| res.1);
+
+        // Only look for objects cheaper than the one found on the dynamic tree.
+        if let Some((_, cost)) = res {
+            visitor.max_cost = cost;
+        }
+
+        let sres = self.stree.best_first_search(&mut visitor).map(|res| res.1);
+
+        sres.or(res)
+            .map(|(handle, cost)| (&self.proxies[handle.uid()].data, cost))
+    }
+
+    fn first_interference_with_ray<'a>(
+        &'a self,
+        ray: &Ray<N>,
+        max_toi: N,
+        ray_cast: &mut dyn FnMut(&'a T, &Ray<N>, N) -> Option<RayIntersection<N>>,
+    ) -> Option<(&'a T, RayIntersection<N>)> {
+        let mut visitor = BroadPhaseRayCastVisitor {
+            proxies: &self.proxies,
+            ray,
+            max_toi,
+            ray_cast,
+        };
+
+        let res = self.tree.best_first_search(&mut visitor).map(|res| res.1);
+
+        // Only look for hits closer than the one found on the dynamic tree.
+        if let Some((_, inter)) = &res {
+            visitor.max_toi = inter.toi;
+        }
+
+        let sres = self.stree.best_first_search(&mut visitor).map(|res| res.1);
+
+        sres.or(res)
+            .map(|(handle, inter)| (&self.proxies[handle.uid()].data, inter))
+    }
+}
+
+struct BroadPhaseRayCastVisitor<'a, 'b, 'c, N: RealField, T> {
+    proxies: &'a Slab<DBVTBroadPhaseProxy<T>>,
+    ray: &'b Ray<N>,
+    max_toi: N,
+    ray_cast: &'c mut dyn FnMut(&'a T, &Ray<N>, N) -> Option<RayIntersection<N>>,
+}
+
+impl<'a, 'b, 'c, N, BV, T> BestFirstVisitor<N, BroadPhaseProxyHandle, BV>
+    for BroadPhaseRayCastVisitor<'a, 'b, 'c, N, T>
+where
+    N: RealField,
+    BV: RayCast<N>,
+{
+    type Result = (BroadPhaseProxyHandle, RayIntersection<N>);
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best: N,
+        bv: &BV,
+        data: Option<&BroadPhaseProxyHandle>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        let max_toi = best.min(self.max_toi);
+
+        match bv.toi_with_ray(&Isometry::identity(), self.ray, true) {
+            Some(toi) if toi <= max_toi => {
+                let mut res = BestFirstVisitStatus::Continue {
+                    cost: toi,
+                    result: None,
+                };
+
+                if let Some(handle) = data {
+                    let proxy_data = &self.proxies[handle.uid()].data;
+
+                    if let Some(inter) = (self.ray_cast)(proxy_data, self.ray, max_toi) {
+                        res = BestFirstVisitStatus::Continue {
+                            cost: inter.toi,
+                            result: Some((*handle, inter)),
+                        };
+                    }
+                }
+
+                res
+            }
+            _ => BestFirstVisitStatus::Stop,
+        }
+    }
+}
+
+struct BroadPhaseCostVisitor<'a, 'c, N: RealField, BV, T> {
+    proxies: &'a Slab<DBVTBroadPhaseProxy<T>>,
+    max_cost: N,
+    bv_cost: &'c mut dyn FnMut(&BV, N) -> Option<N>,
+    object_cost: &'c mut dyn FnMut(&'a T, N) -> Option<N>,
+}
+
+impl<'a, 'c, N, BV, T> BestFirstVisitor<N, BroadPhaseProxyHandle, BV>
+    for BroadPhaseCostVisitor<'a, 'c, N, BV, T>
+where
+    N: RealField,
+{
+    type Result = (BroadPhaseProxyHandle, N);
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best: N,
+        bv: &BV,
+        data: Option<&BroadPhaseProxyHandle>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        let max_cost = best.min(self.max_cost);
+
+        match (self.bv_cost)(bv, max_cost) {
+            Some(cost) if cost < max_cost => {
+                let mut res = BestFirstVisitStatus::Continue { cost, result: None };
+
+                if let Some(handle) = data {
+                    let proxy_data = &self.proxies[handle.uid()].data;
+
+                    if let Some(cost) = (self.object_cost)(proxy_data, max_cost) {
+                        res = BestFirstVisitStatus::Continue {
+                            cost,
+                            result: Some((*handle, cost)),
+                        };
+                    }
+                }
+
+                res
+            }
+            _ => BestFirstVisitStatus::Stop,
+        }
+    }
 }
@@ -0,0 +1,426 @@
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Isometry, Point, DIM};
+use crate::pipeline::broad_phase::{
+    broad_phase, BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle,
+};
+use crate::query::{Ray, RayCast};
+use crate::utils::{DeterministicState, SortedPair};
+use na::RealField;
+use slab::Slab;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::mem;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+struct SAPEndpoint<N: RealField> {
+    value: N,
+    proxy: BroadPhaseProxyHandle,
+    is_max: bool,
+}
+
+impl<N: RealField> SAPEndpoint<N> {
+    fn new(value: N, proxy: BroadPhaseProxyHandle, is_max: bool) -> Self {
+        SAPEndpoint {
+            value,
+            proxy,
+            is_max,
+        }
+    }
+
+    // Ordering of the endpoints along an axis. Lower bounds are placed
+    // before upper bounds with the same value so that touching AABBs
+    // are considered as intersecting, as `AABB::intersects` does.
+    #[inline]
+    fn is_before(&self, other: &Self) -> bool {
+        self.value < other.value || (self.value == other.value && !self.is_max && other.is_max)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+struct SAPBroadPhaseProxy<N: RealField, T> {
+    data: T,
+    aabb: AABB<N>,
+    // `true` if the endpoints of this proxy have been inserted into the axes.
+    attached: bool,
+    updated: bool,
+    deleted: bool,
+}
+
+impl<N: RealField, T> SAPBroadPhaseProxy<N, T> {
+    fn new(data: T, aabb: AABB<N>) -> SAPBroadPhaseProxy<N, T> {
+        SAPBroadPhaseProxy {
+            data,
+            aabb,
+            attached: false,
+            updated: true,
+            deleted: false,
+        }
+    }
+}
+
+/// Broad phase based on a multi-axis incremental Sweep-and-Prune.
+///
+/// The bounds of every AABB are kept sorted along each coordinate axis. Because objects usually
+/// move only a little between two updates, the sorted lists are updated with an insertion sort
+/// and each swap of two bounds is used to detect the start or the end of an interference.
+/// This works best with many small objects that move coherently.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct SAPBroadPhase<N: RealField, T> {
+    proxies: Slab<SAPBroadPhaseProxy<N, T>>,
+    // Sorted endpoints along each axis.
+    axes: Vec<Vec<SAPEndpoint<N>>>,
+    // Pairs detected.
+    pairs: HashMap<SortedPair<BroadPhaseProxyHandle>, (), DeterministicState>,
+    // The margin added to each bounding volume.
+    margin: N,
+    recompute_all: bool,
+
+    proxies_to_update: Vec<(BroadPhaseProxyHandle, AABB<N>)>,
+    proxies_to_recompute: Vec<BroadPhaseProxyHandle>,
+}
+
+impl<N: RealField, T> SAPBroadPhase<N, T> {
+    /// Creates a new broad phase based on the Sweep-and-Prune algorithm.
+    pub fn new(margin: N) -> SAPBroadPhase<N, T> {
+        SAPBroadPhase {
+            proxies: Slab::new(),
+            axes: (0..DIM).map(|_| Vec::new()).collect(),
+            pairs: HashMap::with_hasher(DeterministicState::new()),
+            margin,
+            recompute_all: false,
+            proxies_to_update: Vec::new(),
+            proxies_to_recompute: Vec::new(),
+        }
+    }
+
+    /// Number of interferences detected by this broad phase.
+    #[inline]
+    pub fn num_interferences(&self) -> usize {
+        self.pairs.len()
+    }
+
+    fn update_endpoint_values(&mut self) {
+        let proxies = &self.proxies;
+
+        for (i, axis) in self.axes.iter_mut().enumerate() {
+            for endpoint in axis.iter_mut() {
+                let proxy = &proxies[endpoint.proxy.uid()];
+
+                if proxy.updated {
+                    endpoint.value = if endpoint.is_max {
+                        proxy.aabb.maxs()[i]
+                    } else {
+                        proxy.aabb.mins()[i]
+                    };
+                }
+            }
+        }
+    }
+
+    // Re-sorts one axis with an insertion sort, reporting interferences
+    // starting or stopping each time two endpoints are swapped.
+    fn sort_axis(
+        axis: &mut Vec<SAPEndpoint<N>>,
+        proxies: &Slab<SAPBroadPhaseProxy<N, T>>,
+        pairs: &mut HashMap<SortedPair<BroadPhaseProxyHandle>, (), DeterministicState>,
+        handler: &mut dyn BroadPhaseInterferenceHandler<T>,
+    ) {
+        for i in 1..axis.len() {
+            let endpoint = axis[i];
+            let mut j = i;
+
+            while j > 0 && endpoint.is_before(&axis[j - 1]) {
+                let other = axis[j - 1];
+
+                if endpoint.proxy != other.proxy && endpoint.is_max != other.is_max {
+                    let proxy1 = &proxies[endpoint.proxy.uid()];
+                    let proxy2 = &proxies[other.proxy.uid()];
+                    let key = SortedPair::new(endpoint.proxy, other.proxy);
+
+                    if endpoint.is_max {
+                        // The upper bound moved before the lower bound of another proxy:
+                        // they no longer overlap along this axis.
+                        if pairs.remove(&key).is_some() {
+                            handler.interference_stopped(&proxy1.data, &proxy2.data);
+                        }
+                    } else if proxy1.aabb.intersects(&proxy2.aabb)
+                        && handler.is_interference_allowed(&proxy1.data, &proxy2.data)
+                    {
+                        // The lower bound moved before the upper bound of another proxy:
+                        // they may start overlapping.
+                        if let Entry::Vacant(entry) = pairs.entry(key) {
+                            handler.interference_started(&proxy1.data, &proxy2.data);
+                            let _ = entry.insert(());
+                        }
+                    }
+                }
+
+                axis[j] = other;
+                j -= 1;
+            }
+
+            axis[j] = endpoint;
+        }
+    }
+
+    // Recomputes all the interferences involving `handle`, or all the interferences if `handle` is `None`.
+    fn recompute_proximities(
+        &mut self,
+        handle: Option<BroadPhaseProxyHandle>,
+        handler: &mut dyn BroadPhaseInterferenceHandler<T>,
+    ) {
+        let proxies = &self.proxies;
+
+        // Remove pairs that are no longer allowed.
+        self.pairs.retain(|pair, _| {
+            if handle.is_some() && handle != Some(pair.0) && handle != Some(pair.1) {
+                return true;
+            }
+
+            let proxy1 = &proxies[pair.0.uid()];
+            let proxy2 = &proxies[pair.1.uid()];
+
+            if handler.is_interference_allowed(&proxy1.data, &proxy2.data) {
+                true
+            } else {
+                handler.interference_stopped(&proxy1.data, &proxy2.data);
+                false
+            }
+        });
+
+        // Add pairs that were previously not allowed.
+        let mut active: Vec<BroadPhaseProxyHandle> = Vec::new();
+
+        for endpoint in &self.axes[0] {
+            if endpoint.is_max {
+                if let Some(i) = active.iter().position(|h| *h == endpoint.proxy) {
+                    let _ = active.swap_remove(i);
+                }
+
+                continue;
+            }
+
+            let proxy1 = &proxies[endpoint.proxy.uid()];
+
+            for other in &active {
+                if handle.is_some() && handle != Some(endpoint.proxy) && handle != Some(*other) {
+                    continue;
+                }
+
+                let proxy2 = &proxies[other.uid()];
+
+                if proxy1.aabb.intersects(&proxy2.aabb)
+                    && handler.is_interference_allowed(&proxy1.data, &proxy2.data)
+                {
+                    if let Entry::Vacant(entry) =
+                        self.pairs.entry(SortedPair::new(endpoint.proxy, *other))
+                    {
+                        handler.interference_started(&proxy1.data, &proxy2.data);
+                        let _ = entry.insert(());
+                    }
+                }
+            }
+
+            active.push(endpoint.proxy);
+        }
+    }
+}
+
+impl<N, T> BroadPhase<N, AABB<N>, T> for SAPBroadPhase<N, T>
+where
+    N: RealField,
+    T: Any + Send + Sync,
+{
+    fn update(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
+        /*
+         * Apply the bounding volume modifications and attach new proxies.
+         */
+        let some_proxies_updated = !self.proxies_to_update.is_empty();
+
+        for (handle, aabb) in self.proxies_to_update.drain(..) {
+            if let Some(proxy) = self.proxies.get_mut(handle.uid()) {
+                proxy.aabb = aabb;
+                proxy.updated = true;
+
+                if !proxy.attached {
+                    // New endpoints are pushed at the end of each axis so that
+                    // the insertion sort detects all their interferences.
+                    for (i, axis) in self.axes.iter_mut().enumerate() {
+                        axis.push(SAPEndpoint::new(proxy.aabb.mins()[i], handle, false));
+                        axis.push(SAPEndpoint::new(proxy.aabb.maxs()[i], handle, true));
+                    }
+
+                    proxy.attached = true;
+                }
+            }
+        }
+
+        /*
+         * Sort the endpoints and collect interferences at the same time.
+         */
+        if some_proxies_updated {
+            self.update_endpoint_values();
+
+            for axis in &mut self.axes {
+                Self::sort_axis(axis, &self.proxies, &mut self.pairs, handler);
+            }
+        }
+
+        /*
+         * Recompute proximities if the user asked for it.
+         */
+        if self.recompute_all {
+            self.recompute_proximities(None, handler);
+        } else {
+            for handle in mem::replace(&mut self.proxies_to_recompute, Vec::new()) {
+                self.recompute_proximities(Some(handle), handler);
+            }
+        }
+
+        self.recompute_all = false;
+        self.proxies_to_recompute.clear();
+
+        for (_, proxy) in self.proxies.iter_mut() {
+            proxy.updated = false;
+        }
+    }
+
+    fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&AABB<N>, &T)> {
+        let proxy = self.proxies.get(handle.uid())?;
+
+        if proxy.attached {
+            Some((&proxy.aabb, &proxy.data))
+        } else {
+            None
+        }
+    }
+
+    fn create_proxy(&mut self, bv: AABB<N>, data: T) -> BroadPhaseProxyHandle {
+        let proxy = SAPBroadPhaseProxy::new(data, bv.clone());
+        let handle = BroadPhaseProxyHandle(self.proxies.insert(proxy));
+        self.proxies_to_update.push((handle, bv));
+        handle
+    }
+
+    fn remove(&mut self, handles: &[BroadPhaseProxyHandle], handler: &mut dyn FnMut(&T, &T)) {
+        for handle in handles {
+            if let Some(proxy) = self.proxies.get_mut(handle.uid()) {
+                proxy.deleted = true;
+            } else {
+                panic!("Attempting to remove an object that does not exist.");
+            }
+        }
+
+        {
+            let proxies = &self.proxies;
+
+            for axis in &mut self.axes {
+                axis.retain(|e| !proxies[e.proxy.uid()].deleted);
+            }
+
+            self.pairs.retain(|pair, _| {
+                let proxy1 = &proxies[pair.0.uid()];
+                let proxy2 = &proxies[pair.1.uid()];
+
+                if proxy1.deleted || proxy2.deleted {
+                    handler(&proxy1.data, &proxy2.data);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            self.proxies_to_update
+                .retain(|(h, _)| !proxies[h.uid()].deleted);
+            self.proxies_to_recompute
+                .retain(|h| !proxies[h.uid()].deleted);
+        }
+
+        for handle in handles {
+            let _ = self.proxies.remove(handle.uid());
+        }
+    }
+
+    fn deferred_set_bounding_volume(&mut self, handle: BroadPhaseProxyHandle, bv: AABB<N>) {
+        if let Some(proxy) = self.proxies.get(handle.uid()) {
+            if !proxy.attached || !proxy.aabb.contains(&bv) {
+                let new_bv = bv.loosened(self.margin);
+                self.proxies_to_update.push((handle, new_bv));
+            }
+        } else {
+            panic!("Attempting to set the bounding volume of an object that does not exist.");
+        }
+    }
+
+    fn deferred_recompute_all_proximities_with(&mut self, handle: BroadPhaseProxyHandle) {
+        if self.proxies.contains(handle.uid()) {
+            self.proxies_to_recompute.push(handle);
+        }
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        self.recompute_all = true;
+    }
+
+    fn interferences_with_bounding_volume<'a>(&'a self, bv: &AABB<N>, out: &mut Vec<&'a T>) {
+        for endpoint in &self.axes[0] {
+            if endpoint.value > bv.maxs()[0] {
+                break;
+            }
+
+            if !endpoint.is_max {
+                let proxy = &self.proxies[endpoint.proxy.uid()];
+
+                if proxy.aabb.intersects(bv) {
+                    out.push(&proxy.data)
+                }
+            }
+        }
+    }
+
+    fn interferences_with_ray<'a>(&'a self, ray: &Ray<N>, out: &mut Vec<&'a T>) {
+        for endpoint in &self.axes[0] {
+            if !endpoint.is_max {
+                let proxy = &self.proxies[endpoint.proxy.uid()];
+
+                if proxy.aabb.intersects_ray(&Isometry::identity(), ray) {
+                    out.push(&proxy.data)
+                }
+            }
+        }
+    }
+
+    fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>) {
+        for endpoint in &self.axes[0] {
+            if endpoint.value > point[0] {
+                break;
+            }
+
+            if !endpoint.is_max {
+                let proxy = &self.proxies[endpoint.proxy.uid()];
+
+                if proxy.aabb.contains_local_point(point) {
+                    out.push(&proxy.data)
+                }
+            }
+        }
+    }
+
+    fn first_interference<'a>(
+        &'a self,
+        bv_cost: &mut dyn FnMut(&AABB<N>, N) -> Option<N>,
+        object_cost: &mut dyn FnMut(&'a T, N) -> Option<N>,
+    ) -> Option<(&'a T, N)> {
+        let candidates = self
+            .proxies
+            .iter()
+            .filter(|(_, proxy)| proxy.attached)
+            .map(|(_, proxy)| (&proxy.aabb, &proxy.data));
+
+        broad_phase::first_interference_among(candidates, bv_cost, object_cost)
+    }
+}
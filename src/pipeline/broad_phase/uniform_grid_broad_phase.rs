@@ -0,0 +1,656 @@
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Isometry, Point, DIM};
+use crate::pipeline::broad_phase::{
+    broad_phase, BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle,
+};
+use crate::query::{Ray, RayCast};
+use crate::utils::{DeterministicState, SortedPair};
+use na::RealField;
+use slab::Slab;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+type Cell = Point<i64>;
+
+/// The maximum number of cells a proxy can be registered into.
+const MAX_CELLS_PER_PROXY: f64 = 1024.0;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq)]
+enum GridLocation {
+    // The proxy is not on the grid yet.
+    Pending,
+    // The range of cells covered by the proxy.
+    Cells(Cell, Cell),
+    // The proxy covers too many cells, so it is stored apart.
+    Oversized,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+struct UniformGridBroadPhaseProxy<N: RealField, T> {
+    data: T,
+    aabb: AABB<N>,
+    location: GridLocation,
+    updated: bool,
+}
+
+impl<N: RealField, T> UniformGridBroadPhaseProxy<N, T> {
+    fn new(data: T, aabb: AABB<N>) -> UniformGridBroadPhaseProxy<N, T> {
+        UniformGridBroadPhaseProxy {
+            data,
+            aabb,
+            location: GridLocation::Pending,
+            updated: false,
+        }
+    }
+
+    fn is_registered(&self) -> bool {
+        self.location != GridLocation::Pending
+    }
+}
+
+fn cell_inf(a: &Cell, b: &Cell) -> Cell {
+    let mut res = *a;
+    for i in 0..DIM {
+        res[i] = res[i].min(b[i]);
+    }
+    res
+}
+
+fn cell_sup(a: &Cell, b: &Cell) -> Cell {
+    let mut res = *a;
+    for i in 0..DIM {
+        res[i] = res[i].max(b[i]);
+    }
+    res
+}
+
+// The number of cells of the range `[mins, maxs]` (bounds included).
+fn num_cells(mins: &Cell, maxs: &Cell) -> f64 {
+    (0..DIM).fold(1.0, |acc, i| acc * (maxs[i] as f64 - mins[i] as f64 + 1.0))
+}
+
+// Calls `f` on every cell of the range `[mins, maxs]` (bounds included).
+fn for_each_cell(mins: &Cell, maxs: &Cell, mut f: impl FnMut(&Cell)) {
+    let mut curr = *mins;
+
+    loop {
+        f(&curr);
+
+        let mut i = 0;
+        loop {
+            if i == DIM {
+                return;
+            }
+
+            if curr[i] < maxs[i] {
+                curr[i] += 1;
+                break;
+            }
+
+            curr[i] = mins[i];
+            i += 1;
+        }
+    }
+}
+
+/// Broad phase based on a hashed uniform grid.
+///
+/// Each bounding volume is registered into every cell of the grid it intersects. Only the
+/// non-empty cells are stored, so the grid is unbounded. This works best when all the objects
+/// have roughly the same size, and when this size is close to the cell size.
+///
+/// Bounding volumes covering more than 1024 cells (e.g. the infinite AABB of a plane) are not
+/// registered into the grid. They are instead tested against every other bounding volume.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct UniformGridBroadPhase<N: RealField, T> {
+    proxies: Slab<UniformGridBroadPhaseProxy<N, T>>,
+    // Non-empty cells of the grid.
+    cells: HashMap<Cell, Vec<BroadPhaseProxyHandle>, DeterministicState>,
+    // Proxies covering too many cells to be registered into the grid.
+    oversized: Vec<BroadPhaseProxyHandle>,
+    // Range of cells that may be non-empty.
+    cell_bounds: Option<(Cell, Cell)>,
+    // Pairs detected.
+    pairs: HashMap<SortedPair<BroadPhaseProxyHandle>, (), DeterministicState>,
+    cell_size: N,
+    // The margin added to each bounding volume.
+    margin: N,
+    recompute_all: bool,
+
+    // Just to avoid dynamic allocations.
+    collector: Vec<BroadPhaseProxyHandle>,
+    updated: Vec<BroadPhaseProxyHandle>,
+    proxies_to_update: Vec<(BroadPhaseProxyHandle, AABB<N>)>,
+    proxies_to_recompute: Vec<BroadPhaseProxyHandle>,
+}
+
+impl<N: RealField, T> UniformGridBroadPhase<N, T> {
+    /// Creates a new broad phase based on a hashed uniform grid with cells of size `cell_size`.
+    pub fn new(cell_size: N, margin: N) -> UniformGridBroadPhase<N, T> {
+        assert!(
+            cell_size > N::zero(),
+            "The cell size of a uniform grid must be strictly positive."
+        );
+
+        UniformGridBroadPhase {
+            proxies: Slab::new(),
+            cells: HashMap::with_hasher(DeterministicState::new()),
+            oversized: Vec::new(),
+            cell_bounds: None,
+            pairs: HashMap::with_hasher(DeterministicState::new()),
+            cell_size,
+            margin,
+            recompute_all: false,
+            collector: Vec::new(),
+            updated: Vec::new(),
+            proxies_to_update: Vec::new(),
+            proxies_to_recompute: Vec::new(),
+        }
+    }
+
+    /// The size of the cells of this grid.
+    #[inline]
+    pub fn cell_size(&self) -> N {
+        self.cell_size
+    }
+
+    /// Number of interferences detected by this broad phase.
+    #[inline]
+    pub fn num_interferences(&self) -> usize {
+        self.pairs.len()
+    }
+
+    fn cell_containing(&self, pt: &Point<N>) -> Cell {
+        Cell::from(pt.coords.map(|e| unsafe {
+            na::convert_unchecked::<N, f64>((e / self.cell_size).floor()) as i64
+        }))
+    }
+
+    fn cells_covered_by(&self, aabb: &AABB<N>) -> (Cell, Cell) {
+        (
+            self.cell_containing(aabb.mins()),
+            self.cell_containing(aabb.maxs()),
+        )
+    }
+
+    fn location_of(&self, aabb: &AABB<N>) -> GridLocation {
+        let (mins, maxs) = self.cells_covered_by(aabb);
+
+        if num_cells(&mins, &maxs) > MAX_CELLS_PER_PROXY {
+            GridLocation::Oversized
+        } else {
+            GridLocation::Cells(mins, maxs)
+        }
+    }
+
+    // Calls `f` on every non-empty cell of the given range.
+    fn for_each_occupied_cell(
+        &self,
+        range: (Cell, Cell),
+        mut f: impl FnMut(&[BroadPhaseProxyHandle]),
+    ) {
+        let range = try_ret!(self.clamp_to_cell_bounds(range));
+
+        if num_cells(&range.0, &range.1) > self.cells.len() as f64 {
+            // Cheaper than visiting all the mostly-empty cells of the range.
+            for (cell, handles) in &self.cells {
+                if (0..DIM).all(|i| range.0[i] <= cell[i] && cell[i] <= range.1[i]) {
+                    f(handles)
+                }
+            }
+        } else {
+            for_each_cell(&range.0, &range.1, |cell| {
+                if let Some(handles) = self.cells.get(cell) {
+                    f(handles)
+                }
+            });
+        }
+    }
+
+    fn clamp_to_cell_bounds(&self, range: (Cell, Cell)) -> Option<(Cell, Cell)> {
+        let bounds = self.cell_bounds?;
+        let mins = cell_sup(&range.0, &bounds.0);
+        let maxs = cell_inf(&range.1, &bounds.1);
+
+        if (0..DIM).all(|i| mins[i] <= maxs[i]) {
+            Some((mins, maxs))
+        } else {
+            None
+        }
+    }
+
+    fn oversized_proxies(&self) -> impl Iterator<Item = &UniformGridBroadPhaseProxy<N, T>> {
+        self.oversized.iter().map(move |h| &self.proxies[h.uid()])
+    }
+
+    fn cell_aabb(&self, cell: &Cell) -> AABB<N> {
+        let mins =
+            Point::from(cell.coords.map(|e| na::convert::<f64, N>(e as f64)) * self.cell_size);
+        let maxs = Point::from(
+            cell.coords.map(|e| na::convert::<f64, N>((e + 1) as f64)) * self.cell_size,
+        );
+        AABB::new(mins, maxs)
+    }
+
+    fn update_cell_bounds(&mut self) {
+        let mut keys = self.cells.keys();
+
+        self.cell_bounds = keys.next().map(|first| {
+            keys.fold((*first, *first), |bounds, cell| {
+                (cell_inf(&bounds.0, cell), cell_sup(&bounds.1, cell))
+            })
+        });
+    }
+
+    fn detach(&mut self, handle: BroadPhaseProxyHandle, location: GridLocation) {
+        let range = match location {
+            GridLocation::Cells(mins, maxs) => (mins, maxs),
+            GridLocation::Oversized => {
+                self.oversized.retain(|h| *h != handle);
+                return;
+            }
+            GridLocation::Pending => return,
+        };
+        let cells = &mut self.cells;
+
+        for_each_cell(&range.0, &range.1, |cell| {
+            if let Entry::Occupied(mut entry) = cells.entry(*cell) {
+                entry.get_mut().retain(|h| *h != handle);
+
+                if entry.get().is_empty() {
+                    let _ = entry.remove();
+                }
+            }
+        });
+    }
+
+    fn attach(&mut self, handle: BroadPhaseProxyHandle, location: GridLocation) {
+        let range = match location {
+            GridLocation::Cells(mins, maxs) => (mins, maxs),
+            GridLocation::Oversized => {
+                self.oversized.push(handle);
+                return;
+            }
+            GridLocation::Pending => return,
+        };
+        let cells = &mut self.cells;
+
+        for_each_cell(&range.0, &range.1, |cell| {
+            cells.entry(*cell).or_insert_with(Vec::new).push(handle)
+        });
+    }
+
+    // Collects (without duplicates) all the proxies that may intersect a proxy at the given
+    // location.
+    fn collect_candidates(&mut self, location: GridLocation) {
+        let mut collector = mem::replace(&mut self.collector, Vec::new());
+        collector.clear();
+
+        match location {
+            GridLocation::Cells(mins, maxs) => {
+                let cells = &self.cells;
+
+                for_each_cell(&mins, &maxs, |cell| {
+                    if let Some(handles) = cells.get(cell) {
+                        collector.extend_from_slice(handles)
+                    }
+                });
+
+                collector.extend_from_slice(&self.oversized);
+                collector.sort();
+                collector.dedup();
+            }
+            GridLocation::Oversized => collector.extend(
+                self.proxies
+                    .iter()
+                    .filter(|(_, proxy)| proxy.is_registered())
+                    .map(|(i, _)| BroadPhaseProxyHandle(i)),
+            ),
+            GridLocation::Pending => {}
+        }
+
+        self.collector = collector;
+    }
+
+    // Finds all the new interferences involving the given proxy.
+    fn find_interferences_with(
+        &mut self,
+        handle: BroadPhaseProxyHandle,
+        handler: &mut dyn BroadPhaseInterferenceHandler<T>,
+    ) {
+        let location = match self.proxies.get(handle.uid()) {
+            Some(proxy) => proxy.location,
+            None => return,
+        };
+
+        self.collect_candidates(location);
+
+        let proxy1 = &self.proxies[handle.uid()];
+
+        for other in &self.collector {
+            if *other == handle {
+                continue;
+            }
+
+            let proxy2 = &self.proxies[other.uid()];
+
+            if proxy1.aabb.intersects(&proxy2.aabb)
+                && handler.is_interference_allowed(&proxy1.data, &proxy2.data)
+            {
+                if let Entry::Vacant(entry) = self.pairs.entry(SortedPair::new(handle, *other)) {
+                    handler.interference_started(&proxy1.data, &proxy2.data);
+                    let _ = entry.insert(());
+                }
+            }
+        }
+    }
+
+    // Removes the pairs involving an updated proxy that no longer intersect or that are no longer allowed.
+    fn purge_some_contact_pairs(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
+        let purge_all = self.recompute_all;
+        let proxies = &self.proxies;
+
+        self.pairs.retain(|pair, _| {
+            let proxy1 = &proxies[pair.0.uid()];
+            let proxy2 = &proxies[pair.1.uid()];
+
+            if purge_all || proxy1.updated || proxy2.updated {
+                if !proxy1.aabb.intersects(&proxy2.aabb)
+                    || !handler.is_interference_allowed(&proxy1.data, &proxy2.data)
+                {
+                    handler.interference_stopped(&proxy1.data, &proxy2.data);
+                    return false;
+                }
+            }
+
+            true
+        });
+    }
+}
+
+impl<N, T> BroadPhase<N, AABB<N>, T> for UniformGridBroadPhase<N, T>
+where
+    N: RealField,
+    T: Any + Send + Sync,
+{
+    fn update(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
+        /*
+         * Move the modified proxies on the grid.
+         */
+        let mut cells_changed = false;
+
+        for (handle, aabb) in mem::replace(&mut self.proxies_to_update, Vec::new()) {
+            let new_location = self.location_of(&aabb);
+            let old_location = match self.proxies.get_mut(handle.uid()) {
+                Some(proxy) => {
+                    proxy.aabb = aabb;
+
+                    if !proxy.updated {
+                        proxy.updated = true;
+                        self.updated.push(handle);
+                    }
+
+                    mem::replace(&mut proxy.location, new_location)
+                }
+                None => continue,
+            };
+
+            if old_location != new_location {
+                self.detach(handle, old_location);
+                self.attach(handle, new_location);
+                cells_changed = true;
+            }
+        }
+
+        if cells_changed {
+            self.update_cell_bounds();
+        }
+
+        /*
+         * Proxies the user asked to recompute interferences with.
+         */
+        if self.recompute_all {
+            for (i, proxy) in self.proxies.iter_mut() {
+                if proxy.is_registered() && !proxy.updated {
+                    proxy.updated = true;
+                    self.updated.push(BroadPhaseProxyHandle(i));
+                }
+            }
+        } else {
+            for handle in self.proxies_to_recompute.drain(..) {
+                let proxy = &mut self.proxies[handle.uid()];
+
+                if proxy.is_registered() && !proxy.updated {
+                    proxy.updated = true;
+                    self.updated.push(handle);
+                }
+            }
+        }
+
+        /*
+         * Collect interferences.
+         */
+        if !self.updated.is_empty() {
+            self.purge_some_contact_pairs(handler);
+
+            let updated = mem::replace(&mut self.updated, Vec::new());
+
+            for handle in &updated {
+                self.find_interferences_with(*handle, handler);
+            }
+
+            for handle in &updated {
+                self.proxies[handle.uid()].updated = false;
+            }
+
+            self.updated = updated;
+            self.updated.clear();
+        }
+
+        self.recompute_all = false;
+        self.proxies_to_recompute.clear();
+    }
+
+    fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&AABB<N>, &T)> {
+        let proxy = self.proxies.get(handle.uid())?;
+
+        if proxy.is_registered() {
+            Some((&proxy.aabb, &proxy.data))
+        } else {
+            None
+        }
+    }
+
+    fn create_proxy(&mut self, bv: AABB<N>, data: T) -> BroadPhaseProxyHandle {
+        let proxy = UniformGridBroadPhaseProxy::new(data, bv.clone());
+        let handle = BroadPhaseProxyHandle(self.proxies.insert(proxy));
+        self.proxies_to_update.push((handle, bv));
+        handle
+    }
+
+    fn remove(&mut self, handles: &[BroadPhaseProxyHandle], handler: &mut dyn FnMut(&T, &T)) {
+        for handle in handles {
+            let location = match self.proxies.get_mut(handle.uid()) {
+                Some(proxy) => mem::replace(&mut proxy.location, GridLocation::Pending),
+                None => panic!("Attempting to remove an object that does not exist."),
+            };
+
+            self.detach(*handle, location);
+        }
+
+        {
+            let proxies = &self.proxies;
+
+            self.pairs.retain(|pair, _| {
+                if handles.contains(&pair.0) || handles.contains(&pair.1) {
+                    let proxy1 = &proxies[pair.0.uid()];
+                    let proxy2 = &proxies[pair.1.uid()];
+                    handler(&proxy1.data, &proxy2.data);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        self.proxies_to_update.retain(|(h, _)| !handles.contains(h));
+        self.proxies_to_recompute.retain(|h| !handles.contains(h));
+
+        for handle in handles {
+            let _ = self.proxies.remove(handle.uid());
+        }
+
+        self.update_cell_bounds();
+    }
+
+    fn deferred_set_bounding_volume(&mut self, handle: BroadPhaseProxyHandle, bv: AABB<N>) {
+        if let Some(proxy) = self.proxies.get(handle.uid()) {
+            if !proxy.is_registered() || !proxy.aabb.contains(&bv) {
+                let new_bv = bv.loosened(self.margin);
+                self.proxies_to_update.push((handle, new_bv));
+            }
+        } else {
+            panic!("Attempting to set the bounding volume of an object that does not exist.");
+        }
+    }
+
+    fn deferred_recompute_all_proximities_with(&mut self, handle: BroadPhaseProxyHandle) {
+        if self.proxies.contains(handle.uid()) {
+            self.proxies_to_recompute.push(handle);
+        }
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        self.recompute_all = true;
+    }
+
+    fn interferences_with_bounding_volume<'a>(&'a self, bv: &AABB<N>, out: &mut Vec<&'a T>) {
+        for proxy in self.oversized_proxies() {
+            if proxy.aabb.intersects(bv) {
+                out.push(&proxy.data)
+            }
+        }
+
+        let mut visited = HashSet::with_hasher(DeterministicState::new());
+
+        self.for_each_occupied_cell(self.cells_covered_by(bv), |handles| {
+            for handle in handles {
+                let proxy = &self.proxies[handle.uid()];
+
+                if visited.insert(*handle) && proxy.aabb.intersects(bv) {
+                    out.push(&proxy.data)
+                }
+            }
+        });
+    }
+
+    fn interferences_with_ray<'a>(&'a self, ray: &Ray<N>, out: &mut Vec<&'a T>) {
+        for proxy in self.oversized_proxies() {
+            if proxy.aabb.intersects_ray(&Isometry::identity(), ray) {
+                out.push(&proxy.data)
+            }
+        }
+
+        let bounds = try_ret!(self.cell_bounds);
+        let grid_aabb = self.cell_aabb(&bounds.0).merged(&self.cell_aabb(&bounds.1));
+        let toi = try_ret!(grid_aabb.toi_with_ray(&Isometry::identity(), ray, true));
+
+        /*
+         * Traverse the grid cell by cell, following the ray (3D-DDA).
+         */
+        let start = ray.point_at(toi);
+        let mut cell = cell_inf(
+            &cell_sup(&self.cell_containing(&start), &bounds.0),
+            &bounds.1,
+        );
+        let mut step = Cell::origin();
+        let mut next_toi = Point::<N>::origin();
+        let mut delta_toi = Point::<N>::origin();
+
+        for i in 0..DIM {
+            if ray.dir[i] > N::zero() {
+                step[i] = 1;
+                let boundary = na::convert::<f64, N>((cell[i] + 1) as f64) * self.cell_size;
+                next_toi[i] = (boundary - ray.origin[i]) / ray.dir[i];
+                delta_toi[i] = self.cell_size / ray.dir[i];
+            } else if ray.dir[i] < N::zero() {
+                step[i] = -1;
+                let boundary = na::convert::<f64, N>(cell[i] as f64) * self.cell_size;
+                next_toi[i] = (boundary - ray.origin[i]) / ray.dir[i];
+                delta_toi[i] = -self.cell_size / ray.dir[i];
+            } else {
+                next_toi[i] = N::max_value();
+                delta_toi[i] = N::max_value();
+            }
+        }
+
+        let mut visited = HashSet::with_hasher(DeterministicState::new());
+
+        loop {
+            if let Some(handles) = self.cells.get(&cell) {
+                for handle in handles {
+                    let proxy = &self.proxies[handle.uid()];
+
+                    if visited.insert(*handle)
+                        && proxy.aabb.intersects_ray(&Isometry::identity(), ray)
+                    {
+                        out.push(&proxy.data)
+                    }
+                }
+            }
+
+            // Move to the next cell along the axis with the closest boundary.
+            let mut axis = 0;
+            for i in 1..DIM {
+                if next_toi[i] < next_toi[axis] {
+                    axis = i;
+                }
+            }
+
+            cell[axis] += step[axis];
+
+            if step[axis] == 0 || cell[axis] < bounds.0[axis] || cell[axis] > bounds.1[axis] {
+                break;
+            }
+
+            next_toi[axis] += delta_toi[axis];
+        }
+    }
+
+    fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>) {
+        for proxy in self.oversized_proxies() {
+            if proxy.aabb.contains_local_point(point) {
+                out.push(&proxy.data)
+            }
+        }
+
+        if let Some(handles) = self.cells.get(&self.cell_containing(point)) {
+            for handle in handles {
+                let proxy = &self.proxies[handle.uid()];
+
+                if proxy.aabb.contains_local_point(point) {
+                    out.push(&proxy.data)
+                }
+            }
+        }
+    }
+
+    fn first_interference<'a>(
+        &'a self,
+        bv_cost: &mut dyn FnMut(&AABB<N>, N) -> Option<N>,
+        object_cost: &mut dyn FnMut(&'a T, N) -> Option<N>,
+    ) -> Option<(&'a T, N)> {
+        let candidates = self
+            .proxies
+            .iter()
+            .filter(|(_, proxy)| proxy.is_registered())
+            .map(|(_, proxy)| (&proxy.aabb, &proxy.data));
+
+        broad_phase::first_interference_among(candidates, bv_cost, object_cost)
+    }
+}
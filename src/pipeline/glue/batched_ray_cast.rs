@@ -0,0 +1,231 @@
+use na::RealField;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::bounding_volume::AABB;
+use crate::pipeline::broad_phase::BroadPhase;
+use crate::pipeline::object::{CollisionGroups, CollisionObjectRef, CollisionObjectSet};
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::utils::MaybeSync;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// The hits a batched ray cast should report for each ray.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RayCastMode {
+    /// Only report the hit with the smallest time of impact.
+    FirstHit,
+    /// Report all the hits, sorted by increasing time of impact.
+    AllHits,
+}
+
+/// Reusable storage for the results of a batched ray cast.
+///
+/// The buffers are kept between two calls to `cast_rays` so that casting the same number of
+/// rays at each frame does not allocate.
+pub struct RayCastResults<N: RealField, Handle> {
+    hits: Vec<(Handle, RayIntersection<N>)>,
+    ranges: Vec<Range<usize>>,
+    // The hits and ranges computed by each parallel task, relative to the start of its buffer.
+    #[cfg(feature = "parallel")]
+    chunks: Vec<(Vec<(Handle, RayIntersection<N>)>, Vec<Range<usize>>)>,
+}
+
+impl<N: RealField, Handle> RayCastResults<N, Handle> {
+    /// Creates an empty set of results.
+    pub fn new() -> Self {
+        RayCastResults {
+            hits: Vec::new(),
+            ranges: Vec::new(),
+            #[cfg(feature = "parallel")]
+            chunks: Vec::new(),
+        }
+    }
+
+    /// The number of rays these results were computed for.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Whether these results are empty, i.e., were computed for no ray at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The hits found for the `i`-th ray of the batch.
+    #[inline]
+    pub fn hits(&self, i: usize) -> &[(Handle, RayIntersection<N>)] {
+        &self.hits[self.ranges[i].clone()]
+    }
+
+    /// The hit with the smallest time of impact found for the `i`-th ray of the batch.
+    #[inline]
+    pub fn first_hit(&self, i: usize) -> Option<&(Handle, RayIntersection<N>)> {
+        self.hits(i).first()
+    }
+
+    /// An iterator through the hits found for each ray of the batch, in the order of the rays.
+    pub fn iter(&self) -> impl Iterator<Item = &[(Handle, RayIntersection<N>)]> {
+        self.ranges.iter().map(move |r| &self.hits[r.clone()])
+    }
+
+    /// Removes all the results, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.hits.clear();
+        self.ranges.clear();
+    }
+}
+
+// Casts one ray, appending its hits to `hits`. The `candidates` buffer is reused from one ray to the next.
+fn cast_ray<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    ray: &Ray<N>,
+    groups: &CollisionGroups,
+    mode: RayCastMode,
+    candidates: &mut Vec<&'a Objects::CollisionObjectHandle>,
+    hits: &mut Vec<(Objects::CollisionObjectHandle, RayIntersection<N>)>,
+) where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let start = hits.len();
+
+    candidates.clear();
+    broad_phase.interferences_with_ray(ray, candidates);
+
+    for handle in candidates.iter() {
+        if let Some(co) = objects.collision_object(**handle) {
+            if co.collision_groups().can_interact_with_groups(groups) {
+                let inter = co.shape().toi_and_normal_with_ray(co.position(), ray, true);
+
+                if let Some(inter) = inter {
+                    match mode {
+                        RayCastMode::AllHits => hits.push((**handle, inter)),
+                        RayCastMode::FirstHit => {
+                            if hits.len() == start {
+                                hits.push((**handle, inter))
+                            } else if inter.toi < hits[start].1.toi {
+                                hits[start] = (**handle, inter)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if mode == RayCastMode::AllHits {
+        hits[start..].sort_by(|a, b| a.1.toi.partial_cmp(&b.1.toi).unwrap_or(Ordering::Equal));
+    }
+}
+
+// Casts consecutive rays, appending their hits to `hits` and their ranges to `ranges`.
+fn cast_ray_sequence<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    rays: &[Ray<N>],
+    groups: &[CollisionGroups],
+    mode: RayCastMode,
+    hits: &mut Vec<(Objects::CollisionObjectHandle, RayIntersection<N>)>,
+    ranges: &mut Vec<Range<usize>>,
+) where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut candidates = Vec::new();
+
+    for (ray, groups) in rays.iter().zip(groups.iter()) {
+        let start = hits.len();
+        cast_ray(
+            objects,
+            broad_phase,
+            ray,
+            groups,
+            mode,
+            &mut candidates,
+            hits,
+        );
+        ranges.push(start..hits.len());
+    }
+}
+
+/// Casts a batch of rays against all the collision objects.
+///
+/// The hits of the `i`-th ray of `rays` are written to `results.hits(i)`. Only collision objects
+/// in a group that can interact with `groups[i]` are considered for the `i`-th ray. If the
+/// `parallel` feature is enabled, the rays are cast on the rayon thread pool.
+///
+/// Panics if `rays` and `groups` do not have the same length.
+pub fn cast_rays<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    rays: &[Ray<N>],
+    groups: &[CollisionGroups],
+    mode: RayCastMode,
+    results: &mut RayCastResults<N, Objects::CollisionObjectHandle>,
+) where
+    N: RealField,
+    Objects: CollisionObjectSet<N> + MaybeSync,
+{
+    assert_eq!(
+        rays.len(),
+        groups.len(),
+        "A batched ray cast needs one set of collision groups per ray."
+    );
+    results.clear();
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        cast_ray_sequence(
+            objects,
+            broad_phase,
+            rays,
+            groups,
+            mode,
+            &mut results.hits,
+            &mut results.ranges,
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        // A few tasks per thread for load balancing. Each task reuses its buffers from the
+        // previous batch.
+        let num_chunks = rayon::current_num_threads() * 4;
+        let chunk_size = (rays.len() + num_chunks - 1) / num_chunks;
+
+        if chunk_size == 0 {
+            return;
+        }
+
+        results
+            .chunks
+            .resize_with((rays.len() + chunk_size - 1) / chunk_size, || {
+                (Vec::new(), Vec::new())
+            });
+
+        results
+            .chunks
+            .par_iter_mut()
+            .zip(
+                rays.par_chunks(chunk_size)
+                    .zip(groups.par_chunks(chunk_size)),
+            )
+            .for_each(|((hits, ranges), (rays, groups))| {
+                hits.clear();
+                ranges.clear();
+                cast_ray_sequence(objects, broad_phase, rays, groups, mode, hits, ranges);
+            });
+
+        for (hits, ranges) in &mut results.chunks {
+            let offset = results.hits.len();
+            results.hits.extend(hits.drain(..));
+            results
+                .ranges
+                .extend(ranges.drain(..).map(|r| r.start + offset..r.end + offset));
+        }
+    }
+}
@@ -1,15 +1,23 @@
 //! Glue code between each part of the collision-detection pipeline.
 
+pub use self::batched_ray_cast::{cast_rays, RayCastMode, RayCastResults};
 pub use self::query::{
-    interferences_with_aabb, interferences_with_point, interferences_with_ray,
-    InterferencesWithAABB, InterferencesWithPoint, InterferencesWithRay,
+    first_interference_with_ray, interferences_with_aabb, interferences_with_point,
+    interferences_with_ray, interferences_with_shape, nearest_object_to_point,
+    FirstInterferenceWithRay, InterferencesWithAABB, InterferencesWithPoint, InterferencesWithRay,
+    InterferencesWithShape, NearestObjectToPoint,
+};
+pub use self::sweep::{
+    first_sweep_test, first_sweep_test_with_motion, sweep_test, sweep_test_with_motion,
 };
 pub use setup::{
     create_proxies, default_broad_phase, default_interaction_graph, default_narrow_phase,
-    remove_proxies,
+    default_sap_broad_phase, default_uniform_grid_broad_phase, remove_proxies,
 };
 pub use update::{perform_all_pipeline, perform_broad_phase, perform_narrow_phase};
 
+mod batched_ray_cast;
 mod query;
 mod setup;
+mod sweep;
 mod update;
@@ -2,10 +2,12 @@ use na::RealField;
 use std::vec::IntoIter;
 
 use crate::bounding_volume::AABB;
-use crate::math::Point;
+use crate::math::{Isometry, Point};
 use crate::pipeline::broad_phase::BroadPhase;
+use crate::pipeline::narrow_phase::ProximityDispatcher;
 use crate::pipeline::object::{CollisionGroups, CollisionObjectRef, CollisionObjectSet};
-use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
+use crate::query::{PointProjection, PointQuery, Proximity, Ray, RayCast, RayIntersection};
+use crate::shape::{FeatureId, Shape};
 
 /// Returns an iterator yielding all the collision objects intersecting with the given ray.
 ///
@@ -70,6 +72,54 @@ where
     }
 }
 
+/// The result of a successful `first_interference_with_ray` query.
+pub struct FirstInterferenceWithRay<'a, N: RealField, Objects: CollisionObjectSet<N>> {
+    /// The handle of the first collision object hit by the ray.
+    pub handle: Objects::CollisionObjectHandle,
+    /// The first collision object hit by the ray.
+    pub co: &'a Objects::CollisionObject,
+    /// The intersection between the ray and the collision object, including the feature hit.
+    pub inter: RayIntersection<N>,
+}
+
+/// Returns the first collision object hit by the given ray, i.e., the one with the smallest time of impact.
+///
+/// Only hits with a time of impact smaller than `max_toi`, and collision objects in a group that can
+/// interact with the given `groups` are considered. The broad phase is traversed best-first when it
+/// supports it, so most of the objects along the ray are never tested.
+pub fn first_interference_with_ray<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    ray: &Ray<N>,
+    max_toi: N,
+    groups: &CollisionGroups,
+) -> Option<FirstInterferenceWithRay<'a, N, Objects>>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut ray_cast = |handle: &Objects::CollisionObjectHandle, ray: &Ray<N>, max_toi: N| {
+        let co = objects.collision_object(*handle)?;
+
+        if co.collision_groups().can_interact_with_groups(groups) {
+            co.shape()
+                .toi_and_normal_with_ray(&co.position(), ray, true)
+                .filter(|inter| inter.toi <= max_toi)
+        } else {
+            None
+        }
+    };
+
+    let (handle, inter) = broad_phase.first_interference_with_ray(ray, max_toi, &mut ray_cast)?;
+    let co = objects.collision_object(*handle)?;
+
+    Some(FirstInterferenceWithRay {
+        handle: *handle,
+        co,
+        inter,
+    })
+}
+
 /// Returns an iterator yielding all the collision objects containing the given point.
 ///
 /// The result will only include collision objects in a group that can interact with the given `groups`.
@@ -125,6 +175,94 @@ where
     }
 }
 
+/// The result of a successful `nearest_object_to_point` query.
+pub struct NearestObjectToPoint<'a, N: RealField, Objects: CollisionObjectSet<N>> {
+    /// The handle of the collision object closest to the point.
+    pub handle: Objects::CollisionObjectHandle,
+    /// The collision object closest to the point.
+    pub co: &'a Objects::CollisionObject,
+    /// The projection of the point on the collision object.
+    pub proj: PointProjection<N>,
+    /// The feature of the collision object the point is projected on.
+    pub feature: FeatureId,
+}
+
+/// Returns the collision object closest to the given point, with the projection of the point on it.
+///
+/// Only collision objects closer than `max_dist`, and in a group that can interact with the given
+/// `groups` are considered. A point inside of a collision object is at a distance of zero from
+/// it. The broad phase is traversed best-first when it supports it.
+pub fn nearest_object_to_point<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    point: &Point<N>,
+    max_dist: N,
+    groups: &CollisionGroups,
+) -> Option<NearestObjectToPoint<'a, N, Objects>>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut best = None;
+
+    {
+        let mut bv_cost = |bv: &AABB<N>, max: N| {
+            let dist = bv.distance_to_point(&Isometry::identity(), point, true);
+
+            if dist <= max.min(max_dist) {
+                Some(dist)
+            } else {
+                None
+            }
+        };
+
+        let mut object_cost = |handle: &'a Objects::CollisionObjectHandle, max: N| {
+            let co = objects.collision_object(*handle)?;
+
+            if !co.collision_groups().can_interact_with_groups(groups) {
+                return None;
+            }
+
+            let (proj, feature) = co.shape().project_point_with_feature(co.position(), point);
+            let dist = distance_to_projection(point, &proj);
+
+            if dist > max.min(max_dist) {
+                return None;
+            }
+
+            let is_better = match &best {
+                Some(NearestObjectToPoint {
+                    proj: best_proj, ..
+                }) => dist < distance_to_projection(point, best_proj),
+                None => true,
+            };
+
+            if is_better {
+                best = Some(NearestObjectToPoint {
+                    handle: *handle,
+                    co,
+                    proj,
+                    feature,
+                });
+            }
+
+            Some(dist)
+        };
+
+        let _ = broad_phase.first_interference(&mut bv_cost, &mut object_cost);
+    }
+
+    best
+}
+
+fn distance_to_projection<N: RealField>(point: &Point<N>, proj: &PointProjection<N>) -> N {
+    if proj.is_inside {
+        N::zero()
+    } else {
+        na::distance(point, &proj.point)
+    }
+}
+
 /// Returns an iterator yielding all the collision objects with an AABB intersecting with the given AABB.
 ///
 /// The result will only include collision objects in a group that can interact with the given `groups`.
@@ -173,3 +311,82 @@ impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
         None
     }
 }
+
+/// Returns an iterator yielding all the collision objects intersecting with the given shape.
+///
+/// Unlike `interferences_with_aabb`, this performs an exact intersection test between the shape
+/// and each broad-phase candidate, using the proximity detectors given by `dispatcher`. The
+/// candidates for which `dispatcher` has no proximity detector are ignored. The result will only
+/// include collision objects in a group that can interact with the given `groups`.
+pub fn interferences_with_shape<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    dispatcher: &'a dyn ProximityDispatcher<N>,
+    shape: &'b dyn Shape<N>,
+    isometry: &'b Isometry<N>,
+    groups: &'b CollisionGroups,
+) -> InterferencesWithShape<'a, 'b, N, Objects>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut handles = Vec::new();
+    broad_phase.interferences_with_bounding_volume(&shape.aabb(isometry), &mut handles);
+
+    InterferencesWithShape {
+        dispatcher,
+        shape,
+        isometry,
+        groups,
+        objects,
+        handles: handles.into_iter(),
+    }
+}
+
+/// Iterator through all the objects on the world that intersect a specific shape.
+pub struct InterferencesWithShape<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
+    dispatcher: &'a dyn ProximityDispatcher<N>,
+    shape: &'b dyn Shape<N>,
+    isometry: &'b Isometry<N>,
+    objects: &'a Objects,
+    groups: &'b CollisionGroups,
+    handles: IntoIter<&'a Objects::CollisionObjectHandle>,
+}
+
+impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
+    for InterferencesWithShape<'a, 'b, N, Objects>
+{
+    type Item = (Objects::CollisionObjectHandle, &'a Objects::CollisionObject);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(handle) = self.handles.next() {
+            if let Some(co) = self.objects.collision_object(*handle) {
+                if !co.collision_groups().can_interact_with_groups(self.groups) {
+                    continue;
+                }
+
+                let detector = self
+                    .dispatcher
+                    .get_proximity_algorithm(self.shape, co.shape());
+
+                if let Some(mut detector) = detector {
+                    let proximity = detector.update(
+                        self.dispatcher,
+                        self.isometry,
+                        self.shape,
+                        co.position(),
+                        co.shape(),
+                        N::zero(),
+                    );
+
+                    if proximity == Some(Proximity::Intersecting) {
+                        return Some((*handle, co));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
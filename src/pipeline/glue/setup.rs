@@ -2,10 +2,12 @@ use na::RealField;
 
 use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::Isometry;
-use crate::pipeline::broad_phase::{BroadPhase, BroadPhaseProxyHandle, DBVTBroadPhase};
+use crate::pipeline::broad_phase::{
+    BroadPhase, BroadPhaseProxyHandle, DBVTBroadPhase, SAPBroadPhase, UniformGridBroadPhase,
+};
 use crate::pipeline::narrow_phase::{
-    CollisionObjectGraphIndex, DefaultContactDispatcher, DefaultProximityDispatcher,
-    InteractionGraph, NarrowPhase,
+    CollisionObjectGraphIndex, DefaultContactDispatcher, DefaultDistanceDispatcher,
+    DefaultProximityDispatcher, InteractionGraph, NarrowPhase,
 };
 use crate::pipeline::object::{CollisionObjectHandle, GeometricQueryType};
 use crate::shape::Shape;
@@ -61,12 +63,13 @@ pub fn remove_proxies<'a, N: RealField, Handle: CollisionObjectHandle>(
         .map(|h| (h, graph_index))
 }
 
-/// Allocate a default narrow-phase, configured with the default contact and proximity dispatchers.
+/// Allocate a default narrow-phase, configured with the default contact, proximity and distance dispatchers.
 pub fn default_narrow_phase<N: RealField, Handle: CollisionObjectHandle>() -> NarrowPhase<N, Handle>
 {
     let coll_dispatcher = Box::new(DefaultContactDispatcher::new());
     let prox_dispatcher = Box::new(DefaultProximityDispatcher::new());
-    NarrowPhase::new(coll_dispatcher, prox_dispatcher)
+    let dist_dispatcher = Box::new(DefaultDistanceDispatcher::new());
+    NarrowPhase::new(coll_dispatcher, prox_dispatcher, dist_dispatcher)
 }
 
 /// Allocate a default broad-phase, configured with a default coherence margin (set to 0.01).
@@ -76,6 +79,27 @@ pub fn default_broad_phase<N: RealField, Handle: CollisionObjectHandle>(
     DBVTBroadPhase::new(na::convert(default_margin))
 }
 
+/// Allocate a sweep-and-prune broad-phase, configured with a default coherence margin (set to 0.01).
+///
+/// This is an alternative to the `default_broad_phase` that performs better for scenes with
+/// many small objects moving coherently.
+pub fn default_sap_broad_phase<N: RealField, Handle: CollisionObjectHandle>(
+) -> SAPBroadPhase<N, Handle> {
+    let default_margin = 0.01f64;
+    SAPBroadPhase::new(na::convert(default_margin))
+}
+
+/// Allocate a uniform grid broad-phase with the given cell size, configured with a default coherence margin (set to 0.01).
+///
+/// This is an alternative to the `default_broad_phase` that performs better for scenes with
+/// many objects of similar sizes. The cell size should be close to the typical object size.
+pub fn default_uniform_grid_broad_phase<N: RealField, Handle: CollisionObjectHandle>(
+    cell_size: N,
+) -> UniformGridBroadPhase<N, Handle> {
+    let default_margin = 0.01f64;
+    UniformGridBroadPhase::new(cell_size, na::convert(default_margin))
+}
+
 /// Allocate a default interaction graph.
 pub fn default_interaction_graph<N: RealField, Handle: CollisionObjectHandle>(
 ) -> InteractionGraph<N, Handle> {
@@ -0,0 +1,268 @@
+use na::{RealField, Unit};
+
+use crate::bounding_volume::AABB;
+use crate::interpolation::{RigidMotion, RigidMotionComposition};
+use crate::math::{Isometry, Vector};
+use crate::pipeline::broad_phase::BroadPhase;
+use crate::pipeline::object::{CollisionGroups, CollisionObjectRef, CollisionObjectSet};
+use crate::query::{self, Ray, RayCast, TOI};
+use crate::shape::{Ball, Shape};
+
+// Traverses the broad phase by increasing lower bound of time of impact.
+//
+// If `first_only` is `true`, the search stops as soon as no object can be hit before the
+// closest hit found so far. Otherwise, every hit is collected. The result is sorted by
+// increasing time of impact.
+fn sweep<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    groups: &CollisionGroups,
+    first_only: bool,
+    bv_cost: &mut dyn FnMut(&AABB<N>, N) -> Option<N>,
+    object_toi: &mut dyn FnMut(&'a Objects::CollisionObject, N) -> Option<TOI<N>>,
+) -> Vec<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut hits = Vec::new();
+
+    {
+        let mut object_cost = |handle: &'a Objects::CollisionObjectHandle, max_toi: N| {
+            let co = objects.collision_object(*handle)?;
+
+            if !co.collision_groups().can_interact_with_groups(groups) {
+                return None;
+            }
+
+            let toi = object_toi(co, max_toi)?;
+
+            if first_only {
+                let cost = toi.toi;
+
+                if hits
+                    .first()
+                    .map_or(true, |hit: &(_, TOI<N>)| cost < hit.1.toi)
+                {
+                    hits.clear();
+                    hits.push((*handle, toi));
+                }
+
+                Some(cost)
+            } else {
+                // Don't report any cost so the search is never pruned.
+                hits.push((*handle, toi));
+                None
+            }
+        };
+
+        let _ = broad_phase.first_interference(bv_cost, &mut object_cost);
+    }
+
+    hits.sort_by(|a, b| a.1.toi.partial_cmp(&b.1.toi).unwrap());
+    hits
+}
+
+fn linear_sweep<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    isometry: &Isometry<N>,
+    direction: &Unit<Vector<N>>,
+    maximum_distance: N,
+    groups: &CollisionGroups,
+    first_only: bool,
+) -> Vec<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    // The AABB of the swept shape hits a bounding volume when the ray cast from its center hits
+    // this bounding volume enlarged by its half-extents.
+    let shape_aabb = shape.aabb(isometry);
+    let half_extents = shape_aabb.half_extents();
+    let ray = Ray::new(shape_aabb.center(), direction.into_inner());
+
+    let mut bv_cost = |bv: &AABB<N>, max_toi: N| {
+        let enlarged = AABB::new(bv.mins() - half_extents, bv.maxs() + half_extents);
+        enlarged
+            .toi_with_ray(&Isometry::identity(), &ray, true)
+            .filter(|toi| *toi <= max_toi.min(maximum_distance))
+    };
+
+    let mut object_toi = |co: &Objects::CollisionObject, max_toi: N| {
+        query::time_of_impact(
+            isometry,
+            direction.as_ref(),
+            shape,
+            co.position(),
+            &Vector::zeros(),
+            co.shape(),
+            max_toi.min(maximum_distance),
+            N::zero(),
+        )
+    };
+
+    sweep(
+        objects,
+        broad_phase,
+        groups,
+        first_only,
+        &mut bv_cost,
+        &mut object_toi,
+    )
+}
+
+fn nonlinear_sweep<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    motion: &(impl RigidMotion<N> + ?Sized),
+    max_toi: N,
+    groups: &CollisionGroups,
+    first_only: bool,
+) -> Vec<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    // Bounding volumes are approximated by their bounding spheres.
+    let sphere = shape.bounding_sphere(&Isometry::identity());
+    let ball = Ball::new(sphere.radius());
+    let sphere_motion = motion.prepend_translation(sphere.center().coords);
+
+    let mut bv_cost = |bv: &AABB<N>, max: N| {
+        let bv_sphere = bv.bounding_sphere();
+        let bv_ball = Ball::new(bv_sphere.radius());
+        let bv_pos = Isometry::new(bv_sphere.center().coords, na::zero());
+
+        query::nonlinear_time_of_impact_ball_ball(
+            &sphere_motion,
+            &ball,
+            &bv_pos,
+            &bv_ball,
+            max.min(max_toi),
+            N::zero(),
+        )
+        .map(|toi| toi.toi)
+    };
+
+    let mut object_toi = |co: &Objects::CollisionObject, max: N| {
+        query::nonlinear_time_of_impact(
+            motion,
+            shape,
+            co.position(),
+            co.shape(),
+            max.min(max_toi),
+            N::zero(),
+        )
+    };
+
+    sweep(
+        objects,
+        broad_phase,
+        groups,
+        first_only,
+        &mut bv_cost,
+        &mut object_toi,
+    )
+}
+
+/// Returns all the collision objects hit by `shape` translated from `isometry` along `direction`.
+///
+/// Only objects hit before `shape` travels `maximum_distance`, and in a group that can interact
+/// with the given `groups` are returned. They are sorted by increasing time of impact, which is
+/// also the distance traveled since `direction` is normalized.
+pub fn sweep_test<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    isometry: &Isometry<N>,
+    direction: &Unit<Vector<N>>,
+    maximum_distance: N,
+    groups: &CollisionGroups,
+) -> Vec<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    linear_sweep(
+        objects,
+        broad_phase,
+        shape,
+        isometry,
+        direction,
+        maximum_distance,
+        groups,
+        false,
+    )
+}
+
+/// Returns the first collision object hit by `shape` translated from `isometry` along `direction`.
+///
+/// This is the same as the first result of `sweep_test`, but the broad phase is traversed
+/// best-first so that objects farther than the first hit are not tested.
+pub fn first_sweep_test<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    isometry: &Isometry<N>,
+    direction: &Unit<Vector<N>>,
+    maximum_distance: N,
+    groups: &CollisionGroups,
+) -> Option<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    linear_sweep(
+        objects,
+        broad_phase,
+        shape,
+        isometry,
+        direction,
+        maximum_distance,
+        groups,
+        true,
+    )
+    .pop()
+}
+
+/// Returns all the collision objects hit by `shape` following the given rigid motion.
+///
+/// The motion may include rotations, in which case the times of impact are computed with
+/// `query::nonlinear_time_of_impact`. Only objects hit before `max_toi`, and in a group that can
+/// interact with the given `groups` are returned. They are sorted by increasing time of impact.
+pub fn sweep_test_with_motion<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    motion: &(impl RigidMotion<N> + ?Sized),
+    max_toi: N,
+    groups: &CollisionGroups,
+) -> Vec<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    nonlinear_sweep(objects, broad_phase, shape, motion, max_toi, groups, false)
+}
+
+/// Returns the first collision object hit by `shape` following the given rigid motion.
+///
+/// This is the same as the first result of `sweep_test_with_motion`, but the broad phase is
+/// traversed best-first so that objects hit after the first hit are not tested.
+pub fn first_sweep_test_with_motion<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    motion: &(impl RigidMotion<N> + ?Sized),
+    max_toi: N,
+    groups: &CollisionGroups,
+) -> Option<(Objects::CollisionObjectHandle, TOI<N>)>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    nonlinear_sweep(objects, broad_phase, shape, motion, max_toi, groups, true).pop()
+}
@@ -6,6 +6,7 @@ use crate::pipeline::broad_phase::{
 };
 use crate::pipeline::narrow_phase::{InteractionGraph, NarrowPhase};
 use crate::pipeline::object::{CollisionGroupsPairFilter, CollisionObjectRef, CollisionObjectSet};
+use crate::utils::MaybeSync;
 
 struct CollisionWorldInterferenceHandler<'a, 'b, N, Objects, Filter>
 where
@@ -112,7 +113,7 @@ pub fn perform_narrow_phase<N, Objects>(
     interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
 ) where
     N: RealField,
-    Objects: CollisionObjectSet<N>,
+    Objects: CollisionObjectSet<N> + MaybeSync,
 {
     narrow_phase.update(interactions, objects);
 }
@@ -132,7 +133,7 @@ pub fn perform_all_pipeline<'a, N, Objects>(
     >,
 ) where
     N: RealField,
-    Objects: CollisionObjectSet<N>,
+    Objects: CollisionObjectSet<N> + MaybeSync,
 {
     perform_broad_phase(
         objects,
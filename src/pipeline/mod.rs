@@ -5,9 +5,11 @@ pub use self::glue::*;
 pub use self::narrow_phase::*;
 pub use self::object::*;
 pub use self::world::*;
+pub use self::world_snapshot::*;
 
 pub mod broad_phase;
 pub mod glue;
 pub mod narrow_phase;
 pub mod object;
 pub mod world;
+pub mod world_snapshot;
@@ -1,5 +1,7 @@
 use crate::math::{Isometry, Point};
-use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
 use crate::query::{
     Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
     NeighborhoodGeometry,
@@ -141,4 +143,8 @@ impl<N: RealField> ContactManifoldGenerator<N> for BallConvexPolyhedronManifoldG
             self.do_generate(m2, b, proc2, m1, a, proc1, prediction, manifold)
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -1,12 +1,14 @@
 use crate::math::Isometry;
 use crate::pipeline::narrow_phase::{
-    ContactDispatcher, ContactManifoldGenerator, ConvexPolyhedronConvexPolyhedronManifoldGenerator,
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+    ConvexPolyhedronConvexPolyhedronManifoldGenerator,
 };
 use crate::query::{ContactManifold, ContactPrediction, ContactPreprocessor};
 use crate::shape::{Capsule, Shape};
 use na::{self, RealField};
 
 /// Collision detector between a concave shape and another shape.
+#[derive(Clone)]
 pub struct CapsuleCapsuleManifoldGenerator<N: RealField> {
     // FIXME: use a dedicated segment-segment algorithm instead.
     sub_detector: ConvexPolyhedronConvexPolyhedronManifoldGenerator<N>,
@@ -73,4 +75,8 @@ impl<N: RealField> ContactManifoldGenerator<N> for CapsuleCapsuleManifoldGenerat
             false
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
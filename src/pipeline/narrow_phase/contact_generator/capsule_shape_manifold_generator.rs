@@ -99,4 +99,16 @@ impl<N: RealField> ContactManifoldGenerator<N> for CapsuleShapeManifoldGenerator
 
         return false;
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        let sub_detector = match &self.sub_detector {
+            Some(detector) => Some(detector.clone_algorithm()?),
+            None => None,
+        };
+
+        Some(Box::new(CapsuleShapeManifoldGenerator {
+            sub_detector,
+            flip: self.flip,
+        }))
+    }
 }
@@ -9,11 +9,12 @@ use crate::query::{
 use crate::shape::{CompositeShape, Shape};
 use crate::utils::DeterministicState;
 use na::RealField;
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::HashMap;
 
 /// Collision detector between a concave shape and another shape.
 pub struct CompositeShapeCompositeShapeManifoldGenerator<N> {
-    sub_detectors: HashMap<(usize, usize), (ContactAlgorithm<N>, usize), DeterministicState>,
+    sub_detectors:
+        HashMap<(usize, usize), (ContactAlgorithm<N>, usize, (usize, usize)), DeterministicState>,
     interferences: Vec<(usize, usize)>,
     timestamp: usize,
 }
@@ -60,22 +61,31 @@ impl<N: RealField> CompositeShapeCompositeShapeManifoldGenerator<N> {
         }
 
         for id in self.interferences.drain(..) {
-            match self.sub_detectors.entry(id) {
-                Entry::Occupied(mut entry) => {
-                    entry.get_mut().1 = self.timestamp;
+            let revisions = (g1.part_revision(id.0), g2.part_revision(id.1));
+            let up_to_date = match self.sub_detectors.get_mut(&id) {
+                Some(detector) if detector.2 == revisions => {
+                    detector.1 = self.timestamp;
+                    true
                 }
-                Entry::Vacant(entry) => {
-                    let mut new_detector = None;
+                _ => false,
+            };
 
-                    g1.map_part_at(id.0, &Isometry::identity(), &mut |_, g1| {
-                        g2.map_part_at(id.1, &Isometry::identity(), &mut |_, g2| {
-                            new_detector = dispatcher.get_contact_algorithm(g1, g2)
-                        });
+            if !up_to_date {
+                // One of the parts is new, or has been replaced since the detector was created.
+                let mut new_detector = None;
+
+                g1.map_part_at(id.0, &Isometry::identity(), &mut |_, g1| {
+                    g2.map_part_at(id.1, &Isometry::identity(), &mut |_, g2| {
+                        new_detector = dispatcher.get_contact_algorithm(g1, g2)
                     });
+                });
 
-                    if let Some(new_detector) = new_detector {
-                        let _ = entry.insert((new_detector, self.timestamp));
-                    }
+                if let Some(new_detector) = new_detector {
+                    let _ = self
+                        .sub_detectors
+                        .insert(id, (new_detector, self.timestamp, revisions));
+                } else {
+                    let _ = self.sub_detectors.remove(&id);
                 }
             }
         }
@@ -142,4 +152,20 @@ impl<N: RealField> ContactManifoldGenerator<N>
             false
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        let mut sub_detectors =
+            HashMap::with_capacity_and_hasher(self.sub_detectors.capacity(), DeterministicState);
+
+        for (key, (detector, timestamp, revisions)) in &self.sub_detectors {
+            let _ =
+                sub_detectors.insert(*key, (detector.clone_algorithm()?, *timestamp, *revisions));
+        }
+
+        Some(Box::new(CompositeShapeCompositeShapeManifoldGenerator {
+            sub_detectors,
+            interferences: self.interferences.clone(),
+            timestamp: self.timestamp,
+        }))
+    }
 }
@@ -10,11 +10,11 @@ use crate::query::{
 use crate::shape::{CompositeShape, Shape};
 use crate::utils::DeterministicState;
 use na::{self, RealField};
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::HashMap;
 
 /// Collision detector between a concave shape and another shape.
 pub struct CompositeShapeShapeManifoldGenerator<N: RealField> {
-    sub_detectors: HashMap<usize, (ContactAlgorithm<N>, usize), DeterministicState>,
+    sub_detectors: HashMap<usize, (ContactAlgorithm<N>, usize, usize), DeterministicState>,
     interferences: Vec<usize>,
     flip: bool,
     timestamp: usize,
@@ -57,22 +57,33 @@ impl<N: RealField> CompositeShapeShapeManifoldGenerator<N> {
         }
 
         for i in self.interferences.drain(..) {
-            match self.sub_detectors.entry(i) {
-                Entry::Occupied(mut entry) => entry.get_mut().1 = self.timestamp,
-                Entry::Vacant(entry) => {
-                    let mut new_detector = None;
-
-                    g1.map_part_at(i, &Isometry::identity(), &mut |_, g1| {
-                        if flip {
-                            new_detector = dispatcher.get_contact_algorithm(g2, g1)
-                        } else {
-                            new_detector = dispatcher.get_contact_algorithm(g1, g2)
-                        }
-                    });
-
-                    if let Some(new_detector) = new_detector {
-                        let _ = entry.insert((new_detector, self.timestamp));
+            let revision = g1.part_revision(i);
+            let up_to_date = match self.sub_detectors.get_mut(&i) {
+                Some(detector) if detector.2 == revision => {
+                    detector.1 = self.timestamp;
+                    true
+                }
+                _ => false,
+            };
+
+            if !up_to_date {
+                // The part is new, or it has been replaced since its detector was created.
+                let mut new_detector = None;
+
+                g1.map_part_at(i, &Isometry::identity(), &mut |_, g1| {
+                    if flip {
+                        new_detector = dispatcher.get_contact_algorithm(g2, g1)
+                    } else {
+                        new_detector = dispatcher.get_contact_algorithm(g1, g2)
                     }
+                });
+
+                if let Some(new_detector) = new_detector {
+                    let _ = self
+                        .sub_detectors
+                        .insert(i, (new_detector, self.timestamp, revision));
+                } else {
+                    let _ = self.sub_detectors.remove(&i);
                 }
             }
         }
@@ -153,4 +164,21 @@ impl<N: RealField> ContactManifoldGenerator<N> for CompositeShapeShapeManifoldGe
         res.set_tracking_mode(ContactTrackingMode::FeatureBased);
         res
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        let mut sub_detectors =
+            HashMap::with_capacity_and_hasher(self.sub_detectors.capacity(), DeterministicState);
+
+        for (key, (detector, timestamp, revision)) in &self.sub_detectors {
+            let _ =
+                sub_detectors.insert(*key, (detector.clone_algorithm()?, *timestamp, *revision));
+        }
+
+        Some(Box::new(CompositeShapeShapeManifoldGenerator {
+            sub_detectors,
+            interferences: self.interferences.clone(),
+            flip: self.flip,
+            timestamp: self.timestamp,
+        }))
+    }
 }
@@ -33,6 +33,14 @@ pub trait ContactManifoldGenerator<N: RealField>: Any + Send + Sync {
     fn init_manifold(&self) -> ContactManifold<N> {
         ContactManifold::new()
     }
+
+    /// Duplicates this contact manifold generator together with all its cached state.
+    ///
+    /// Returns `None` if this generator cannot be duplicated, in which case a new generator is
+    /// requested from the contact dispatcher instead.
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        None
+    }
 }
 
 pub type ContactAlgorithm<N> = Box<dyn ContactManifoldGenerator<N>>;
@@ -1,5 +1,7 @@
 use crate::math::{Isometry, Vector};
-use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
 use crate::query::algorithms::gjk::GJKResult;
 use crate::query::algorithms::VoronoiSimplex;
 use crate::query::{self, Contact, ContactManifold, ContactPrediction, ContactPreprocessor};
@@ -165,4 +167,8 @@ impl<N: RealField> ContactManifoldGenerator<N>
             false
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -1,16 +1,19 @@
 #[cfg(feature = "dim3")]
-use crate::pipeline::narrow_phase::TriMeshTriMeshManifoldGenerator;
+use crate::pipeline::narrow_phase::{
+    TriMeshTriMeshManifoldGenerator, VoxelsShapeManifoldGenerator,
+};
 use crate::pipeline::{
     BallBallManifoldGenerator, BallConvexPolyhedronManifoldGenerator,
     CapsuleCapsuleManifoldGenerator, CapsuleShapeManifoldGenerator,
     CompositeShapeCompositeShapeManifoldGenerator, CompositeShapeShapeManifoldGenerator,
     ContactAlgorithm, ContactDispatcher, ConvexPolyhedronConvexPolyhedronManifoldGenerator,
     HeightFieldShapeManifoldGenerator, PlaneBallManifoldGenerator,
-    PlaneConvexPolyhedronManifoldGenerator,
+    PlaneConvexPolyhedronManifoldGenerator, RoundShapeShapeManifoldGenerator,
+    SDFShapeSupportMapManifoldGenerator, SupportMapSupportMapManifoldGenerator,
 };
+use crate::shape::{Ball, Capsule, HeightField, Plane, SDFShape, Shape};
 #[cfg(feature = "dim3")]
-use crate::shape::TriMesh;
-use crate::shape::{Ball, Capsule, HeightField, Plane, Shape};
+use crate::shape::{TriMesh, Voxels};
 use na::RealField;
 
 /// Collision dispatcher for shapes defined by `ncollide_entities`.
@@ -35,29 +38,54 @@ impl<N: RealField> ContactDispatcher<N> for DefaultContactDispatcher {
         let b_is_plane = b.is_shape::<Plane<N>>();
         let a_is_capsule = a.is_shape::<Capsule<N>>();
         let b_is_capsule = b.is_shape::<Capsule<N>>();
+        let a_is_round_shape = a.is_round_shape();
+        let b_is_round_shape = b.is_round_shape();
         let a_is_heightfield = a.is_shape::<HeightField<N>>();
         let b_is_heightfield = b.is_shape::<HeightField<N>>();
+        let a_is_sdf = a.is_shape::<SDFShape<N>>();
+        let b_is_sdf = b.is_shape::<SDFShape<N>>();
 
         #[cfg(feature = "dim3")]
         {
             let a_is_trimesh = a.is_shape::<TriMesh<N>>();
             let b_is_trimesh = b.is_shape::<TriMesh<N>>();
 
+            let a_is_voxels = a.is_shape::<Voxels<N>>();
+            let b_is_voxels = b.is_shape::<Voxels<N>>();
+
             if a_is_trimesh && b_is_trimesh {
                 return Some(Box::new(TriMeshTriMeshManifoldGenerator::<N>::new()));
             }
+
+            if a_is_voxels || b_is_voxels {
+                return Some(Box::new(VoxelsShapeManifoldGenerator::<N>::new(
+                    b_is_voxels,
+                )));
+            }
         }
 
         if a_is_heightfield || b_is_heightfield {
             return Some(Box::new(HeightFieldShapeManifoldGenerator::<N>::new(
                 b_is_heightfield,
             )));
+        } else if a_is_sdf && b.is_support_map() {
+            Some(Box::new(SDFShapeSupportMapManifoldGenerator::<N>::new(
+                false,
+            )))
+        } else if b_is_sdf && a.is_support_map() {
+            Some(Box::new(SDFShapeSupportMapManifoldGenerator::<N>::new(
+                true,
+            )))
         } else if a_is_capsule && b_is_capsule {
             Some(Box::new(CapsuleCapsuleManifoldGenerator::<N>::new()))
         } else if a_is_capsule || b_is_capsule {
             Some(Box::new(CapsuleShapeManifoldGenerator::<N>::new(
                 b_is_capsule,
             )))
+        } else if a_is_round_shape || b_is_round_shape {
+            Some(Box::new(RoundShapeShapeManifoldGenerator::<N>::new(
+                b_is_round_shape,
+            )))
         } else if a_is_ball && b_is_ball {
             Some(Box::new(BallBallManifoldGenerator::<N>::new()))
         } else if a_is_plane && b_is_ball {
@@ -79,6 +107,9 @@ impl<N: RealField> ContactDispatcher<N> for DefaultContactDispatcher {
         } else if a.is_convex_polyhedron() && b.is_convex_polyhedron() {
             let gen = ConvexPolyhedronConvexPolyhedronManifoldGenerator::new();
             Some(Box::new(gen))
+        } else if a.is_support_map() && b.is_support_map() {
+            let gen = SupportMapSupportMapManifoldGenerator::new();
+            Some(Box::new(gen))
         } else if a.is_composite_shape() && b.is_composite_shape() {
             Some(Box::new(
                 CompositeShapeCompositeShapeManifoldGenerator::<N>::new(),
@@ -156,4 +156,19 @@ impl<N: RealField> ContactManifoldGenerator<N> for HeightFieldShapeManifoldGener
     //        res.set_tracking_mode(ContactTrackingMode::FeatureBased);
     //        res
     //    }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        let mut sub_detectors =
+            HashMap::with_capacity_and_hasher(self.sub_detectors.capacity(), DeterministicState);
+
+        for (key, (detector, timestamp)) in &self.sub_detectors {
+            let _ = sub_detectors.insert(*key, (detector.clone_algorithm()?, *timestamp));
+        }
+
+        Some(Box::new(HeightFieldShapeManifoldGenerator {
+            sub_detectors,
+            flip: self.flip,
+            timestamp: self.timestamp,
+        }))
+    }
 }
@@ -14,8 +14,13 @@ pub use self::default_contact_dispatcher::DefaultContactDispatcher;
 pub use self::heightfield_shape_manifold_generator::HeightFieldShapeManifoldGenerator;
 pub use self::plane_ball_manifold_generator::PlaneBallManifoldGenerator;
 pub use self::plane_convex_polyhedron_manifold_generator::PlaneConvexPolyhedronManifoldGenerator;
+pub use self::round_shape_shape_manifold_generator::RoundShapeShapeManifoldGenerator;
+pub use self::sdf_shape_support_map_manifold_generator::SDFShapeSupportMapManifoldGenerator;
+pub use self::support_map_support_map_manifold_generator::SupportMapSupportMapManifoldGenerator;
 #[cfg(feature = "dim3")]
 pub use self::trimesh_trimesh_manifold_generator::TriMeshTriMeshManifoldGenerator;
+#[cfg(feature = "dim3")]
+pub use self::voxels_shape_manifold_generator::VoxelsShapeManifoldGenerator;
 
 // // FIXME: un-hide this and move everything to a folder.
 mod ball_ball_manifold_generator;
@@ -31,5 +36,10 @@ mod default_contact_dispatcher;
 mod heightfield_shape_manifold_generator;
 mod plane_ball_manifold_generator;
 mod plane_convex_polyhedron_manifold_generator;
+mod round_shape_shape_manifold_generator;
+mod sdf_shape_support_map_manifold_generator;
+mod support_map_support_map_manifold_generator;
 #[cfg(feature = "dim3")]
 mod trimesh_trimesh_manifold_generator;
+#[cfg(feature = "dim3")]
+mod voxels_shape_manifold_generator;
@@ -1,5 +1,7 @@
 use crate::math::{Isometry, Point};
-use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
 use crate::query::{
     Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
     NeighborhoodGeometry,
@@ -103,4 +105,8 @@ impl<N: RealField> ContactManifoldGenerator<N> for PlaneBallManifoldGenerator<N>
             Self::do_update_to(m2, g2, proc2, m1, g1, proc1, prediction, manifold, true)
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -1,5 +1,7 @@
 use crate::math::{Isometry, Point};
-use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
 use crate::query::{
     Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
     NeighborhoodGeometry,
@@ -122,4 +124,8 @@ impl<N: RealField> ContactManifoldGenerator<N> for PlaneConvexPolyhedronManifold
             )
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -0,0 +1,126 @@
+use crate::math::Isometry;
+use crate::pipeline::{ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator};
+use crate::query::{ContactManifold, ContactPrediction, ContactPreprocessor};
+use crate::shape::{RoundShapeContactPreprocessor, Shape};
+use na::RealField;
+
+/// Collision detector between a round shape and another shape.
+///
+/// Contacts are computed between the inner shape of the round shape and the other shape, using
+/// the contact manifold generator selected by the dispatcher for those shapes. Those contacts are
+/// then shifted by the radius of the round shape.
+pub struct RoundShapeShapeManifoldGenerator<N: RealField> {
+    sub_detector: Option<ContactAlgorithm<N>>,
+    flip: bool,
+}
+
+impl<N: RealField> RoundShapeShapeManifoldGenerator<N> {
+    /// Creates a new collision detector between a round shape and another shape.
+    ///
+    /// If `flip` is `true`, the round shape is expected to be the second shape.
+    pub fn new(flip: bool) -> RoundShapeShapeManifoldGenerator<N> {
+        RoundShapeShapeManifoldGenerator {
+            sub_detector: None,
+            flip,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn do_update(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<N>,
+        m1: &Isometry<N>,
+        inner1: &dyn Shape<N>,
+        radius1: N,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        m2: &Isometry<N>,
+        g2: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+        flip: bool,
+    ) -> bool {
+        let mut prediction = prediction.clone();
+        let new_linear_prediction = prediction.linear() + radius1;
+        prediction.set_linear(new_linear_prediction);
+
+        if self.sub_detector.is_none() {
+            self.sub_detector = if flip {
+                dispatcher.get_contact_algorithm(g2, inner1)
+            } else {
+                dispatcher.get_contact_algorithm(inner1, g2)
+            }
+        }
+
+        let dilation = RoundShapeContactPreprocessor::new(radius1);
+
+        match &mut self.sub_detector {
+            Some(sub_detector) if flip => sub_detector.generate_contacts(
+                dispatcher,
+                m2,
+                g2,
+                proc2,
+                m1,
+                inner1,
+                Some(&(proc1, &dilation)),
+                &prediction,
+                manifold,
+            ),
+            Some(sub_detector) => sub_detector.generate_contacts(
+                dispatcher,
+                m1,
+                inner1,
+                Some(&(proc1, &dilation)),
+                m2,
+                g2,
+                proc2,
+                &prediction,
+                manifold,
+            ),
+            None => false,
+        }
+    }
+}
+
+impl<N: RealField> ContactManifoldGenerator<N> for RoundShapeShapeManifoldGenerator<N> {
+    fn generate_contacts(
+        &mut self,
+        d: &dyn ContactDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        if !self.flip {
+            if let Some((inner, radius)) = a.as_round_shape() {
+                return self.do_update(
+                    d, ma, inner, radius, proc1, mb, b, proc2, prediction, manifold, false,
+                );
+            }
+        } else {
+            if let Some((inner, radius)) = b.as_round_shape() {
+                return self.do_update(
+                    d, mb, inner, radius, proc2, ma, a, proc1, prediction, manifold, true,
+                );
+            }
+        }
+
+        false
+    }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        let sub_detector = match &self.sub_detector {
+            Some(detector) => Some(detector.clone_algorithm()?),
+            None => None,
+        };
+
+        Some(Box::new(RoundShapeShapeManifoldGenerator {
+            sub_detector,
+            flip: self.flip,
+        }))
+    }
+}
@@ -0,0 +1,200 @@
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
+use crate::query::{
+    Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
+    NeighborhoodGeometry,
+};
+#[cfg(feature = "dim3")]
+use crate::shape::ConvexHull as PolyhedronWithVertices;
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon as PolyhedronWithVertices;
+use crate::shape::{Ball, FeatureId, SDFShape, Shape};
+use na::{self, RealField, Unit};
+use std::marker::PhantomData;
+
+/// Collision detector between a signed distance field and a support-mapped shape.
+///
+/// The support-mapped shape is sampled at its vertices if it is a convex hull (or a convex
+/// polygon in 2D), at its center if it is a ball, and at its support points toward a fixed set
+/// of directions otherwise. One contact is generated for each sample close enough to the
+/// surface of the signed distance field.
+#[derive(Clone)]
+pub struct SDFShapeSupportMapManifoldGenerator<N: RealField> {
+    phantom: PhantomData<N>,
+    flip: bool,
+}
+
+impl<N: RealField> SDFShapeSupportMapManifoldGenerator<N> {
+    /// Creates a new persistent collision detector between a signed distance field and a
+    /// support-mapped shape.
+    ///
+    /// If `flip` is `true`, the signed distance field is expected to be the second shape.
+    #[inline]
+    pub fn new(flip: bool) -> SDFShapeSupportMapManifoldGenerator<N> {
+        SDFShapeSupportMapManifoldGenerator {
+            phantom: PhantomData,
+            flip,
+        }
+    }
+
+    fn do_generate(
+        &mut self,
+        m1: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        m2: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        let sdf = match a.as_shape::<SDFShape<N>>() {
+            Some(sdf) => sdf,
+            None => return false,
+        };
+
+        // The samples as local points on `b`, with their features, neighborhoods and dilations.
+        let mut samples = Vec::new();
+
+        if let Some(ball) = b.as_shape::<Ball<N>>() {
+            samples.push((
+                Point::origin(),
+                FeatureId::Face(0),
+                NeighborhoodGeometry::Point,
+                ball.radius(),
+            ));
+        } else if let Some(poly) = b.as_shape::<PolyhedronWithVertices<N>>() {
+            for (i, pt) in poly.points().iter().enumerate() {
+                samples.push((
+                    *pt,
+                    FeatureId::Vertex(i),
+                    NeighborhoodGeometry::Point,
+                    N::zero(),
+                ));
+            }
+        } else if let Some(sm) = b.as_support_map() {
+            let cp = b.as_convex_polyhedron();
+            let tolerance = N::default_epsilon() * na::convert(100.0);
+
+            for dir in sample_directions() {
+                let pt = sm.support_point_toward(&Isometry::identity(), &dir);
+
+                if samples
+                    .iter()
+                    .any(|s: &(Point<N>, _, _, _)| na::distance_squared(&s.0, &pt) <= tolerance)
+                {
+                    continue;
+                }
+
+                let (feature, geom) = match cp {
+                    // We only care about the vertices of polyhedra.
+                    Some(cp) => (
+                        cp.support_feature_id_toward(&dir),
+                        NeighborhoodGeometry::Point,
+                    ),
+                    // The shape is assumed to be smooth.
+                    None => (FeatureId::Face(0), NeighborhoodGeometry::Plane(dir)),
+                };
+
+                samples.push((pt, feature, geom, N::zero()));
+            }
+        } else {
+            return false;
+        }
+
+        for (local2, f2, geom2, dilation2) in samples {
+            let ls_pt = m1.inverse_transform_point(&(m2 * local2));
+            let dist = sdf.distance_at(&ls_pt);
+            let depth = dilation2 - dist;
+
+            if depth < -prediction.linear() {
+                continue;
+            }
+
+            let local_n1 = match Unit::try_new(sdf.gradient_at(&ls_pt), N::default_epsilon()) {
+                Some(n) => n,
+                None => continue,
+            };
+            let local1 = ls_pt - *local_n1 * dist;
+            let normal = m1 * local_n1;
+            let world1 = m1 * local1;
+            let world2 = m2 * local2 - *normal * dilation2;
+            let mut kinematic = ContactKinematic::new();
+
+            if !self.flip {
+                let contact = Contact::new(world1, world2, normal, depth);
+                kinematic.set_approx1(
+                    FeatureId::Face(0),
+                    local1,
+                    NeighborhoodGeometry::Plane(local_n1),
+                );
+                kinematic.set_approx2(f2, local2, geom2);
+                kinematic.set_dilation2(dilation2);
+                let _ = manifold.push(contact, kinematic, local1, proc1, proc2);
+            } else {
+                let contact = Contact::new(world2, world1, -normal, depth);
+                kinematic.set_approx1(f2, local2, geom2);
+                kinematic.set_dilation1(dilation2);
+                kinematic.set_approx2(
+                    FeatureId::Face(0),
+                    local1,
+                    NeighborhoodGeometry::Plane(local_n1),
+                );
+                let _ = manifold.push(contact, kinematic, local2, proc2, proc1);
+            }
+        }
+
+        true
+    }
+}
+
+// The directions toward the vertices, the middle of the edges, and the center of the faces of
+// a cube (or a square in 2D), vertices first.
+fn sample_directions<N: RealField>() -> Vec<Unit<Vector<N>>> {
+    let mut dirs = Vec::new();
+
+    for nonzeros in (1..=DIM).rev() {
+        for code in 0..3usize.pow(DIM as u32) {
+            let mut dir = Vector::<N>::zeros();
+            let mut rem = code;
+
+            for k in 0..DIM {
+                dir[k] = na::convert((rem % 3) as f64 - 1.0);
+                rem /= 3;
+            }
+
+            if dir.iter().filter(|e: &&N| !e.is_zero()).count() == nonzeros {
+                dirs.push(Unit::new_normalize(dir));
+            }
+        }
+    }
+
+    dirs
+}
+
+impl<N: RealField> ContactManifoldGenerator<N> for SDFShapeSupportMapManifoldGenerator<N> {
+    fn generate_contacts(
+        &mut self,
+        _: &dyn ContactDispatcher<N>,
+        m1: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        m2: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        if !self.flip {
+            self.do_generate(m1, a, proc1, m2, b, proc2, prediction, manifold)
+        } else {
+            self.do_generate(m2, b, proc2, m1, a, proc1, prediction, manifold)
+        }
+    }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::math::{Isometry, Vector};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
+use crate::query::algorithms::gjk::GJKResult;
+use crate::query::algorithms::VoronoiSimplex;
+use crate::query::{
+    self, Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
+    NeighborhoodGeometry,
+};
+use crate::shape::{FeatureId, Shape};
+use na::{RealField, Unit};
+
+/// Persistent collision detector between two support-mapped shapes, one of which at least is
+/// not a convex polyhedron.
+///
+/// This generates a single contact point, which is exact for smooth shapes.
+#[derive(Clone)]
+pub struct SupportMapSupportMapManifoldGenerator<N: RealField> {
+    simplex: VoronoiSimplex<N>,
+    last_gjk_dir: Option<Unit<Vector<N>>>,
+}
+
+impl<N: RealField> SupportMapSupportMapManifoldGenerator<N> {
+    /// Creates a new persistent collision detector between two support-mapped shapes.
+    pub fn new() -> Self {
+        SupportMapSupportMapManifoldGenerator {
+            simplex: VoronoiSimplex::new(),
+            last_gjk_dir: None,
+        }
+    }
+}
+
+// The feature of `g` with a normal cone containing the local direction `local_n`, and its
+// neighborhood.
+fn local_approx<N: RealField>(
+    g: &dyn Shape<N>,
+    local_n: &Unit<Vector<N>>,
+) -> (FeatureId, NeighborhoodGeometry<N>) {
+    if let Some(cp) = g.as_convex_polyhedron() {
+        let feature = cp.support_feature_id_toward(local_n);
+
+        match feature {
+            #[cfg(feature = "dim3")]
+            FeatureId::Edge(..) => {
+                let edge = cp.edge(feature);
+
+                if let Some(dir) = Unit::try_new(edge.1 - edge.0, N::default_epsilon()) {
+                    return (feature, NeighborhoodGeometry::Line(dir));
+                }
+            }
+            FeatureId::Vertex(..) => return (feature, NeighborhoodGeometry::Point),
+            _ => {}
+        }
+
+        (feature, NeighborhoodGeometry::Plane(*local_n))
+    } else {
+        // The shape is assumed to be smooth so the contact point is approximated by the tangent
+        // plane.
+        (FeatureId::Face(0), NeighborhoodGeometry::Plane(*local_n))
+    }
+}
+
+impl<N: RealField> ContactManifoldGenerator<N> for SupportMapSupportMapManifoldGenerator<N> {
+    fn generate_contacts(
+        &mut self,
+        _: &dyn ContactDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        if let (Some(sma), Some(smb)) = (a.as_support_map(), b.as_support_map()) {
+            let contact = query::contact_support_map_support_map_with_params(
+                ma,
+                sma,
+                mb,
+                smb,
+                prediction.linear(),
+                &mut self.simplex,
+                self.last_gjk_dir,
+            );
+
+            match contact {
+                GJKResult::ClosestPoints(world1, world2, dir) => {
+                    self.last_gjk_dir = Some(dir);
+                    let contact = Contact::new_wo_depth(world1, world2, dir);
+                    let local1 = ma.inverse_transform_point(&world1);
+                    let local2 = mb.inverse_transform_point(&world2);
+                    let n1 = Unit::new_unchecked(ma.inverse_transform_vector(&dir));
+                    let n2 = Unit::new_unchecked(mb.inverse_transform_vector(&-*dir));
+                    let (f1, approx1) = local_approx(a, &n1);
+                    let (f2, approx2) = local_approx(b, &n2);
+                    let mut kinematic = ContactKinematic::new();
+
+                    kinematic.set_approx1(f1, local1, approx1);
+                    kinematic.set_approx2(f2, local2, approx2);
+                    let _ = manifold.push(contact, kinematic, local1, proc1, proc2);
+                }
+                GJKResult::NoIntersection(dir) => self.last_gjk_dir = Some(dir),
+                _ => {}
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
+}
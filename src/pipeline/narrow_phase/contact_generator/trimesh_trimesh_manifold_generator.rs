@@ -1,5 +1,7 @@
 use crate::math::{Isometry, Vector};
-use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+};
 use crate::query::{
     self, visitors::AABBSetsInterferencesCollector, Contact, ContactKinematic, ContactManifold,
     ContactPrediction, ContactPreprocessor, ContactTrackingMode, NeighborhoodGeometry,
@@ -12,6 +14,7 @@ use na::{self, RealField, Unit};
 use std::mem;
 
 /// Collision detector between a concave shape and another shape.
+#[derive(Clone)]
 pub struct TriMeshTriMeshManifoldGenerator<N: RealField> {
     clip_cache: ClippingCache<N>,
     new_contacts: Vec<(Contact<N>, FeatureId, FeatureId)>,
@@ -65,7 +68,19 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
             m12 * pts2[face2.indices.z],
         );
 
-        if let (Some(n1), Some(n2)) = (face1.normal, face2.normal) {
+        // Locates the contact points on both triangles.
+        let coords = BarycentricCoordinatesPreprocessor {
+            m1,
+            mesh1,
+            i1,
+            m2,
+            mesh2,
+            i2,
+        };
+        let proc1 = (proc1, &coords);
+        let proc2 = (proc2, &coords);
+
+        if let (Some(n1), Some(n2)) = (mesh1.face_normal(i1), mesh2.face_normal(i2)) {
             let n2 = m12 * n2;
 
             /*
@@ -197,7 +212,7 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
 
                 // If we reached this point, no separating axis was found: the triangles intersect.
                 if let (Some(side_normals1), Some(side_normals2)) =
-                    (face1.side_normals.as_ref(), face2.side_normals.as_ref())
+                    (mesh1.face_side_normals(i1), mesh2.face_side_normals(i2))
                 {
                     for i in 0..3 {
                         self.convex_feature1.vertices[i] = m1 * t1.vertices()[i];
@@ -217,7 +232,7 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                         m1 * -penetration_dir
                     };
 
-                    self.convex_feature1.normal = face1.normal.map(|n| m1 * n);
+                    self.convex_feature1.normal = Some(m1 * n1);
                     self.convex_feature1.feature_id = FeatureId::Face(i1);
 
                     // XXX: do we have to swap the vertices and edge normals too?
@@ -233,7 +248,7 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                         }
                     }
 
-                    self.convex_feature2.normal = face2.normal.map(|n| m2 * n);
+                    self.convex_feature2.normal = mesh2.face_normal(i2).map(|n| m2 * n);
                     self.convex_feature2.feature_id = FeatureId::Face(i2);
 
                     if let Some(normal_f2) = self.convex_feature2.normal.as_mut() {
@@ -262,10 +277,10 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                             c,
                             m1,
                             f1,
-                            None,
+                            Some(&coords),
                             m2,
                             f2,
-                            None,
+                            Some(&coords),
                             manifold,
                         );
                     }
@@ -326,7 +341,13 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                                         pts2[ip2],
                                         NeighborhoodGeometry::Point,
                                     );
-                                    let _ = manifold.push(contact, kinematic, p1, proc1, proc2);
+                                    let _ = manifold.push(
+                                        contact,
+                                        kinematic,
+                                        p1,
+                                        Some(&proc1),
+                                        Some(&proc2),
+                                    );
                                 }
                             }
                             (
@@ -356,7 +377,13 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                                         pts2[e2.indices.x],
                                         NeighborhoodGeometry::Line(m21 * seg2.direction().unwrap()),
                                     );
-                                    let _ = manifold.push(contact, kinematic, p1, proc1, proc2);
+                                    let _ = manifold.push(
+                                        contact,
+                                        kinematic,
+                                        p1,
+                                        Some(&proc1),
+                                        Some(&proc2),
+                                    );
                                 }
                             }
                             (
@@ -387,7 +414,13 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                                         NeighborhoodGeometry::Point,
                                     );
 
-                                    let _ = manifold.push(contact, kinematic, p1, proc1, proc2);
+                                    let _ = manifold.push(
+                                        contact,
+                                        kinematic,
+                                        p1,
+                                        Some(&proc1),
+                                        Some(&proc2),
+                                    );
                                 }
                             }
                             (SegmentPointLocation::OnEdge(_), SegmentPointLocation::OnEdge(_)) => {
@@ -413,7 +446,13 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                                         pts2[e2.indices.x],
                                         NeighborhoodGeometry::Line(m21 * seg2.direction().unwrap()),
                                     );
-                                    let _ = manifold.push(contact, kinematic, p1, proc1, proc2);
+                                    let _ = manifold.push(
+                                        contact,
+                                        kinematic,
+                                        p1,
+                                        Some(&proc1),
+                                        Some(&proc2),
+                                    );
                                 }
                             }
                         }
@@ -425,9 +464,8 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
             'vloop1: for iv in face1.indices.iter() {
                 let p1 = pts1[*iv];
 
-                for (side2, ref_pt2) in face2
-                    .side_normals
-                    .as_ref()
+                for (side2, ref_pt2) in mesh2
+                    .face_side_normals(i2)
                     .unwrap()
                     .iter()
                     .zip(t2.vertices().iter())
@@ -458,9 +496,9 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                     kinematic.set_approx2(
                         FeatureId::Face(i2),
                         pts2[face2.indices.x],
-                        NeighborhoodGeometry::Plane(face2.normal.unwrap()),
+                        NeighborhoodGeometry::Plane(mesh2.face_normal(i2).unwrap()),
                     );
-                    let _ = manifold.push(contact, kinematic, p1, proc1, proc2);
+                    let _ = manifold.push(contact, kinematic, p1, Some(&proc1), Some(&proc2));
                 }
             }
 
@@ -470,9 +508,8 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                 // Re-use the corresponding vertex from t2 instead.
                 let p2 = m12 * pts2[*iv];
 
-                for (side1, ref_pt1) in face1
-                    .side_normals
-                    .as_ref()
+                for (side1, ref_pt1) in mesh1
+                    .face_side_normals(i1)
                     .unwrap()
                     .iter()
                     .zip(t1.vertices().iter())
@@ -508,13 +545,45 @@ impl<N: RealField> TriMeshTriMeshManifoldGenerator<N> {
                         m21 * p2,
                         NeighborhoodGeometry::Point,
                     );
-                    let _ = manifold.push(contact, kinematic, proj, proc1, proc2);
+                    let _ = manifold.push(contact, kinematic, proj, Some(&proc1), Some(&proc2));
                 }
             }
         }
     }
 }
 
+// Sets the barycentric coordinates of the contact points on the two triangles they were
+// computed from.
+struct BarycentricCoordinatesPreprocessor<'a, N: RealField> {
+    m1: &'a Isometry<N>,
+    mesh1: &'a TriMesh<N>,
+    i1: usize,
+    m2: &'a Isometry<N>,
+    mesh2: &'a TriMesh<N>,
+    i2: usize,
+}
+
+impl<'a, N: RealField> ContactPreprocessor<N> for BarycentricCoordinatesPreprocessor<'a, N> {
+    fn process_contact(
+        &self,
+        c: &mut Contact<N>,
+        kinematic: &mut ContactKinematic<N>,
+        is_first: bool,
+    ) -> bool {
+        if is_first {
+            let pt = self.m1.inverse_transform_point(&c.world1);
+            let coords = self.mesh1.face_barycentric_coordinates(self.i1, &pt);
+            kinematic.set_barycentric_coordinates1(Some((self.i1, coords)));
+        } else {
+            let pt = self.m2.inverse_transform_point(&c.world2);
+            let coords = self.mesh2.face_barycentric_coordinates(self.i2, &pt);
+            kinematic.set_barycentric_coordinates2(Some((self.i2, coords)));
+        }
+
+        true
+    }
+}
+
 impl<N: RealField> ContactManifoldGenerator<N> for TriMeshTriMeshManifoldGenerator<N> {
     fn generate_contacts(
         &mut self,
@@ -568,4 +637,8 @@ impl<N: RealField> ContactManifoldGenerator<N> for TriMeshTriMeshManifoldGenerat
         res.set_tracking_mode(ContactTrackingMode::FeatureBased);
         res
     }
+
+    fn clone_algorithm(&self) -> Option<ContactAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
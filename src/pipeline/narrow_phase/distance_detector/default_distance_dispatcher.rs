@@ -0,0 +1,58 @@
+use crate::pipeline::narrow_phase::distance_detector::{
+    DistanceAlgorithm, DistanceDispatcher, ShapeShapeDistanceDetector,
+    SupportMapSupportMapDistanceDetector,
+};
+use crate::shape::{Ball, Plane, Shape};
+use na::RealField;
+
+/// Distance dispatcher for shapes defined by `ncollide_entities`.
+pub struct DefaultDistanceDispatcher {}
+
+impl DefaultDistanceDispatcher {
+    /// Creates a new basic distance dispatcher.
+    pub fn new() -> DefaultDistanceDispatcher {
+        DefaultDistanceDispatcher {}
+    }
+}
+
+impl<N: RealField> DistanceDispatcher<N> for DefaultDistanceDispatcher {
+    fn get_distance_algorithm(
+        &self,
+        a: &dyn Shape<N>,
+        b: &dyn Shape<N>,
+    ) -> Option<DistanceAlgorithm<N>> {
+        let a_is_ball = a.is_shape::<Ball<N>>();
+        let b_is_ball = b.is_shape::<Ball<N>>();
+        let a_is_plane = a.is_shape::<Plane<N>>();
+        let b_is_plane = b.is_shape::<Plane<N>>();
+
+        if a_is_ball && b_is_ball {
+            // This case has a closed-form solution: no need for persistence.
+            Some(Box::new(ShapeShapeDistanceDetector::new()))
+        } else if a_is_plane || b_is_plane {
+            let other = if a_is_plane { b } else { a };
+
+            // Planes only have closed-form solutions with support maps and the parts of composite
+            // shapes.
+            if other.is_support_map() || other.is_composite_shape() {
+                Some(Box::new(ShapeShapeDistanceDetector::new()))
+            } else {
+                None
+            }
+        } else if a.is_support_map() && b.is_support_map() {
+            Some(Box::new(SupportMapSupportMapDistanceDetector::new()))
+        } else if a.is_composite_shape() || b.is_composite_shape() {
+            let other = if a.is_composite_shape() { b } else { a };
+
+            // The parts of composite shapes only have closed-form solutions with support maps
+            // and the parts of other composite shapes.
+            if other.is_support_map() || other.is_composite_shape() {
+                Some(Box::new(ShapeShapeDistanceDetector::new()))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
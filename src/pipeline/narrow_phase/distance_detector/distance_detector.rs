@@ -0,0 +1,42 @@
+use crate::math::Isometry;
+use crate::query::ClosestPoints;
+use crate::shape::Shape;
+use na::RealField;
+use std::any::Any;
+
+/// Trait implemented by algorithms that track the closest points between two objects.
+pub trait DistanceDetector<N: RealField>: Any + Send + Sync {
+    /// Runs the closest points computation on two objects. It is assumed that the same distance
+    /// detector (the same instance) is always used with the same pair of object.
+    ///
+    /// Objects separated by a distance greater than `max_dist` are reported as
+    /// `ClosestPoints::Disjoint`.
+    fn update(
+        &mut self,
+        dispatcher: &dyn DistanceDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        max_dist: N,
+    ) -> Option<ClosestPoints<N>>;
+
+    /// Duplicates this distance detector together with all its cached state.
+    ///
+    /// Returns `None` if this detector cannot be duplicated, in which case a new detector is
+    /// requested from the distance dispatcher instead.
+    fn clone_algorithm(&self) -> Option<DistanceAlgorithm<N>> {
+        None
+    }
+}
+
+pub type DistanceAlgorithm<N> = Box<dyn DistanceDetector<N>>;
+
+pub trait DistanceDispatcher<N>: Any + Send + Sync {
+    /// Allocate a distance algorithm corresponding to the given pair of shapes.
+    fn get_distance_algorithm(
+        &self,
+        a: &dyn Shape<N>,
+        b: &dyn Shape<N>,
+    ) -> Option<DistanceAlgorithm<N>>;
+}
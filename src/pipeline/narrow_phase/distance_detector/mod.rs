@@ -0,0 +1,12 @@
+//! Persistant distance and closest points computation algorithms.
+
+pub use self::default_distance_dispatcher::DefaultDistanceDispatcher;
+pub use self::distance_detector::{DistanceAlgorithm, DistanceDetector, DistanceDispatcher};
+pub use self::shape_shape_distance_detector::ShapeShapeDistanceDetector;
+pub use self::support_map_support_map_distance_detector::SupportMapSupportMapDistanceDetector;
+
+mod default_distance_dispatcher;
+#[doc(hidden)]
+pub mod distance_detector;
+mod shape_shape_distance_detector;
+mod support_map_support_map_distance_detector;
@@ -0,0 +1,40 @@
+use crate::math::Isometry;
+use crate::pipeline::narrow_phase::{DistanceAlgorithm, DistanceDetector, DistanceDispatcher};
+use crate::query::{self, ClosestPoints};
+use crate::shape::Shape;
+use na::RealField;
+
+/// Non-persistent distance detector between any pair of shapes supported by `query::closest_points`.
+pub struct ShapeShapeDistanceDetector {}
+
+impl Clone for ShapeShapeDistanceDetector {
+    fn clone(&self) -> ShapeShapeDistanceDetector {
+        ShapeShapeDistanceDetector {}
+    }
+}
+
+impl ShapeShapeDistanceDetector {
+    /// Creates a new distance detector that recomputes the closest points from scratch at each update.
+    #[inline]
+    pub fn new() -> ShapeShapeDistanceDetector {
+        ShapeShapeDistanceDetector {}
+    }
+}
+
+impl<N: RealField> DistanceDetector<N> for ShapeShapeDistanceDetector {
+    fn update(
+        &mut self,
+        _: &dyn DistanceDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        max_dist: N,
+    ) -> Option<ClosestPoints<N>> {
+        Some(query::closest_points(ma, a, mb, b, max_dist))
+    }
+
+    fn clone_algorithm(&self) -> Option<DistanceAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
+}
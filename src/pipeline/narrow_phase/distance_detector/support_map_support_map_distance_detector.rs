@@ -0,0 +1,74 @@
+use crate::math::{Isometry, Vector};
+use crate::pipeline::narrow_phase::{DistanceAlgorithm, DistanceDetector, DistanceDispatcher};
+use crate::query::algorithms::{gjk::GJKResult, VoronoiSimplex};
+use crate::query::{self, ClosestPoints};
+use crate::shape::Shape;
+use na::RealField;
+
+/// Persistent distance detector between two shapes having a support mapping function.
+///
+/// It is based on the GJK algorithm.
+#[derive(Clone)]
+pub struct SupportMapSupportMapDistanceDetector<N: RealField> {
+    simplex: VoronoiSimplex<N>,
+    sep_axis: Option<Vector<N>>,
+}
+
+impl<N: RealField> SupportMapSupportMapDistanceDetector<N> {
+    /// Creates a new persistant distance detector between two shapes with support mapping
+    /// functions.
+    ///
+    /// It is initialized with a pre-created simplex.
+    pub fn new() -> SupportMapSupportMapDistanceDetector<N> {
+        SupportMapSupportMapDistanceDetector {
+            simplex: VoronoiSimplex::new(),
+            sep_axis: None,
+        }
+    }
+}
+
+impl<N: RealField> DistanceDetector<N> for SupportMapSupportMapDistanceDetector<N> {
+    #[inline]
+    fn update(
+        &mut self,
+        _: &dyn DistanceDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        max_dist: N,
+    ) -> Option<ClosestPoints<N>> {
+        let sma = a.as_support_map()?;
+        let smb = b.as_support_map()?;
+
+        let res = query::closest_points_support_map_support_map_with_params(
+            ma,
+            sma,
+            mb,
+            smb,
+            max_dist,
+            &mut self.simplex,
+            self.sep_axis,
+        );
+
+        match res {
+            GJKResult::ClosestPoints(pt1, pt2, dir) => {
+                self.sep_axis = Some(dir.into_inner());
+                Some(ClosestPoints::WithinMargin(pt1, pt2))
+            }
+            GJKResult::NoIntersection(dir) => {
+                self.sep_axis = Some(dir.into_inner());
+                Some(ClosestPoints::Disjoint)
+            }
+            GJKResult::Intersection => {
+                self.sep_axis = None;
+                Some(ClosestPoints::Intersecting)
+            }
+            GJKResult::Proximity(_) => unreachable!(),
+        }
+    }
+
+    fn clone_algorithm(&self) -> Option<DistanceAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
+}
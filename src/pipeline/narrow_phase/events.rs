@@ -1,12 +1,15 @@
 //! Structures for describing and storing collision-related events.
 
-use crate::query::Proximity;
+use crate::query::{ClosestPoints, Proximity};
+use na::RealField;
 use std::iter::IntoIterator;
 use std::slice::Iter;
 
 // FIXME: we want a structure where we can add elements, iterate on them, but not remove them
 // without clearing the whole structure.
 /// A set of events.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 pub struct EventPool<E> {
     events: Vec<E>,
 }
@@ -15,6 +18,8 @@ pub struct EventPool<E> {
 pub type ContactEvents<Handle> = EventPool<ContactEvent<Handle>>;
 /// A set of proximity events.
 pub type ProximityEvents<Handle> = EventPool<ProximityEvent<Handle>>;
+/// A set of distance events.
+pub type DistanceEvents<N, Handle> = EventPool<DistanceEvent<N, Handle>>;
 
 impl<E> EventPool<E> {
     /// Creates a new empty set of events.
@@ -63,6 +68,7 @@ impl<'a, E> IntoIterator for &'a EventPool<E> {
 
 #[derive(Copy, Clone, Hash, Debug)]
 /// Events occuring when two collision objects start or stop being in contact (or penetration).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ContactEvent<Handle> {
     /// Event occuring when two collision objects start being in contact.
     ///
@@ -76,6 +82,7 @@ pub enum ContactEvent<Handle> {
 
 #[derive(Copy, Clone, Debug)]
 /// Events occuring when two collision objects start or stop being in close proximity, contact, or disjoint.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProximityEvent<Handle> {
     /// The first collider to which the proximity event applies.
     pub collider1: Handle,
@@ -109,3 +116,40 @@ impl<Handle> ProximityEvent<Handle> {
         }
     }
 }
+
+#[derive(Copy, Clone, Debug)]
+/// Events occuring when the closest points between two collision objects change.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceEvent<N: RealField, Handle> {
+    /// The first collider to which the distance event applies.
+    pub collider1: Handle,
+    /// The second collider to which the distance event applies.
+    pub collider2: Handle,
+    /// The previous closest points between the two collision objects.
+    pub prev_status: ClosestPoints<N>,
+    /// The new closest points between the two collision objects.
+    pub new_status: ClosestPoints<N>,
+}
+
+impl<N: RealField, Handle> DistanceEvent<N, Handle> {
+    /// Instantiates a new distance event.
+    ///
+    /// Panics if `prev_status` is equal to `new_status`.
+    pub fn new(
+        collider1: Handle,
+        collider2: Handle,
+        prev_status: ClosestPoints<N>,
+        new_status: ClosestPoints<N>,
+    ) -> Self {
+        assert_ne!(
+            prev_status, new_status,
+            "The previous and new status of a distance event must not be the same."
+        );
+        Self {
+            collider1,
+            collider2,
+            prev_status,
+            new_status,
+        }
+    }
+}
@@ -2,9 +2,11 @@ use na::RealField;
 use petgraph::graph::{NodeIndex, UnGraph};
 use petgraph::visit::EdgeRef;
 
-use crate::pipeline::narrow_phase::{ContactAlgorithm, ProximityAlgorithm, ProximityDetector};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, DistanceAlgorithm, DistanceDetector, ProximityAlgorithm, ProximityDetector,
+};
 use crate::pipeline::object::CollisionObjectHandle;
-use crate::query::{ContactManifold, Proximity};
+use crate::query::{ClosestPoints, ContactManifold, Proximity};
 use petgraph::prelude::EdgeIndex;
 use petgraph::Direction;
 
@@ -25,6 +27,12 @@ pub enum Interaction<N: RealField> {
     /// Generated only for pairs of collision objects with at least one configured
     /// with a `GeometricQueryType::Contact(..)`.
     Proximity(ProximityAlgorithm<N>, Proximity),
+    /// A tracking of the closest points between two collision objects.
+    ///
+    /// Generated only for pairs of collision objects with at least one configured
+    /// with a `GeometricQueryType::Distance(..)` and none configured with
+    /// a `GeometricQueryType::Proximity(..)`.
+    Distance(DistanceAlgorithm<N>, ClosestPoints<N>),
 }
 
 impl<N: RealField> Interaction<N> {
@@ -43,6 +51,14 @@ impl<N: RealField> Interaction<N> {
             _ => false,
         }
     }
+
+    /// Checks if this interaction is a potential distance interaction.
+    pub fn is_distance(&self) -> bool {
+        match self {
+            Interaction::Distance(..) => true,
+            _ => false,
+        }
+    }
 }
 
 /// A graph where nodes are collision objects and edges are contact or proximity algorithms.
@@ -130,6 +146,21 @@ impl<N: RealField, Handle: CollisionObjectHandle> InteractionGraph<N, Handle> {
             })
     }
 
+    /// All the distance pairs on this graph.
+    ///
+    /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pairs(
+        &self,
+        effective_only: bool,
+    ) -> impl Iterator<Item = (Handle, Handle, &dyn DistanceDetector<N>, ClosestPoints<N>)> {
+        self.interaction_pairs(effective_only)
+            .filter_map(|(h1, h2, inter)| match inter {
+                Interaction::Distance(algo, pts) => Some((h1, h2, &**algo, *pts)),
+                _ => None,
+            })
+    }
+
     /// The interaction between the two collision objects identified by their graph index.
     ///
     /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -225,6 +256,44 @@ impl<N: RealField, Handle: CollisionObjectHandle> InteractionGraph<N, Handle> {
         }
     }
 
+    /// The distance pair between the two collision objects identified by their graph index.
+    ///
+    /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pair(
+        &self,
+        id1: CollisionObjectGraphIndex,
+        id2: CollisionObjectGraphIndex,
+        effective_only: bool,
+    ) -> Option<(Handle, Handle, &dyn DistanceDetector<N>, ClosestPoints<N>)> {
+        self.interaction_pair(id1, id2, effective_only)
+            .and_then(|inter| match inter.2 {
+                Interaction::Distance(algo, pts) => Some((inter.0, inter.1, &**algo, *pts)),
+                _ => None,
+            })
+    }
+
+    /// The distance pair between the two collision objects identified by their graph index.
+    ///
+    /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pair_mut(
+        &mut self,
+        id1: CollisionObjectGraphIndex,
+        id2: CollisionObjectGraphIndex,
+    ) -> Option<(
+        Handle,
+        Handle,
+        &mut dyn DistanceDetector<N>,
+        &mut ClosestPoints<N>,
+    )> {
+        let inter = self.interaction_pair_mut(id1, id2)?;
+        match inter.2 {
+            Interaction::Distance(algo, pts) => Some((inter.0, inter.1, &mut **algo, pts)),
+            _ => None,
+        }
+    }
+
     /// All the interaction involving the collision object with graph index `id`.
     ///
     /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -299,6 +368,22 @@ impl<N: RealField, Handle: CollisionObjectHandle> InteractionGraph<N, Handle> {
             })
     }
 
+    /// All the distance pairs involving the collision object with graph index `id`.
+    ///
+    /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distances_with(
+        &self,
+        handle: CollisionObjectGraphIndex,
+        effective_only: bool,
+    ) -> impl Iterator<Item = (Handle, Handle, &dyn DistanceDetector<N>, ClosestPoints<N>)> {
+        self.interactions_with(handle, effective_only)
+            .filter_map(|(h1, h2, inter)| match inter {
+                Interaction::Distance(algo, pts) => Some((h1, h2, &**algo, *pts)),
+                _ => None,
+            })
+    }
+
     /// All the contact pairs involving the collision object with graph index `id`.
     ///
     /// Refer to the official [user guide](https://ncollide.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -397,6 +482,7 @@ impl<N: RealField, Handle: CollisionObjectHandle> InteractionGraph<N, Handle> {
                 }
             }
             Interaction::Proximity(_, prox) => *prox == Proximity::Intersecting,
+            Interaction::Distance(_, pts) => *pts != ClosestPoints::Disjoint,
         }
     }
 }
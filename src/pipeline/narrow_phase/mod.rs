@@ -1,7 +1,7 @@
 //! Persistent collision detection algorithms to compute contact points.
 
 #[cfg(feature = "dim3")]
-pub use self::contact_generator::TriMeshTriMeshManifoldGenerator;
+pub use self::contact_generator::{TriMeshTriMeshManifoldGenerator, VoxelsShapeManifoldGenerator};
 #[doc(inline)]
 pub use self::contact_generator::{
     BallBallManifoldGenerator, BallConvexPolyhedronManifoldGenerator,
@@ -10,9 +10,18 @@ pub use self::contact_generator::{
     ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
     ConvexPolyhedronConvexPolyhedronManifoldGenerator, DefaultContactDispatcher,
     HeightFieldShapeManifoldGenerator, PlaneBallManifoldGenerator,
-    PlaneConvexPolyhedronManifoldGenerator,
+    PlaneConvexPolyhedronManifoldGenerator, RoundShapeShapeManifoldGenerator,
+    SDFShapeSupportMapManifoldGenerator, SupportMapSupportMapManifoldGenerator,
+};
+#[doc(inline)]
+pub use self::distance_detector::{
+    DefaultDistanceDispatcher, DistanceAlgorithm, DistanceDetector, DistanceDispatcher,
+    ShapeShapeDistanceDetector, SupportMapSupportMapDistanceDetector,
+};
+pub use self::events::{
+    ContactEvent, ContactEvents, DistanceEvent, DistanceEvents, EventPool, ProximityEvent,
+    ProximityEvents,
 };
-pub use self::events::{ContactEvent, ContactEvents, EventPool, ProximityEvent, ProximityEvents};
 pub use self::interaction_graph::{
     CollisionObjectGraphIndex, Interaction, InteractionGraph, TemporaryInteractionIndex,
 };
@@ -26,6 +35,8 @@ pub use self::proximity_detector::{
 
 #[doc(hidden)]
 pub mod contact_generator;
+#[doc(hidden)]
+pub mod distance_detector;
 mod events;
 mod interaction_graph;
 mod narrow_phase;
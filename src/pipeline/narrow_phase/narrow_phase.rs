@@ -2,21 +2,37 @@ use na::RealField;
 use slotmap::{Key, SlotMap};
 
 use crate::pipeline::narrow_phase::{
-    ContactDispatcher, ContactEvent, ContactEvents, ContactManifoldGenerator, Interaction,
-    InteractionGraph, ProximityDetector, ProximityDispatcher, ProximityEvent, ProximityEvents,
+    ContactDispatcher, ContactEvent, ContactEvents, ContactManifoldGenerator, DistanceDetector,
+    DistanceDispatcher, DistanceEvent, DistanceEvents, Interaction, InteractionGraph,
+    ProximityDetector, ProximityDispatcher, ProximityEvent, ProximityEvents,
 };
 use crate::pipeline::object::{
     CollisionObjectHandle, CollisionObjectRef, CollisionObjectSet, GeometricQueryType,
 };
-use crate::query::{ContactId, ContactManifold, Proximity};
+use crate::query::{ClosestPoints, ContactId, ContactManifold, Proximity};
+use crate::utils::MaybeSync;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// The result of an interaction update computed in parallel, before events are emitted.
+#[cfg(feature = "parallel")]
+enum InteractionUpdate<N: RealField> {
+    Contact(bool),
+    Proximity(Option<Proximity>),
+    Distance(Option<ClosestPoints<N>>),
+}
 
 /// Collision detector dispatcher for collision objects.
 pub struct NarrowPhase<N: RealField, Handle: CollisionObjectHandle> {
-    contact_dispatcher: Box<dyn ContactDispatcher<N>>,
-    proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
-    contact_events: ContactEvents<Handle>,
-    proximity_events: ProximityEvents<Handle>,
-    id_allocator: SlotMap<ContactId, bool>,
+    pub(crate) contact_dispatcher: Box<dyn ContactDispatcher<N>>,
+    pub(crate) proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
+    pub(crate) distance_dispatcher: Box<dyn DistanceDispatcher<N>>,
+    pub(crate) contact_events: ContactEvents<Handle>,
+    pub(crate) proximity_events: ProximityEvents<Handle>,
+    pub(crate) distance_events: DistanceEvents<N, Handle>,
+    pub(crate) id_allocator: SlotMap<ContactId, bool>,
+    #[cfg(feature = "parallel")]
+    parallel: bool,
 }
 
 impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
@@ -24,16 +40,28 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
     pub fn new(
         contact_dispatcher: Box<dyn ContactDispatcher<N>>,
         proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
+        distance_dispatcher: Box<dyn DistanceDispatcher<N>>,
     ) -> NarrowPhase<N, Handle> {
         NarrowPhase {
             contact_dispatcher,
             proximity_dispatcher,
+            distance_dispatcher,
             contact_events: ContactEvents::new(),
             proximity_events: ProximityEvents::new(),
+            distance_events: DistanceEvents::new(),
             id_allocator: SlotMap::with_key(),
+            #[cfg(feature = "parallel")]
+            parallel: true,
         }
     }
 
+    /// Sets whether the interactions are updated on the rayon thread pool, which is the default,
+    /// or sequentially.
+    #[cfg(feature = "parallel")]
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel
+    }
+
     fn garbage_collect_ids(&mut self, interactions: &mut InteractionGraph<N, Handle>) {
         for interaction in interactions.0.edge_weights_mut() {
             match interaction {
@@ -44,7 +72,7 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                         }
                     }
                 }
-                Interaction::Proximity(..) => {}
+                Interaction::Proximity(..) | Interaction::Distance(..) => {}
             }
         }
 
@@ -62,6 +90,20 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         detector: &mut dyn ContactManifoldGenerator<N>,
         manifold: &mut ContactManifold<N>,
     ) {
+        let had_contacts =
+            Self::generate_contacts(&*self.contact_dispatcher, co1, co2, detector, manifold);
+        self.register_contacts(handle1, handle2, had_contacts, manifold)
+    }
+
+    // Computes the new contacts without allocating their IDs nor emitting any event.
+    // Returns `true` if the manifold contained contacts before this update.
+    fn generate_contacts(
+        dispatcher: &dyn ContactDispatcher<N>,
+        co1: &impl CollisionObjectRef<N>,
+        co2: &impl CollisionObjectRef<N>,
+        detector: &mut dyn ContactManifoldGenerator<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
         let had_contacts = manifold.len() != 0;
 
         if let Some(prediction) = co1
@@ -70,7 +112,7 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         {
             manifold.save_cache_and_clear();
             let _ = detector.generate_contacts(
-                &*self.contact_dispatcher,
+                dispatcher,
                 &co1.position(),
                 co1.shape(),
                 None,
@@ -80,16 +122,27 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                 &prediction,
                 manifold,
             );
-
-            for contact in manifold.contacts_mut() {
-                if contact.id.is_null() {
-                    contact.id = self.id_allocator.insert(false)
-                }
-            }
         } else {
             panic!("Unable to compute contact between collision objects with query types different from `GeometricQueryType::Contacts(..)`.")
         }
 
+        had_contacts
+    }
+
+    // Allocates the IDs of the new contacts and emits the contact events.
+    fn register_contacts(
+        &mut self,
+        handle1: Handle,
+        handle2: Handle,
+        had_contacts: bool,
+        manifold: &mut ContactManifold<N>,
+    ) {
+        for contact in manifold.contacts_mut() {
+            if contact.id.is_null() {
+                contact.id = self.id_allocator.insert(false)
+            }
+        }
+
         if manifold.len() == 0 {
             if had_contacts {
                 self.contact_events
@@ -142,6 +195,45 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         }
     }
 
+    // FIXME: the fact this is public is only useful for nphysics.
+    // Perhaps the event pools should not be owned by the NarrowPhase struct?
+    #[doc(hidden)]
+    pub fn emit_distance_event(
+        &mut self,
+        handle1: Handle,
+        handle2: Handle,
+        prev_pts: ClosestPoints<N>,
+        new_pts: ClosestPoints<N>,
+    ) {
+        if prev_pts != new_pts {
+            self.distance_events
+                .push(DistanceEvent::new(handle1, handle2, prev_pts, new_pts));
+        }
+    }
+
+    /// Update the specified closest points between two collision objects.
+    pub fn update_distance(
+        &mut self,
+        co1: &impl CollisionObjectRef<N>,
+        co2: &impl CollisionObjectRef<N>,
+        handle1: Handle,
+        handle2: Handle,
+        detector: &mut dyn DistanceDetector<N>,
+        curr_pts: &mut ClosestPoints<N>,
+    ) {
+        if let Some(new_pts) = detector.update(
+            &*self.distance_dispatcher,
+            &co1.position(),
+            co1.shape(),
+            &co2.position(),
+            co2.shape(),
+            co1.query_type().query_limit() + co2.query_type().query_limit(),
+        ) {
+            self.emit_distance_event(handle1, handle2, *curr_pts, new_pts);
+            *curr_pts = new_pts;
+        }
+    }
+
     /// Update the specified interaction between two collision objects.
     pub fn update_interaction(
         &mut self,
@@ -158,17 +250,48 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
             Interaction::Proximity(detector, prox) => {
                 self.update_proximity(co1, co2, handle1, handle2, &mut **detector, prox)
             }
+            Interaction::Distance(detector, pts) => {
+                self.update_distance(co1, co2, handle1, handle2, &mut **detector, pts)
+            }
         }
     }
 
     /// Updates the narrow-phase by actually computing contact points and proximities between the
     /// interactions pairs reported by the broad-phase.
     ///
-    /// This will push relevant events to `contact_events` and `proximity_events`.
+    /// This will push relevant events to `contact_events`, `proximity_events` and `distance_events`.
+    /// If the `parallel` feature is enabled, the interactions are updated on the rayon thread pool
+    /// unless this was disabled with `set_parallel`. The events are still emitted in the same order
+    /// as with a sequential update.
     pub fn update<Objects>(
         &mut self,
         interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
         objects: &Objects,
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle> + MaybeSync,
+    {
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.update_sequential(interactions, objects)
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            if self.parallel {
+                self.update_parallel(interactions, objects)
+            } else {
+                self.update_sequential(interactions, objects)
+            }
+        }
+
+        // FIXME: don't do this at each update?
+        self.garbage_collect_ids(interactions)
+    }
+
+    fn update_sequential<Objects>(
+        &mut self,
+        interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
+        objects: &Objects,
     ) where
         Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
     {
@@ -191,9 +314,102 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                 )
             }
         }
+    }
 
-        // FIXME: don't do this at each update?
-        self.garbage_collect_ids(interactions)
+    #[cfg(feature = "parallel")]
+    fn update_parallel<Objects>(
+        &mut self,
+        interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
+        objects: &Objects,
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle> + Sync,
+    {
+        let graph = &mut interactions.0;
+        let endpoints: Vec<_> = graph
+            .edge_indices()
+            .map(|eid| {
+                let (id1, id2) = graph.edge_endpoints(eid).unwrap();
+                (graph[id1], graph[id2])
+            })
+            .collect();
+
+        // Both iterators yield the edges in the order of their indices.
+        let mut to_update: Vec<_> = graph
+            .edge_weights_mut()
+            .zip(endpoints.into_iter())
+            .filter(|(_, (handle1, handle2))| {
+                let flags1 = objects.collision_object(*handle1).unwrap().update_flags();
+                let flags2 = objects.collision_object(*handle2).unwrap().update_flags();
+                flags1.needs_narrow_phase_update() || flags2.needs_narrow_phase_update()
+            })
+            .collect();
+
+        // Run the detectors in parallel, without touching the ID allocator nor the event pools.
+        let contact_dispatcher = &*self.contact_dispatcher;
+        let proximity_dispatcher = &*self.proximity_dispatcher;
+        let distance_dispatcher = &*self.distance_dispatcher;
+        let updates: Vec<_> = to_update
+            .par_iter_mut()
+            .map(|(interaction, (handle1, handle2))| {
+                let co1 = objects.collision_object(*handle1).unwrap();
+                let co2 = objects.collision_object(*handle2).unwrap();
+                let limit = co1.query_type().query_limit() + co2.query_type().query_limit();
+
+                match &mut **interaction {
+                    Interaction::Contact(detector, manifold) => {
+                        InteractionUpdate::Contact(Self::generate_contacts(
+                            contact_dispatcher,
+                            co1,
+                            co2,
+                            &mut **detector,
+                            manifold,
+                        ))
+                    }
+                    Interaction::Proximity(detector, _) => {
+                        InteractionUpdate::Proximity(detector.update(
+                            proximity_dispatcher,
+                            &co1.position(),
+                            co1.shape(),
+                            &co2.position(),
+                            co2.shape(),
+                            limit,
+                        ))
+                    }
+                    Interaction::Distance(detector, _) => {
+                        InteractionUpdate::Distance(detector.update(
+                            distance_dispatcher,
+                            &co1.position(),
+                            co1.shape(),
+                            &co2.position(),
+                            co2.shape(),
+                            limit,
+                        ))
+                    }
+                }
+            })
+            .collect();
+
+        // Allocate contact IDs and emit events sequentially so the result is deterministic.
+        for ((interaction, (handle1, handle2)), update) in to_update.into_iter().zip(updates) {
+            match (interaction, update) {
+                (Interaction::Contact(_, manifold), InteractionUpdate::Contact(had_contacts)) => {
+                    self.register_contacts(handle1, handle2, had_contacts, manifold)
+                }
+                (Interaction::Proximity(_, prox), InteractionUpdate::Proximity(new_prox)) => {
+                    if let Some(new_prox) = new_prox {
+                        self.emit_proximity_event(handle1, handle2, *prox, new_prox);
+                        *prox = new_prox;
+                    }
+                }
+                (Interaction::Distance(_, pts), InteractionUpdate::Distance(new_pts)) => {
+                    if let Some(new_pts) = new_pts {
+                        self.emit_distance_event(handle1, handle2, *pts, new_pts);
+                        *pts = new_pts;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
     }
 
     /// Handles a pair of collision objects detected as either started or stopped interacting.
@@ -243,6 +459,19 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                             );
                         }
                     }
+                    (_, GeometricQueryType::Distance(_)) | (GeometricQueryType::Distance(_), _) => {
+                        let dispatcher = &self.distance_dispatcher;
+
+                        if let Some(detector) =
+                            dispatcher.get_distance_algorithm(co1.shape(), co2.shape())
+                        {
+                            let _ = interactions.0.add_edge(
+                                id1,
+                                id2,
+                                Interaction::Distance(detector, ClosestPoints::Disjoint),
+                            );
+                        }
+                    }
                 }
             }
         } else {
@@ -271,6 +500,15 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                                 Proximity::Disjoint,
                             );
                         }
+                        Interaction::Distance(_, prev_pts) => {
+                            // Register a distance lost signal if they were not disjoint.
+                            self.emit_distance_event(
+                                handle1,
+                                handle2,
+                                prev_pts,
+                                ClosestPoints::Disjoint,
+                            );
+                        }
                     }
                 }
             }
@@ -287,9 +525,15 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         &self.proximity_events
     }
 
+    /// The set of distance events generated by this narrow-phase.
+    pub fn distance_events(&self) -> &DistanceEvents<N, Handle> {
+        &self.distance_events
+    }
+
     /// Clear the events generated by this narrow-phase.
     pub fn clear_events(&mut self) {
         self.contact_events.clear();
         self.proximity_events.clear();
+        self.distance_events.clear();
     }
 }
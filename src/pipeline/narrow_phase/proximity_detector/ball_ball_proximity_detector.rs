@@ -1,5 +1,5 @@
 use crate::math::{Isometry, Point};
-use crate::pipeline::narrow_phase::{ProximityDetector, ProximityDispatcher};
+use crate::pipeline::narrow_phase::{ProximityAlgorithm, ProximityDetector, ProximityDispatcher};
 use crate::query::{self, Proximity};
 use crate::shape::{Ball, Shape};
 use na::RealField;
@@ -41,4 +41,8 @@ impl<N: RealField> ProximityDetector<N> for BallBallProximityDetector {
             margin,
         ))
     }
+
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -9,7 +9,7 @@ use std::collections::{hash_map::Entry, HashMap};
 
 /// Proximity detector between a concave shape and another shape.
 pub struct CompositeShapeShapeProximityDetector<N> {
-    sub_detectors: HashMap<usize, ProximityAlgorithm<N>, DeterministicState>,
+    sub_detectors: HashMap<usize, (ProximityAlgorithm<N>, usize), DeterministicState>,
     to_delete: Vec<usize>,
     interferences: Vec<usize>,
     intersecting_key: usize,
@@ -50,12 +50,21 @@ impl<N: RealField> CompositeShapeShapeProximityDetector<N> {
         self.to_delete.clear();
         self.interferences.clear();
 
+        // Remove the sub detectors of the parts that were removed or replaced.
+        let nparts = g1.nparts();
+        self.sub_detectors
+            .retain(|key, detector| *key < nparts && detector.1 == g1.part_revision(*key));
+
+        if !self.sub_detectors.contains_key(&self.intersecting_key) {
+            self.intersecting_key = usize::max_value();
+        }
+
         // First, test if the previously intersecting shapes are still intersecting.
         if self.intersecting_key != usize::max_value() {
             let detector = self.sub_detectors.get_mut(&self.intersecting_key).unwrap();
             let mut prox = None;
             g1.map_part_at(self.intersecting_key, m1, &mut |m1, g1| {
-                prox = detector.update(dispatcher, m1, g1, m2, g2, margin)
+                prox = detector.0.update(dispatcher, m1, g1, m2, g2, margin)
             });
 
             match prox? {
@@ -81,7 +90,7 @@ impl<N: RealField> CompositeShapeShapeProximityDetector<N> {
                 let mut prox = None;
 
                 g1.map_part_at(key, m1, &mut |m1, g1| {
-                    prox = detector.1.update(dispatcher, m1, g1, m2, g2, margin)
+                    prox = (detector.1).0.update(dispatcher, m1, g1, m2, g2, margin)
                 });
 
                 match prox? {
@@ -108,7 +117,7 @@ impl<N: RealField> CompositeShapeShapeProximityDetector<N> {
         for key in &self.interferences {
             let entry = self.sub_detectors.entry(*key);
             let detector = match entry {
-                Entry::Occupied(entry) => Some(entry.into_mut()),
+                Entry::Occupied(entry) => Some(&mut entry.into_mut().0),
                 Entry::Vacant(entry) => {
                     let mut new_detector = None;
 
@@ -121,7 +130,7 @@ impl<N: RealField> CompositeShapeShapeProximityDetector<N> {
                     });
 
                     if let Some(new_detector) = new_detector {
-                        Some(entry.insert(new_detector))
+                        Some(&mut entry.insert((new_detector, g1.part_revision(*key))).0)
                     } else {
                         None
                     }
@@ -174,4 +183,21 @@ impl<N: RealField> ProximityDetector<N> for CompositeShapeShapeProximityDetector
             self.do_update(dispatcher, m2, cs, m1, g1, margin, true)
         }
     }
+
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        let mut sub_detectors =
+            HashMap::with_capacity_and_hasher(self.sub_detectors.capacity(), DeterministicState);
+
+        for (key, (detector, revision)) in &self.sub_detectors {
+            let _ = sub_detectors.insert(*key, (detector.clone_algorithm()?, *revision));
+        }
+
+        Some(Box::new(CompositeShapeShapeProximityDetector {
+            sub_detectors,
+            to_delete: self.to_delete.clone(),
+            interferences: self.interferences.clone(),
+            intersecting_key: self.intersecting_key,
+            flip: self.flip,
+        }))
+    }
 }
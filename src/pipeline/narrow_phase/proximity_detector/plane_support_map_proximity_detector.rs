@@ -1,5 +1,5 @@
 use crate::math::Isometry;
-use crate::pipeline::narrow_phase::{ProximityDetector, ProximityDispatcher};
+use crate::pipeline::narrow_phase::{ProximityAlgorithm, ProximityDetector, ProximityDispatcher};
 use crate::query::{self, Proximity};
 use crate::shape::{Plane, Shape};
 use na::RealField;
@@ -49,6 +49,10 @@ impl<N: RealField> ProximityDetector<N> for PlaneSupportMapProximityDetector {
         let sm = b.as_support_map()?;
         Some(query::proximity_plane_support_map(ma, p, mb, sm, margin))
     }
+
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
 
 impl<N: RealField> ProximityDetector<N> for SupportMapPlaneProximityDetector {
@@ -64,4 +68,8 @@ impl<N: RealField> ProximityDetector<N> for SupportMapPlaneProximityDetector {
     ) -> Option<Proximity> {
         self.subdetector.update(disp, mb, b, ma, a, margin)
     }
+
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
@@ -17,6 +17,14 @@ pub trait ProximityDetector<N: RealField>: Any + Send + Sync {
         b: &dyn Shape<N>,
         margin: N,
     ) -> Option<Proximity>;
+
+    /// Duplicates this proximity detector together with all its cached state.
+    ///
+    /// Returns `None` if this detector cannot be duplicated, in which case a new detector is
+    /// requested from the proximity dispatcher instead.
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        None
+    }
 }
 
 pub type ProximityAlgorithm<N> = Box<dyn ProximityDetector<N>>;
@@ -1,5 +1,5 @@
 use crate::math::{Isometry, Vector};
-use crate::pipeline::narrow_phase::{ProximityDetector, ProximityDispatcher};
+use crate::pipeline::narrow_phase::{ProximityAlgorithm, ProximityDetector, ProximityDispatcher};
 use crate::query::algorithms::VoronoiSimplex;
 use crate::query::{self, Proximity};
 use crate::shape::Shape;
@@ -59,4 +59,8 @@ impl<N: RealField> ProximityDetector<N> for SupportMapSupportMapProximityDetecto
 
         Some(res.0)
     }
+
+    fn clone_algorithm(&self) -> Option<ProximityAlgorithm<N>> {
+        Some(Box::new(self.clone()))
+    }
 }
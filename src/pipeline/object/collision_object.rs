@@ -8,6 +8,7 @@ use crate::shape::{Shape, ShapeHandle};
 use alga::general::RealField;
 
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(Default)]
     /// Flags indicating what changed in a collision object since the last collision world update.
     pub struct CollisionObjectUpdateFlags: u8 {
@@ -127,6 +128,8 @@ impl CollisionObjectSlabHandle {
 }
 
 /// A stand-alone object that has a position and a shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 pub struct CollisionObject<N: RealField, T> {
     proxy_handle: Option<BroadPhaseProxyHandle>,
     graph_index: Option<CollisionObjectGraphIndex>,
@@ -255,6 +258,18 @@ impl<N: RealField, T> CollisionObject<N, T> {
         &self.shape
     }
 
+    /// Mutable reference to the collision object shape.
+    ///
+    /// The shape is cloned first if it is shared with other collision objects. Unlike
+    /// `.set_shape`, this does not make the broad-phase recompute all the proximities of
+    /// this collision object: its interactions only update what changed on the shape, e.g.,
+    /// the parts of a `Compound` with a new revision.
+    #[inline]
+    pub fn shape_mut(&mut self) -> &mut dyn Shape<N> {
+        self.update_flags |= CollisionObjectUpdateFlags::POSITION_CHANGED;
+        self.shape.make_mut()
+    }
+
     /// Set the collision object shape.
     #[inline]
     pub fn set_shape(&mut self, shape: ShapeHandle<N>) {
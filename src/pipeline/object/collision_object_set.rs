@@ -48,6 +48,8 @@ impl<N: RealField, T> CollisionObjectSet<N> for CollisionObjectSlab<N, T> {
 }
 
 /// A set of collision objects that can be indexed by collision object handles.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 pub struct CollisionObjectSlab<N: RealField, T> {
     pub(crate) objects: Slab<CollisionObject<N, T>>,
 }
@@ -9,13 +9,21 @@ use na::RealField;
 /// * Contacts + Contacts = exact contact point coputation.
 /// * Contacts + Proximity = proximity test only.
 /// * Proximity + Proximity = proximity test only.
+/// * Proximity + Distance = proximity test only.
+/// * Contacts + Distance = closest points and distance computation.
+/// * Distance + Distance = closest points and distance computation.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GeometricQueryType<N: RealField> {
     /// This objects can respond to both contact point computation and proximity queries.
     Contacts(N, N),
     /// This object can respond to proximity tests only.
     Proximity(N),
-    // FIXME: not yet implemented: Distance
+    /// This object can respond to closest points and distance computation only.
+    ///
+    /// The closest points will be tracked as long as both objects are separated by a distance
+    /// smaller than the sum of their respective limits.
+    Distance(N),
 }
 
 impl<N: RealField> GeometricQueryType<N> {
@@ -24,12 +32,14 @@ impl<N: RealField> GeometricQueryType<N> {
     /// If two objects are separated by a distance greater than the sum of their respective
     /// `query_limit`, the corresponding query will not by performed. For proximity queries,
     /// non-intersecting object closer than a distance equal to the sum of their `query_limit` will
-    /// be reported as `Proximity::WithinMargin`.
+    /// be reported as `Proximity::WithinMargin`. For distance queries, the closest points will only
+    /// be computed for objects closer than a distance equal to the sum of their `query_limit`.
     #[inline]
     pub fn query_limit(&self) -> N {
         match *self {
             GeometricQueryType::Contacts(ref val, _) => *val,
             GeometricQueryType::Proximity(ref val) => *val,
+            GeometricQueryType::Distance(ref val) => *val,
         }
     }
 
@@ -69,4 +79,14 @@ impl<N: RealField> GeometricQueryType<N> {
             false
         }
     }
+
+    /// Returns `true` if this is a distance query type.
+    #[inline]
+    pub fn is_distance_query(&self) -> bool {
+        if let GeometricQueryType::Distance(_) = *self {
+            true
+        } else {
+            false
+        }
+    }
 }
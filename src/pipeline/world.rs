@@ -2,23 +2,28 @@
 
 use na::{RealField, Unit};
 
-use crate::bounding_volume::{BoundingVolume, AABB};
-use crate::math::{Isometry, Point, Rotation, Translation, Vector};
+use crate::bounding_volume::AABB;
+use crate::interpolation::RigidMotion;
+use crate::math::{Isometry, Point, Vector};
 use crate::pipeline::broad_phase::{BroadPhase, BroadPhasePairFilter, DBVTBroadPhase};
 use crate::pipeline::glue::{
-    self, InterferencesWithAABB, InterferencesWithPoint, InterferencesWithRay,
+    self, FirstInterferenceWithRay, InterferencesWithAABB, InterferencesWithPoint,
+    InterferencesWithRay, InterferencesWithShape, NearestObjectToPoint, RayCastMode,
+    RayCastResults,
 };
 use crate::pipeline::narrow_phase::{
-    ContactAlgorithm, ContactEvents, DefaultContactDispatcher, DefaultProximityDispatcher,
-    Interaction, InteractionGraph, NarrowPhase, ProximityDetector, ProximityEvents,
-    TemporaryInteractionIndex,
+    ContactAlgorithm, ContactEvents, DefaultContactDispatcher, DefaultDistanceDispatcher,
+    DefaultProximityDispatcher, DistanceDetector, DistanceEvents, Interaction, InteractionGraph,
+    NarrowPhase, ProximityDetector, ProximityEvents, TemporaryInteractionIndex,
 };
 use crate::pipeline::object::{
     CollisionGroups, CollisionObject, CollisionObjectSet, CollisionObjectSlab,
     CollisionObjectSlabHandle, CollisionObjects, GeometricQueryType,
 };
-use crate::query::{self, ContactManifold, Proximity, Ray, TOI};
+use crate::pipeline::world_snapshot::CollisionWorldSnapshot;
+use crate::query::{ClosestPoints, ContactManifold, Proximity, Ray, TOI};
 use crate::shape::{Shape, ShapeHandle};
+use crate::utils::MaybeSync;
 
 /// Type of the broad phase trait-object used by the collision world.
 pub type BroadPhaseObject<N> = Box<dyn BroadPhase<N, AABB<N>, CollisionObjectSlabHandle>>;
@@ -42,12 +47,20 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// Creates a new collision world.
     // FIXME: use default values for `margin` and allow its modification by the user ?
     pub fn new(margin: N) -> CollisionWorld<N, T> {
+        let broad_phase =
+            Box::new(DBVTBroadPhase::<N, AABB<N>, CollisionObjectSlabHandle>::new(margin));
+        CollisionWorld::with_broad_phase(broad_phase)
+    }
+
+    /// Creates a new collision world using the given broad-phase.
+    ///
+    /// This can be used to replace the default `DBVTBroadPhase` by, e.g., a `SAPBroadPhase`.
+    pub fn with_broad_phase(broad_phase: BroadPhaseObject<N>) -> CollisionWorld<N, T> {
         let objects = CollisionObjectSlab::new();
         let coll_dispatcher = Box::new(DefaultContactDispatcher::new());
         let prox_dispatcher = Box::new(DefaultProximityDispatcher::new());
-        let broad_phase =
-            Box::new(DBVTBroadPhase::<N, AABB<N>, CollisionObjectSlabHandle>::new(margin));
-        let narrow_phase = NarrowPhase::new(coll_dispatcher, prox_dispatcher);
+        let dist_dispatcher = Box::new(DefaultDistanceDispatcher::new());
+        let narrow_phase = NarrowPhase::new(coll_dispatcher, prox_dispatcher, dist_dispatcher);
 
         CollisionWorld {
             interactions: InteractionGraph::new(),
@@ -97,7 +110,12 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// 1. Clears the event pools.
     /// 2. Executes the broad phase first.
     /// 3. Executes the narrow phase.
-    pub fn update(&mut self) {
+    ///
+    /// With the `parallel` feature enabled, this requires `T: Sync`.
+    pub fn update(&mut self)
+    where
+        T: MaybeSync,
+    {
         self.narrow_phase.clear_events();
 
         glue::perform_all_pipeline(
@@ -114,11 +132,32 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         }
     }
 
-    /// Empty the contact and proximity event pools.
+    /// Empty the contact, proximity and distance event pools.
     pub fn clear_events(&mut self) {
         self.narrow_phase.clear_events();
     }
 
+    /// Takes a snapshot of the whole state of this world.
+    ///
+    /// Returns `None` if the broad phase of this world is not a `DBVTBroadPhase`, a
+    /// `SAPBroadPhase`, or a `UniformGridBroadPhase`.
+    pub fn snapshot(&self) -> Option<CollisionWorldSnapshot<N, T>>
+    where
+        T: Clone,
+    {
+        CollisionWorldSnapshot::new(self)
+    }
+
+    /// Sets the state of this world to the state saved by the given snapshot.
+    ///
+    /// The dispatchers and pair filters of this world are kept.
+    pub fn restore(&mut self, snapshot: &CollisionWorldSnapshot<N, T>)
+    where
+        T: Clone,
+    {
+        snapshot.restore(self)
+    }
+
     /// Removed the specified set of collision objects from the world.
     ///
     /// Panics of any handle is invalid, or if the list contains duplicates.
@@ -228,7 +267,12 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     }
 
     /// Executes the narrow phase of the collision detection pipeline.
-    pub fn perform_narrow_phase(&mut self) {
+    ///
+    /// With the `parallel` feature enabled, this requires `T: Sync`.
+    pub fn perform_narrow_phase(&mut self)
+    where
+        T: MaybeSync,
+    {
         glue::perform_narrow_phase(
             &self.objects,
             &mut self.narrow_phase,
@@ -297,8 +341,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
 
     /// Returns all objects in the collision world that intersect with the shape
     /// transformed by `isometry` along `direction` until `maximum_distance` is
-    /// reached. The objects are not returned in any particular order. You may
-    /// use the `toi` returned for each object to determine the closest object.
+    /// reached. The objects are returned sorted by increasing time of impact.
     #[inline]
     pub fn sweep_test<'a>(
         &'a self,
@@ -308,29 +351,80 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         maximum_distance: N,
         groups: &'a CollisionGroups,
     ) -> impl Iterator<Item = (CollisionObjectSlabHandle, TOI<N>)> + 'a {
-        let a = shape.aabb(&isometry);
-        let b = shape.aabb(&Isometry::from_parts(
-            Translation::from(isometry.translation.vector + direction.as_ref() * maximum_distance),
-            Rotation::identity(),
-        ));
-        let aabb = a.merged(&b);
-
-        // FIXME: avoid allocation.
-        let interferences: Vec<_> = self.interferences_with_aabb(&aabb, groups).collect();
-
-        interferences.into_iter().filter_map(move |(handle, x)| {
-            query::time_of_impact(
-                &isometry,
-                &direction,
-                shape,
-                x.position(),
-                &Vector::zeros(),
-                x.shape().as_ref(),
-                N::max_value(),
-                N::zero(),
-            )
-            .map(|toi| (handle, toi))
-        })
+        glue::sweep_test(
+            &self.objects,
+            &*self.broad_phase,
+            shape,
+            isometry,
+            direction,
+            maximum_distance,
+            groups,
+        )
+        .into_iter()
+    }
+
+    /// Returns the first object in the collision world that intersects with the shape
+    /// transformed by `isometry` along `direction` until `maximum_distance` is reached.
+    #[inline]
+    pub fn first_sweep_test(
+        &self,
+        shape: &dyn Shape<N>,
+        isometry: &Isometry<N>,
+        direction: &Unit<Vector<N>>,
+        maximum_distance: N,
+        groups: &CollisionGroups,
+    ) -> Option<(CollisionObjectSlabHandle, TOI<N>)> {
+        glue::first_sweep_test(
+            &self.objects,
+            &*self.broad_phase,
+            shape,
+            isometry,
+            direction,
+            maximum_distance,
+            groups,
+        )
+    }
+
+    /// Returns all objects in the collision world that intersect with the shape
+    /// following `motion` until `max_toi` is reached. The objects are returned sorted by
+    /// increasing time of impact.
+    #[inline]
+    pub fn sweep_test_with_motion<'a>(
+        &'a self,
+        shape: &'a dyn Shape<N>,
+        motion: &'a (impl RigidMotion<N> + ?Sized),
+        max_toi: N,
+        groups: &'a CollisionGroups,
+    ) -> impl Iterator<Item = (CollisionObjectSlabHandle, TOI<N>)> + 'a {
+        glue::sweep_test_with_motion(
+            &self.objects,
+            &*self.broad_phase,
+            shape,
+            motion,
+            max_toi,
+            groups,
+        )
+        .into_iter()
+    }
+
+    /// Returns the first object in the collision world that intersects with the shape
+    /// following `motion` until `max_toi` is reached.
+    #[inline]
+    pub fn first_sweep_test_with_motion(
+        &self,
+        shape: &dyn Shape<N>,
+        motion: &(impl RigidMotion<N> + ?Sized),
+        max_toi: N,
+        groups: &CollisionGroups,
+    ) -> Option<(CollisionObjectSlabHandle, TOI<N>)> {
+        glue::first_sweep_test_with_motion(
+            &self.objects,
+            &*self.broad_phase,
+            shape,
+            motion,
+            max_toi,
+            groups,
+        )
     }
 
     /// Computes the interferences between every rigid bodies on this world and a ray.
@@ -343,6 +437,44 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         glue::interferences_with_ray(&self.objects, &*self.broad_phase, ray, groups)
     }
 
+    /// Computes the first collision object hit by the given ray.
+    ///
+    /// Only hits with a time of impact smaller than `max_toi`, and collision objects in a group
+    /// that can interact with the given `groups` are considered.
+    #[inline]
+    pub fn first_interference_with_ray<'a>(
+        &'a self,
+        ray: &Ray<N>,
+        max_toi: N,
+        groups: &CollisionGroups,
+    ) -> Option<FirstInterferenceWithRay<'a, N, CollisionObjectSlab<N, T>>> {
+        glue::first_interference_with_ray(&self.objects, &*self.broad_phase, ray, max_toi, groups)
+    }
+
+    /// Casts a batch of rays against all the collision objects of this world.
+    ///
+    /// The hits of the `i`-th ray of `rays` are written to `results.hits(i)`, and only collision
+    /// objects in a group that can interact with `groups[i]` are considered for this ray.
+    /// With the `parallel` feature enabled, this requires `T: Sync` and the rays are cast in parallel.
+    pub fn cast_rays(
+        &self,
+        rays: &[Ray<N>],
+        groups: &[CollisionGroups],
+        mode: RayCastMode,
+        results: &mut RayCastResults<N, CollisionObjectSlabHandle>,
+    ) where
+        T: MaybeSync,
+    {
+        glue::cast_rays(
+            &self.objects,
+            &*self.broad_phase,
+            rays,
+            groups,
+            mode,
+            results,
+        )
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a point.
     #[inline]
     pub fn interferences_with_point<'a, 'b>(
@@ -353,6 +485,41 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         glue::interferences_with_point(&self.objects, &*self.broad_phase, point, groups)
     }
 
+    /// Computes the collision objects intersecting the given shape transformed by `isometry`.
+    ///
+    /// Unlike `interferences_with_aabb`, only the objects actually intersecting the shape are returned.
+    /// Objects for which the proximity dispatcher of this world has no proximity detector are ignored.
+    #[inline]
+    pub fn interferences_with_shape<'a, 'b>(
+        &'a self,
+        shape: &'b dyn Shape<N>,
+        isometry: &'b Isometry<N>,
+        groups: &'b CollisionGroups,
+    ) -> InterferencesWithShape<'a, 'b, N, CollisionObjectSlab<N, T>> {
+        glue::interferences_with_shape(
+            &self.objects,
+            &*self.broad_phase,
+            &*self.narrow_phase.proximity_dispatcher,
+            shape,
+            isometry,
+            groups,
+        )
+    }
+
+    /// Computes the collision object closest to the given point, and the projection of the point on it.
+    ///
+    /// Only collision objects closer than `max_dist`, and in a group that can interact with the
+    /// given `groups` are considered.
+    #[inline]
+    pub fn nearest_object_to_point<'a>(
+        &'a self,
+        point: &Point<N>,
+        max_dist: N,
+        groups: &CollisionGroups,
+    ) -> Option<NearestObjectToPoint<'a, N, CollisionObjectSlab<N, T>>> {
+        glue::nearest_object_to_point(&self.objects, &*self.broad_phase, point, max_dist, groups)
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a aabb.
     #[inline]
     pub fn interferences_with_aabb<'a, 'b>(
@@ -428,6 +595,24 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         self.interactions.proximity_pairs(effective_only)
     }
 
+    /// All the potential distance pairs.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pairs(
+        &self,
+        effective_only: bool,
+    ) -> impl Iterator<
+        Item = (
+            CollisionObjectSlabHandle,
+            CollisionObjectSlabHandle,
+            &dyn DistanceDetector<N>,
+            ClosestPoints<N>,
+        ),
+    > {
+        self.interactions.distance_pairs(effective_only)
+    }
+
     /// The potential interaction pair between the two specified collision objects.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -493,6 +678,28 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         self.interactions.proximity_pair(id1, id2, effective_only)
     }
 
+    /// The potential distance pair between the two specified collision objects.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pair(
+        &self,
+        handle1: CollisionObjectSlabHandle,
+        handle2: CollisionObjectSlabHandle,
+        effective_only: bool,
+    ) -> Option<(
+        CollisionObjectSlabHandle,
+        CollisionObjectSlabHandle,
+        &dyn DistanceDetector<N>,
+        ClosestPoints<N>,
+    )> {
+        let co1 = self.objects.collision_object(handle1)?;
+        let co2 = self.objects.collision_object(handle2)?;
+        let id1 = co1.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id2 = co2.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        self.interactions.distance_pair(id1, id2, effective_only)
+    }
+
     /// All the interaction pairs involving the specified collision object.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -564,6 +771,29 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         Some(self.interactions.proximities_with(id, effective_only))
     }
 
+    /// All the distance pairs involving the specified collision object.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distances_with(
+        &self,
+        handle: CollisionObjectSlabHandle,
+        effective_only: bool,
+    ) -> Option<
+        impl Iterator<
+            Item = (
+                CollisionObjectSlabHandle,
+                CollisionObjectSlabHandle,
+                &dyn DistanceDetector<N>,
+                ClosestPoints<N>,
+            ),
+        >,
+    > {
+        let co = self.objects.collision_object(handle)?;
+        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        Some(self.interactions.distances_with(id, effective_only))
+    }
+
     /// All the contact pairs involving the specified collision object.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -642,4 +872,9 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     pub fn proximity_events(&self) -> &ProximityEvents<CollisionObjectSlabHandle> {
         self.narrow_phase.proximity_events()
     }
+
+    /// The distance events pool.
+    pub fn distance_events(&self) -> &DistanceEvents<N, CollisionObjectSlabHandle> {
+        self.narrow_phase.distance_events()
+    }
 }
@@ -0,0 +1,255 @@
+//! Snapshots of the whole state of a collision world.
+
+use na::RealField;
+use petgraph::graph::{NodeIndex, UnGraph};
+use slotmap::SlotMap;
+
+use crate::bounding_volume::AABB;
+use crate::pipeline::broad_phase::{DBVTBroadPhase, SAPBroadPhase, UniformGridBroadPhase};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactEvents, DistanceAlgorithm, DistanceEvents, Interaction,
+    InteractionGraph, ProximityAlgorithm, ProximityEvents,
+};
+use crate::pipeline::object::{CollisionObjectSlab, CollisionObjectSlabHandle};
+use crate::pipeline::world::{BroadPhaseObject, CollisionWorld};
+use crate::query::{ClosestPoints, ContactId, ContactManifold, Proximity};
+
+// One of the broad phases provided by ncollide.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum SavedBroadPhase<N: RealField> {
+    DBVT(DBVTBroadPhase<N, AABB<N>, CollisionObjectSlabHandle>),
+    SAP(SAPBroadPhase<N, CollisionObjectSlabHandle>),
+    UniformGrid(UniformGridBroadPhase<N, CollisionObjectSlabHandle>),
+}
+
+impl<N: RealField> SavedBroadPhase<N> {
+    fn new(broad_phase: &BroadPhaseObject<N>) -> Option<Self> {
+        if let Some(dbvt) = broad_phase.downcast_ref::<DBVTBroadPhase<_, _, _>>() {
+            Some(SavedBroadPhase::DBVT(dbvt.clone()))
+        } else if let Some(sap) = broad_phase.downcast_ref::<SAPBroadPhase<_, _>>() {
+            Some(SavedBroadPhase::SAP(sap.clone()))
+        } else if let Some(grid) = broad_phase.downcast_ref::<UniformGridBroadPhase<_, _>>() {
+            Some(SavedBroadPhase::UniformGrid(grid.clone()))
+        } else {
+            None
+        }
+    }
+
+    fn to_broad_phase(&self) -> BroadPhaseObject<N> {
+        match self {
+            SavedBroadPhase::DBVT(dbvt) => Box::new(dbvt.clone()),
+            SavedBroadPhase::SAP(sap) => Box::new(sap.clone()),
+            SavedBroadPhase::UniformGrid(grid) => Box::new(grid.clone()),
+        }
+    }
+}
+
+// The state of an interaction, without the algorithm that updates it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum InteractionState<N: RealField> {
+    Contact(ContactManifold<N>),
+    Proximity(Proximity),
+    Distance(ClosestPoints<N>),
+}
+
+// The algorithm that updates an interaction, together with its cached state.
+enum InteractionAlgorithm<N: RealField> {
+    Contact(ContactAlgorithm<N>),
+    Proximity(ProximityAlgorithm<N>),
+    Distance(DistanceAlgorithm<N>),
+}
+
+impl<N: RealField> InteractionAlgorithm<N> {
+    fn from_interaction(interaction: &Interaction<N>) -> Option<Self> {
+        match interaction {
+            Interaction::Contact(detector, _) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Contact),
+            Interaction::Proximity(detector, _) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Proximity),
+            Interaction::Distance(detector, _) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Distance),
+        }
+    }
+
+    fn clone_algorithm(&self) -> Option<Self> {
+        match self {
+            InteractionAlgorithm::Contact(detector) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Contact),
+            InteractionAlgorithm::Proximity(detector) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Proximity),
+            InteractionAlgorithm::Distance(detector) => detector
+                .clone_algorithm()
+                .map(InteractionAlgorithm::Distance),
+        }
+    }
+}
+
+// The algorithms of the interactions of a snapshot, in the same order as the interactions.
+//
+// Algorithms are not serializable so this is empty for deserialized snapshots.
+struct SavedAlgorithms<N: RealField>(Vec<Option<InteractionAlgorithm<N>>>);
+
+impl<N: RealField> Clone for SavedAlgorithms<N> {
+    fn clone(&self) -> Self {
+        SavedAlgorithms(
+            self.0
+                .iter()
+                .map(|algorithm| algorithm.as_ref()?.clone_algorithm())
+                .collect(),
+        )
+    }
+}
+
+impl<N: RealField> Default for SavedAlgorithms<N> {
+    fn default() -> Self {
+        SavedAlgorithms(Vec::new())
+    }
+}
+
+/// A snapshot of the whole state of a `CollisionWorld`.
+///
+/// This includes the collision objects, the broad phase, the contact manifolds together with the
+/// identifiers of their contacts, the contact, proximity and distance algorithms together with
+/// their cached state, and the events that have not been cleared yet. Restoring a snapshot with
+/// `CollisionWorld::restore` yields a world that evolves exactly like the world the snapshot was
+/// taken from. Algorithms that do not support `clone_algorithm` are the exception: they are
+/// re-created from the dispatchers of the restored world so their cached state (e.g. the
+/// warm-start data of GJK) is lost, and the restored world may then differ slightly from the
+/// original one.
+///
+/// With the `serde-serialize` feature, snapshots can be serialized. Shapes that are not provided
+/// by ncollide must be registered on a `ShapeRegistry`, and the serialization must be run
+/// inside of `ShapeRegistry::scope`. Shapes shared by several collision objects are serialized
+/// once per object. The algorithms are not serialized: a deserialized snapshot re-creates them
+/// from the dispatchers. Worlds restored from the same deserialized snapshot evolve identically
+/// to each other, but may differ slightly from the world the snapshot was taken from.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CollisionWorldSnapshot<N: RealField, T> {
+    objects: CollisionObjectSlab<N, T>,
+    broad_phase: SavedBroadPhase<N>,
+    nodes: Vec<CollisionObjectSlabHandle>,
+    interactions: Vec<(usize, usize, InteractionState<N>)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    algorithms: SavedAlgorithms<N>,
+    contact_ids: SlotMap<ContactId, bool>,
+    contact_events: ContactEvents<CollisionObjectSlabHandle>,
+    proximity_events: ProximityEvents<CollisionObjectSlabHandle>,
+    distance_events: DistanceEvents<N, CollisionObjectSlabHandle>,
+}
+
+impl<N: RealField, T: Clone> CollisionWorldSnapshot<N, T> {
+    /// Takes a snapshot of the given world.
+    ///
+    /// Returns `None` if the broad phase of this world is not a `DBVTBroadPhase`, a
+    /// `SAPBroadPhase`, or a `UniformGridBroadPhase`.
+    pub fn new(world: &CollisionWorld<N, T>) -> Option<Self> {
+        let broad_phase = SavedBroadPhase::new(&world.broad_phase)?;
+        let graph = &world.interactions.0;
+        let nodes = graph.raw_nodes().iter().map(|node| node.weight).collect();
+        let interactions = graph
+            .raw_edges()
+            .iter()
+            .map(|edge| {
+                let state = match &edge.weight {
+                    Interaction::Contact(_, manifold) => {
+                        InteractionState::Contact(manifold.clone())
+                    }
+                    Interaction::Proximity(_, prox) => InteractionState::Proximity(*prox),
+                    Interaction::Distance(_, pts) => InteractionState::Distance(*pts),
+                };
+
+                (edge.source().index(), edge.target().index(), state)
+            })
+            .collect();
+        let algorithms = graph
+            .raw_edges()
+            .iter()
+            .map(|edge| InteractionAlgorithm::from_interaction(&edge.weight))
+            .collect();
+        let narrow_phase = &world.narrow_phase;
+
+        Some(CollisionWorldSnapshot {
+            objects: world.objects.clone(),
+            broad_phase,
+            nodes,
+            interactions,
+            algorithms: SavedAlgorithms(algorithms),
+            contact_ids: narrow_phase.id_allocator.clone(),
+            contact_events: narrow_phase.contact_events.clone(),
+            proximity_events: narrow_phase.proximity_events.clone(),
+            distance_events: narrow_phase.distance_events.clone(),
+        })
+    }
+
+    /// Sets the state of the given world to the state saved by this snapshot.
+    ///
+    /// The broad phase of the world is replaced by the one of this snapshot. The dispatchers and
+    /// the pair filters of the world are kept.
+    pub fn restore(&self, world: &mut CollisionWorld<N, T>) {
+        world.objects = self.objects.clone();
+        world.broad_phase = self.broad_phase.to_broad_phase();
+
+        let narrow_phase = &mut world.narrow_phase;
+        narrow_phase.id_allocator = self.contact_ids.clone();
+        narrow_phase.contact_events = self.contact_events.clone();
+        narrow_phase.proximity_events = self.proximity_events.clone();
+        narrow_phase.distance_events = self.distance_events.clone();
+
+        let mut graph = UnGraph::with_capacity(self.nodes.len(), self.interactions.len());
+
+        for handle in &self.nodes {
+            let _ = graph.add_node(*handle);
+        }
+
+        for (i, (id1, id2, state)) in self.interactions.iter().enumerate() {
+            let shape1 = world.objects[self.nodes[*id1]].shape().as_ref();
+            let shape2 = world.objects[self.nodes[*id2]].shape().as_ref();
+            let saved = self
+                .algorithms
+                .0
+                .get(i)
+                .and_then(|algorithm| algorithm.as_ref()?.clone_algorithm());
+
+            let interaction = match (state, saved) {
+                (
+                    InteractionState::Contact(manifold),
+                    Some(InteractionAlgorithm::Contact(detector)),
+                ) => Some(Interaction::Contact(detector, manifold.clone())),
+                (
+                    InteractionState::Proximity(prox),
+                    Some(InteractionAlgorithm::Proximity(detector)),
+                ) => Some(Interaction::Proximity(detector, *prox)),
+                (
+                    InteractionState::Distance(pts),
+                    Some(InteractionAlgorithm::Distance(detector)),
+                ) => Some(Interaction::Distance(detector, *pts)),
+                (InteractionState::Contact(manifold), _) => narrow_phase
+                    .contact_dispatcher
+                    .get_contact_algorithm(shape1, shape2)
+                    .map(|detector| Interaction::Contact(detector, manifold.clone())),
+                (InteractionState::Proximity(prox), _) => narrow_phase
+                    .proximity_dispatcher
+                    .get_proximity_algorithm(shape1, shape2)
+                    .map(|detector| Interaction::Proximity(detector, *prox)),
+                (InteractionState::Distance(pts), _) => narrow_phase
+                    .distance_dispatcher
+                    .get_distance_algorithm(shape1, shape2)
+                    .map(|detector| Interaction::Distance(detector, *pts)),
+            };
+
+            if let Some(interaction) = interaction {
+                let _ = graph.add_edge(NodeIndex::new(*id1), NodeIndex::new(*id2), interaction);
+            }
+        }
+
+        world.interactions = InteractionGraph(graph);
+    }
+}
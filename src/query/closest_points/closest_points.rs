@@ -1,5 +1,5 @@
 use crate::math::Point;
-use na::RealField;
+use na::{self, RealField};
 use std::mem;
 
 /// Closest points information.
@@ -21,4 +21,15 @@ impl<N: RealField> ClosestPoints<N> {
             mem::swap(p1, p2)
         }
     }
+
+    /// The distance separating the two closest points.
+    ///
+    /// Returns zero if the objects are intersecting, and `None` if they are disjoint.
+    pub fn distance(&self) -> Option<N> {
+        match *self {
+            ClosestPoints::Intersecting => Some(N::zero()),
+            ClosestPoints::WithinMargin(ref p1, ref p2) => Some(na::distance(p1, p2)),
+            ClosestPoints::Disjoint => None,
+        }
+    }
 }
@@ -62,6 +62,7 @@ impl<N: RealField> Contact<N> {
 /// they can be seen as the same contact point that moved in-between frames. Two matching
 /// contact points are given the same `id` here.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TrackedContact<N: RealField> {
     /// The geometric contact information.
     pub contact: Contact<N>,
@@ -5,6 +5,7 @@ use na::{self, RealField, Unit};
 
 /// A shape geometry type at the neighborhood of a point.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NeighborhoodGeometry<N: RealField> {
     /// A punctual approximation.
     Point,
@@ -16,6 +17,7 @@ pub enum NeighborhoodGeometry<N: RealField> {
 
 /// The approximation of a shape on the neighborhood of a point.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LocalShapeApproximation<N: RealField> {
     // XXX: currently, there is no explicit representation
     // of the point where the approximation occurs in terms
@@ -54,12 +56,16 @@ impl<N: RealField> LocalShapeApproximation<N> {
 /// around the given points are approximated by either dilated lines (unbounded
 /// cylinders), planes, dilated points (spheres).
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContactKinematic<N: RealField> {
     approx1: LocalShapeApproximation<N>,
     approx2: LocalShapeApproximation<N>,
 
     margin1: N,
     margin2: N,
+
+    barycentric_coordinates1: Option<(usize, [N; 3])>,
+    barycentric_coordinates2: Option<(usize, [N; 3])>,
 }
 
 impl<N: RealField> ContactKinematic<N> {
@@ -79,6 +85,8 @@ impl<N: RealField> ContactKinematic<N> {
             margin2: na::zero(),
             approx1: approx.clone(),
             approx2: approx,
+            barycentric_coordinates1: None,
+            barycentric_coordinates2: None,
         }
     }
 
@@ -190,6 +198,34 @@ impl<N: RealField> ContactKinematic<N> {
         &mut self.approx2
     }
 
+    /// The index of the triangle of the first shape the contact point lies on, and the
+    /// barycentric coordinates of the contact point on this triangle.
+    ///
+    /// This is only set for contacts on triangle meshes. The coordinates are relative to the
+    /// vertices of the triangle at the time the contact was computed.
+    pub fn barycentric_coordinates1(&self) -> Option<(usize, [N; 3])> {
+        self.barycentric_coordinates1
+    }
+
+    /// The index of the triangle of the second shape the contact point lies on, and the
+    /// barycentric coordinates of the contact point on this triangle.
+    ///
+    /// This is only set for contacts on triangle meshes. The coordinates are relative to the
+    /// vertices of the triangle at the time the contact was computed.
+    pub fn barycentric_coordinates2(&self) -> Option<(usize, [N; 3])> {
+        self.barycentric_coordinates2
+    }
+
+    /// Sets the triangle and barycentric coordinates of the contact point on the first shape.
+    pub fn set_barycentric_coordinates1(&mut self, coords: Option<(usize, [N; 3])>) {
+        self.barycentric_coordinates1 = coords
+    }
+
+    /// Sets the triangle and barycentric coordinates of the contact point on the second shape.
+    pub fn set_barycentric_coordinates2(&mut self, coords: Option<(usize, [N; 3])>) {
+        self.barycentric_coordinates2 = coords
+    }
+
     /// Sets the local approximation of the first shape.
     pub fn set_approx1(
         &mut self,
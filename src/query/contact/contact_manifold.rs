@@ -8,6 +8,7 @@ use std::collections::{hash_map::Entry, HashMap};
 
 /// The technique used for contact tracking.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ContactTrackingMode<N: RealField> {
     /// Contact tracking using features.
     /// Two contacts are considered the same if they are on the same features.
@@ -18,6 +19,7 @@ pub enum ContactTrackingMode<N: RealField> {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum ContactCache<N: RealField> {
     FeatureBased(HashMap<(FeatureId, FeatureId), usize>),
     DistanceBased(Vec<(Point<N>, usize)>, N),
@@ -30,6 +32,7 @@ enum ContactCache<N: RealField> {
 /// This structure is responsible for matching new contacts with old ones in order to perform an
 /// approximate tracking of the contact points.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContactManifold<N: RealField> {
     ncontacts: usize,
     persistence: usize,
@@ -1,5 +1,6 @@
-use crate::query::{Contact, ContactKinematic};
-use na::RealField;
+use crate::math::{Isometry, Vector};
+use crate::query::{Contact, ContactKinematic, NeighborhoodGeometry};
+use na::{RealField, Unit};
 
 /// Pre-process a contact before it is added to a contact manifold.
 pub trait ContactPreprocessor<N: RealField> {
@@ -37,3 +38,38 @@ where
         }
     }
 }
+
+/// Replaces the normal of a contact by `local_n`, the new outward normal of the first shape if
+/// `is_first` is `true` (of the second shape otherwise), expressed in the local-space `pos` of
+/// this shape.
+///
+/// The contact point of the other shape is moved so that it stays on its dilated surface, the
+/// penetration depth is recomputed along the new normal, and the neighborhood of the contact on
+/// the corrected shape is approximated by a plane orthogonal to `local_n`.
+pub(crate) fn replace_contact_normal<N: RealField>(
+    c: &mut Contact<N>,
+    kinematic: &mut ContactKinematic<N>,
+    is_first: bool,
+    pos: &Isometry<N>,
+    local_n: Unit<Vector<N>>,
+) {
+    let world_n = if is_first { c.normal } else { -c.normal };
+    let new_world_n = Unit::new_unchecked(pos.transform_vector(&local_n));
+    let (world_v, world_o, dilation) = if is_first {
+        (c.world1, &mut c.world2, kinematic.dilation2())
+    } else {
+        (c.world2, &mut c.world1, kinematic.dilation1())
+    };
+
+    let center = *world_o + *world_n * dilation;
+    *world_o = center - *new_world_n * dilation;
+    c.depth = (world_v - *world_o).dot(&new_world_n);
+    c.normal = if is_first { new_world_n } else { -new_world_n };
+
+    let approx = if is_first {
+        kinematic.approx1_mut()
+    } else {
+        kinematic.approx2_mut()
+    };
+    approx.geometry = NeighborhoodGeometry::Plane(local_n);
+}
@@ -6,6 +6,7 @@ pub use self::contact_kinematic::{
 };
 pub use self::contact_manifold::{ContactManifold, ContactTrackingMode};
 pub use self::contact_preprocessor::ContactPreprocessor;
+pub(crate) use self::contact_preprocessor::replace_contact_normal;
 
 pub use self::contact_ball_ball::contact_ball_ball;
 pub use self::contact_composite_shape_shape::{
@@ -10,11 +10,17 @@ mod point_bounding_sphere;
 mod point_capsule;
 mod point_compound;
 mod point_cuboid;
+mod point_ellipsoid;
 mod point_heightfield;
+mod point_kdop;
+mod point_obb;
 mod point_plane;
 mod point_polyline;
 #[doc(hidden)]
 pub mod point_query;
+mod point_round_shape;
+mod point_scaled;
+mod point_sdf_shape;
 mod point_segment;
 mod point_shape;
 mod point_support_map;
@@ -23,3 +29,5 @@ mod point_tetrahedron;
 mod point_triangle;
 #[cfg(feature = "dim3")]
 mod point_trimesh;
+#[cfg(feature = "dim3")]
+mod point_voxels;
@@ -0,0 +1,152 @@
+use na::{self, RealField};
+
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::{Ellipsoid, FeatureId};
+
+const MAX_BISECTION_ITERATIONS: usize = 100;
+
+impl<N: RealField> Ellipsoid<N> {
+    // Projects the local point `pt` on the boundary of this ellipsoid.
+    //
+    // The projection is `radii² * pt / (t + radii²)` where `t` is the root greater than
+    // `-min_i radii_i²` of
+    //     f(t) = sum_i (radii_i * pt_i / (t + radii_i²))² - 1.
+    // To avoid cancellations when the root gets close to `-min_i radii_i²`, this is solved for
+    // `s = t + min_i radii_i²` by bisection. Computations are performed on the absolute values of
+    // the coordinates of `pt`, and the signs are restored at the end.
+    fn local_boundary_projection(&self, pt: &Point<N>) -> Point<N> {
+        let radii = self.radii();
+        let y = pt.coords.abs();
+        let sq = |x: N| x * x;
+        let k = radii.imin();
+        let min_sq_radius = sq(radii[k]);
+        let shifts = radii.map(|r| sq(r) - min_sq_radius);
+        let f = |s: N| {
+            let mut res = -N::one();
+
+            for i in 0..DIM {
+                let denominator = s + shifts[i];
+
+                // A term with a zero denominator only appears when `s` is zero and the
+                // corresponding coordinate is zero too.
+                if !y[i].is_zero() && denominator.is_positive() {
+                    res += sq(radii[i] * y[i] / denominator);
+                }
+            }
+
+            res
+        };
+
+        let mut proj = Vector::zeros();
+
+        if f(min_sq_radius).is_positive() {
+            // The point is outside, so `t` is between zero and the norm of `radii * y`.
+            let root = bisect(
+                &f,
+                min_sq_radius,
+                min_sq_radius + radii.component_mul(&y).norm(),
+            );
+
+            for i in 0..DIM {
+                proj[i] = sq(radii[i]) * y[i] / (root + shifts[i]);
+            }
+        } else {
+            // The point is inside, so `t` is negative. Each non-zero term of `f` is greater
+            // than 1 at `s = radii_i * y_i - shifts_i`, so this is a lower bound of the root.
+            let mut lower = N::zero();
+
+            for i in 0..DIM {
+                if !y[i].is_zero() {
+                    lower = lower.max(radii[i] * y[i] - shifts[i]);
+                }
+            }
+
+            if lower.is_zero() && !f(N::zero()).is_positive() {
+                // Degenerate case: the point lies on the plane orthogonal to the smallest axis
+                // and is so deep that its projection is not on this plane anymore.
+                let mut sum = N::zero();
+
+                for i in 0..DIM {
+                    if i != k && !y[i].is_zero() && shifts[i].is_positive() {
+                        proj[i] = sq(radii[i]) * y[i] / shifts[i];
+                        sum += sq(proj[i] / radii[i]);
+                    }
+                }
+
+                proj[k] = radii[k] * (N::one() - sum).max(N::zero()).sqrt();
+            } else {
+                let root = bisect(&f, lower, min_sq_radius);
+
+                for i in 0..DIM {
+                    proj[i] = sq(radii[i]) * y[i] / (root + shifts[i]);
+                }
+            }
+        }
+
+        for i in 0..DIM {
+            if pt[i].is_negative() {
+                proj[i] = -proj[i];
+            }
+        }
+
+        Point::from(proj)
+    }
+}
+
+// Finds the root of the decreasing function `f` between `lower` and `upper`.
+//
+// The result is never equal to `lower` unless `f(lower)` is zero.
+fn bisect<N: RealField>(f: &impl Fn(N) -> N, mut lower: N, mut upper: N) -> N {
+    let _0_5: N = na::convert(0.5f64);
+
+    for _ in 0..MAX_BISECTION_ITERATIONS {
+        let mid = (lower + upper) * _0_5;
+
+        if mid <= lower || mid >= upper {
+            break;
+        }
+
+        let val = f(mid);
+
+        if val.is_zero() {
+            return mid;
+        } else if val.is_positive() {
+            lower = mid;
+        } else {
+            upper = mid;
+        }
+    }
+
+    upper
+}
+
+impl<N: RealField> PointQuery<N> for Ellipsoid<N> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        let ls_pt = m.inverse_transform_point(pt);
+        let inside = ls_pt.coords.component_div(self.radii()).norm_squared() <= N::one();
+
+        if inside && solid {
+            PointProjection::new(true, *pt)
+        } else {
+            let ls_proj = self.local_boundary_projection(&ls_pt);
+            PointProjection::new(inside, m * ls_proj)
+        }
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        (self.project_point(m, pt, false), FeatureId::Face(0))
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        let ls_pt = m.inverse_transform_point(pt);
+        ls_pt.coords.component_div(self.radii()).norm_squared() <= N::one()
+    }
+}
@@ -0,0 +1,149 @@
+use crate::bounding_volume::{KDOP, KDOP_NUM_AXES};
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::FeatureId;
+use na::{self, DMatrix, DVector, RealField};
+
+impl<N: RealField> KDOP<N> {
+    // The `i`-th face plane, as a normal `n` and an offset `d` such that the KDOP is on the
+    // side of `n.dot(x) <= d`. The normal is not normalized.
+    fn plane(&self, i: usize) -> (Vector<N>, N) {
+        if i < KDOP_NUM_AXES {
+            (Self::axis(i), self.maxs()[i])
+        } else {
+            (
+                -Self::axis(i - KDOP_NUM_AXES),
+                -self.mins()[i - KDOP_NUM_AXES],
+            )
+        }
+    }
+
+    // Projects a local point on the boundary of this KDOP.
+    fn local_boundary_projection(&self, pt: &Point<N>) -> (bool, Point<N>, FeatureId) {
+        let planes: Vec<_> = (0..KDOP_NUM_AXES * 2).map(|i| self.plane(i)).collect();
+        let inside = planes.iter().all(|(n, d)| n.dot(&pt.coords) <= *d);
+
+        if inside {
+            // The closest face plane is also the closest face.
+            let mut best = (N::max_value(), 0);
+
+            for (i, (n, d)) in planes.iter().enumerate() {
+                let dist = (*d - n.dot(&pt.coords)) / n.norm();
+
+                if dist < best.0 {
+                    best = (dist, i);
+                }
+            }
+
+            let n = planes[best.1].0;
+            let proj = pt + n * (best.0 / n.norm());
+            return (true, proj, FeatureId::Face(best.1));
+        }
+
+        // The projection is the closest point to `pt` on the intersection of some face planes.
+        // Those planes are found by testing all the subsets of at most `DIM` planes, until the
+        // optimality conditions are satisfied.
+        let tolerance = N::default_epsilon().sqrt();
+        let mut best = (N::max_value(), *pt, FeatureId::Unknown);
+
+        for num in 1..=DIM {
+            let mut ids: Vec<usize> = (0..num).collect();
+
+            loop {
+                let gram =
+                    DMatrix::from_fn(num, num, |a, b| planes[ids[a]].0.dot(&planes[ids[b]].0));
+                let rhs = DVector::from_fn(num, |a, _| {
+                    planes[ids[a]].0.dot(&pt.coords) - planes[ids[a]].1
+                });
+
+                if let Some(inv) = gram.try_inverse() {
+                    let lambdas = inv * rhs;
+                    let mut candidate = *pt;
+
+                    for a in 0..num {
+                        candidate -= planes[ids[a]].0 * lambdas[a];
+                    }
+
+                    let feasible = planes.iter().all(|(n, d)| {
+                        n.dot(&candidate.coords) - *d <= tolerance * (N::one() + d.abs())
+                    });
+
+                    if feasible {
+                        let feature = if num == 1 {
+                            FeatureId::Face(ids[0])
+                        } else {
+                            FeatureId::Unknown
+                        };
+
+                        if lambdas.iter().all(|l| *l >= N::zero()) {
+                            return (false, candidate, feature);
+                        }
+
+                        let dist = na::distance_squared(pt, &candidate);
+
+                        if dist < best.0 {
+                            best = (dist, candidate, feature);
+                        }
+                    }
+                }
+
+                if !next_combination(&mut ids, KDOP_NUM_AXES * 2) {
+                    break;
+                }
+            }
+        }
+
+        (false, best.1, best.2)
+    }
+}
+
+// Advances `ids` to the next strictly increasing sequence of indices smaller than `n`.
+fn next_combination(ids: &mut [usize], n: usize) -> bool {
+    let k = ids.len();
+
+    for i in (0..k).rev() {
+        if ids[i] < n - k + i {
+            ids[i] += 1;
+
+            for j in i + 1..k {
+                ids[j] = ids[j - 1] + 1;
+            }
+
+            return true;
+        }
+    }
+
+    false
+}
+
+impl<N: RealField> PointQuery<N> for KDOP<N> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        let ls_pt = m.inverse_transform_point(pt);
+
+        if solid && self.contains_local_point(&ls_pt) {
+            return PointProjection::new(true, *pt);
+        }
+
+        let (inside, ls_proj, _) = self.local_boundary_projection(&ls_pt);
+        PointProjection::new(inside, m * ls_proj)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        let ls_pt = m.inverse_transform_point(pt);
+        let (inside, ls_proj, feature) = self.local_boundary_projection(&ls_pt);
+
+        (PointProjection::new(inside, m * ls_proj), feature)
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        let ls_pt = m.inverse_transform_point(pt);
+        self.contains_local_point(&ls_pt)
+    }
+}
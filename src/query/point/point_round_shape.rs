@@ -0,0 +1,62 @@
+use crate::math::{Isometry, Point};
+use crate::query::algorithms::VoronoiSimplex;
+use crate::query::{point_projection_on_support_map, PointProjection, PointQuery};
+use crate::shape::{FeatureId, RoundShape, SupportMap};
+use na::{RealField, Unit};
+
+impl<N: RealField, S: PointQuery<N> + SupportMap<N>> RoundShape<N, S> {
+    // Computes the projection on `self` from the projection `inner` of the same point on the
+    // inner shape, computed with `solid` set to `false`.
+    fn dilate_projection(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+        inner: PointProjection<N>,
+        solid: bool,
+    ) -> PointProjection<N> {
+        if inner.is_inside {
+            if solid {
+                return PointProjection::new(true, *pt);
+            }
+
+            if let Some(dir) = Unit::try_new(inner.point - *pt, N::default_epsilon()) {
+                return PointProjection::new(true, inner.point + dir.into_inner() * self.radius());
+            }
+        } else if let Some((dir, dist)) =
+            Unit::try_new_and_get(*pt - inner.point, N::default_epsilon())
+        {
+            let inside = dist <= self.radius();
+
+            if solid && inside {
+                return PointProjection::new(true, *pt);
+            } else {
+                return PointProjection::new(
+                    inside,
+                    inner.point + dir.into_inner() * self.radius(),
+                );
+            }
+        }
+
+        // The point lies on the boundary of the inner shape, so the projection direction is
+        // not given by the projection on the inner shape.
+        point_projection_on_support_map(m, self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+}
+
+impl<N: RealField, S: PointQuery<N> + SupportMap<N>> PointQuery<N> for RoundShape<N, S> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        let inner = self.inner_shape().project_point(m, pt, false);
+        self.dilate_projection(m, pt, inner, solid)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        let (inner, feature) = self.inner_shape().project_point_with_feature(m, pt);
+        (self.dilate_projection(m, pt, inner, false), feature)
+    }
+}
@@ -0,0 +1,94 @@
+use na::{RealField, Unit};
+
+use crate::math::{Isometry, Point};
+use crate::query::algorithms::VoronoiSimplex;
+use crate::query::visitors::CompositeClosestPointVisitor;
+use crate::query::{point_projection_on_support_map, PointProjection, PointQuery};
+use crate::shape::{CompositeShape, ConvexPolyhedron, FeatureId, ScalableShape, Scaled};
+
+impl<N: RealField, S: ScalableShape<N>> Scaled<N, S> {
+    #[inline]
+    fn inner_point_query(&self) -> &dyn PointQuery<N> {
+        self.inner_shape()
+            .shape()
+            .as_point_query()
+            .expect("The inner shape of this scaled shape does not support point queries.")
+    }
+}
+
+// NOTE: the projection is exact if the inner shape is a support map or a composite shape, in which
+// case the closest point is searched among the scaled parts. Otherwise, it is computed on the
+// inner shape and scaled back, which only approximates the closest point if the scaling is not
+// uniform.
+impl<N: RealField, S: ScalableShape<N>> PointQuery<N> for Scaled<N, S> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        if self.inner_shape().shape().is_support_map() {
+            point_projection_on_support_map(m, self, &mut VoronoiSimplex::new(), pt, solid)
+        } else if self.is_composite() {
+            let ls_pt = m.inverse_transform_point(pt);
+            let mut visitor = CompositeClosestPointVisitor::new(self, &ls_pt, solid);
+            let mut proj = self.bvh().best_first_search(&mut visitor).unwrap().1;
+            proj.point = m * proj.point;
+
+            proj
+        } else {
+            let inner_pt = self.unscale_point(&m.inverse_transform_point(pt));
+            let proj =
+                self.inner_point_query()
+                    .project_point(&Isometry::identity(), &inner_pt, solid);
+
+            PointProjection::new(proj.is_inside, m * self.scale_point(&proj.point))
+        }
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        if self.is_composite() {
+            // XXX: propagate the feature id of the closest part.
+            return (self.project_point(m, pt, false), FeatureId::Unknown);
+        }
+
+        let inner_pt = self.unscale_point(&m.inverse_transform_point(pt));
+        let (proj, feature) = self
+            .inner_point_query()
+            .project_point_with_feature(&Isometry::identity(), &inner_pt);
+
+        if self.inner_shape().shape().is_support_map() {
+            let proj = self.project_point(m, pt, false);
+            let mut feature = feature;
+
+            if self.inner_shape().shape().is_convex_polyhedron() {
+                // The direction from the projection to the point is in the normal cone of the
+                // feature containing the projection.
+                let dir = if proj.is_inside {
+                    proj.point - *pt
+                } else {
+                    *pt - proj.point
+                };
+
+                if let Some(local_dir) =
+                    Unit::try_new(m.inverse_transform_vector(&dir), N::default_epsilon())
+                {
+                    feature = self.support_feature_id_toward(&local_dir);
+                }
+            }
+
+            (proj, feature)
+        } else {
+            let proj = PointProjection::new(proj.is_inside, m * self.scale_point(&proj.point));
+            (proj, feature)
+        }
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        let inner_pt = self.unscale_point(&m.inverse_transform_point(pt));
+        self.inner_point_query()
+            .contains_point(&Isometry::identity(), &inner_pt)
+    }
+}
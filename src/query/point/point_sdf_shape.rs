@@ -0,0 +1,71 @@
+use crate::math::{Isometry, Point};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::{FeatureId, SDFShape};
+use na::{self, RealField};
+
+// Maximum number of Newton iterations used to project a point on the zero level set.
+const MAX_PROJECTION_ITERATIONS: usize = 10;
+
+impl<N: RealField> PointQuery<N> for SDFShape<N> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        let ls_pt = m.inverse_transform_point(pt);
+        let dist = self.distance_at(&ls_pt);
+
+        if solid && dist <= N::zero() {
+            return PointProjection::new(true, *pt);
+        }
+
+        let proj = project_on_surface(self, &ls_pt, dist);
+        PointProjection::new(dist < N::zero(), m * proj)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        // The surface is assumed to be smooth.
+        (self.project_point(m, pt, false), FeatureId::Face(0))
+    }
+
+    #[inline]
+    fn distance_to_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> N {
+        let dist = self.distance_at(&m.inverse_transform_point(pt));
+
+        if solid && dist < N::zero() {
+            N::zero()
+        } else {
+            dist
+        }
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        self.distance_at(&m.inverse_transform_point(pt)) <= N::zero()
+    }
+}
+
+// Moves the local point `pt`, at the signed distance `dist`, along the gradient of the field
+// until it reaches the surface.
+fn project_on_surface<N: RealField>(sdf: &SDFShape<N>, pt: &Point<N>, dist: N) -> Point<N> {
+    let tolerance = sdf.spacing().amin() * na::convert(1.0e-6);
+    let mut proj = *pt;
+    let mut dist = dist;
+
+    for _ in 0..MAX_PROJECTION_ITERATIONS {
+        if dist.abs() <= tolerance {
+            break;
+        }
+
+        match sdf.gradient_at(&proj).try_normalize(N::default_epsilon()) {
+            Some(normal) => proj -= normal * dist,
+            None => break,
+        }
+
+        dist = sdf.distance_at(&proj);
+    }
+
+    proj
+}
@@ -1,10 +1,7 @@
-use crate::bounding_volume::AABB;
 use crate::math::{Isometry, Point};
-use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
-use crate::query::{
-    visitors::CompositePointContainmentTest, PointProjection, PointQuery, PointQueryWithLocation,
-};
-use crate::shape::{CompositeShape, FeatureId, TriMesh, TrianglePointLocation};
+use crate::partitioning::VisitStatus;
+use crate::query::{PointProjection, PointQuery, PointQueryWithLocation};
+use crate::shape::{FeatureId, TriMesh, TrianglePointLocation};
 use na::{self, RealField};
 
 impl<N: RealField> PointQuery<N> for TriMesh<N> {
@@ -28,15 +25,24 @@ impl<N: RealField> PointQuery<N> for TriMesh<N> {
     #[inline]
     fn contains_point(&self, m: &Isometry<N>, point: &Point<N>) -> bool {
         let ls_pt = m.inverse_transform_point(point);
-        let mut visitor = CompositePointContainmentTest {
-            shape: self,
-            point: &ls_pt,
-            found: false,
-        };
-
-        self.bvh().visit(&mut visitor);
-
-        visitor.found
+        let mut found = false;
+
+        self.qbvh().quad_visit(
+            |aabbs| aabbs.contains_point(&ls_pt),
+            |i| {
+                found = self
+                    .triangle_at(*i)
+                    .contains_point(&Isometry::identity(), &ls_pt);
+
+                if found {
+                    VisitStatus::ExitEarly
+                } else {
+                    VisitStatus::Continue
+                }
+            },
+        );
+
+        found
     }
 }
 
@@ -51,59 +57,22 @@ impl<N: RealField> PointQueryWithLocation<N> for TriMesh<N> {
         _: bool,
     ) -> (PointProjection<N>, Self::Location) {
         let ls_pt = m.inverse_transform_point(point);
-        let mut visitor = TriMeshPointProjVisitor {
-            polyline: self,
-            point: &ls_pt,
-        };
-
-        let (mut proj, extra_info) = self.bvh().best_first_search(&mut visitor).unwrap().1;
+        let (mut proj, extra_info) = self
+            .qbvh()
+            .quad_best_first_search(
+                |aabbs| aabbs.distance_to_point(&ls_pt),
+                |_, i| {
+                    let (proj, location) = self.triangle_at(*i).project_point_with_location(
+                        &Isometry::identity(),
+                        &ls_pt,
+                        true,
+                    );
+                    Some((na::distance(&ls_pt, &proj.point), (proj, (*i, location))))
+                },
+            )
+            .unwrap();
         proj.point = m * proj.point;
 
         (proj, extra_info)
     }
 }
-
-/*
- * Visitors
- */
-struct TriMeshPointProjVisitor<'a, N: 'a + RealField> {
-    polyline: &'a TriMesh<N>,
-    point: &'a Point<N>,
-}
-
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>> for TriMeshPointProjVisitor<'a, N> {
-    type Result = (PointProjection<N>, (usize, TrianglePointLocation<N>));
-
-    #[inline]
-    fn visit(
-        &mut self,
-        best: N,
-        aabb: &AABB<N>,
-        data: Option<&usize>,
-    ) -> BestFirstVisitStatus<N, Self::Result> {
-        let dist = aabb.distance_to_point(&Isometry::identity(), self.point, true);
-
-        let mut res = BestFirstVisitStatus::Continue {
-            cost: dist,
-            result: None,
-        };
-
-        if let Some(b) = data {
-            if dist < best {
-                let (proj, extra_info) = self.polyline.triangle_at(*b).project_point_with_location(
-                    &Isometry::identity(),
-                    self.point,
-                    true,
-                );
-
-                let extra_info = (*b, extra_info);
-                res = BestFirstVisitStatus::Continue {
-                    cost: na::distance(self.point, &proj.point),
-                    result: Some((proj, extra_info)),
-                };
-            }
-        }
-
-        res
-    }
-}
@@ -0,0 +1,208 @@
+use crate::bounding_volume::AABB;
+use crate::math::{Isometry, Point};
+use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor, BVH};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::{FeatureId, Voxels};
+use na::{self, Point3, RealField};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+impl<N: RealField> PointQuery<N> for Voxels<N> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, point: &Point<N>, solid: bool) -> PointProjection<N> {
+        let ls_pt = m.inverse_transform_point(point);
+
+        if solid && self.is_filled(&self.voxel_at_point(&ls_pt)) {
+            return PointProjection::new(true, *point);
+        }
+
+        let mut proj = local_project_point_with_feature(self, &ls_pt).0;
+        proj.point = m * proj.point;
+        proj
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        point: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        let ls_pt = m.inverse_transform_point(point);
+        let (mut proj, feature) = local_project_point_with_feature(self, &ls_pt);
+        proj.point = m * proj.point;
+        (proj, feature)
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, point: &Point<N>) -> bool {
+        let ls_pt = m.inverse_transform_point(point);
+        self.is_filled(&self.voxel_at_point(&ls_pt))
+    }
+}
+
+// Projects a local point on the boundary of the voxels.
+fn local_project_point_with_feature<N: RealField>(
+    voxels: &Voxels<N>,
+    pt: &Point<N>,
+) -> (PointProjection<N>, FeatureId) {
+    let cell = voxels.voxel_at_point(pt);
+
+    if voxels.is_filled(&cell) {
+        return project_inner_point(voxels, pt, cell);
+    }
+
+    let mut visitor = VoxelsPointProjVisitor { voxels, point: pt };
+
+    voxels
+        .dbvt()
+        .best_first_search(&mut visitor)
+        .map(|res| res.1)
+        .unwrap_or((PointProjection::new(false, *pt), FeatureId::Unknown))
+}
+
+// Projects a point contained by the filled cell `start` on the closest face shared with an
+// empty cell, exploring the filled cells by increasing distance to the point.
+fn project_inner_point<N: RealField>(
+    voxels: &Voxels<N>,
+    pt: &Point<N>,
+    start: Point3<i32>,
+) -> (PointProjection<N>, FeatureId) {
+    let mut queue = BinaryHeap::new();
+    let mut visited = HashSet::new();
+    let mut best = (N::max_value(), *pt, FeatureId::Unknown);
+
+    let _ = visited.insert(start);
+    queue.push(CellCandidate {
+        dist: N::zero(),
+        cell: start,
+    });
+
+    while let Some(candidate) = queue.pop() {
+        if candidate.dist >= best.0 {
+            break;
+        }
+
+        let cell = candidate.cell;
+        let aabb = voxels.voxel_aabb(&cell);
+        let part_id = voxels.part_id(&cell).unwrap();
+
+        for i in 0..3 {
+            for &(shift, face) in &[(1, i), (-1, i + 3)] {
+                let mut neighbor = cell;
+                neighbor[i] += shift;
+
+                if voxels.is_filled(&neighbor) {
+                    if visited.insert(neighbor) {
+                        let dist = voxels.voxel_aabb(&neighbor).distance_to_point(
+                            &Isometry::identity(),
+                            pt,
+                            true,
+                        );
+                        queue.push(CellCandidate {
+                            dist,
+                            cell: neighbor,
+                        });
+                    }
+                } else {
+                    let mut proj = *pt;
+
+                    for j in 0..3 {
+                        proj[j] = if j != i {
+                            na::clamp(pt[j], aabb.mins()[j], aabb.maxs()[j])
+                        } else if shift > 0 {
+                            aabb.maxs()[j]
+                        } else {
+                            aabb.mins()[j]
+                        };
+                    }
+
+                    let dist = na::distance(pt, &proj);
+
+                    if dist < best.0 {
+                        best = (
+                            dist,
+                            proj,
+                            voxels.part_feature_id(part_id, FeatureId::Face(face)),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    (PointProjection::new(true, best.1), best.2)
+}
+
+// A cell to be explored, ordered such that the closest cell has the greatest priority.
+struct CellCandidate<N> {
+    dist: N,
+    cell: Point3<i32>,
+}
+
+impl<N: PartialEq> PartialEq for CellCandidate<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.dist.eq(&other.dist)
+    }
+}
+
+impl<N: PartialEq> Eq for CellCandidate<N> {}
+
+impl<N: PartialOrd> PartialOrd for CellCandidate<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: PartialOrd> Ord for CellCandidate<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .partial_cmp(&self.dist)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/*
+ * Visitors
+ */
+struct VoxelsPointProjVisitor<'a, N: 'a + RealField> {
+    voxels: &'a Voxels<N>,
+    point: &'a Point<N>,
+}
+
+impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>> for VoxelsPointProjVisitor<'a, N> {
+    type Result = (PointProjection<N>, FeatureId);
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best: N,
+        aabb: &AABB<N>,
+        data: Option<&usize>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        let dist = aabb.distance_to_point(&Isometry::identity(), self.point, true);
+
+        let mut res = BestFirstVisitStatus::Continue {
+            cost: dist,
+            result: None,
+        };
+
+        if let Some(part_id) = data {
+            if dist < best {
+                // The leaf bounding volumes are exactly the voxels.
+                let (proj, feature) =
+                    aabb.project_point_with_feature(&Isometry::identity(), self.point);
+
+                res = BestFirstVisitStatus::Continue {
+                    cost: dist,
+                    result: Some((proj, self.voxels.part_feature_id(*part_id, feature))),
+                };
+            }
+        }
+
+        res
+    }
+}
@@ -15,12 +15,19 @@ mod ray_ball;
 mod ray_bounding_sphere;
 mod ray_compound;
 mod ray_cuboid;
+mod ray_ellipsoid;
 mod ray_heightfield;
+mod ray_kdop;
+mod ray_obb;
 mod ray_plane;
 mod ray_polyline;
+mod ray_scaled;
+mod ray_sdf_shape;
 mod ray_shape;
 mod ray_support_map;
 #[cfg(feature = "dim3")]
 mod ray_triangle;
 #[cfg(feature = "dim3")]
 mod ray_trimesh;
+#[cfg(feature = "dim3")]
+mod ray_voxels;
@@ -0,0 +1,48 @@
+use na::RealField;
+
+use crate::math::{Isometry, Point, Vector};
+use crate::query::{ray_toi_with_ball, Ray, RayCast, RayIntersection};
+use crate::shape::{Ellipsoid, FeatureId};
+
+impl<N: RealField> Ellipsoid<N> {
+    // Casts the local-space ray `ls_ray` on the unit ball obtained by scaling down this ellipsoid.
+    //
+    // The time of impact is invariant under this scaling.
+    #[inline]
+    fn local_toi_with_ray(&self, ls_ray: &Ray<N>, solid: bool) -> (bool, Option<N>) {
+        let unit_ray = Ray::new(
+            Point::from(ls_ray.origin.coords.component_div(self.radii())),
+            ls_ray.dir.component_div(self.radii()),
+        );
+
+        ray_toi_with_ball(&Point::origin(), N::one(), &unit_ray, solid)
+    }
+}
+
+impl<N: RealField> RayCast<N> for Ellipsoid<N> {
+    #[inline]
+    fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, solid: bool) -> Option<N> {
+        let ls_ray = ray.inverse_transform_by(m);
+        self.local_toi_with_ray(&ls_ray, solid).1
+    }
+
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+        let (inside, toi) = self.local_toi_with_ray(&ls_ray, solid);
+
+        toi.map(|toi| {
+            let normal = self
+                .local_normal_at(&ls_ray.point_at(toi))
+                .map_or(Vector::zeros(), |n| n.into_inner());
+            let normal = if inside { -normal } else { normal };
+
+            RayIntersection::new(toi, m * normal, FeatureId::Face(0))
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::bounding_volume::{KDOP, KDOP_NUM_AXES};
+use crate::math::{Isometry, Vector};
+use crate::num::Bounded;
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::FeatureId;
+use na::{self, RealField};
+
+impl<N: RealField> RayCast<N> for KDOP<N> {
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        clip_ray(self, &ls_ray).map(|(near, far)| {
+            let (toi, normal, feature) = if near.0 >= N::zero() {
+                near
+            } else if solid {
+                (na::zero(), na::zero(), far.2)
+            } else {
+                far
+            };
+
+            RayIntersection::new(toi, m * normal, feature)
+        })
+    }
+}
+
+// A ray parameter, with the outward normal and the id of the face it is on.
+type Clip<N> = (N, Vector<N>, FeatureId);
+
+// The entry and exit points of a ray.
+// The entry point has a negative parameter if the ray starts inside of the KDOP.
+fn clip_ray<N: RealField>(kdop: &KDOP<N>, ray: &Ray<N>) -> Option<(Clip<N>, Clip<N>)> {
+    let mut tmax: N = Bounded::max_value();
+    let mut tmin: N = -tmax;
+    let mut near = (Vector::zeros(), FeatureId::Unknown);
+    let mut far = (Vector::zeros(), FeatureId::Unknown);
+
+    for i in 0..KDOP_NUM_AXES {
+        let axis = KDOP::axis(i);
+        let origin = axis.dot(&ray.origin.coords);
+        let dir = axis.dot(&ray.dir);
+
+        if dir.is_zero() {
+            if origin < kdop.mins()[i] || origin > kdop.maxs()[i] {
+                return None;
+            }
+        } else {
+            let inter_with_min_plane = (kdop.mins()[i] - origin) / dir;
+            let inter_with_max_plane = (kdop.maxs()[i] - origin) / dir;
+            let normal = axis.normalize();
+            let min_side = (-normal, FeatureId::Face(i + KDOP_NUM_AXES));
+            let max_side = (normal, FeatureId::Face(i));
+
+            let (near_inter, near_side, far_inter, far_side) = if dir > N::zero() {
+                (
+                    inter_with_min_plane,
+                    min_side,
+                    inter_with_max_plane,
+                    max_side,
+                )
+            } else {
+                (
+                    inter_with_max_plane,
+                    max_side,
+                    inter_with_min_plane,
+                    min_side,
+                )
+            };
+
+            if near_inter > tmin {
+                tmin = near_inter;
+                near = near_side;
+            }
+
+            if far_inter < tmax {
+                tmax = far_inter;
+                far = far_side;
+            }
+
+            if tmax < N::zero() || tmin > tmax {
+                return None;
+            }
+        }
+    }
+
+    Some(((tmin, near.0, near.1), (tmax, far.0, far.1)))
+}
@@ -0,0 +1,46 @@
+use crate::bounding_volume::OBB;
+use crate::math::Isometry;
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::Cuboid;
+use na::RealField;
+
+impl<N: RealField> RayCast<N> for OBB<N> {
+    #[inline]
+    fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, solid: bool) -> Option<N> {
+        Cuboid::new(*self.half_extents()).toi_with_ray(&(m * self.position()), ray, solid)
+    }
+
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        Cuboid::new(*self.half_extents()).toi_and_normal_with_ray(
+            &(m * self.position()),
+            ray,
+            solid,
+        )
+    }
+
+    #[cfg(feature = "dim3")]
+    #[inline]
+    fn toi_and_normal_and_uv_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        Cuboid::new(*self.half_extents()).toi_and_normal_and_uv_with_ray(
+            &(m * self.position()),
+            ray,
+            solid,
+        )
+    }
+
+    #[inline]
+    fn intersects_ray(&self, m: &Isometry<N>, ray: &Ray<N>) -> bool {
+        Cuboid::new(*self.half_extents()).intersects_ray(&(m * self.position()), ray)
+    }
+}
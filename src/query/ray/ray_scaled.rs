@@ -0,0 +1,67 @@
+use na::RealField;
+
+use crate::math::Isometry;
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::{ScalableShape, Scaled};
+
+impl<N: RealField, S: ScalableShape<N>> Scaled<N, S> {
+    // Expresses the local-space ray `ls_ray` in the local-space of the inner shape.
+    //
+    // The time of impact is invariant under this scaling.
+    #[inline]
+    fn unscale_ray(&self, ls_ray: &Ray<N>) -> Ray<N> {
+        Ray::new(
+            self.unscale_point(&ls_ray.origin),
+            ls_ray.dir.component_div(self.scale()),
+        )
+    }
+}
+
+impl<N: RealField, S: ScalableShape<N>> RayCast<N> for Scaled<N, S> {
+    #[inline]
+    fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, solid: bool) -> Option<N> {
+        let inner_ray = self.unscale_ray(&ray.inverse_transform_by(m));
+        self.inner_shape().shape().as_ray_cast()?.toi_with_ray(
+            &Isometry::identity(),
+            &inner_ray,
+            solid,
+        )
+    }
+
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let inner_ray = self.unscale_ray(&ray.inverse_transform_by(m));
+        let mut inter = self
+            .inner_shape()
+            .shape()
+            .as_ray_cast()?
+            .toi_and_normal_with_ray(&Isometry::identity(), &inner_ray, solid)?;
+
+        inter.normal = m * self.scale_normal(&inter.normal);
+        Some(inter)
+    }
+
+    #[cfg(feature = "dim3")]
+    #[inline]
+    fn toi_and_normal_and_uv_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let inner_ray = self.unscale_ray(&ray.inverse_transform_by(m));
+        let mut inter = self
+            .inner_shape()
+            .shape()
+            .as_ray_cast()?
+            .toi_and_normal_and_uv_with_ray(&Isometry::identity(), &inner_ray, solid)?;
+
+        inter.normal = m * self.scale_normal(&inter.normal);
+        Some(inter)
+    }
+}
@@ -0,0 +1,111 @@
+use na::{self, RealField};
+
+use crate::math::{Isometry, Vector};
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::{FeatureId, SDFShape};
+
+// Maximum number of steps of the sphere tracing.
+const MAX_STEPS: usize = 1024;
+// Maximum number of bisections refining a time of impact after a step crossed the surface.
+const MAX_BISECTIONS: usize = 64;
+
+impl<N: RealField> SDFShape<N> {
+    // Sphere-traces the local-space ray `ls_ray`.
+    //
+    // Returns the time of impact and whether the ray started inside of the shape.
+    fn local_toi_with_ray(&self, ls_ray: &Ray<N>, solid: bool) -> Option<(N, bool)> {
+        let (t0, t1) = self.aabb().clip_ray_parameters(ls_ray)?;
+        let dir_norm = ls_ray.dir.norm();
+        let tolerance = self.spacing().amin() * na::convert(1.0e-6);
+        let start = self.distance_at(&ls_ray.point_at(t0));
+        let inside = start < N::zero();
+
+        if inside && solid {
+            return Some((N::zero(), true));
+        }
+
+        if dir_norm.is_zero() {
+            return None;
+        }
+
+        // When starting inside, we trace the surface from the inside.
+        let sign = if inside { -N::one() } else { N::one() };
+        let dist_at = |t| self.distance_at(&ls_ray.point_at(t)) * sign;
+        let mut prev_t = t0;
+        let mut t = t0;
+        let mut dist = start * sign;
+
+        for _ in 0..MAX_STEPS {
+            if dist <= tolerance {
+                if dist < N::zero() {
+                    // We stepped over the surface: refine the time of impact between the last
+                    // two steps.
+                    let mut t_outside = prev_t;
+
+                    for _ in 0..MAX_BISECTIONS {
+                        let mid = (t_outside + t) * na::convert(0.5);
+                        let dist_mid = dist_at(mid);
+
+                        if dist_mid < N::zero() {
+                            t = mid;
+                        } else if dist_mid <= tolerance {
+                            t = mid;
+                            break;
+                        } else {
+                            t_outside = mid;
+                        }
+                    }
+                }
+
+                return Some((t, inside));
+            }
+
+            prev_t = t;
+            t += dist.max(tolerance) / dir_norm;
+
+            if t > t1 {
+                if prev_t < t1 {
+                    t = t1;
+                } else {
+                    return None;
+                }
+            }
+
+            dist = dist_at(t);
+        }
+
+        None
+    }
+}
+
+impl<N: RealField> RayCast<N> for SDFShape<N> {
+    #[inline]
+    fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, solid: bool) -> Option<N> {
+        let ls_ray = ray.inverse_transform_by(m);
+        self.local_toi_with_ray(&ls_ray, solid).map(|res| res.0)
+    }
+
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        self.local_toi_with_ray(&ls_ray, solid)
+            .map(|(toi, inside)| {
+                let normal = if inside && solid {
+                    Vector::zeros()
+                } else {
+                    self.gradient_at(&ls_ray.point_at(toi))
+                        .try_normalize(N::default_epsilon())
+                        .unwrap_or(Vector::zeros())
+                };
+                let normal = if inside { -normal } else { normal };
+
+                RayIntersection::new(toi, m * normal, FeatureId::Face(0))
+            })
+    }
+}
@@ -7,7 +7,7 @@ use crate::query::algorithms::{gjk, CSOPoint, VoronoiSimplex};
 use crate::query::{Ray, RayCast, RayIntersection};
 #[cfg(feature = "dim2")]
 use crate::shape::ConvexPolygon;
-use crate::shape::{Capsule, FeatureId, Segment, SupportMap};
+use crate::shape::{Capsule, FeatureId, RoundShape, Segment, SupportMap};
 #[cfg(feature = "dim3")]
 use crate::shape::{Cone, ConvexHull, Cylinder};
 
@@ -123,6 +123,29 @@ impl<N: RealField> RayCast<N> for Capsule<N> {
     }
 }
 
+impl<N: RealField, S: SupportMap<N>> RayCast<N> for RoundShape<N, S> {
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        ray_intersection_with_support_map_with_params(
+            &Isometry::identity(),
+            self,
+            &mut VoronoiSimplex::new(),
+            &ls_ray,
+            solid,
+        )
+        .map(|mut res| {
+            res.normal = m * res.normal;
+            res
+        })
+    }
+}
+
 #[cfg(feature = "dim3")]
 impl<N: RealField> RayCast<N> for ConvexHull<N> {
     fn toi_and_normal_with_ray(
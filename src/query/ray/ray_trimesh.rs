@@ -1,21 +1,23 @@
-use crate::bounding_volume::AABB;
 use crate::math::Isometry;
-use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
 use crate::query::{self, Ray, RayCast, RayIntersection};
-use crate::shape::{CompositeShape, FeatureId, TriMesh};
-use na::{Point2, RealField, Vector3};
+use crate::shape::{FeatureId, TriMesh};
+use na::{Point2, RealField};
 
 impl<N: RealField> RayCast<N> for TriMesh<N> {
     #[inline]
     fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, _: bool) -> Option<N> {
         let ls_ray = ray.inverse_transform_by(m);
 
-        let mut visitor = TriMeshRayToiVisitor {
-            mesh: self,
-            ray: &ls_ray,
-        };
-
-        self.bvh().best_first_search(&mut visitor).map(|res| res.1)
+        self.qbvh().quad_best_first_search(
+            |aabbs| aabbs.toi_with_ray(&ls_ray),
+            |_, i| {
+                // FIXME: optimize this by not using Isometry identity.
+                let toi =
+                    self.triangle_at(*i)
+                        .toi_with_ray(&Isometry::identity(), &ls_ray, true)?;
+                Some((toi, toi))
+            },
+        )
     }
 
     #[inline]
@@ -27,14 +29,20 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
     ) -> Option<RayIntersection<N>> {
         let ls_ray = ray.inverse_transform_by(m);
 
-        let mut visitor = TriMeshRayToiAndNormalVisitor {
-            mesh: self,
-            ray: &ls_ray,
-        };
-
-        self.bvh()
-            .best_first_search(&mut visitor)
-            .map(|(_, (best, mut res))| {
+        self.qbvh()
+            .quad_best_first_search(
+                |aabbs| aabbs.toi_with_ray(&ls_ray),
+                |_, i| {
+                    // FIXME: optimize this by not using Isometry identity.
+                    let inter = self.triangle_at(*i).toi_and_normal_with_ray(
+                        &Isometry::identity(),
+                        &ls_ray,
+                        true,
+                    )?;
+                    Some((inter.toi, (*i, inter)))
+                },
+            )
+            .map(|(best, mut res)| {
                 if let FeatureId::Face(1) = res.feature {
                     res.feature = FeatureId::Face(best + self.faces().len());
                 } else {
@@ -58,13 +66,22 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
 
         let ls_ray = ray.inverse_transform_by(m);
 
-        let mut visitor = TriMeshRayToiAndNormalAndUVsVisitor {
-            mesh: self,
-            ray: &ls_ray,
-        };
-        let cast = self.bvh().best_first_search(&mut visitor);
-
-        cast.map(|(_, (best, inter, uv))| {
+        let cast = self.qbvh().quad_best_first_search(
+            |aabbs| aabbs.toi_with_ray(&ls_ray),
+            |_, i| {
+                let vs = self.points();
+                let idx = self.faces()[*i].indices;
+                let (inter, uv) = query::ray_intersection_with_triangle(
+                    &vs[idx[0]],
+                    &vs[idx[1]],
+                    &vs[idx[2]],
+                    &ls_ray,
+                )?;
+                Some((inter.toi, (*i, inter, uv)))
+            },
+        );
+
+        cast.map(|(best, inter, uv)| {
             let toi = inter.toi;
             let n = inter.normal;
 
@@ -88,141 +105,3 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
         })
     }
 }
-
-/*
- * Costs functions.
- */
-struct TriMeshRayToiVisitor<'a, N: 'a + RealField> {
-    mesh: &'a TriMesh<N>,
-    ray: &'a Ray<N>,
-}
-
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>> for TriMeshRayToiVisitor<'a, N> {
-    type Result = N;
-
-    #[inline]
-    fn visit(
-        &mut self,
-        best: N,
-        aabb: &AABB<N>,
-        data: Option<&usize>,
-    ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, true) {
-            let mut res = BestFirstVisitStatus::Continue {
-                cost: toi,
-                result: None,
-            };
-
-            if let Some(b) = data {
-                if toi < best {
-                    // FIXME: optimize this by not using Isometry identity.
-                    let triangle = self.mesh.triangle_at(*b);
-                    if let Some(toi) = triangle.toi_with_ray(&Isometry::identity(), self.ray, true)
-                    {
-                        res = BestFirstVisitStatus::Continue {
-                            cost: toi,
-                            result: Some(toi),
-                        }
-                    }
-                }
-            }
-
-            res
-        } else {
-            BestFirstVisitStatus::Stop
-        }
-    }
-}
-
-struct TriMeshRayToiAndNormalVisitor<'a, N: 'a + RealField> {
-    mesh: &'a TriMesh<N>,
-    ray: &'a Ray<N>,
-}
-
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
-    for TriMeshRayToiAndNormalVisitor<'a, N>
-{
-    type Result = (usize, RayIntersection<N>);
-
-    #[inline]
-    fn visit(
-        &mut self,
-        best: N,
-        aabb: &AABB<N>,
-        data: Option<&usize>,
-    ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, true) {
-            let mut res = BestFirstVisitStatus::Continue {
-                cost: toi,
-                result: None,
-            };
-
-            if let Some(b) = data {
-                if toi < best {
-                    // FIXME: optimize this by not using Isometry identity.
-                    let triangle = self.mesh.triangle_at(*b);
-                    if let Some(toi) =
-                        triangle.toi_and_normal_with_ray(&Isometry::identity(), self.ray, true)
-                    {
-                        res = BestFirstVisitStatus::Continue {
-                            cost: toi.toi,
-                            result: Some((*b, toi)),
-                        };
-                    }
-                }
-            }
-
-            res
-        } else {
-            BestFirstVisitStatus::Stop
-        }
-    }
-}
-
-struct TriMeshRayToiAndNormalAndUVsVisitor<'a, N: 'a + RealField> {
-    mesh: &'a TriMesh<N>,
-    ray: &'a Ray<N>,
-}
-
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
-    for TriMeshRayToiAndNormalAndUVsVisitor<'a, N>
-{
-    type Result = (usize, RayIntersection<N>, Vector3<N>);
-
-    #[inline]
-    fn visit(
-        &mut self,
-        best: N,
-        aabb: &AABB<N>,
-        data: Option<&usize>,
-    ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, true) {
-            let mut res = BestFirstVisitStatus::Continue {
-                cost: toi,
-                result: None,
-            };
-
-            if let Some(i) = data {
-                if toi < best {
-                    let vs = self.mesh.points();
-                    let idx = self.mesh.faces()[*i].indices;
-
-                    let a = &vs[idx[0]];
-                    let b = &vs[idx[1]];
-                    let c = &vs[idx[2]];
-
-                    if let Some(inter) = query::ray_intersection_with_triangle(a, b, c, self.ray) {
-                        res = BestFirstVisitStatus::Continue {
-                            cost: inter.0.toi,
-                            result: Some((*i, inter.0, inter.1)),
-                        };
-                    }
-                }
-            }
-
-            res
-        } else {
-            BestFirstVisitStatus::Stop
-        }
-    }
-}
@@ -0,0 +1,133 @@
+use crate::math::{Isometry, Vector};
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::{FeatureId, Voxels};
+use na::{self, Point3, RealField};
+
+impl<N: RealField> RayCast<N> for Voxels<N> {
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        local_toi_and_normal_with_ray(self, &ls_ray, solid).map(|mut inter| {
+            inter.normal = m * inter.normal;
+            inter
+        })
+    }
+}
+
+// Traverses the cells crossed by the ray with a 3D-DDA (Amanatides & Woo).
+fn local_toi_and_normal_with_ray<N: RealField>(
+    voxels: &Voxels<N>,
+    ray: &Ray<N>,
+    solid: bool,
+) -> Option<RayIntersection<N>> {
+    let (t_enter, t_exit) = voxels.root_aabb()?.clip_ray_parameters(ray)?;
+    let size = voxels.voxel_size();
+    let entry = ray.point_at(t_enter);
+    let mut cell = voxels.voxel_at_point(&entry);
+    let mut step = Point3::origin();
+    let mut t_max = Vector::repeat(N::max_value());
+    let mut t_delta = Vector::repeat(N::max_value());
+    // The axis of the last cell boundary crossed by the ray, and the time it was crossed.
+    let mut axis = None;
+    let mut t_cell = -N::max_value();
+
+    for i in 0..3 {
+        if ray.dir[i] > N::zero() {
+            step[i] = 1;
+        } else if ray.dir[i] < N::zero() {
+            step[i] = -1;
+
+            // The ray leaves the boundary of a cell toward the cell below it.
+            if entry[i] == na::convert::<_, N>(cell[i] as f64) * size[i] {
+                cell[i] -= 1;
+            }
+        } else {
+            continue;
+        }
+
+        let mins = na::convert::<_, N>(cell[i] as f64) * size[i];
+        let (near, far) = if step[i] > 0 {
+            (mins, mins + size[i])
+        } else {
+            (mins + size[i], mins)
+        };
+        let t_near = (near - ray.origin[i]) / ray.dir[i];
+
+        t_max[i] = (far - ray.origin[i]) / ray.dir[i];
+        t_delta[i] = size[i] / ray.dir[i].abs();
+
+        if t_near > t_cell {
+            t_cell = t_near;
+            axis = Some(i);
+        }
+    }
+
+    let mut t = t_enter;
+    // Set if the ray starts inside of a voxel, in which case we look for the first empty cell.
+    let exiting = t_cell < N::zero() && voxels.is_filled(&cell);
+    let mut prev_part = None;
+
+    if exiting && solid {
+        return Some(RayIntersection::new(
+            N::zero(),
+            Vector::zeros(),
+            FeatureId::Unknown,
+        ));
+    }
+
+    loop {
+        let part = voxels.part_id(&cell);
+
+        match (exiting, part, prev_part) {
+            (false, Some(part), _) => {
+                let (normal, face) = crossed_face(axis, &step, false);
+                let feature = voxels.part_feature_id(part, face);
+                return Some(RayIntersection::new(t, normal, feature));
+            }
+            (true, None, Some(prev_part)) => {
+                let (normal, face) = crossed_face(axis, &step, true);
+                let feature = voxels.part_feature_id(prev_part, face);
+                return Some(RayIntersection::new(t, normal, feature));
+            }
+            _ => {}
+        }
+
+        prev_part = part;
+
+        let i = t_max.imin();
+        t = t_max[i];
+
+        if t == N::max_value() || (!exiting && t > t_exit) {
+            return None;
+        }
+
+        cell[i] += step[i];
+        t_max[i] += t_delta[i];
+        axis = Some(i);
+    }
+}
+
+// The normal of the cell boundary crossed along `axis`, pointing against the ray direction,
+// and the corresponding face of the cell entered (or exited if `exit` is set) by the ray.
+fn crossed_face<N: RealField>(
+    axis: Option<usize>,
+    step: &Point3<i32>,
+    exit: bool,
+) -> (Vector<N>, FeatureId) {
+    match axis {
+        Some(i) => {
+            let mut normal = Vector::zeros();
+            normal[i] = if step[i] > 0 { -N::one() } else { N::one() };
+
+            // The entered face faces the ray, the exited one faces away from it.
+            let face = if (step[i] < 0) != exit { i } else { i + 3 };
+            (normal, FeatureId::Face(face))
+        }
+        None => (Vector::zeros(), FeatureId::Unknown),
+    }
+}
@@ -38,4 +38,15 @@ pub trait CompositeShape<N: RealField> {
 
     /// Gets the acceleration structure of the concave shape.
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>>;
+
+    /// A number that changes whenever the sub-shape identified by the index `i` is replaced
+    /// by another one.
+    ///
+    /// Collision detectors caching data for each sub-shape rely on this to detect that this
+    /// data is outdated. The default implementation is suited to composite shapes which
+    /// sub-shapes are never replaced.
+    #[inline]
+    fn part_revision(&self, _i: usize) -> usize {
+        0
+    }
 }
@@ -20,36 +20,145 @@ pub struct Compound<N: RealField> {
     shapes: Vec<(Isometry<N>, ShapeHandle<N>)>,
     bvt: BVT<usize, AABB<N>>,
     bvs: Vec<AABB<N>>,
+    // The index of the BVT leaf of each shape.
+    bvt_leaves: Vec<usize>,
+    revisions: Vec<usize>,
+    next_revision: usize,
     nbits: usize,
 }
 
 impl<N: RealField> Compound<N> {
     /// Builds a new compound shape.
     pub fn new(shapes: Vec<(Isometry<N>, ShapeHandle<N>)>) -> Compound<N> {
-        let mut bvs = Vec::new();
-        let mut leaves = Vec::new();
+        let bvs = shapes
+            .iter()
+            .map(|&(ref delta, ref shape)| Self::part_aabb(delta, shape))
+            .collect();
+        let revisions = (0..shapes.len()).collect();
 
-        for (i, &(ref delta, ref shape)) in shapes.iter().enumerate() {
-            // loosen for better persistancy
-            let bv = shape.as_ref().aabb(delta).loosened(na::convert(0.04f64));
+        let mut res = Compound {
+            next_revision: shapes.len(),
+            shapes,
+            bvt: BVT::new_with_sah(Vec::new()),
+            bvs,
+            bvt_leaves: Vec::new(),
+            revisions,
+            nbits: 0,
+        };
 
-            bvs.push(bv.clone());
-            leaves.push((i, bv));
+        res.rebuild_bvt();
+        res
+    }
 
-            if let Some(_comp) = shape.as_composite_shape() {
-                panic!("Nested composite shapes are not allowed.");
-            }
+    fn part_aabb(delta: &Isometry<N>, shape: &ShapeHandle<N>) -> AABB<N> {
+        if let Some(_comp) = shape.as_composite_shape() {
+            panic!("Nested composite shapes are not allowed.");
         }
 
-        let nbits = mem::size_of::<usize>() * 8 - leaves.len().leading_zeros() as usize;
-        let bvt = BVT::new_balanced(leaves);
+        // loosen for better persistancy
+        shape.as_ref().aabb(delta).loosened(na::convert(0.04f64))
+    }
 
-        Compound {
-            shapes: shapes,
-            bvt: bvt,
-            bvs: bvs,
-            nbits,
+    fn rebuild_bvt(&mut self) {
+        let leaves = self.bvs.iter().cloned().enumerate().collect::<Vec<_>>();
+
+        self.update_nbits();
+        self.bvt = BVT::new_with_sah(leaves);
+        self.bvt_leaves = vec![0; self.shapes.len()];
+
+        for (i, leaf) in self.bvt.leaves().iter().enumerate() {
+            self.bvt_leaves[*leaf.data()] = i;
+        }
+    }
+
+    // The number of bits needed to encode the index of a part into a feature identifier.
+    fn update_nbits(&mut self) {
+        self.nbits = mem::size_of::<usize>() * 8 - self.shapes.len().leading_zeros() as usize;
+    }
+
+    fn new_revision(&mut self) -> usize {
+        self.next_revision += 1;
+        self.next_revision - 1
+    }
+
+    /// Adds a shape to this compound shape and returns its index.
+    ///
+    /// The new shape is inserted into the bounding volume tree of this compound shape
+    /// incrementally.
+    pub fn push_shape(&mut self, delta: Isometry<N>, shape: ShapeHandle<N>) -> usize {
+        let i = self.shapes.len();
+        let bv = Self::part_aabb(&delta, &shape);
+        let revision = self.new_revision();
+
+        self.bvt_leaves.push(self.bvt.insert_leaf(i, bv.clone()));
+        self.bvs.push(bv);
+        self.shapes.push((delta, shape));
+        self.revisions.push(revision);
+        self.update_nbits();
+        i
+    }
+
+    /// Removes the `i`-th shape of this compound shape and returns it.
+    ///
+    /// The last shape of this compound shape takes the index `i`. The removed shape is removed
+    /// from the bounding volume tree of this compound shape incrementally.
+    pub fn remove_shape(&mut self, i: usize) -> (Isometry<N>, ShapeHandle<N>) {
+        let leaf = self.bvt_leaves[i];
+        let _ = self.bvt.remove_leaf(leaf);
+
+        // The last leaf of the BVT took the index of the removed one.
+        if leaf < self.bvt.leaves().len() {
+            self.bvt_leaves[*self.bvt.leaf(leaf).data()] = leaf;
         }
+
+        let _ = self.bvt_leaves.swap_remove(i);
+
+        // The last shape takes the index `i`.
+        if i < self.bvt_leaves.len() {
+            *self.bvt.leaf_data_mut(self.bvt_leaves[i]) = i;
+        }
+
+        let _ = self.bvs.swap_remove(i);
+        let _ = self.revisions.swap_remove(i);
+        let removed = self.shapes.swap_remove(i);
+        self.update_nbits();
+        removed
+    }
+
+    /// Replaces the `i`-th shape of this compound shape and returns the previous one.
+    ///
+    /// The bounding volume tree of this compound shape is refitted in-place.
+    pub fn replace_shape(
+        &mut self,
+        i: usize,
+        delta: Isometry<N>,
+        shape: ShapeHandle<N>,
+    ) -> (Isometry<N>, ShapeHandle<N>) {
+        let bv = Self::part_aabb(&delta, &shape);
+        self.revisions[i] = self.new_revision();
+        self.set_part_aabb(i, bv);
+        mem::replace(&mut self.shapes[i], (delta, shape))
+    }
+
+    /// Sets the position of the `i`-th shape of this compound shape, relative to this compound
+    /// shape.
+    ///
+    /// The bounding volume tree of this compound shape is refitted in-place if the shape leaves
+    /// its current bounding volume.
+    pub fn set_shape_position(&mut self, i: usize, delta: Isometry<N>) {
+        let aabb = self.shapes[i].1.as_ref().aabb(&delta);
+
+        if !self.bvs[i].contains(&aabb) {
+            self.set_part_aabb(i, aabb.loosened(na::convert(0.04f64)));
+        }
+
+        self.shapes[i].0 = delta;
+    }
+
+    fn set_part_aabb(&mut self, i: usize, bv: AABB<N>) {
+        self.bvs[i] = bv.clone();
+        self.bvt
+            .set_leaf_bounding_volume(self.bvt_leaves[i], bv, true);
     }
 }
 
@@ -146,6 +255,11 @@ impl<N: RealField> CompositeShape<N> for Compound<N> {
         self.bounding_volumes()[i].clone()
     }
 
+    #[inline]
+    fn part_revision(&self, i: usize) -> usize {
+        self.revisions[i]
+    }
+
     #[inline]
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
         BVHImpl::BVT(&self.bvt)
@@ -104,6 +104,23 @@ impl<N: RealField> ConvexPolygonalFeature<N> {
         }
     }
 
+    /// Scales all the vertices of this feature by the given non-uniform scaling factors.
+    ///
+    /// The normals are updated accordingly. All the scaling factors must be strictly positive.
+    pub fn scale_by(&mut self, scale: &Vector<N>) {
+        for p in &mut self.vertices {
+            p.coords.component_mul_assign(scale);
+        }
+
+        if let Some(ref mut n) = self.normal {
+            *n = Unit::new_normalize(n.component_div(scale));
+        }
+
+        if !self.edge_normals.is_empty() {
+            self.recompute_edge_normals();
+        }
+    }
+
     /// Adds a vertex to this face.
     ///
     /// It is not checked whether `pt` breaks the convexity of the polyhedral face.
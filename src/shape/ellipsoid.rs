@@ -0,0 +1,58 @@
+use na::{RealField, Unit};
+
+use crate::math::{Isometry, Point, Vector};
+use crate::shape::SupportMap;
+
+/// An ellipsoid (or an ellipse in 2D) centered at the origin, with its axes aligned with the
+/// coordinate axes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Debug, Clone)]
+pub struct Ellipsoid<N: RealField> {
+    radii: Vector<N>,
+}
+
+impl<N: RealField> Ellipsoid<N> {
+    /// Creates a new ellipsoid from its radius along each coordinate axis.
+    #[inline]
+    pub fn new(radii: Vector<N>) -> Ellipsoid<N> {
+        assert!(
+            radii.iter().all(|r| *r > N::zero()),
+            "The radii of an ellipsoid must be strictly positive."
+        );
+
+        Ellipsoid { radii }
+    }
+
+    /// The radius of this ellipsoid along each coordinate axis.
+    #[inline]
+    pub fn radii(&self) -> &Vector<N> {
+        &self.radii
+    }
+
+    /// The outward normal of this ellipsoid at the point `pt` of its boundary, in local space.
+    #[inline]
+    pub fn local_normal_at(&self, pt: &Point<N>) -> Option<Unit<Vector<N>>> {
+        let gradient = pt
+            .coords
+            .component_div(&self.radii.component_mul(&self.radii));
+        Unit::try_new(gradient, N::default_epsilon())
+    }
+}
+
+impl<N: RealField> SupportMap<N> for Ellipsoid<N> {
+    #[inline]
+    fn support_point(&self, m: &Isometry<N>, dir: &Vector<N>) -> Point<N> {
+        self.support_point_toward(m, &Unit::new_normalize(*dir))
+    }
+
+    #[inline]
+    fn support_point_toward(&self, m: &Isometry<N>, dir: &Unit<Vector<N>>) -> Point<N> {
+        // The ellipsoid is the image of the unit ball by the scaling `radii`, so its support
+        // point is the scaled support point of the unit ball in the direction `radii * dir`.
+        let local_dir = m.inverse_transform_vector(dir);
+        let scaled_dir = local_dir.component_mul(&self.radii);
+        let local_pt = scaled_dir.component_mul(&self.radii) / scaled_dir.norm();
+
+        m * Point::from(local_pt)
+    }
+}
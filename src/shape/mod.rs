@@ -20,6 +20,7 @@ pub use self::cuboid::Cuboid;
 #[cfg(feature = "dim3")]
 pub use self::cylinder::Cylinder;
 pub use self::deformable_shape::{DeformableShape, DeformationsType};
+pub use self::ellipsoid::Ellipsoid;
 //#[cfg(feature = "dim3")]
 //pub use self::deformable_trimesh::DeformableTriMesh;
 #[cfg(feature = "dim2")]
@@ -27,17 +28,24 @@ pub use self::heightfield2::HeightField;
 #[cfg(feature = "dim3")]
 pub use self::heightfield3::{HeightField, HeightFieldCellStatus};
 pub use self::plane::Plane;
-pub use self::polyline::Polyline;
+pub use self::polyline::{Polyline, PolylineContactPreprocessor};
+pub use self::round_shape::RoundShape;
+pub(crate) use self::round_shape::RoundShapeContactPreprocessor;
+pub use self::scaled::{ScalableShape, Scaled};
+pub use self::sdf_shape::SDFShape;
 pub use self::segment::{Segment, SegmentPointLocation};
 #[doc(inline)]
 pub use self::shape::{Shape, ShapeHandle};
+pub use self::shape_registry::{ShapeRegistry, TaggedShape};
 #[doc(inline)]
 pub use self::support_map::SupportMap;
 #[cfg(feature = "dim3")]
 pub use self::tetrahedron::{Tetrahedron, TetrahedronPointLocation};
 pub use self::triangle::{Triangle, TrianglePointLocation};
 #[cfg(feature = "dim3")]
-pub use self::trimesh::{TriMesh, TriMeshFace};
+pub use self::trimesh::{TriMesh, TriMeshContactPreprocessor, TriMeshFace};
+#[cfg(feature = "dim3")]
+pub use self::voxels::Voxels;
 
 mod ball;
 mod capsule;
@@ -59,16 +67,21 @@ mod cuboid;
 #[cfg(feature = "dim3")]
 mod cylinder;
 mod deformable_shape;
+mod ellipsoid;
 #[cfg(feature = "dim2")]
 mod heightfield2;
 #[cfg(feature = "dim3")]
 mod heightfield3;
 mod plane;
 mod polyline;
+mod round_shape;
+mod scaled;
+mod sdf_shape;
 mod segment;
 #[doc(hidden)]
 pub mod shape;
 mod shape_impl;
+mod shape_registry;
 #[doc(hidden)]
 pub mod support_map;
 #[cfg(feature = "dim3")]
@@ -76,3 +89,5 @@ mod tetrahedron;
 mod triangle;
 #[cfg(feature = "dim3")]
 mod trimesh;
+#[cfg(feature = "dim3")]
+mod voxels;
@@ -4,10 +4,11 @@ use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
 use crate::partitioning::{BVHImpl, BVT};
 use crate::query::{
-    Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
-    NeighborhoodGeometry,
+    replace_contact_normal, Contact, ContactKinematic, ContactPrediction, ContactPreprocessor,
+    LocalShapeApproximation, NeighborhoodGeometry,
 };
 use crate::shape::{CompositeShape, DeformableShape, DeformationsType, FeatureId, Segment, Shape};
+use crate::utils::IsometryOps;
 use na::{self, Id, Point2, RealField, Unit};
 use std::iter;
 use std::ops::Range;
@@ -91,7 +92,7 @@ impl<N: RealField> Polyline<N> {
             }
         }
 
-        let bvt = BVT::new_balanced(leaves);
+        let bvt = BVT::new_with_sah(leaves);
 
         // Set edge.bvt_leaf
         for (i, leaf) in bvt.leaves().iter().enumerate() {
@@ -419,6 +420,56 @@ impl<N: RealField> Polyline<N> {
         true
     }
 
+    /// The valid contact normal on the specified feature of this polyline that is the closest to
+    /// `dir`.
+    ///
+    /// A contact normal on a vertex is valid if none of the adjacent segments extends toward it
+    /// (within an angular tolerance). Contacts violating this occur on the vertices shared by
+    /// several segments and cause objects sliding on the polyline to snag. Invalid normals are
+    /// replaced by the projection of `dir` orthogonal to the adjacent segment that changes it
+    /// the least. Returns `None` if no such projection exists. Normals on segments are always
+    /// valid.
+    pub fn closest_valid_contact_normal(
+        &self,
+        feature: FeatureId,
+        dir: &Unit<Vector<N>>,
+        sin_ang_tol: N,
+    ) -> Option<Unit<Vector<N>>> {
+        let i = match feature {
+            FeatureId::Vertex(i) => i,
+            _ => return Some(*dir),
+        };
+
+        let tol = sin_ang_tol.max(N::default_epsilon().sqrt());
+
+        if self.vertex_tangent_cone_polar_contains_dir(i, dir, tol) {
+            return Some(*dir);
+        }
+
+        let mut best: Option<Unit<Vector<N>>> = None;
+
+        for adj_edge in &self.adj_edge_list[self.vertices[i].adj_edges.clone()] {
+            let edge = &self.edges[*adj_edge];
+            let seg = Segment::new(self.points[edge.indices.x], self.points[edge.indices.y]);
+
+            if let Some(seg_dir) = seg.direction() {
+                let projected = **dir - *seg_dir * seg_dir.dot(dir);
+
+                if let Some(n) = Unit::try_new(projected, N::default_epsilon()) {
+                    if self.oriented && edge.normal.map(|en| en.dot(&n) < N::zero()) == Some(true) {
+                        continue;
+                    }
+
+                    if best.map(|b| n.dot(dir) > b.dot(dir)).unwrap_or(true) {
+                        best = Some(n);
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
     /// Tests that the given `dir` is on the tangent cone of the `i`th edge
     /// of this polyline.
     #[cfg(feature = "dim3")]
@@ -555,7 +606,7 @@ impl<N: RealField> CompositeShape<N> for Polyline<N> {
         f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>, &dyn ContactPreprocessor<N>),
     ) {
         let element = self.segment_at(i);
-        let proc = PolylineContactProcessor::new(self, m, i, prediction);
+        let proc = PolylineContactPreprocessor::new(self, m, i, prediction);
         f(m, &element, &proc)
     }
 
@@ -700,22 +751,28 @@ impl<N: RealField> DeformableShape<N> for Polyline<N> {
     }
 }
 
-#[allow(dead_code)]
-struct PolylineContactProcessor<'a, N: RealField> {
+/// Contact preprocessor of a single segment of a polyline.
+///
+/// It maps the features of the segment to the features of the polyline, and corrects the
+/// normals of the contacts on the vertices shared by several segments. Corrected contacts that
+/// are no longer within the contact prediction are discarded.
+pub struct PolylineContactPreprocessor<'a, N: RealField> {
     polyline: &'a Polyline<N>,
     pos: &'a Isometry<N>,
     edge_id: usize,
     prediction: &'a ContactPrediction<N>,
 }
 
-impl<'a, N: RealField> PolylineContactProcessor<'a, N> {
+impl<'a, N: RealField> PolylineContactPreprocessor<'a, N> {
+    /// Initializes a contact preprocessor for the `edge_id`-th segment of `polyline` positioned
+    /// at `pos`.
     pub fn new(
         polyline: &'a Polyline<N>,
         pos: &'a Isometry<N>,
         edge_id: usize,
         prediction: &'a ContactPrediction<N>,
     ) -> Self {
-        PolylineContactProcessor {
+        PolylineContactPreprocessor {
             polyline,
             pos,
             edge_id,
@@ -724,10 +781,10 @@ impl<'a, N: RealField> PolylineContactProcessor<'a, N> {
     }
 }
 
-impl<'a, N: RealField> ContactPreprocessor<N> for PolylineContactProcessor<'a, N> {
+impl<'a, N: RealField> ContactPreprocessor<N> for PolylineContactPreprocessor<'a, N> {
     fn process_contact(
         &self,
-        _c: &mut Contact<N>,
+        c: &mut Contact<N>,
         kinematic: &mut ContactKinematic<N>,
         is_first: bool,
     ) -> bool {
@@ -759,20 +816,26 @@ impl<'a, N: RealField> ContactPreprocessor<N> for PolylineContactProcessor<'a, N
             kinematic.set_feature2(actual_feature);
         }
 
-        /*
-        // TODO: Test the validity of the LMD.
-        if c.depth > N::zero() {
-            true
+        // Correct the normals of contacts on shared vertices.
+        let world_dir = if is_first { c.normal } else { -c.normal };
+        let local_dir = self.pos.inverse_transform_unit_vector(&world_dir);
+        let sin_ang_tol = if is_first {
+            self.prediction.sin_angular1()
         } else {
-            let local_dir = self.pos.inverse_transform_unit_vector(&c.normal);
+            self.prediction.sin_angular2()
+        };
 
-            if is_first {
-                self.polyline.tangent_cone_polar_contains_dir(actual_feature, &local_dir, self.prediction.sin_angular1(), self.prediction.cos_angular1())
-            } else {
-                self.polyline.tangent_cone_polar_contains_dir(actual_feature, &-local_dir, self.prediction.sin_angular2(), self.prediction.cos_angular2())
+        match self
+            .polyline
+            .closest_valid_contact_normal(actual_feature, &local_dir, sin_ang_tol)
+        {
+            Some(n) if n != local_dir => {
+                replace_contact_normal(c, kinematic, is_first, self.pos, n);
+                c.depth >= -self.prediction.linear()
             }
-        }*/
-        true
+            Some(_) => true,
+            None => false,
+        }
     }
 }
 
@@ -0,0 +1,99 @@
+//! Support mapping based rounded shape.
+
+use na::{RealField, Unit};
+
+use crate::math::{Isometry, Point, Vector};
+use crate::query::{Contact, ContactKinematic, ContactPreprocessor};
+use crate::shape::SupportMap;
+
+/// A shape dilated by a ball, i.e., with all its edges and vertices rounded.
+///
+/// This is the Minkowski sum of the inner shape and a ball with radius `radius`. For example, a
+/// `RoundShape<N, Cuboid<N>>` is a rounded cuboid. Contact determination is performed on the
+/// inner shape, and the result is then dilated by the radius, so the inner shape must also be
+/// supported by the contact dispatcher.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Debug, Clone)]
+pub struct RoundShape<N, S> {
+    shape: S,
+    radius: N,
+}
+
+impl<N: RealField, S> RoundShape<N, S> {
+    /// Creates a new shape resulting from the dilation of `shape` by `radius`.
+    pub fn new(shape: S, radius: N) -> RoundShape<N, S> {
+        assert!(
+            !radius.is_negative(),
+            "The radius of a round shape must be positive."
+        );
+
+        RoundShape { shape, radius }
+    }
+
+    /// The shape dilated by `self.radius()` to yield this round shape.
+    #[inline]
+    pub fn inner_shape(&self) -> &S {
+        &self.shape
+    }
+
+    /// The radius of the rounded part of this shape.
+    #[inline]
+    pub fn radius(&self) -> N {
+        self.radius
+    }
+
+    /// The contact preprocessor to be used for contact determination with this round shape.
+    ///
+    /// It turns contacts computed with the inner shape into contacts with the round shape.
+    #[inline]
+    pub fn contact_preprocessor(&self) -> impl ContactPreprocessor<N> {
+        RoundShapeContactPreprocessor::new(self.radius)
+    }
+}
+
+impl<N: RealField, S: SupportMap<N>> SupportMap<N> for RoundShape<N, S> {
+    #[inline]
+    fn support_point(&self, m: &Isometry<N>, dir: &Vector<N>) -> Point<N> {
+        self.support_point_toward(m, &Unit::new_normalize(*dir))
+    }
+
+    #[inline]
+    fn support_point_toward(&self, m: &Isometry<N>, dir: &Unit<Vector<N>>) -> Point<N> {
+        self.shape.support_point_toward(m, dir) + **dir * self.radius
+    }
+}
+
+// Shared with the contact manifold generator, which only knows the radius of the round shape.
+pub(crate) struct RoundShapeContactPreprocessor<N: RealField> {
+    radius: N,
+}
+
+impl<N: RealField> RoundShapeContactPreprocessor<N> {
+    pub(crate) fn new(radius: N) -> Self {
+        RoundShapeContactPreprocessor { radius }
+    }
+}
+
+impl<N: RealField> ContactPreprocessor<N> for RoundShapeContactPreprocessor<N> {
+    fn process_contact(
+        &self,
+        c: &mut Contact<N>,
+        kinematic: &mut ContactKinematic<N>,
+        is_first: bool,
+    ) -> bool {
+        // The features of the inner shape are kept: the dilation alone is enough to
+        // describe the neighborhood of the contact on the round shape.
+        if is_first {
+            let dilation = kinematic.dilation1() + self.radius;
+            kinematic.set_dilation1(dilation);
+            c.world1 += *c.normal * self.radius;
+        } else {
+            let dilation = kinematic.dilation2() + self.radius;
+            kinematic.set_dilation2(dilation);
+            c.world2 -= *c.normal * self.radius;
+        }
+
+        c.depth += self.radius;
+        true
+    }
+}
@@ -0,0 +1,479 @@
+use na::{RealField, Unit};
+
+use crate::bounding_volume::AABB;
+use crate::math::{Isometry, Point, Translation, Vector};
+use crate::partitioning::BVHImpl;
+use crate::query::{
+    replace_contact_normal, Contact, ContactKinematic, ContactPrediction, ContactPreprocessor,
+};
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon;
+use crate::shape::{
+    Ball, Capsule, CompositeShape, Compound, ConvexPolygonalFeature, ConvexPolyhedron, Cuboid,
+    Ellipsoid, FeatureId, HeightField, Plane, Polyline, RoundShape, SDFShape, Segment, Shape,
+    ShapeHandle, SupportMap,
+};
+#[cfg(feature = "dim3")]
+use crate::shape::{ConvexHull, TriMesh, Triangle, Voxels};
+
+/// Trait implemented by shapes that can be wrapped into a `Scaled` shape.
+///
+/// This is implemented by every shape of ncollide except `Plane`, `HeightField` and `SDFShape`, as
+/// well as by `ShapeHandle` so that a single shape can be shared by several `Scaled` shapes with
+/// different scaling factors.
+pub trait ScalableShape<N: RealField>: Clone + Send + Sync + 'static {
+    /// The shape to be scaled.
+    fn shape(&self) -> &dyn Shape<N>;
+}
+
+macro_rules! impl_scalable_shape(
+    ($($S: ident),*) => {$(
+        impl<N: RealField> ScalableShape<N> for $S<N> {
+            #[inline]
+            fn shape(&self) -> &dyn Shape<N> {
+                self
+            }
+        }
+    )*}
+);
+
+impl_scalable_shape!(Ball, Capsule, Compound, Cuboid, Ellipsoid, Polyline, Segment);
+#[cfg(feature = "dim2")]
+impl_scalable_shape!(ConvexPolygon);
+#[cfg(feature = "dim3")]
+impl_scalable_shape!(ConvexHull, Triangle, TriMesh, Voxels);
+
+impl<N: RealField, S> ScalableShape<N> for RoundShape<N, S>
+where
+    RoundShape<N, S>: Shape<N> + Clone,
+{
+    #[inline]
+    fn shape(&self) -> &dyn Shape<N> {
+        self
+    }
+}
+
+impl<N: RealField, S: ScalableShape<N>> ScalableShape<N> for Scaled<N, S> {
+    #[inline]
+    fn shape(&self) -> &dyn Shape<N> {
+        self
+    }
+}
+
+impl<N: RealField> ScalableShape<N> for ShapeHandle<N> {
+    #[inline]
+    fn shape(&self) -> &dyn Shape<N> {
+        self.as_ref()
+    }
+}
+
+/// A shape scaled by a non-uniform scaling factor along each coordinate axis of its local-space.
+///
+/// The scaling is applied to the shape before its position. The scaled shape is a support map
+/// (resp. a convex polyhedron) if the inner shape is. Composite inner shapes are supported as
+/// long as all their parts are segments, triangles, or shapes that are not rotated with regard
+/// to the composite shape, e.g., triangle meshes and polylines. Composite shapes with rotated
+/// parts, e.g., some compound shapes, are only supported if the scaling is uniform.
+///
+/// The parts of a scaled composite shape are scaled on the fly, and its queries traverse the
+/// BVH of the inner shape with scaled AABBs.
+#[derive(Clone)]
+pub struct Scaled<N: RealField, S> {
+    shape: S,
+    scale: Vector<N>,
+}
+
+impl<N: RealField, S: ScalableShape<N>> Scaled<N, S> {
+    /// Creates a new shape scaled by `scale`.
+    ///
+    /// All the components of `scale` must be strictly positive. Panics if `shape`, or one of its
+    /// parts if it is a compound shape, is a plane, a heightfield, or a shape defined by a signed
+    /// distance function. Also panics if `shape` is a compound shape with rotated parts other than
+    /// segments and triangles while `scale` is not uniform.
+    pub fn new(shape: S, scale: Vector<N>) -> Self {
+        assert!(
+            scale.iter().all(|s| *s > N::zero()),
+            "The scaling factors of a scaled shape must be strictly positive."
+        );
+
+        // A `ShapeHandle` may contain any shape.
+        Self::assert_scalable(shape.shape());
+
+        if let Some(compound) = shape.shape().as_shape::<Compound<N>>() {
+            for (part_pos, part) in compound.shapes() {
+                Self::assert_scalable(part.as_ref());
+                Self::assert_bakeable(&scale, part_pos, part.as_ref());
+            }
+        }
+
+        Scaled { shape, scale }
+    }
+
+    /// The shape being scaled.
+    #[inline]
+    pub fn inner_shape(&self) -> &S {
+        &self.shape
+    }
+
+    /// The scaling factor along each coordinate axis.
+    #[inline]
+    pub fn scale(&self) -> &Vector<N> {
+        &self.scale
+    }
+
+    /// Whether this shape is composite, i.e., if its inner shape is a composite shape that
+    /// could be scaled.
+    #[inline]
+    pub fn is_composite(&self) -> bool {
+        self.shape.shape().is_composite_shape()
+    }
+
+    /// Applies the scaling to a point of the inner shape local-space.
+    #[inline]
+    pub fn scale_point(&self, pt: &Point<N>) -> Point<N> {
+        Point::from(pt.coords.component_mul(&self.scale))
+    }
+
+    /// Applies the inverse of the scaling to a point of this shape local-space.
+    #[inline]
+    pub fn unscale_point(&self, pt: &Point<N>) -> Point<N> {
+        Point::from(pt.coords.component_div(&self.scale))
+    }
+
+    /// Transforms a normal of the inner shape into a normal of this shape.
+    #[inline]
+    pub fn scale_normal(&self, normal: &Vector<N>) -> Vector<N> {
+        normal
+            .component_div(&self.scale)
+            .try_normalize(N::zero())
+            .unwrap_or(*normal)
+    }
+
+    /// Transforms a normal of this shape into a normal of the inner shape.
+    #[inline]
+    pub fn unscale_normal(&self, normal: &Vector<N>) -> Vector<N> {
+        normal
+            .component_mul(&self.scale)
+            .try_normalize(N::zero())
+            .unwrap_or(*normal)
+    }
+
+    // The contact dispatchers identify these shapes by their types, so their scaled versions
+    // would never collide.
+    fn assert_scalable(shape: &dyn Shape<N>) {
+        assert!(
+            !shape.is_shape::<Plane<N>>()
+                && !shape.is_shape::<HeightField<N>>()
+                && !shape.is_shape::<SDFShape<N>>(),
+            "Planes, heightfields and shapes defined by signed distance functions cannot be \
+             scaled."
+        );
+    }
+
+    fn assert_bakeable(scale: &Vector<N>, pos: &Isometry<N>, part: &dyn Shape<N>) {
+        #[cfg(feature = "dim3")]
+        let is_triangle = part.is_shape::<Triangle<N>>();
+        #[cfg(feature = "dim2")]
+        let is_triangle = false;
+
+        assert!(
+            is_triangle
+                || part.is_shape::<Segment<N>>()
+                || pos.rotation.angle().is_zero()
+                || scale.iter().all(|s| *s == scale[0]),
+            "The parts of a composite shape scaled non-uniformly must not be rotated, unless \
+             they are segments or triangles."
+        );
+    }
+
+    // Calls `f` with the scaled version of the `i`-th part of the inner shape, `part`, positioned
+    // at `part_pos` in the local-space of the inner shape, and with its position relative to `m`.
+    fn map_scaled_part<F>(
+        &self,
+        i: usize,
+        m: &Isometry<N>,
+        part_pos: &Isometry<N>,
+        part: &dyn Shape<N>,
+        mut f: F,
+    ) where
+        F: FnMut(&Isometry<N>, &dyn Shape<N>),
+    {
+        let scale = &self.scale;
+        let bake = |pt: &Point<N>| Point::from((part_pos * pt).coords.component_mul(scale));
+
+        if let Some(s) = part.as_shape::<Segment<N>>() {
+            return f(m, &Segment::new(bake(s.a()), bake(s.b())));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            if let Some(t) = part.as_shape::<Triangle<N>>() {
+                return f(m, &Triangle::new(bake(t.a()), bake(t.b()), bake(t.c())));
+            }
+        }
+
+        // The part is not rotated, or the scaling is uniform, so it can be scaled along the axes
+        // of its own local-space.
+        Self::assert_bakeable(scale, part_pos, part);
+        let shift = part_pos.translation.vector.component_mul(scale);
+        let pos = m * Isometry::from_parts(Translation::from(shift), part_pos.rotation);
+
+        if let Some(c) = part.as_shape::<Cuboid<N>>() {
+            return f(&pos, &Cuboid::new(c.half_extents().component_mul(scale)));
+        }
+
+        // Avoid copying the parts of compound shapes, which are already shared.
+        let part = match self.shape.shape().as_shape::<Compound<N>>() {
+            Some(compound) => compound.shapes()[i].1.clone(),
+            None => ShapeHandle::from_arc(part.clone_arc()),
+        };
+        f(&pos, &Scaled::new(part, *scale))
+    }
+
+    #[inline]
+    fn inner_support_map(&self) -> &dyn SupportMap<N> {
+        self.shape
+            .shape()
+            .as_support_map()
+            .expect("The inner shape of this scaled shape is not a support map.")
+    }
+
+    #[inline]
+    fn inner_convex_polyhedron(&self) -> &dyn ConvexPolyhedron<N> {
+        self.shape
+            .shape()
+            .as_convex_polyhedron()
+            .expect("The inner shape of this scaled shape is not a convex polyhedron.")
+    }
+
+    #[inline]
+    fn inner_composite_shape(&self) -> &dyn CompositeShape<N> {
+        self.shape
+            .shape()
+            .as_composite_shape()
+            .expect("The inner shape of this scaled shape is not a composite shape.")
+    }
+
+    // The direction, in the inner shape local-space, of the normal cone that is mapped to
+    // the normal cone containing `local_dir` by the scaling.
+    #[inline]
+    fn unscale_dir(&self, local_dir: &Vector<N>) -> Unit<Vector<N>> {
+        Unit::new_unchecked(self.unscale_normal(local_dir))
+    }
+}
+
+impl<N: RealField, S: ScalableShape<N>> SupportMap<N> for Scaled<N, S> {
+    #[inline]
+    fn support_point(&self, m: &Isometry<N>, dir: &Vector<N>) -> Point<N> {
+        let local_dir = m.inverse_transform_vector(dir);
+        let inner_dir = local_dir.component_mul(&self.scale);
+        let inner_pt = self
+            .inner_support_map()
+            .support_point(&Isometry::identity(), &inner_dir);
+
+        m * self.scale_point(&inner_pt)
+    }
+
+    #[inline]
+    fn support_point_toward(&self, m: &Isometry<N>, dir: &Unit<Vector<N>>) -> Point<N> {
+        let local_dir = m.inverse_transform_vector(dir);
+        let inner_pt = self
+            .inner_support_map()
+            .support_point_toward(&Isometry::identity(), &self.unscale_dir(&local_dir));
+
+        m * self.scale_point(&inner_pt)
+    }
+}
+
+// NOTE: the scaling maps the normal cones of the inner shape features to the normal cones of
+// the features of the scaled shape. Therefore, the feature supporting the direction `d` is
+// the feature of the inner shape supporting the direction `scale * d`.
+impl<N: RealField, S: ScalableShape<N>> ConvexPolyhedron<N> for Scaled<N, S> {
+    fn vertex(&self, id: FeatureId) -> Point<N> {
+        self.scale_point(&self.inner_convex_polyhedron().vertex(id))
+    }
+
+    #[cfg(feature = "dim3")]
+    fn edge(&self, id: FeatureId) -> (Point<N>, Point<N>, FeatureId, FeatureId) {
+        let (a, b, ida, idb) = self.inner_convex_polyhedron().edge(id);
+        (self.scale_point(&a), self.scale_point(&b), ida, idb)
+    }
+
+    fn face(&self, id: FeatureId, out: &mut ConvexPolygonalFeature<N>) {
+        self.inner_convex_polyhedron().face(id, out);
+        out.scale_by(&self.scale);
+    }
+
+    fn feature_normal(&self, feature: FeatureId) -> Unit<Vector<N>> {
+        let normal = self.inner_convex_polyhedron().feature_normal(feature);
+        Unit::new_unchecked(self.scale_normal(&normal))
+    }
+
+    fn support_face_toward(
+        &self,
+        m: &Isometry<N>,
+        dir: &Unit<Vector<N>>,
+        out: &mut ConvexPolygonalFeature<N>,
+    ) {
+        let local_dir = m.inverse_transform_vector(dir);
+        self.inner_convex_polyhedron().support_face_toward(
+            &Isometry::identity(),
+            &self.unscale_dir(&local_dir),
+            out,
+        );
+        out.scale_by(&self.scale);
+        out.transform_by(m);
+    }
+
+    fn support_feature_toward(
+        &self,
+        m: &Isometry<N>,
+        dir: &Unit<Vector<N>>,
+        angle: N,
+        out: &mut ConvexPolygonalFeature<N>,
+    ) {
+        let local_dir = m.inverse_transform_vector(dir);
+        self.inner_convex_polyhedron().support_feature_toward(
+            &Isometry::identity(),
+            &self.unscale_dir(&local_dir),
+            angle,
+            out,
+        );
+        out.scale_by(&self.scale);
+        out.transform_by(m);
+    }
+
+    fn support_feature_id_toward(&self, local_dir: &Unit<Vector<N>>) -> FeatureId {
+        self.inner_convex_polyhedron()
+            .support_feature_id_toward(&self.unscale_dir(local_dir))
+    }
+}
+
+impl<N: RealField, S: ScalableShape<N>> CompositeShape<N> for Scaled<N, S> {
+    #[inline]
+    fn nparts(&self) -> usize {
+        self.inner_composite_shape().nparts()
+    }
+
+    #[inline(always)]
+    fn map_part_at(
+        &self,
+        i: usize,
+        m: &Isometry<N>,
+        f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>),
+    ) {
+        self.inner_composite_shape()
+            .map_part_at(i, &Isometry::identity(), &mut |part_pos, part| {
+                self.map_scaled_part(i, m, part_pos, part, &mut *f)
+            })
+    }
+
+    fn map_part_and_preprocessor_at(
+        &self,
+        i: usize,
+        m: &Isometry<N>,
+        prediction: &ContactPrediction<N>,
+        f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>, &dyn ContactPreprocessor<N>),
+    ) {
+        // The inner preprocessor must be given the position of the whole shape.
+        let inv_m = m.inverse();
+        self.inner_composite_shape().map_part_and_preprocessor_at(
+            i,
+            m,
+            prediction,
+            &mut |part_pos, part, inner_preprocessor| {
+                let preprocessor = ScaledContactPreprocessor {
+                    scale: &self.scale,
+                    pos: m,
+                    inner: inner_preprocessor,
+                };
+                self.map_scaled_part(i, m, &(inv_m * part_pos), part, |pos, part| {
+                    f(pos, part, &preprocessor)
+                })
+            },
+        )
+    }
+
+    #[inline]
+    fn aabb_at(&self, i: usize) -> AABB<N> {
+        self.inner_composite_shape()
+            .aabb_at(i)
+            .scaled_by(&self.scale)
+    }
+
+    #[inline]
+    fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
+        self.inner_composite_shape().bvh().scaled(&self.scale)
+    }
+
+    #[inline]
+    fn part_revision(&self, i: usize) -> usize {
+        self.inner_composite_shape().part_revision(i)
+    }
+}
+
+// Runs the contact preprocessor of a part of the inner composite shape on a contact with the
+// corresponding scaled part.
+struct ScaledContactPreprocessor<'a, N: RealField> {
+    scale: &'a Vector<N>,
+    pos: &'a Isometry<N>,
+    inner: &'a dyn ContactPreprocessor<N>,
+}
+
+impl<'a, N: RealField> ContactPreprocessor<N> for ScaledContactPreprocessor<'a, N> {
+    fn process_contact(
+        &self,
+        c: &mut Contact<N>,
+        kinematic: &mut ContactKinematic<N>,
+        is_first: bool,
+    ) -> bool {
+        // The inner preprocessor only sees the contact point and normal of the inner shape.
+        let original = *c;
+        let local_normal = self.pos.inverse_transform_vector(&c.normal);
+        let inner_normal = local_normal
+            .component_mul(self.scale)
+            .try_normalize(N::zero())
+            .unwrap_or(local_normal);
+        let inner_world_normal = Unit::new_unchecked(self.pos * inner_normal);
+        let world_pt = if is_first {
+            &mut c.world1
+        } else {
+            &mut c.world2
+        };
+        let local_pt = self.pos.inverse_transform_point(world_pt);
+        *world_pt = self.pos * Point::from(local_pt.coords.component_div(self.scale));
+        c.normal = inner_world_normal;
+
+        if !self.inner.process_contact(c, kinematic, is_first) {
+            return false;
+        }
+
+        let corrected_normal = c.normal;
+        *c = original;
+
+        if corrected_normal != inner_world_normal {
+            // The inner preprocessor corrected the normal: apply the same correction to the
+            // scaled shape.
+            let outward = if is_first {
+                corrected_normal
+            } else {
+                -corrected_normal
+            };
+            let local_outward = self.pos.inverse_transform_vector(&outward);
+            let scaled_outward = local_outward
+                .component_div(self.scale)
+                .try_normalize(N::zero())
+                .unwrap_or(local_outward);
+
+            replace_contact_normal(
+                c,
+                kinematic,
+                is_first,
+                self.pos,
+                Unit::new_unchecked(scaled_outward),
+            );
+        }
+
+        true
+    }
+}
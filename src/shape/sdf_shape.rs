@@ -0,0 +1,207 @@
+use na::{self, RealField};
+
+use crate::bounding_volume::AABB;
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::query::PointQuery;
+
+/// A shape described by a signed distance field sampled on a regular grid.
+///
+/// The distance to the surface is negative inside of the shape and positive outside. It is
+/// interpolated linearly along each coordinate axis (trilinearly in 3D, bilinearly in 2D)
+/// between the samples. The surface of the shape is expected to lie inside of the grid, i.e.,
+/// the samples on the boundary of the grid should all be positive.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct SDFShape<N: RealField> {
+    values: Vec<N>,
+    resolution: Vector<usize>,
+    spacing: Vector<N>,
+    aabb: AABB<N>,
+}
+
+impl<N: RealField> SDFShape<N> {
+    /// Creates a signed distance field from its samples.
+    ///
+    /// The grid has `resolution[i]` samples along the `i`-th coordinate axis and covers the box
+    /// `aabb`. The sample with grid coordinates `(i, j, k)` is `values[i + j * nx + k * nx * ny]`
+    /// where `(nx, ny, nz)` is the resolution. It is located at
+    /// `aabb.mins() + (i * sx, j * sy, k * sz)` where `(sx, sy, sz)` is the spacing of the grid.
+    pub fn new(values: Vec<N>, resolution: Vector<usize>, aabb: AABB<N>) -> Self {
+        assert!(
+            resolution.iter().all(|r| *r >= 2),
+            "A signed distance field must have at least 2 samples along each axis."
+        );
+        assert_eq!(
+            values.len(),
+            resolution.iter().product::<usize>(),
+            "The number of samples does not match the resolution of the signed distance field."
+        );
+        assert!(
+            aabb.extents().iter().all(|e| *e > N::zero()),
+            "The domain of a signed distance field must not be empty."
+        );
+
+        let cells: Vector<N> = na::convert(resolution.map(|r| (r - 1) as f64));
+        let spacing = aabb.extents().component_div(&cells);
+
+        SDFShape {
+            values,
+            resolution,
+            spacing,
+            aabb,
+        }
+    }
+
+    /// Creates a signed distance field by sampling the function `f` at each point of the grid.
+    pub fn from_fn(
+        aabb: AABB<N>,
+        resolution: Vector<usize>,
+        mut f: impl FnMut(&Point<N>) -> N,
+    ) -> Self {
+        let cells: Vector<N> = na::convert(resolution.map(|r| (r - 1).max(1) as f64));
+        let spacing = aabb.extents().component_div(&cells);
+        let num_samples = resolution.iter().product();
+        let mut values = Vec::with_capacity(num_samples);
+
+        for i in 0..num_samples {
+            let mut pt = *aabb.mins();
+            let mut rem = i;
+
+            for k in 0..DIM {
+                pt[k] += na::convert::<_, N>((rem % resolution[k]) as f64) * spacing[k];
+                rem /= resolution[k];
+            }
+
+            values.push(f(&pt));
+        }
+
+        Self::new(values, resolution, aabb)
+    }
+
+    /// Creates the signed distance field of a shape, sampled on the given grid.
+    ///
+    /// The distances are computed by point projection, with a sign given by
+    /// `PointProjection::is_inside`.
+    pub fn from_point_query(
+        shape: &dyn PointQuery<N>,
+        aabb: AABB<N>,
+        resolution: Vector<usize>,
+    ) -> Self {
+        let m = Isometry::identity();
+        Self::from_fn(aabb, resolution, |pt| {
+            shape.distance_to_point(&m, pt, false)
+        })
+    }
+
+    /// The sampled distances.
+    #[inline]
+    pub fn values(&self) -> &[N] {
+        &self.values[..]
+    }
+
+    /// The number of samples along each coordinate axis.
+    #[inline]
+    pub fn resolution(&self) -> &Vector<usize> {
+        &self.resolution
+    }
+
+    /// The distance between two consecutive samples along each coordinate axis.
+    #[inline]
+    pub fn spacing(&self) -> &Vector<N> {
+        &self.spacing
+    }
+
+    /// The domain covered by the samples, in the local-space of this shape.
+    #[inline]
+    pub fn aabb(&self) -> &AABB<N> {
+        &self.aabb
+    }
+
+    /// The sample at the given grid coordinates.
+    #[inline]
+    pub fn value(&self, coords: &Vector<usize>) -> N {
+        let mut id = 0;
+        let mut stride = 1;
+
+        for k in 0..DIM {
+            id += coords[k] * stride;
+            stride *= self.resolution[k];
+        }
+
+        self.values[id]
+    }
+
+    /// The interpolated signed distance at a point expressed in the local-space of this shape.
+    ///
+    /// Outside of the domain of the grid, this is the distance at the closest point of the
+    /// domain, plus the distance to this point.
+    pub fn distance_at(&self, pt: &Point<N>) -> N {
+        let clamped = self.clamp_to_domain(pt);
+        self.interpolate(&clamped) + na::distance(pt, &clamped)
+    }
+
+    /// The gradient of the signed distance at a point expressed in the local-space of this shape.
+    ///
+    /// The gradient of a signed distance field gives the outward normal of the surface. It is
+    /// estimated by central differences over one grid spacing so that it varies continuously
+    /// across the boundaries of the cells.
+    pub fn gradient_at(&self, pt: &Point<N>) -> Vector<N> {
+        let mut gradient = Vector::zeros();
+
+        for k in 0..DIM {
+            let mut shift = Vector::zeros();
+            shift[k] = self.spacing[k];
+
+            let forward = self.distance_at(&(pt + shift));
+            let backward = self.distance_at(&(pt - shift));
+            gradient[k] = (forward - backward) / (self.spacing[k] * na::convert(2.0));
+        }
+
+        gradient
+    }
+
+    fn clamp_to_domain(&self, pt: &Point<N>) -> Point<N> {
+        let mut res = *pt;
+
+        for k in 0..DIM {
+            res[k] = na::clamp(pt[k], self.aabb.mins()[k], self.aabb.maxs()[k]);
+        }
+
+        res
+    }
+
+    // The interpolated value at a point of the domain of the grid.
+    fn interpolate(&self, pt: &Point<N>) -> N {
+        let mut cell = Vector::<usize>::zeros();
+        let mut t = Vector::zeros();
+
+        for k in 0..DIM {
+            let x = (pt[k] - self.aabb.mins()[k]) / self.spacing[k];
+            let i = unsafe { na::convert_unchecked::<N, f64>(x.floor()) };
+            let i = (i.max(0.0) as usize).min(self.resolution[k] - 2);
+            cell[k] = i;
+            t[k] = x - na::convert(i as f64);
+        }
+
+        let mut value = N::zero();
+
+        // Iterate through the corners of the cell.
+        for corner in 0..1 << DIM {
+            let mut coords = cell;
+            let mut weight = N::one();
+
+            for k in 0..DIM {
+                if corner & (1 << k) != 0 {
+                    coords[k] += 1;
+                    weight *= t[k];
+                } else {
+                    weight *= N::one() - t[k];
+                }
+            }
+
+            value += weight * self.value(&coords);
+        }
+
+        value
+    }
+}
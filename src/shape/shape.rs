@@ -1,5 +1,5 @@
 // Queries.
-use crate::bounding_volume::{BoundingSphere, AABB};
+use crate::bounding_volume::{BoundingSphere, AABB, OBB};
 use crate::math::{Isometry, Vector};
 use crate::query::{PointQuery, RayCast};
 use crate::shape::{CompositeShape, ConvexPolyhedron, DeformableShape, FeatureId, SupportMap};
@@ -47,6 +47,18 @@ pub trait Shape<N: RealField>: Send + Sync + Downcast + ShapeClone<N> {
         BoundingSphere::new(aabb.center(), aabb.half_extents().norm())
     }
 
+    /// The oriented bounding box of `self` transformed by `m`.
+    #[inline]
+    fn obb(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::from_aabb(&self.local_aabb(), m)
+    }
+
+    /// The oriented bounding box of `self`.
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        self.obb(&Isometry::identity())
+    }
+
     /// Check if if the feature `_feature` of the `i-th` subshape of `self` transformed by `m` has a tangent
     /// cone that contains `dir` at the point `pt`.
     // NOTE: for the moment, we assume the tangent cone is the same for the whole feature.
@@ -108,6 +120,14 @@ pub trait Shape<N: RealField>: Send + Sync + Downcast + ShapeClone<N> {
         None
     }
 
+    /// The shape dilated by a ball to yield `self`, with the radius of this ball, if applicable.
+    ///
+    /// This allows contact determination with a `RoundShape` to be performed on its inner shape.
+    #[inline]
+    fn as_round_shape(&self) -> Option<(&dyn Shape<N>, N)> {
+        None
+    }
+
     /// Whether `self` uses a convex polyhedron representation.
     #[inline]
     fn is_convex_polyhedron(&self) -> bool {
@@ -131,6 +151,12 @@ pub trait Shape<N: RealField>: Send + Sync + Downcast + ShapeClone<N> {
     fn is_deformable_shape(&self) -> bool {
         self.as_deformable_shape().is_some()
     }
+
+    /// Whether `self` is a shape dilated by a ball.
+    #[inline]
+    fn is_round_shape(&self) -> bool {
+        self.as_round_shape().is_some()
+    }
 }
 
 impl_downcast!(Shape<N> where N: RealField);
@@ -148,6 +174,12 @@ impl<N: RealField> dyn Shape<N> {
     pub fn as_shape<T: Shape<N>>(&self) -> Option<&T> {
         self.downcast_ref()
     }
+
+    /// Performs the mutable cast.
+    #[inline]
+    pub fn as_shape_mut<T: Shape<N>>(&mut self) -> Option<&mut T> {
+        self.downcast_mut()
+    }
 }
 
 /// A shared handle to an abstract shape.
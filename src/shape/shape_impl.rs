@@ -1,16 +1,17 @@
-use crate::bounding_volume::{self, BoundingSphere, AABB};
+use crate::bounding_volume::{self, BoundingSphere, BoundingVolume, AABB, OBB};
 use crate::math::{Isometry, Vector};
 use crate::query::{PointQuery, RayCast};
 #[cfg(feature = "dim2")]
 use crate::shape::ConvexPolygon;
 use crate::shape::{
-    Ball, Capsule, CompositeShape, Compound, ConvexPolyhedron, Cuboid, DeformableShape, FeatureId,
-    HeightField, Plane, Polyline, Segment, Shape, SupportMap,
+    Ball, Capsule, CompositeShape, Compound, ConvexPolyhedron, Cuboid, DeformableShape, Ellipsoid,
+    FeatureId, HeightField, Plane, Polyline, RoundShape, SDFShape, ScalableShape, Scaled, Segment,
+    Shape, SupportMap,
 };
 #[cfg(feature = "dim3")]
-use crate::shape::{ConvexHull, TriMesh, Triangle};
+use crate::shape::{ConvexHull, TriMesh, Triangle, Voxels};
 use crate::utils::IsometryOps;
-use na::{RealField, Unit};
+use na::{self, RealField, Unit};
 
 macro_rules! impl_as_convex_polyhedron (
     () => {
@@ -95,6 +96,16 @@ macro_rules! impl_shape_common (
             bounding_volume::bounding_sphere(self, m)
         }
 
+        #[inline]
+        fn obb(&self, m: &Isometry<N>) -> OBB<N> {
+            bounding_volume::obb(self, m)
+        }
+
+        #[inline]
+        fn local_obb(&self) -> OBB<N> {
+            bounding_volume::local_obb(self)
+        }
+
         #[inline]
         fn as_ray_cast(&self) -> Option<&dyn RayCast<N>> {
             Some(self)
@@ -138,6 +149,24 @@ impl<N: RealField> Shape<N> for Ball<N> {
     }
 }
 
+impl<N: RealField> Shape<N> for Ellipsoid<N> {
+    impl_shape_common!();
+    impl_as_support_map!();
+
+    // FIXME: this is wrong in theory but keep it this
+    // way for now because of the way the ContactKinematic
+    // currently works.
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry<N>,
+        _: Option<&[N]>,
+        _: &Unit<Vector<N>>,
+    ) -> bool {
+        false
+    }
+}
+
 impl<N: RealField> Shape<N> for Cuboid<N> {
     impl_shape_common!();
     impl_as_support_map!();
@@ -162,6 +191,180 @@ impl<N: RealField> Shape<N> for Capsule<N> {
     }
 }
 
+impl<N, S> Shape<N> for RoundShape<N, S>
+where
+    N: RealField,
+    S: Shape<N> + SupportMap<N> + PointQuery<N> + Clone,
+{
+    impl_as_support_map!();
+
+    #[inline]
+    fn aabb(&self, m: &Isometry<N>) -> AABB<N> {
+        self.inner_shape().aabb(m).loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_aabb(&self) -> AABB<N> {
+        self.inner_shape().local_aabb().loosened(self.radius())
+    }
+
+    #[inline]
+    fn bounding_sphere(&self, m: &Isometry<N>) -> BoundingSphere<N> {
+        self.inner_shape()
+            .bounding_sphere(m)
+            .loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_bounding_sphere(&self) -> BoundingSphere<N> {
+        self.inner_shape()
+            .local_bounding_sphere()
+            .loosened(self.radius())
+    }
+
+    #[inline]
+    fn obb(&self, m: &Isometry<N>) -> OBB<N> {
+        self.inner_shape().obb(m).loosened(self.radius())
+    }
+
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        self.inner_shape().local_obb().loosened(self.radius())
+    }
+
+    #[inline]
+    fn as_ray_cast(&self) -> Option<&dyn RayCast<N>> {
+        Some(self)
+    }
+
+    #[inline]
+    fn as_point_query(&self) -> Option<&dyn PointQuery<N>> {
+        Some(self)
+    }
+
+    #[inline]
+    fn as_round_shape(&self) -> Option<(&dyn Shape<N>, N)> {
+        Some((self.inner_shape(), self.radius()))
+    }
+
+    // FIXME: this is wrong in theory but keep it this
+    // way for now because of the way the ContactKinematic
+    // currently works.
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry<N>,
+        _: Option<&[N]>,
+        _: &Unit<Vector<N>>,
+    ) -> bool {
+        false
+    }
+}
+
+impl<N: RealField, S: ScalableShape<N>> Shape<N> for Scaled<N, S> {
+    #[inline]
+    fn aabb(&self, m: &Isometry<N>) -> AABB<N> {
+        bounding_volume::aabb(self, m)
+    }
+
+    #[inline]
+    fn local_aabb(&self) -> AABB<N> {
+        bounding_volume::local_aabb(self)
+    }
+
+    #[inline]
+    fn bounding_sphere(&self, m: &Isometry<N>) -> BoundingSphere<N> {
+        bounding_volume::bounding_sphere(self, m)
+    }
+
+    #[inline]
+    fn local_bounding_sphere(&self) -> BoundingSphere<N> {
+        bounding_volume::local_bounding_sphere(self)
+    }
+
+    #[inline]
+    fn obb(&self, m: &Isometry<N>) -> OBB<N> {
+        bounding_volume::obb(self, m)
+    }
+
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        bounding_volume::local_obb(self)
+    }
+
+    fn tangent_cone_contains_dir(
+        &self,
+        feature: FeatureId,
+        m: &Isometry<N>,
+        deformations: Option<&[N]>,
+        dir: &Unit<Vector<N>>,
+    ) -> bool {
+        // The tangent cones of the inner shape are mapped to the tangent cones of `self` by
+        // the scaling.
+        let local_dir = m.inverse_transform_vector(dir).component_div(self.scale());
+
+        match Unit::try_new(local_dir, N::zero()) {
+            Some(inner_dir) => self.inner_shape().shape().tangent_cone_contains_dir(
+                feature,
+                &Isometry::identity(),
+                deformations,
+                &inner_dir,
+            ),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn subshape_containing_feature(&self, i: FeatureId) -> usize {
+        self.inner_shape().shape().subshape_containing_feature(i)
+    }
+
+    #[inline]
+    fn as_ray_cast(&self) -> Option<&dyn RayCast<N>> {
+        if self.inner_shape().shape().as_ray_cast().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn as_point_query(&self) -> Option<&dyn PointQuery<N>> {
+        if self.inner_shape().shape().as_point_query().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn as_support_map(&self) -> Option<&dyn SupportMap<N>> {
+        if self.inner_shape().shape().is_support_map() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn as_convex_polyhedron(&self) -> Option<&dyn ConvexPolyhedron<N>> {
+        if self.inner_shape().shape().is_convex_polyhedron() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn as_composite_shape(&self) -> Option<&dyn CompositeShape<N>> {
+        if self.is_composite() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(feature = "dim3")]
 impl<N: RealField> Shape<N> for ConvexHull<N> {
     impl_shape_common!();
@@ -228,6 +431,34 @@ impl<N: RealField> Shape<N> for TriMesh<N> {
     }
 }
 
+#[cfg(feature = "dim3")]
+impl<N: RealField> Shape<N> for Voxels<N> {
+    impl_shape_common!();
+    impl_as_composite_shape!();
+
+    fn tangent_cone_contains_dir(
+        &self,
+        feature: FeatureId,
+        m: &Isometry<N>,
+        _: Option<&[N]>,
+        dir: &Unit<Vector<N>>,
+    ) -> bool {
+        let (i, fid) = self.subshape_feature_id(feature);
+        let cell = match self.cell(i) {
+            Some(cell) => cell,
+            None => return false,
+        };
+        let part_pos = Isometry::new(self.voxel_center(&cell).coords, na::zero());
+        let ls_dir = m.inverse_transform_unit_vector(dir);
+        self.voxel_cuboid()
+            .tangent_cone_contains_dir(fid, &part_pos, &ls_dir)
+    }
+
+    fn subshape_containing_feature(&self, feature: FeatureId) -> usize {
+        self.subshape_feature_id(feature).0
+    }
+}
+
 impl<N: RealField> Shape<N> for Polyline<N> {
     impl_shape_common!();
     impl_as_composite_shape!();
@@ -269,6 +500,21 @@ impl<N: RealField> Shape<N> for HeightField<N> {
     }
 }
 
+impl<N: RealField> Shape<N> for SDFShape<N> {
+    impl_shape_common!();
+
+    fn tangent_cone_contains_dir(
+        &self,
+        _fid: FeatureId,
+        _m: &Isometry<N>,
+        _deformations: Option<&[N]>,
+        _dir: &Unit<Vector<N>>,
+    ) -> bool {
+        // The single feature of this shape does not identify any point of its surface.
+        false
+    }
+}
+
 impl<N: RealField> Shape<N> for Plane<N> {
     impl_shape_common!();
 
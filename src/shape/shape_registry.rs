@@ -0,0 +1,420 @@
+use std::any::Any;
+use std::cell::RefCell;
+#[cfg(feature = "serde")]
+use std::rc::Rc;
+use std::sync::Arc;
+
+use na::RealField;
+
+use crate::math::{Isometry, Vector};
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon;
+use crate::shape::{
+    Ball, Capsule, Compound, Cuboid, Ellipsoid, HeightField, Plane, Polyline, RoundShape, SDFShape,
+    Scaled, Segment, Shape, ShapeHandle,
+};
+#[cfg(feature = "dim3")]
+use crate::shape::{ConvexHull, TriMesh, Triangle, Voxels};
+
+/// A description of a shape, tagged with the type of this shape.
+///
+/// This is the representation used to serialize a `ShapeHandle`. Each shape provided by
+/// ncollide has its own variant. User-defined shapes registered on a `ShapeRegistry` are
+/// encoded to bytes and identified by the tag they were registered with.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TaggedShape<N: RealField> {
+    /// A ball.
+    Ball(Ball<N>),
+    /// A cuboid.
+    Cuboid(Cuboid<N>),
+    /// A capsule.
+    Capsule(Capsule<N>),
+    /// An ellipsoid.
+    Ellipsoid(Ellipsoid<N>),
+    /// A plane.
+    Plane(Plane<N>),
+    /// A segment.
+    Segment(Segment<N>),
+    /// A polyline.
+    Polyline(Polyline<N>),
+    /// A heightfield.
+    HeightField(HeightField<N>),
+    /// A signed distance field.
+    SDFShape(SDFShape<N>),
+    /// A convex polygon.
+    #[cfg(feature = "dim2")]
+    ConvexPolygon(ConvexPolygon<N>),
+    /// A convex hull.
+    #[cfg(feature = "dim3")]
+    ConvexHull(ConvexHull<N>),
+    /// A triangle.
+    #[cfg(feature = "dim3")]
+    Triangle(Triangle<N>),
+    /// A triangle mesh.
+    #[cfg(feature = "dim3")]
+    TriMesh(TriMesh<N>),
+    /// A set of voxels.
+    #[cfg(feature = "dim3")]
+    Voxels(Voxels<N>),
+    /// A compound shape, described by the position and the description of each of its parts.
+    Compound(Vec<(Isometry<N>, TaggedShape<N>)>),
+    /// A scaled shape.
+    Scaled {
+        /// The description of the shape being scaled.
+        shape: Box<TaggedShape<N>>,
+        /// The scaling factor along each coordinate axis.
+        scale: Vector<N>,
+        /// Whether the shape being scaled is stored in a `ShapeHandle`.
+        shared: bool,
+    },
+    /// A round shape.
+    RoundShape {
+        /// The description of the shape being dilated.
+        shape: Box<TaggedShape<N>>,
+        /// The radius of the rounded part of the shape.
+        radius: N,
+    },
+    /// A user-defined shape.
+    Custom {
+        /// The tag this shape type was registered with.
+        tag: String,
+        /// The shape, as encoded by the function registered with `tag`.
+        data: Vec<u8>,
+    },
+}
+
+type ShapeEncoder<N> = Arc<dyn Fn(&dyn Shape<N>) -> Option<Vec<u8>> + Send + Sync>;
+type ShapeDecoder<N> = Arc<dyn Fn(&[u8]) -> Option<ShapeHandle<N>> + Send + Sync>;
+
+thread_local! {
+    // The registry set by `ShapeRegistry::scope`, stored as a `ShapeRegistry<N>`.
+    static CURRENT_REGISTRY: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+    // The registry of the shapes provided by ncollide, used outside of `ShapeRegistry::scope`.
+    #[cfg(feature = "serde")]
+    static DEFAULT_REGISTRY: RefCell<Option<Rc<dyn Any>>> = RefCell::new(None);
+}
+
+/// The set of shape types that can be converted to and from a `TaggedShape`.
+///
+/// All the shapes provided by ncollide are always supported. For the generic shapes, this covers
+/// the `RoundShape` of any support map provided by ncollide, as well as the `Scaled` version of
+/// a `ShapeHandle` or of any non-generic `ScalableShape` provided by ncollide. Other shape types must be
+/// registered with a unique tag, together with functions to encode and decode them.
+#[derive(Clone)]
+pub struct ShapeRegistry<N: RealField> {
+    codecs: Vec<(String, ShapeEncoder<N>, ShapeDecoder<N>)>,
+}
+
+impl<N: RealField> ShapeRegistry<N> {
+    /// Creates a registry supporting only the shapes provided by ncollide.
+    pub fn new() -> Self {
+        ShapeRegistry { codecs: Vec::new() }
+    }
+
+    /// Registers the shape type `S` with the given tag.
+    ///
+    /// Panics if another shape type is already registered with this tag.
+    pub fn register<S: Shape<N>>(
+        &mut self,
+        tag: &str,
+        encode: fn(&S) -> Vec<u8>,
+        decode: fn(&[u8]) -> Option<S>,
+    ) {
+        assert!(
+            self.codecs.iter().all(|codec| codec.0 != tag),
+            "A shape type is already registered with the tag `{}`.",
+            tag
+        );
+
+        let encoder: ShapeEncoder<N> = Arc::new(move |shape| shape.as_shape::<S>().map(encode));
+        let decoder: ShapeDecoder<N> = Arc::new(move |data| decode(data).map(ShapeHandle::new));
+        self.codecs.push((tag.to_string(), encoder, decoder));
+    }
+
+    /// Describes the given shape.
+    ///
+    /// Returns `None` if the type of this shape, or of one of its parts, is not supported.
+    pub fn to_tagged_shape(&self, shape: &dyn Shape<N>) -> Option<TaggedShape<N>> {
+        macro_rules! try_tag(
+            ($($Shape: ident),*) => {$(
+                if let Some(s) = shape.as_shape::<$Shape<N>>() {
+                    return Some(TaggedShape::$Shape(s.clone()));
+                }
+            )*}
+        );
+
+        try_tag!(
+            Ball,
+            Cuboid,
+            Capsule,
+            Ellipsoid,
+            Plane,
+            Segment,
+            Polyline,
+            HeightField,
+            SDFShape
+        );
+        #[cfg(feature = "dim2")]
+        try_tag!(ConvexPolygon);
+        #[cfg(feature = "dim3")]
+        try_tag!(ConvexHull, Triangle, TriMesh, Voxels);
+
+        if let Some(compound) = shape.as_shape::<Compound<N>>() {
+            let parts = compound
+                .shapes()
+                .iter()
+                .map(|(pos, part)| Some((*pos, self.to_tagged_shape(part.as_ref())?)))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(TaggedShape::Compound(parts));
+        }
+
+        macro_rules! try_tag_scaled(
+            ($($Shape: ident),*) => {$(
+                if let Some(s) = shape.as_shape::<Scaled<N, $Shape<N>>>() {
+                    return Some(TaggedShape::Scaled {
+                        shape: Box::new(self.to_tagged_shape(s.inner_shape())?),
+                        scale: *s.scale(),
+                        shared: false,
+                    });
+                }
+            )*}
+        );
+
+        try_tag_scaled!(Ball, Cuboid, Capsule, Ellipsoid, Segment, Polyline, Compound);
+        #[cfg(feature = "dim2")]
+        try_tag_scaled!(ConvexPolygon);
+        #[cfg(feature = "dim3")]
+        try_tag_scaled!(ConvexHull, Triangle, TriMesh, Voxels);
+
+        if let Some(s) = shape.as_shape::<Scaled<N, ShapeHandle<N>>>() {
+            return Some(TaggedShape::Scaled {
+                shape: Box::new(self.to_tagged_shape(s.inner_shape().as_ref())?),
+                scale: *s.scale(),
+                shared: true,
+            });
+        }
+
+        macro_rules! try_tag_round(
+            ($($Shape: ident),*) => {$(
+                if let Some(s) = shape.as_shape::<RoundShape<N, $Shape<N>>>() {
+                    return Some(TaggedShape::RoundShape {
+                        shape: Box::new(TaggedShape::$Shape(s.inner_shape().clone())),
+                        radius: s.radius(),
+                    });
+                }
+            )*}
+        );
+
+        try_tag_round!(Ball, Cuboid, Capsule, Ellipsoid, Segment);
+        #[cfg(feature = "dim2")]
+        try_tag_round!(ConvexPolygon);
+        #[cfg(feature = "dim3")]
+        try_tag_round!(ConvexHull, Triangle);
+
+        self.codecs.iter().find_map(|(tag, encode, _)| {
+            encode(shape).map(|data| TaggedShape::Custom {
+                tag: tag.clone(),
+                data,
+            })
+        })
+    }
+
+    /// Creates the shape described by `shape`.
+    ///
+    /// Returns `None` if this describes a user-defined shape that is not registered, or that
+    /// fails to be decoded.
+    pub fn from_tagged_shape(&self, shape: TaggedShape<N>) -> Option<ShapeHandle<N>> {
+        let handle = match shape {
+            TaggedShape::Ball(s) => ShapeHandle::new(s),
+            TaggedShape::Cuboid(s) => ShapeHandle::new(s),
+            TaggedShape::Capsule(s) => ShapeHandle::new(s),
+            TaggedShape::Ellipsoid(s) => ShapeHandle::new(s),
+            TaggedShape::Plane(s) => ShapeHandle::new(s),
+            TaggedShape::Segment(s) => ShapeHandle::new(s),
+            TaggedShape::Polyline(s) => ShapeHandle::new(s),
+            TaggedShape::HeightField(s) => ShapeHandle::new(s),
+            TaggedShape::SDFShape(s) => ShapeHandle::new(s),
+            #[cfg(feature = "dim2")]
+            TaggedShape::ConvexPolygon(s) => ShapeHandle::new(s),
+            #[cfg(feature = "dim3")]
+            TaggedShape::ConvexHull(s) => ShapeHandle::new(s),
+            #[cfg(feature = "dim3")]
+            TaggedShape::Triangle(s) => ShapeHandle::new(s),
+            #[cfg(feature = "dim3")]
+            TaggedShape::TriMesh(s) => ShapeHandle::new(s),
+            #[cfg(feature = "dim3")]
+            TaggedShape::Voxels(s) => ShapeHandle::new(s),
+            TaggedShape::Compound(parts) => {
+                ShapeHandle::new(self.compound_from_tagged_parts(parts)?)
+            }
+            TaggedShape::Scaled {
+                shape,
+                scale,
+                shared: true,
+            } => ShapeHandle::new(Scaled::new(self.from_tagged_shape(*shape)?, scale)),
+            TaggedShape::Scaled {
+                shape,
+                scale,
+                shared: false,
+            } => return self.scaled_from_tagged_shape(*shape, scale),
+            TaggedShape::RoundShape { shape, radius } => {
+                return self.round_shape_from_tagged_shape(*shape, radius)
+            }
+            TaggedShape::Custom { tag, data } => {
+                let codec = self.codecs.iter().find(|codec| codec.0 == tag)?;
+                return (codec.2)(&data);
+            }
+        };
+
+        Some(handle)
+    }
+
+    // Creates the compound shape with the parts described by `parts`.
+    fn compound_from_tagged_parts(
+        &self,
+        parts: Vec<(Isometry<N>, TaggedShape<N>)>,
+    ) -> Option<Compound<N>> {
+        let parts = parts
+            .into_iter()
+            .map(|(pos, part)| Some((pos, self.from_tagged_shape(part)?)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Compound::new(parts))
+    }
+
+    // Creates a `Scaled<N, S>` where `S` is the type of the shape described by `shape`.
+    fn scaled_from_tagged_shape(
+        &self,
+        shape: TaggedShape<N>,
+        scale: Vector<N>,
+    ) -> Option<ShapeHandle<N>> {
+        let handle = match shape {
+            TaggedShape::Ball(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Cuboid(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Capsule(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Ellipsoid(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Segment(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Polyline(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            #[cfg(feature = "dim2")]
+            TaggedShape::ConvexPolygon(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::ConvexHull(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::Triangle(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::TriMesh(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::Voxels(s) => ShapeHandle::new(Scaled::new(s, scale)),
+            TaggedShape::Compound(parts) => {
+                ShapeHandle::new(Scaled::new(self.compound_from_tagged_parts(parts)?, scale))
+            }
+            _ => return None,
+        };
+
+        Some(handle)
+    }
+
+    // Creates a `RoundShape<N, S>` where `S` is the type of the shape described by `shape`.
+    fn round_shape_from_tagged_shape(
+        &self,
+        shape: TaggedShape<N>,
+        radius: N,
+    ) -> Option<ShapeHandle<N>> {
+        let handle = match shape {
+            TaggedShape::Ball(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            TaggedShape::Cuboid(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            TaggedShape::Capsule(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            TaggedShape::Ellipsoid(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            TaggedShape::Segment(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            #[cfg(feature = "dim2")]
+            TaggedShape::ConvexPolygon(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::ConvexHull(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            #[cfg(feature = "dim3")]
+            TaggedShape::Triangle(s) => ShapeHandle::new(RoundShape::new(s, radius)),
+            _ => return None,
+        };
+
+        Some(handle)
+    }
+
+    /// Runs `f` with this registry used to serialize and deserialize every `ShapeHandle` on the
+    /// current thread.
+    ///
+    /// Outside of this method, only the shapes provided by ncollide can be serialized.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Box<dyn Any>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT_REGISTRY.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+
+        let registry: Box<dyn Any> = Box::new(self.clone());
+        let _restore = Restore(CURRENT_REGISTRY.with(|current| current.replace(Some(registry))));
+        f()
+    }
+
+    /// Applies `f` to the registry set by `scope` on the current thread, or to a registry of the
+    /// shapes provided by ncollide if there is none.
+    #[cfg(feature = "serde")]
+    pub(crate) fn with_current<R>(f: impl FnOnce(&ShapeRegistry<N>) -> R) -> R {
+        CURRENT_REGISTRY.with(|current| {
+            let current = current.borrow();
+
+            match current
+                .as_ref()
+                .and_then(|registry| registry.downcast_ref::<ShapeRegistry<N>>())
+            {
+                Some(registry) => f(registry),
+                None => f(&Self::default_registry()),
+            }
+        })
+    }
+
+    // The registry of the shapes provided by ncollide, created once per thread.
+    #[cfg(feature = "serde")]
+    fn default_registry() -> Rc<ShapeRegistry<N>> {
+        DEFAULT_REGISTRY.with(|default| {
+            let mut default = default.borrow_mut();
+
+            match default
+                .clone()
+                .and_then(|registry| registry.downcast::<ShapeRegistry<N>>().ok())
+            {
+                Some(registry) => registry,
+                None => {
+                    let registry = Rc::new(ShapeRegistry::new());
+                    *default = Some(registry.clone());
+                    registry
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<N: RealField + serde::Serialize> serde::Serialize for ShapeHandle<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match ShapeRegistry::with_current(|registry| registry.to_tagged_shape(self.as_ref())) {
+            Some(shape) => serde::Serialize::serialize(&shape, serializer),
+            None => Err(serde::ser::Error::custom(
+                "the shape type is not registered on the current shape registry",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N: RealField + serde::Deserialize<'de>> serde::Deserialize<'de> for ShapeHandle<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let shape = <TaggedShape<N> as serde::Deserialize>::deserialize(deserializer)?;
+        ShapeRegistry::with_current(|registry| registry.from_tagged_shape(shape)).ok_or_else(|| {
+            serde::de::Error::custom(
+                "the shape type is not registered on the current shape registry",
+            )
+        })
+    }
+}
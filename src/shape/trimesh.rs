@@ -2,11 +2,11 @@
 
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVT, QBVH};
 use crate::procedural;
 use crate::query::{
-    Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
-    NeighborhoodGeometry,
+    replace_contact_normal, Contact, ContactKinematic, ContactPrediction, ContactPreprocessor,
+    LocalShapeApproximation, NeighborhoodGeometry, PointQueryWithLocation,
 };
 use crate::shape::{
     CompositeShape, DeformableShape, DeformationsType, FeatureId, Segment, Shape, Triangle,
@@ -54,8 +54,12 @@ pub struct TriMeshFace<N: RealField> {
     pub edges: Point3<usize>,
     bvt_leaf: usize,
     /// The normal of this face if it is not degenerate.
+    ///
+    /// This may be outdated if the mesh is in deformable mode, see `TriMesh::face_normal`.
     pub normal: Option<Unit<Vector<N>>>,
     /// Outward edge normals on the face's plane.
+    ///
+    /// This may be outdated if the mesh is in deformable mode, see `TriMesh::face_side_normals`.
     pub side_normals: Option<[Unit<Vector<N>>; 3]>,
 }
 
@@ -84,6 +88,7 @@ pub struct TriMeshVertex {
 #[derive(Clone)]
 pub struct TriMesh<N: RealField> {
     bvt: BVT<usize, AABB<N>>,
+    qbvh: QBVH<N, usize>,
     uvs: Option<Vec<Point2<N>>>,
     points: Vec<Point<N>>,
     vertices: Vec<TriMeshVertex>,
@@ -93,6 +98,8 @@ pub struct TriMesh<N: RealField> {
     adj_vertex_list: Vec<usize>,
     deformations: DeformationInfos<N>,
     oriented: bool,
+    deformable: bool,
+    outdated_normals: bool,
 }
 
 impl<N: RealField> TriMesh<N> {
@@ -120,14 +127,7 @@ impl<N: RealField> TriMesh<N> {
 
             for (i, is) in is.iter().enumerate() {
                 let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
-                let normal = triangle.normal();
-                let side_normals = normal.map(|n| {
-                    [
-                        Unit::new_normalize((triangle.b() - triangle.a()).cross(&n)),
-                        Unit::new_normalize((triangle.c() - triangle.b()).cross(&n)),
-                        Unit::new_normalize((triangle.a() - triangle.c()).cross(&n)),
-                    ]
-                });
+                let (normal, side_normals) = triangle_normals(&triangle);
 
                 let bv = triangle.local_aabb();
                 leaves.push((i, bv.clone()));
@@ -141,7 +141,7 @@ impl<N: RealField> TriMesh<N> {
             }
         }
 
-        let bvt = BVT::new_balanced(leaves);
+        let bvt = BVT::new_with_sah(leaves);
 
         // Set face.bvt_leaf
         for (i, leaf) in bvt.leaves().iter().enumerate() {
@@ -183,6 +183,7 @@ impl<N: RealField> TriMesh<N> {
         };
 
         TriMesh {
+            qbvh: QBVH::from_bvt(&bvt),
             bvt,
             points,
             uvs,
@@ -193,6 +194,8 @@ impl<N: RealField> TriMesh<N> {
             adj_face_list,
             adj_vertex_list,
             oriented: false,
+            deformable: false,
+            outdated_normals: false,
         }
     }
 
@@ -402,6 +405,98 @@ impl<N: RealField> TriMesh<N> {
         self.oriented = oriented
     }
 
+    /// Whether this trimesh is in deformable mode.
+    ///
+    /// By default a trimesh is not in deformable mode.
+    #[inline]
+    pub fn deformable(&self) -> bool {
+        self.deformable
+    }
+
+    /// Sets whether this trimesh is in deformable mode.
+    ///
+    /// By default, `.set_deformations` only updates the bounding volumes of the triangles that
+    /// moved farther than a margin, which suits meshes that are deformed occasionally or locally.
+    /// In deformable mode, the whole mesh is assumed to move at each call to `.set_deformations`:
+    /// all the bounding volumes are refitted exactly in a single bottom-up pass, and the face
+    /// normals are only recomputed when they are needed. This suits meshes simulated as soft
+    /// bodies, where every vertex moves at each step.
+    pub fn set_deformable(&mut self, deformable: bool) {
+        if self.deformable && !deformable {
+            self.update_normals();
+            // Force the next deformation to update all the bounding volumes.
+            self.deformations.ref_vertices.clear();
+        }
+
+        self.deformable = deformable
+    }
+
+    /// The normal of the `i`-th face of this mesh if it is not degenerate.
+    ///
+    /// Unlike `self.faces()[i].normal`, this is computed from the current position of the
+    /// vertices if the normals stored on the faces are outdated.
+    #[inline]
+    pub fn face_normal(&self, i: usize) -> Option<Unit<Vector<N>>> {
+        if self.outdated_normals {
+            triangle_normals(&self.triangle_at(i)).0
+        } else {
+            self.faces[i].normal
+        }
+    }
+
+    /// The outward edge normals on the plane of the `i`-th face of this mesh if it is not
+    /// degenerate.
+    ///
+    /// Unlike `self.faces()[i].side_normals`, this is computed from the current position of the
+    /// vertices if the normals stored on the faces are outdated.
+    #[inline]
+    pub fn face_side_normals(&self, i: usize) -> Option<[Unit<Vector<N>>; 3]> {
+        if self.outdated_normals {
+            triangle_normals(&self.triangle_at(i)).1
+        } else {
+            self.faces[i].side_normals
+        }
+    }
+
+    /// Recomputes the normals stored on the faces of this mesh if they are outdated.
+    ///
+    /// In deformable mode, `.set_deformations` does not update the normals stored on the faces.
+    /// Call this after a deformation to read them from `.faces()`, or to avoid recomputing them
+    /// at each call to `.face_normal` and `.face_side_normals`.
+    pub fn update_normals(&mut self) {
+        if self.outdated_normals {
+            self.compute_normals();
+        }
+    }
+
+    fn compute_normals(&mut self) {
+        for f in &mut self.faces {
+            let triangle = Triangle::new(
+                self.points[f.indices.x],
+                self.points[f.indices.y],
+                self.points[f.indices.z],
+            );
+            let (normal, side_normals) = triangle_normals(&triangle);
+            f.normal = normal;
+            f.side_normals = side_normals;
+        }
+
+        self.outdated_normals = false;
+    }
+
+    /// The barycentric coordinates of the projection of `pt` on the `i`-th face of this mesh.
+    ///
+    /// The point is expressed in the local space of this mesh, and the coordinates are given with
+    /// respect to the current position of the vertices `self.faces()[i].indices`.
+    pub fn face_barycentric_coordinates(&self, i: usize, pt: &Point<N>) -> [N; 3] {
+        let (_, location) =
+            self.triangle_at(i)
+                .project_point_with_location(&Isometry::identity(), pt, false);
+        location
+            .barycentric_coordinates()
+            .expect("A non-solid projection is always located on the boundary.")
+    }
+
     /// Face containing feature.
     #[inline]
     pub fn face_containing_feature(&self, id: FeatureId) -> usize {
@@ -451,6 +546,12 @@ impl<N: RealField> TriMesh<N> {
         &self.bvt
     }
 
+    /// The flattened hierarchy used by this triangle mesh for ray casts and point queries.
+    #[inline]
+    pub fn qbvh(&self) -> &QBVH<N, usize> {
+        &self.qbvh
+    }
+
     /// Tests that the given `dir` is on the tangent cone of the `i`th vertex
     /// of this mesh.
     pub fn vertex_tangent_cone_contains_dir(
@@ -480,9 +581,7 @@ impl<N: RealField> TriMesh<N> {
             }
         } else {
             for adj_face in &self.adj_face_list[v.adj_faces.clone()] {
-                let face = &self.faces[*adj_face];
-
-                if let Some(ref n) = face.normal {
+                if let Some(ref n) = self.face_normal(*adj_face) {
                     if n.dot(dir) > N::zero() {
                         return false;
                     }
@@ -544,9 +643,7 @@ impl<N: RealField> TriMesh<N> {
             }
         } else {
             for adj_face in [e.adj_faces.0.face_id, e.adj_faces.1.face_id].iter() {
-                let face = &self.faces[*adj_face];
-
-                if let Some(ref n) = face.normal {
+                if let Some(ref n) = self.face_normal(*adj_face) {
                     if n.dot(dir) > N::zero() {
                         return false;
                     }
@@ -568,22 +665,22 @@ impl<N: RealField> TriMesh<N> {
         sin_ang_tol: N,
     ) -> bool {
         let e = &self.edges[i];
-        let f1 = &self.faces[e.adj_faces.0.face_id];
-        let f2 = &self.faces[e.adj_faces.1.face_id];
+        let f1 = e.adj_faces.0.face_id;
+        let f2 = e.adj_faces.1.face_id;
 
-        if let Some(side_normal1) = f1.side_normals.as_ref() {
+        if let Some(side_normal1) = self.face_side_normals(f1) {
             if side_normal1[e.adj_faces.0.edge_id].dot(dir) <= na::convert(-sin_ang_tol) {
                 return false;
             }
         }
 
-        if let Some(side_normal2) = f2.side_normals.as_ref() {
+        if let Some(side_normal2) = self.face_side_normals(f2) {
             if side_normal2[e.adj_faces.1.edge_id].dot(dir) <= na::convert(-sin_ang_tol) {
                 return false;
             }
         }
 
-        if let (Some(n1), Some(n2)) = (f1.normal, f2.normal) {
+        if let (Some(n1), Some(n2)) = (self.face_normal(f1), self.face_normal(f2)) {
             if (n1.into_inner() + n2.into_inner()).dot(dir) < N::zero() {
                 return false;
             }
@@ -637,13 +734,13 @@ impl<N: RealField> TriMesh<N> {
             }
         } else {
             if i >= self.faces.len() {
-                normal = -self.faces[i - self.faces.len()]
-                    .normal
+                normal = -self
+                    .face_normal(i - self.faces.len())
                     .map(|n| n.into_inner())
                     .unwrap_or(Vector::zeros());
             } else {
-                normal = self.faces[i]
-                    .normal
+                normal = self
+                    .face_normal(i)
                     .map(|n| n.into_inner())
                     .unwrap_or(Vector::zeros());
             }
@@ -663,13 +760,13 @@ impl<N: RealField> TriMesh<N> {
         let normal;
 
         if i >= self.faces.len() {
-            normal = -self.faces[i - self.faces.len()]
-                .normal
+            normal = -self
+                .face_normal(i - self.faces.len())
                 .map(|n| n.into_inner())
                 .unwrap_or(Vector::zeros());
         } else {
-            normal = self.faces[i]
-                .normal
+            normal = self
+                .face_normal(i)
                 .map(|n| n.into_inner())
                 .unwrap_or(Vector::zeros());
         }
@@ -677,6 +774,91 @@ impl<N: RealField> TriMesh<N> {
         normal.dot(dir) >= cos_ang_tol
     }
 
+    /// The valid contact normal on the specified feature of this triangle mesh that is the closest
+    /// to `dir`.
+    ///
+    /// A contact normal on an edge or a vertex is valid if none of the adjacent triangles extends
+    /// toward it (within an angular tolerance). Contacts violating this occur on the internal
+    /// edges of the mesh and cause objects sliding on its surface to snag. Invalid normals are
+    /// replaced by the normal of the adjacent face the most aligned with `dir`, taken on the side
+    /// of `dir` if the mesh is not oriented. Returns `None` if no such face normal points toward
+    /// `dir`. Normals on faces are always valid.
+    pub fn closest_valid_contact_normal(
+        &self,
+        feature: FeatureId,
+        dir: &Unit<Vector<N>>,
+        sin_ang_tol: N,
+    ) -> Option<Unit<Vector<N>>> {
+        let tol = sin_ang_tol.max(N::default_epsilon().sqrt());
+        let edge_faces;
+
+        let adj_faces = match feature {
+            FeatureId::Edge(i) => {
+                if self.edge_neighborhood_is_below(i, dir, tol) {
+                    return Some(*dir);
+                }
+
+                let e = &self.edges[i];
+                edge_faces = [e.adj_faces.0.face_id, e.adj_faces.1.face_id];
+                &edge_faces[..]
+            }
+            FeatureId::Vertex(i) => {
+                if self.vertex_tangent_cone_polar_contains_dir(i, dir, tol) {
+                    return Some(*dir);
+                }
+
+                &self.adj_face_list[self.vertices[i].adj_faces.clone()]
+            }
+            _ => return Some(*dir),
+        };
+
+        let mut best: Option<Unit<Vector<N>>> = None;
+
+        for adj_face in adj_faces {
+            if let Some(n) = self.face_normal(*adj_face) {
+                let n = if !self.oriented && n.dot(dir) < N::zero() {
+                    -n
+                } else {
+                    n
+                };
+
+                if n.dot(dir) < N::zero() {
+                    continue;
+                }
+
+                if best.map(|b| n.dot(dir) > b.dot(dir)).unwrap_or(true) {
+                    best = Some(n);
+                }
+            }
+        }
+
+        best
+    }
+
+    // Tests that `dir` is orthogonal to the `i`-th edge and that the triangles adjacent to
+    // this edge do not extend toward `dir`, regardless of the orientation of the mesh.
+    fn edge_neighborhood_is_below(&self, i: usize, dir: &Unit<Vector<N>>, sin_ang_tol: N) -> bool {
+        let e = &self.edges[i];
+        let a = self.points[e.indices.x];
+        let edge_dir = self.points[e.indices.y] - a;
+
+        if edge_dir.dot(dir).abs() > sin_ang_tol * edge_dir.norm() {
+            return false;
+        }
+
+        for adj in [&e.adj_faces.0, &e.adj_faces.1].iter() {
+            // The vertex of the adjacent face opposite to the edge.
+            let opposite = self.faces[adj.face_id].indices[(adj.edge_id + 2) % 3];
+            let to_opposite = self.points[opposite] - a;
+
+            if to_opposite.dot(dir) > sin_ang_tol * to_opposite.norm() {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Checks if the polar of the tangent cone of the specified feature of this triangle mesh contains
     /// the specified direction within an angular tolerence.
     pub fn tangent_cone_polar_contains_dir(
@@ -734,7 +916,7 @@ impl<N: RealField> CompositeShape<N> for TriMesh<N> {
         f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>, &dyn ContactPreprocessor<N>),
     ) {
         let element = self.triangle_at(i);
-        let preprocessor = TriMeshContactProcessor::new(self, m, i, prediction);
+        let preprocessor = TriMeshContactPreprocessor::new(self, m, i, prediction);
         f(m, &element, &preprocessor)
     }
 
@@ -777,6 +959,24 @@ impl<N: RealField> DeformableShape<N> for TriMesh<N> {
             self.points.copy_from_slice(coords_pt);
         }
 
+        if self.deformable {
+            // All the vertices are assumed to have moved.
+            let points = &self.points;
+            let faces = &self.faces;
+            let face_aabb = |i: &usize| {
+                let idx = &faces[*i].indices;
+                bounding_volume::point_cloud_aabb(
+                    &Id::new(),
+                    &[points[idx.x], points[idx.y], points[idx.z]],
+                )
+            };
+
+            self.bvt.refit_bottom_up(face_aabb);
+            self.qbvh.refit_bottom_up(face_aabb);
+            self.outdated_normals = true;
+            return;
+        }
+
         for (target, pt) in self.points.iter_mut().enumerate() {
             let ref_pt = &mut self.deformations.ref_vertices[target];
             let sq_dist_to_ref = na::distance_squared(pt, ref_pt);
@@ -795,25 +995,11 @@ impl<N: RealField> DeformableShape<N> for TriMesh<N> {
         }
 
         // Update normals.
-        for f in &mut self.faces {
-            let ab = self.points[f.indices.y] - self.points[f.indices.x];
-            let ac = self.points[f.indices.z] - self.points[f.indices.x];
-
-            if let Some(n) = Unit::try_new(ab.cross(&ac), N::default_epsilon()) {
-                let bc = self.points[f.indices.z] - self.points[f.indices.y];
-                f.normal = Some(n);
-                f.side_normals = Some([
-                    Unit::new_normalize(ab.cross(&n)),
-                    Unit::new_normalize(bc.cross(&n)),
-                    Unit::new_normalize(-ac.cross(&n)),
-                ]);
-            } else {
-                f.normal = None;
-                f.side_normals = None;
-            }
-        }
+        self.compute_normals();
 
         // Apply the bounding volumes changes.
+        let bvs_changed = !self.deformations.tri_to_update.is_empty();
+
         for tri_id in self.deformations.tri_to_update.drain(..) {
             if self.deformations.timestamps[tri_id] != self.deformations.curr_timestamp {
                 // Update the BV.
@@ -830,7 +1016,15 @@ impl<N: RealField> DeformableShape<N> for TriMesh<N> {
         }
 
         // FIXME: measure efficiency with a non-zero margin.
-        self.bvt.refit(N::zero())
+        self.bvt.refit(N::zero());
+
+        if bvs_changed {
+            // The QBVH leaves use the loosened bounding volumes of the BVT leaves.
+            let bvt = &self.bvt;
+            let faces = &self.faces;
+            self.qbvh
+                .refit_bottom_up(|i| bvt.leaf(faces[*i].bvt_leaf).bounding_volume().clone());
+        }
     }
 
     fn update_local_approximation(&self, coords: &[N], approx: &mut LocalShapeApproximation<N>) {
@@ -891,6 +1085,22 @@ impl<N: RealField> DeformableShape<N> for TriMesh<N> {
     }
 }
 
+// The normal and the outward edge normals of a triangle if it is not degenerate.
+fn triangle_normals<N: RealField>(
+    triangle: &Triangle<N>,
+) -> (Option<Unit<Vector<N>>>, Option<[Unit<Vector<N>>; 3]>) {
+    let normal = triangle.normal();
+    let side_normals = normal.map(|n| {
+        [
+            Unit::new_normalize((triangle.b() - triangle.a()).cross(&n)),
+            Unit::new_normalize((triangle.c() - triangle.b()).cross(&n)),
+            Unit::new_normalize((triangle.a() - triangle.c()).cross(&n)),
+        ]
+    });
+
+    (normal, side_normals)
+}
+
 impl<N: RealField> From<procedural::TriMesh<N>> for TriMesh<N> {
     fn from(trimesh: procedural::TriMesh<N>) -> Self {
         let indices = trimesh
@@ -902,21 +1112,29 @@ impl<N: RealField> From<procedural::TriMesh<N>> for TriMesh<N> {
     }
 }
 
-struct TriMeshContactProcessor<'a, N: RealField> {
+/// Contact preprocessor of a single triangle of a triangle mesh.
+///
+/// It maps the features of the triangle to the features of the mesh, and corrects the normals
+/// of the contacts on internal edges and vertices of the mesh using its adjacency information.
+/// Corrected contacts that are no longer within the contact prediction are discarded. The
+/// barycentric coordinates of the contact point on the triangle are set on the contact kinematic.
+pub struct TriMeshContactPreprocessor<'a, N: RealField> {
     mesh: &'a TriMesh<N>,
     pos: &'a Isometry<N>,
     face_id: usize,
     prediction: &'a ContactPrediction<N>,
 }
 
-impl<'a, N: RealField> TriMeshContactProcessor<'a, N> {
+impl<'a, N: RealField> TriMeshContactPreprocessor<'a, N> {
+    /// Initializes a contact preprocessor for the `face_id`-th triangle of `mesh` positioned at
+    /// `pos`.
     pub fn new(
         mesh: &'a TriMesh<N>,
         pos: &'a Isometry<N>,
         face_id: usize,
         prediction: &'a ContactPrediction<N>,
     ) -> Self {
-        TriMeshContactProcessor {
+        TriMeshContactPreprocessor {
             mesh,
             pos,
             face_id,
@@ -925,7 +1143,7 @@ impl<'a, N: RealField> TriMeshContactProcessor<'a, N> {
     }
 }
 
-impl<'a, N: RealField> ContactPreprocessor<N> for TriMeshContactProcessor<'a, N> {
+impl<'a, N: RealField> ContactPreprocessor<N> for TriMeshContactPreprocessor<'a, N> {
     fn process_contact(
         &self,
         c: &mut Contact<N>,
@@ -959,27 +1177,43 @@ impl<'a, N: RealField> ContactPreprocessor<N> for TriMeshContactProcessor<'a, N>
             kinematic.set_feature2(actual_feature);
         }
 
-        // Test the validity of the LMD.
-        if c.depth > N::zero() {
-            true
+        // Correct the normals of contacts on internal edges and vertices.
+        let world_dir = if is_first { c.normal } else { -c.normal };
+        let local_dir = self.pos.inverse_transform_unit_vector(&world_dir);
+        let sin_ang_tol = if is_first {
+            self.prediction.sin_angular1()
         } else {
-            let local_dir = self.pos.inverse_transform_unit_vector(&c.normal);
+            self.prediction.sin_angular2()
+        };
+
+        let keep =
+            match self
+                .mesh
+                .closest_valid_contact_normal(actual_feature, &local_dir, sin_ang_tol)
+            {
+                Some(n) if n != local_dir => {
+                    replace_contact_normal(c, kinematic, is_first, self.pos, n);
+                    c.depth >= -self.prediction.linear()
+                }
+                Some(_) => true,
+                None => false,
+            };
+
+        // Locate the contact point on the triangle.
+        if keep {
+            let world_pt = if is_first { &c.world1 } else { &c.world2 };
+            let local_pt = self.pos.inverse_transform_point(world_pt);
+            let coords = self
+                .mesh
+                .face_barycentric_coordinates(self.face_id, &local_pt);
 
             if is_first {
-                self.mesh.tangent_cone_polar_contains_dir(
-                    actual_feature,
-                    &local_dir,
-                    self.prediction.sin_angular1(),
-                    self.prediction.cos_angular1(),
-                )
+                kinematic.set_barycentric_coordinates1(Some((self.face_id, coords)));
             } else {
-                self.mesh.tangent_cone_polar_contains_dir(
-                    actual_feature,
-                    &-local_dir,
-                    self.prediction.sin_angular2(),
-                    self.prediction.cos_angular2(),
-                )
+                kinematic.set_barycentric_coordinates2(Some((self.face_id, coords)));
             }
         }
+
+        keep
     }
 }
@@ -0,0 +1,372 @@
+use na::{self, Point3, RealField, Unit};
+use std::collections::HashMap;
+
+use crate::bounding_volume::AABB;
+use crate::math::{Isometry, Point, Translation, Vector};
+use crate::partitioning::{BVHImpl, DBVTLeaf, DBVTLeafId, BVH, DBVT};
+use crate::query::visitors::BoundingVolumeInterferencesCollector;
+use crate::query::{
+    replace_contact_normal, Contact, ContactKinematic, ContactPrediction, ContactPreprocessor,
+};
+use crate::shape::{CompositeShape, Cuboid, FeatureId, Shape};
+use crate::utils::{DeterministicState, IsometryOps};
+
+// Number of features of each kind on a single voxel, as numbered by `Cuboid`.
+const NUM_VERTICES: usize = 8;
+const NUM_EDGES: usize = 32;
+const NUM_FACES: usize = 6;
+
+/// A shape made of the union of filled cells of a regular 3D grid.
+///
+/// The cell `(i, j, k)` covers the box `[i * sx, (i + 1) * sx] x [j * sy, (j + 1) * sy] x [k * sz, (k + 1) * sz]`
+/// where `(sx, sy, sz)` is the voxel size. Only filled cells are stored so the grid may be
+/// unbounded and sparse. Each filled cell is a part of this composite shape, identified by an
+/// index that remains valid until the cell is emptied.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct Voxels<N: RealField> {
+    voxel_size: Vector<N>,
+    voxel: Cuboid<N>,
+    cells: HashMap<Point3<i32>, usize, DeterministicState>,
+    parts: Vec<Option<(Point3<i32>, DBVTLeafId)>>,
+    free_parts: Vec<usize>,
+    dbvt: DBVT<N, usize, AABB<N>>,
+}
+
+impl<N: RealField> Voxels<N> {
+    /// Creates an empty set of voxels with the given size.
+    pub fn new(voxel_size: Vector<N>) -> Self {
+        assert!(
+            voxel_size.iter().all(|s| *s > N::zero()),
+            "The size of a voxel must be strictly positive."
+        );
+
+        Voxels {
+            voxel_size,
+            voxel: Cuboid::new(voxel_size * na::convert::<_, N>(0.5)),
+            cells: HashMap::with_hasher(DeterministicState),
+            parts: Vec::new(),
+            free_parts: Vec::new(),
+            dbvt: DBVT::new(),
+        }
+    }
+
+    /// Creates a set of voxels where all the given cells are filled.
+    pub fn with_voxels(
+        voxel_size: Vector<N>,
+        cells: impl IntoIterator<Item = Point3<i32>>,
+    ) -> Self {
+        let mut res = Self::new(voxel_size);
+
+        for cell in cells {
+            let _ = res.set_voxel(&cell, true);
+        }
+
+        res
+    }
+
+    /// The size of each voxel along each coordinate axis.
+    #[inline]
+    pub fn voxel_size(&self) -> &Vector<N> {
+        &self.voxel_size
+    }
+
+    /// The cuboid shape of a voxel, centered at the origin.
+    #[inline]
+    pub fn voxel_cuboid(&self) -> &Cuboid<N> {
+        &self.voxel
+    }
+
+    /// The number of filled voxels.
+    #[inline]
+    pub fn num_voxels(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether this shape contains no filled voxel.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterator through all the filled cells and their part index.
+    pub fn voxels<'a>(&'a self) -> impl Iterator<Item = (Point3<i32>, usize)> + 'a {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, part)| part.map(|(cell, _)| (cell, i)))
+    }
+
+    /// Whether the given cell is filled.
+    #[inline]
+    pub fn is_filled(&self, cell: &Point3<i32>) -> bool {
+        self.cells.contains_key(cell)
+    }
+
+    /// The part index of the given cell, if it is filled.
+    #[inline]
+    pub fn part_id(&self, cell: &Point3<i32>) -> Option<usize> {
+        self.cells.get(cell).cloned()
+    }
+
+    /// The cell corresponding to the given part index, if this part exists.
+    #[inline]
+    pub fn cell(&self, part_id: usize) -> Option<Point3<i32>> {
+        self.parts.get(part_id).and_then(|part| part.map(|p| p.0))
+    }
+
+    /// Fills or empties the given cell.
+    ///
+    /// Returns `true` if the cell was filled before this call.
+    pub fn set_voxel(&mut self, cell: &Point3<i32>, filled: bool) -> bool {
+        if filled {
+            if self.cells.contains_key(cell) {
+                return true;
+            }
+
+            let part_id = self.free_parts.pop().unwrap_or(self.parts.len());
+            let leaf = DBVTLeaf::new(self.voxel_aabb(cell), part_id);
+            let leaf_id = self.dbvt.insert(leaf);
+
+            if part_id == self.parts.len() {
+                self.parts.push(Some((*cell, leaf_id)));
+            } else {
+                self.parts[part_id] = Some((*cell, leaf_id));
+            }
+
+            let _ = self.cells.insert(*cell, part_id);
+            false
+        } else if let Some(part_id) = self.cells.remove(cell) {
+            if let Some((_, leaf_id)) = self.parts[part_id].take() {
+                let _ = self.dbvt.remove(leaf_id);
+            }
+
+            self.free_parts.push(part_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The cell containing the given point, expressed in the local-space of this shape.
+    #[inline]
+    pub fn voxel_at_point(&self, pt: &Point<N>) -> Point3<i32> {
+        Point3::from(
+            pt.coords
+                .component_div(&self.voxel_size)
+                .map(|e| unsafe { na::convert_unchecked::<N, f64>(e.floor()) as i32 }),
+        )
+    }
+
+    /// The local-space AABB of the given cell.
+    #[inline]
+    pub fn voxel_aabb(&self, cell: &Point3<i32>) -> AABB<N> {
+        let mins = self.voxel_mins(cell);
+        AABB::new(mins, mins + self.voxel_size)
+    }
+
+    /// The local-space center of the given cell.
+    #[inline]
+    pub fn voxel_center(&self, cell: &Point3<i32>) -> Point<N> {
+        self.voxel_mins(cell) + self.voxel_size * na::convert::<_, N>(0.5)
+    }
+
+    fn voxel_mins(&self, cell: &Point3<i32>) -> Point<N> {
+        let cell: Vector<N> = na::convert(cell.coords.map(|e| e as f64));
+        Point::from(cell.component_mul(&self.voxel_size))
+    }
+
+    /// The AABB of all the filled voxels, in the local-space of this shape.
+    ///
+    /// This may be larger than necessary after some voxels have been emptied.
+    /// Returns `None` if no voxel is filled.
+    #[inline]
+    pub fn root_aabb(&self) -> Option<&AABB<N>> {
+        self.dbvt.root_bounding_volume()
+    }
+
+    /// The dynamic bounding volume tree of the filled voxels.
+    #[inline]
+    pub fn dbvt(&self) -> &DBVT<N, usize, AABB<N>> {
+        &self.dbvt
+    }
+
+    /// Transforms a feature of this shape into the index of the part containing it, and the
+    /// corresponding feature of the cuboid of this part.
+    pub fn subshape_feature_id(&self, fid: FeatureId) -> (usize, FeatureId) {
+        match fid {
+            FeatureId::Vertex(i) => (i / NUM_VERTICES, FeatureId::Vertex(i % NUM_VERTICES)),
+            FeatureId::Edge(i) => (i / NUM_EDGES, FeatureId::Edge(i % NUM_EDGES)),
+            FeatureId::Face(i) => (i / NUM_FACES, FeatureId::Face(i % NUM_FACES)),
+            FeatureId::Unknown => (0, FeatureId::Unknown),
+        }
+    }
+
+    /// Transforms a feature of the cuboid of the given part into a feature of this shape.
+    pub fn part_feature_id(&self, part_id: usize, fid: FeatureId) -> FeatureId {
+        match fid {
+            FeatureId::Vertex(i) => FeatureId::Vertex(part_id * NUM_VERTICES + i),
+            FeatureId::Edge(i) => FeatureId::Edge(part_id * NUM_EDGES + i),
+            FeatureId::Face(i) => FeatureId::Face(part_id * NUM_FACES + i),
+            FeatureId::Unknown => FeatureId::Unknown,
+        }
+    }
+
+    /// Applies the function `f` to each voxel intersecting the given local-space AABB.
+    ///
+    /// The function is given the part index of the voxel, the position of its cuboid (relative
+    /// to `m`, the position of this shape), its cuboid and a contact preprocessor that discards
+    /// contacts on the faces and edges shared by adjacent voxels.
+    pub fn map_elements_in_local_aabb(
+        &self,
+        m: &Isometry<N>,
+        aabb: &AABB<N>,
+        f: &mut impl FnMut(usize, &Isometry<N>, &Cuboid<N>, &dyn ContactPreprocessor<N>),
+    ) {
+        let mut part_ids = Vec::new();
+        {
+            let mut visitor = BoundingVolumeInterferencesCollector::new(aabb, &mut part_ids);
+            self.dbvt.visit(&mut visitor);
+        }
+
+        // Sort for determinism since the DBVT structure depends on the order of the edits.
+        part_ids.sort();
+
+        for part_id in part_ids {
+            if let Some((cell, _)) = self.parts[part_id] {
+                let pos = m * Translation::from(self.voxel_center(&cell).coords);
+                let proc = VoxelContactPreprocessor::new(self, m, cell, part_id);
+                f(part_id, &pos, &self.voxel, &proc)
+            }
+        }
+    }
+}
+
+impl<N: RealField> CompositeShape<N> for Voxels<N> {
+    #[inline]
+    fn nparts(&self) -> usize {
+        self.parts.len()
+    }
+
+    #[inline(always)]
+    fn map_part_at(
+        &self,
+        i: usize,
+        m: &Isometry<N>,
+        f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>),
+    ) {
+        if let Some((cell, _)) = self.parts[i] {
+            let pos = m * Translation::from(self.voxel_center(&cell).coords);
+            f(&pos, &self.voxel)
+        }
+    }
+
+    fn map_part_and_preprocessor_at(
+        &self,
+        i: usize,
+        m: &Isometry<N>,
+        _prediction: &ContactPrediction<N>,
+        f: &mut dyn FnMut(&Isometry<N>, &dyn Shape<N>, &dyn ContactPreprocessor<N>),
+    ) {
+        if let Some((cell, _)) = self.parts[i] {
+            let pos = m * Translation::from(self.voxel_center(&cell).coords);
+            let proc = VoxelContactPreprocessor::new(self, m, cell, i);
+            f(&pos, &self.voxel, &proc)
+        }
+    }
+
+    #[inline]
+    fn aabb_at(&self, i: usize) -> AABB<N> {
+        match self.parts[i] {
+            Some((cell, _)) => self.voxel_aabb(&cell),
+            None => AABB::new(Point::origin(), Point::origin()),
+        }
+    }
+
+    #[inline]
+    fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
+        BVHImpl::DBVT(&self.dbvt)
+    }
+}
+
+/// Contact preprocessor of a single voxel.
+///
+/// Contacts with a normal pointing toward a filled neighbor of the voxel are internal-edge
+/// ghost contacts: their normal is projected onto the directions of the exposed faces of the
+/// voxel, and they are discarded if no such direction remains.
+struct VoxelContactPreprocessor<'a, N: RealField> {
+    voxels: &'a Voxels<N>,
+    pos: &'a Isometry<N>,
+    cell: Point3<i32>,
+    part_id: usize,
+}
+
+impl<'a, N: RealField> VoxelContactPreprocessor<'a, N> {
+    fn new(voxels: &'a Voxels<N>, pos: &'a Isometry<N>, cell: Point3<i32>, part_id: usize) -> Self {
+        VoxelContactPreprocessor {
+            voxels,
+            pos,
+            cell,
+            part_id,
+        }
+    }
+}
+
+impl<'a, N: RealField> ContactPreprocessor<N> for VoxelContactPreprocessor<'a, N> {
+    fn process_contact(
+        &self,
+        c: &mut Contact<N>,
+        kinematic: &mut ContactKinematic<N>,
+        is_first: bool,
+    ) -> bool {
+        // The outward normal of the voxel, in the local-space of the voxels.
+        let world_n = if is_first { c.normal } else { -c.normal };
+        let local_n = self.pos.inverse_transform_unit_vector(&world_n);
+        let mut corrected = local_n.into_inner();
+
+        for i in 0..3 {
+            let mut neighbor = self.cell;
+
+            if corrected[i] > N::default_epsilon() {
+                neighbor[i] += 1;
+            } else if corrected[i] < -N::default_epsilon() {
+                neighbor[i] -= 1;
+            } else {
+                continue;
+            }
+
+            if self.voxels.is_filled(&neighbor) {
+                corrected[i] = N::zero();
+            }
+        }
+
+        if corrected != local_n.into_inner() {
+            match Unit::try_new(corrected, N::default_epsilon()) {
+                Some(n) => replace_contact_normal(c, kinematic, is_first, self.pos, n),
+                None => return false,
+            }
+        }
+
+        // Fix the feature ID and express the kinematic in the local-space of the voxels.
+        let part_pos = Isometry::from_parts(
+            Translation::from(self.voxels.voxel_center(&self.cell).coords),
+            na::one(),
+        );
+
+        if is_first {
+            let feature = self
+                .voxels
+                .part_feature_id(self.part_id, kinematic.feature1());
+            kinematic.set_feature1(feature);
+            kinematic.transform1(&part_pos);
+        } else {
+            let feature = self
+                .voxels
+                .part_feature_id(self.part_id, kinematic.feature2());
+            kinematic.set_feature2(feature);
+            kinematic.transform2(&part_pos);
+        }
+
+        true
+    }
+}
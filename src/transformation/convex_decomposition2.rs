@@ -0,0 +1,255 @@
+use crate::math::Point;
+use crate::shape::{ConvexPolygon, Polyline};
+use crate::transformation::convex_hull_idx;
+use crate::transformation::polygon_triangulation::triangulate_polygon;
+use na::{self, RealField};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Decomposes a simple polygon with holes into convex polygons.
+///
+/// The polygon is triangulated, then its triangles are merged back with the Hertel-Mehlhorn
+/// algorithm: the diagonals are removed, longest first, as long as the merged pieces remain
+/// convex. This yields at most four times the minimal number of convex pieces.
+///
+/// A positive `tolerance` allows a merged piece to be concave as long as all its vertices are
+/// closer than `tolerance` to its convex hull. The decomposition is then approximate since each
+/// such piece is replaced by its convex hull.
+///
+/// The outline and the holes may have any orientation. The holes must lie inside of the outline
+/// and must not intersect each other.
+pub fn convex_decomposition<N: RealField>(
+    outline: &[Point<N>],
+    holes: &[&[Point<N>]],
+    tolerance: N,
+) -> Vec<ConvexPolygon<N>> {
+    let (points, triangles) = triangulate_polygon(outline, holes);
+    let mut pieces: Vec<Vec<usize>> = triangles.iter().map(|t| vec![t.x, t.y, t.z]).collect();
+    let mut owners: Vec<usize> = (0..pieces.len()).collect();
+    let mut edge_triangles = HashMap::new();
+    let mut diagonals = Vec::new();
+
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let _ = edge_triangles.insert((t[k], t[(k + 1) % 3]), i);
+        }
+    }
+
+    for t in &triangles {
+        for k in 0..3 {
+            let edge = (t[k], t[(k + 1) % 3]);
+
+            if edge.0 < edge.1 && edge_triangles.contains_key(&(edge.1, edge.0)) {
+                diagonals.push(edge);
+            }
+        }
+    }
+
+    let length = |e: &(usize, usize)| na::distance_squared(&points[e.0], &points[e.1]);
+    diagonals.sort_by(|a, b| length(b).partial_cmp(&length(a)).unwrap_or(Ordering::Equal));
+
+    for (a, b) in diagonals {
+        let piece1 = find_owner(&mut owners, edge_triangles[&(a, b)]);
+        let piece2 = find_owner(&mut owners, edge_triangles[&(b, a)]);
+
+        if piece1 == piece2 {
+            continue;
+        }
+
+        if let Some(merged) = merge_pieces(&pieces[piece1], &pieces[piece2], a, b) {
+            if concavity(&points, &merged) <= tolerance {
+                pieces[piece1] = merged;
+                pieces[piece2].clear();
+                owners[piece2] = piece1;
+            }
+        }
+    }
+
+    pieces
+        .iter()
+        .filter(|piece| !piece.is_empty())
+        .filter_map(|piece| {
+            let vertices: Vec<_> = piece.iter().map(|i| points[*i]).collect();
+            ConvexPolygon::try_from_points(&vertices)
+        })
+        .collect()
+}
+
+/// Decomposes the polygons delimited by the closed loops of a polyline into convex polygons.
+///
+/// Each open chain of the polyline is closed by joining its extremities. A loop contained by an
+/// odd number of other loops is a hole of the smallest loop containing it. Each remaining loop
+/// is an outline decomposed with its holes by `convex_decomposition`.
+pub fn polyline_convex_decomposition<N: RealField>(
+    polyline: &Polyline<N>,
+    tolerance: N,
+) -> Vec<ConvexPolygon<N>> {
+    let points = polyline.points();
+    let loops: Vec<Vec<Point<N>>> = polyline_loops(polyline)
+        .into_iter()
+        .map(|l| l.iter().map(|i| points[*i]).collect())
+        .collect();
+    let mut containers = vec![Vec::new(); loops.len()];
+
+    for (i, inner) in loops.iter().enumerate() {
+        for (j, outer) in loops.iter().enumerate() {
+            if i != j && loop_contains_point(outer, &inner[0]) {
+                containers[i].push(j);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for (i, outline) in loops.iter().enumerate() {
+        let depth = containers[i].len();
+
+        if depth % 2 != 0 {
+            continue;
+        }
+
+        // The holes of this outline are contained by it and by exactly as many other loops.
+        let holes: Vec<&[Point<N>]> = loops
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| containers[*j].len() == depth + 1 && containers[*j].contains(&i))
+            .map(|(_, hole)| &hole[..])
+            .collect();
+
+        result.extend(convex_decomposition(outline, &holes, tolerance));
+    }
+
+    result
+}
+
+fn find_owner(owners: &mut [usize], mut i: usize) -> usize {
+    while owners[i] != i {
+        owners[i] = owners[owners[i]];
+        i = owners[i];
+    }
+
+    i
+}
+
+// Merges two counter-clockwise pieces sharing a diagonal: `piece1` contains the edge
+// `from -> to` and `piece2` contains the edge `to -> from`.
+fn merge_pieces(piece1: &[usize], piece2: &[usize], from: usize, to: usize) -> Option<Vec<usize>> {
+    let (n1, n2) = (piece1.len(), piece2.len());
+    let i1 = (0..n1).find(|i| piece1[*i] == from && piece1[(i + 1) % n1] == to)?;
+    let i2 = (0..n2).find(|i| piece2[*i] == to && piece2[(i + 1) % n2] == from)?;
+    let mut merged = Vec::with_capacity(n1 + n2 - 2);
+
+    // Go around `piece1` from `to` to `from`, then around `piece2` back to `to`.
+    for k in 0..n1 {
+        merged.push(piece1[(i1 + 1 + k) % n1]);
+    }
+
+    for k in 1..n2 - 1 {
+        merged.push(piece2[(i2 + 1 + k) % n2]);
+    }
+
+    Some(merged)
+}
+
+// The greatest distance between a vertex of a counter-clockwise polygon and its convex hull.
+fn concavity<N: RealField>(points: &[Point<N>], polygon: &[usize]) -> N {
+    let n = polygon.len();
+    let is_convex = (0..n).all(|i| {
+        let a = &points[polygon[i]];
+        let b = &points[polygon[(i + 1) % n]];
+        let c = &points[polygon[(i + 2) % n]];
+        (b - a).perp(&(c - b)) >= N::zero()
+    });
+
+    if is_convex {
+        return N::zero();
+    }
+
+    let vertices: Vec<_> = polygon.iter().map(|i| points[*i]).collect();
+    let hull = convex_hull_idx(&vertices);
+    let mut res = N::zero();
+
+    for pt in &vertices {
+        let mut dist = N::max_value();
+
+        for i in 0..hull.len() {
+            let a = &vertices[hull[i]];
+            let b = &vertices[hull[(i + 1) % hull.len()]];
+            let ab = b - a;
+            dist = dist.min(ab.perp(&(pt - a)).abs() / ab.norm());
+        }
+
+        res = res.max(dist);
+    }
+
+    res
+}
+
+// The vertex indices of the loops formed by the edges of a polyline.
+fn polyline_loops<N: RealField>(polyline: &Polyline<N>) -> Vec<Vec<usize>> {
+    let num_points = polyline.points().len();
+    let mut next = vec![None; num_points];
+    let mut has_prev = vec![false; num_points];
+    let mut visited = vec![false; num_points];
+    let mut loops = Vec::new();
+
+    for edge in polyline.edges() {
+        let (a, b) = (edge.indices.x, edge.indices.y);
+
+        if next[a].is_none() {
+            next[a] = Some(b);
+            has_prev[b] = true;
+        }
+    }
+
+    // Start with the open chains, then the remaining vertices are on closed loops.
+    let starts = (0..num_points)
+        .filter(|i| next[*i].is_some() && !has_prev[*i])
+        .chain(0..num_points)
+        .collect::<Vec<_>>();
+
+    for start in starts {
+        if visited[start] || next[start].is_none() {
+            continue;
+        }
+
+        let mut curr_loop = Vec::new();
+        let mut curr = Some(start);
+
+        while let Some(i) = curr {
+            if visited[i] {
+                break;
+            }
+
+            visited[i] = true;
+            curr_loop.push(i);
+            curr = next[i];
+        }
+
+        if curr_loop.len() >= 3 {
+            loops.push(curr_loop);
+        }
+    }
+
+    loops
+}
+
+// Tests if a point is inside of a polygon with the even-odd rule.
+fn loop_contains_point<N: RealField>(polygon: &[Point<N>], pt: &Point<N>) -> bool {
+    let mut inside = false;
+
+    for i in 0..polygon.len() {
+        let a = &polygon[i];
+        let b = &polygon[(i + 1) % polygon.len()];
+
+        if (a.y > pt.y) != (b.y > pt.y) {
+            let x = a.x + (pt.y - a.y) * (b.x - a.x) / (b.y - a.y);
+
+            if pt.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
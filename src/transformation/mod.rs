@@ -3,17 +3,26 @@
 #[cfg(feature = "dim3")]
 pub(crate) use self::convex_hull2::convex_hull2_idx;
 #[cfg(feature = "dim2")]
+pub use self::convex_decomposition2::{convex_decomposition, polyline_convex_decomposition};
+#[cfg(feature = "dim2")]
 pub use self::convex_hull2::{convex_hull2 as convex_hull, convex_hull2_idx as convex_hull_idx};
 #[cfg(feature = "dim3")]
 pub use self::convex_hull3::convex_hull3 as convex_hull;
 #[cfg(feature = "dim3")]
 pub use self::hacd::hacd;
+#[cfg(feature = "dim3")]
+pub use self::polygon_triangulation::polygon_triangulation;
+pub use self::polygon_triangulation::polygon_triangulation_idx;
 #[cfg(feature = "dim2")]
 pub use self::to_polyline::ToPolyline;
 #[cfg(feature = "dim3")]
 pub use self::to_trimesh::ToTriMesh;
+#[cfg(feature = "dim3")]
+pub use self::vhacd::{vhacd, VHACDParameters};
 // pub use self::triangulate::triangulate;
 
+#[cfg(feature = "dim2")]
+mod convex_decomposition2;
 mod convex_hull2;
 #[cfg(feature = "dim3")]
 mod convex_hull3;
@@ -21,8 +30,11 @@ mod convex_hull3;
 pub mod convex_hull_utils; // Internal implementation details.
 #[cfg(feature = "dim3")]
 mod hacd;
+mod polygon_triangulation;
 #[cfg(feature = "dim2")]
 mod to_polyline;
 #[cfg(feature = "dim3")]
 mod to_trimesh;
+#[cfg(feature = "dim3")]
+mod vhacd;
 // mod triangulate;
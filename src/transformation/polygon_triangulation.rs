@@ -0,0 +1,380 @@
+#[cfg(feature = "dim3")]
+use crate::procedural::{IndexBuffer, TriMesh};
+#[cfg(feature = "dim3")]
+use na::Vector3;
+use na::{Point2, Point3, RealField};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Triangulates a polygon with holes lying on the `xy` plane.
+///
+/// See `polygon_triangulation_idx` for details. The vertices of the resulting mesh are the
+/// vertices of the outline followed by the vertices of each hole. Its triangles face the
+/// positive `z` axis.
+#[cfg(feature = "dim3")]
+pub fn polygon_triangulation<N: RealField>(
+    outline: &[Point2<N>],
+    holes: &[&[Point2<N>]],
+) -> TriMesh<N> {
+    let (points, triangles) = triangulate_polygon(outline, holes);
+    let triangles = make_delaunay(&points, triangles);
+    let coords = points
+        .iter()
+        .map(|p| Point3::new(p.x, p.y, N::zero()))
+        .collect();
+    let normals = vec![Vector3::z(); points.len()];
+    let indices = triangles
+        .iter()
+        .map(|t| Point3::new(t.x as u32, t.y as u32, t.z as u32))
+        .collect();
+
+    TriMesh::new(
+        coords,
+        Some(normals),
+        None,
+        Some(IndexBuffer::Unified(indices)),
+    )
+}
+
+/// Computes the constrained Delaunay triangulation of a simple polygon with holes.
+///
+/// The outline and the holes may have any orientation. The holes must lie inside of the outline
+/// and must not intersect each other. The triangles are counter-clockwise and index the
+/// concatenation of the outline and of the holes. Each edge of the outline and of the holes is
+/// an edge of the triangulation.
+///
+/// The polygon is first triangulated by ear clipping, after its holes are connected to its
+/// outline. Then the edges are flipped until each triangle satisfies the Delaunay criterion with
+/// respect to its neighbors across the edges that are not constrained.
+pub fn polygon_triangulation_idx<N: RealField>(
+    outline: &[Point2<N>],
+    holes: &[&[Point2<N>]],
+) -> Vec<Point3<usize>> {
+    let (points, triangles) = triangulate_polygon(outline, holes);
+    make_delaunay(&points, triangles)
+}
+
+/// Triangulates a simple polygon with holes by ear clipping.
+///
+/// Returns the vertices of the polygon, i.e., the concatenation of the outline and of the holes,
+/// and the counter-clockwise triangles indexing them. The outline and the holes may have any
+/// orientation. The holes must lie inside of the outline and must not intersect each other.
+pub(crate) fn triangulate_polygon<N: RealField>(
+    outline: &[Point2<N>],
+    holes: &[&[Point2<N>]],
+) -> (Vec<Point2<N>>, Vec<Point3<usize>>) {
+    let mut points = outline.to_vec();
+    let mut polygon: Vec<usize> = (0..outline.len()).collect();
+
+    if signed_area(&points, &polygon) < N::zero() {
+        polygon.reverse();
+    }
+
+    let mut hole_loops = Vec::new();
+
+    for hole in holes {
+        let start = points.len();
+        points.extend_from_slice(hole);
+
+        if hole.len() < 3 {
+            continue;
+        }
+
+        let mut hole_loop: Vec<usize> = (start..points.len()).collect();
+
+        if signed_area(&points, &hole_loop) > N::zero() {
+            hole_loop.reverse();
+        }
+
+        // Start the hole at its rightmost vertex.
+        let rightmost = (0..hole_loop.len())
+            .max_by(|a, b| cmp_x(&points[hole_loop[*a]], &points[hole_loop[*b]]))
+            .unwrap();
+        hole_loop.rotate_left(rightmost);
+        hole_loops.push(hole_loop);
+    }
+
+    // Bridge the holes from the rightmost one so that each bridge stays inside of the polygon.
+    hole_loops.sort_by(|a, b| cmp_x(&points[b[0]], &points[a[0]]));
+
+    for hole_loop in hole_loops {
+        if let Some(i) = find_bridge_vertex(&points, &polygon, &points[hole_loop[0]]) {
+            let mut bridged = Vec::with_capacity(polygon.len() + hole_loop.len() + 2);
+            bridged.extend_from_slice(&polygon[..=i]);
+            bridged.extend_from_slice(&hole_loop);
+            bridged.push(hole_loop[0]);
+            bridged.push(polygon[i]);
+            bridged.extend_from_slice(&polygon[i + 1..]);
+            polygon = bridged;
+        }
+    }
+
+    let triangles = clip_ears(&points, polygon);
+    (points, triangles)
+}
+
+fn cmp_x<N: RealField>(a: &Point2<N>, b: &Point2<N>) -> Ordering {
+    a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal)
+}
+
+fn signed_area<N: RealField>(points: &[Point2<N>], polygon: &[usize]) -> N {
+    let mut area = N::zero();
+
+    for i in 0..polygon.len() {
+        let a = &points[polygon[i]];
+        let b = &points[polygon[(i + 1) % polygon.len()]];
+        area += a.coords.perp(&b.coords);
+    }
+
+    area * na::convert(0.5)
+}
+
+// Twice the signed area of the triangle `(a, b, c)`, positive if it is counter-clockwise.
+fn orientation<N: RealField>(a: &Point2<N>, b: &Point2<N>, c: &Point2<N>) -> N {
+    (b - a).perp(&(c - b))
+}
+
+fn corner_orientation<N: RealField>(points: &[Point2<N>], polygon: &[usize], i: usize) -> N {
+    let n = polygon.len();
+    orientation(
+        &points[polygon[(i + n - 1) % n]],
+        &points[polygon[i]],
+        &points[polygon[(i + 1) % n]],
+    )
+}
+
+// Tests if `p` is inside of the triangle `(a, b, c)` or on its boundary.
+fn triangle_contains<N: RealField>(
+    a: &Point2<N>,
+    b: &Point2<N>,
+    c: &Point2<N>,
+    p: &Point2<N>,
+) -> bool {
+    let s1 = (b - a).perp(&(p - a));
+    let s2 = (c - b).perp(&(p - b));
+    let s3 = (a - c).perp(&(p - c));
+
+    (s1 >= N::zero() && s2 >= N::zero() && s3 >= N::zero())
+        || (s1 <= N::zero() && s2 <= N::zero() && s3 <= N::zero())
+}
+
+// Finds the position of a vertex of the counter-clockwise `polygon` visible from the point `m`
+// along the positive `x` axis (see "Triangulation by Ear Clipping", D. Eberly).
+fn find_bridge_vertex<N: RealField>(
+    points: &[Point2<N>],
+    polygon: &[usize],
+    m: &Point2<N>,
+) -> Option<usize> {
+    let n = polygon.len();
+    let mut closest: Option<(N, usize)> = None;
+
+    // Cast a ray toward the positive `x` axis. Seen from the inside, the edges it can hit go upward.
+    for i in 0..n {
+        let a = &points[polygon[i]];
+        let b = &points[polygon[(i + 1) % n]];
+
+        if a.y <= m.y && b.y >= m.y && a.y != b.y {
+            let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+
+            if x >= m.x && closest.map(|c| x < c.0).unwrap_or(true) {
+                closest = Some((x, i));
+            }
+        }
+    }
+
+    let (x, i) = closest?;
+    let j = (i + 1) % n;
+    let (a, b) = (&points[polygon[i]], &points[polygon[j]]);
+
+    if a.y == m.y && a.x == x {
+        return Some(i);
+    }
+
+    if b.y == m.y && b.x == x {
+        return Some(j);
+    }
+
+    // The endpoint of the hit edge is visible unless a reflex vertex lies inside of the triangle
+    // formed with the hit point. In this case, the reflex vertex closest to the ray is visible.
+    let mut res = if a.x > b.x { i } else { j };
+    let hit = Point2::new(x, m.y);
+    let candidate = points[polygon[res]];
+    let mut best = None;
+
+    for k in 0..n {
+        let r = &points[polygon[k]];
+
+        if *r == candidate
+            || r.x <= m.x
+            || corner_orientation(points, polygon, k) >= N::zero()
+            || !triangle_contains(m, &hit, &candidate, r)
+        {
+            continue;
+        }
+
+        let key = ((r.y - m.y).abs() / (r.x - m.x), na::distance_squared(m, r));
+
+        if best.map(|best| key < best).unwrap_or(true) {
+            best = Some(key);
+            res = k;
+        }
+    }
+
+    Some(res)
+}
+
+fn is_ear<N: RealField>(points: &[Point2<N>], polygon: &[usize], i: usize) -> bool {
+    let n = polygon.len();
+    let (ia, ic) = ((i + n - 1) % n, (i + 1) % n);
+    let (a, b, c) = (
+        &points[polygon[ia]],
+        &points[polygon[i]],
+        &points[polygon[ic]],
+    );
+
+    if orientation(a, b, c) <= N::zero() {
+        return false;
+    }
+
+    for (k, id) in polygon.iter().enumerate() {
+        let p = &points[*id];
+
+        // Vertices duplicated by the bridges do not prevent clipping.
+        if k == ia || k == i || k == ic || p == a || p == b || p == c {
+            continue;
+        }
+
+        if triangle_contains(a, b, c, p) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn clip_ears<N: RealField>(points: &[Point2<N>], mut polygon: Vec<usize>) -> Vec<Point3<usize>> {
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    let mut i = 0;
+    let mut num_failures = 0;
+
+    while polygon.len() > 3 {
+        let n = polygon.len();
+
+        if is_ear(points, &polygon, i) {
+            triangles.push(Point3::new(
+                polygon[(i + n - 1) % n],
+                polygon[i],
+                polygon[(i + 1) % n],
+            ));
+            let _ = polygon.remove(i);
+            i %= polygon.len();
+            num_failures = 0;
+        } else {
+            i = (i + 1) % n;
+            num_failures += 1;
+
+            if num_failures == n {
+                // No ear was found because of degeneracies or rounding errors: clip the most
+                // convex vertex, without creating a triangle if it is flat or reflex.
+                let (best, orient) = (0..n)
+                    .map(|k| (k, corner_orientation(points, &polygon, k)))
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                    .unwrap();
+
+                if orient > N::zero() {
+                    triangles.push(Point3::new(
+                        polygon[(best + n - 1) % n],
+                        polygon[best],
+                        polygon[(best + 1) % n],
+                    ));
+                }
+
+                let _ = polygon.remove(best);
+                i = best % polygon.len();
+                num_failures = 0;
+            }
+        }
+    }
+
+    if polygon.len() == 3 && corner_orientation(points, &polygon, 1) > N::zero() {
+        triangles.push(Point3::new(polygon[0], polygon[1], polygon[2]));
+    }
+
+    triangles
+}
+
+// Flips the edges shared by two triangles until the triangulation is locally Delaunay. The
+// edges of the polygon belong to only one triangle and are never flipped.
+fn make_delaunay<N: RealField>(
+    points: &[Point2<N>],
+    mut triangles: Vec<Point3<usize>>,
+) -> Vec<Point3<usize>> {
+    let mut edges = HashMap::new();
+    let mut stack = Vec::new();
+
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let _ = edges.insert((t[k], t[(k + 1) % 3]), i);
+            stack.push((t[k], t[(k + 1) % 3]));
+        }
+    }
+
+    while let Some((a, b)) = stack.pop() {
+        let (t1, t2) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(t1), Some(t2)) => (*t1, *t2),
+            _ => continue,
+        };
+        let c = opposite_vertex(&triangles[t1], a, b);
+        let d = opposite_vertex(&triangles[t2], a, b);
+        let (pa, pb, pc, pd) = (&points[a], &points[b], &points[c], &points[d]);
+
+        // The flip is only valid if the quadrilateral `(a, d, b, c)` is strictly convex.
+        if !in_circumcircle(pa, pb, pc, pd)
+            || orientation(pa, pd, pc) <= N::zero()
+            || orientation(pd, pb, pc) <= N::zero()
+        {
+            continue;
+        }
+
+        triangles[t1] = Point3::new(a, d, c);
+        triangles[t2] = Point3::new(d, b, c);
+
+        let _ = edges.remove(&(a, b));
+        let _ = edges.remove(&(b, a));
+        let _ = edges.insert((a, d), t1);
+        let _ = edges.insert((d, c), t1);
+        let _ = edges.insert((c, a), t1);
+        let _ = edges.insert((d, b), t2);
+        let _ = edges.insert((b, c), t2);
+        let _ = edges.insert((c, d), t2);
+
+        stack.extend_from_slice(&[(a, d), (d, b), (b, c), (c, a)]);
+    }
+
+    triangles
+}
+
+fn opposite_vertex(triangle: &Point3<usize>, a: usize, b: usize) -> usize {
+    if triangle.x != a && triangle.x != b {
+        triangle.x
+    } else if triangle.y != a && triangle.y != b {
+        triangle.y
+    } else {
+        triangle.z
+    }
+}
+
+// Tests if `d` is strictly inside of the circumcircle of the counter-clockwise triangle
+// `(a, b, c)`.
+fn in_circumcircle<N: RealField>(
+    a: &Point2<N>,
+    b: &Point2<N>,
+    c: &Point2<N>,
+    d: &Point2<N>,
+) -> bool {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let det = ad.norm_squared() * bd.perp(&cd) - bd.norm_squared() * ad.perp(&cd)
+        + cd.norm_squared() * ad.perp(&bd);
+
+    det > N::zero()
+}
@@ -0,0 +1,13 @@
+use super::ToPolyline;
+use crate::procedural::{self, Polyline};
+use crate::shape::Ellipsoid;
+use alga::general::RealField;
+use na;
+
+impl<N: RealField> ToPolyline<N> for Ellipsoid<N> {
+    type DiscretizationParameter = u32;
+
+    fn to_polyline(&self, nsubdiv: u32) -> Polyline<N> {
+        procedural::circle(&N::one(), nsubdiv).scaled(&(self.radii() * na::convert::<_, N>(2.0f64)))
+    }
+}
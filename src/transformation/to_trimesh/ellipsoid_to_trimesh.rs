@@ -0,0 +1,24 @@
+use super::ToTriMesh;
+use crate::procedural;
+use crate::procedural::TriMesh;
+use crate::shape::Ellipsoid;
+use alga::general::RealField;
+use na;
+
+impl<N: RealField> ToTriMesh<N> for Ellipsoid<N> {
+    type DiscretizationParameter = (u32, u32);
+
+    fn to_trimesh(&self, (ntheta_subdiv, nphi_subdiv): (u32, u32)) -> TriMesh<N> {
+        let mut mesh = procedural::unit_sphere(ntheta_subdiv, nphi_subdiv, true);
+        mesh.scale_by(&(self.radii() * na::convert::<_, N>(2.0f64)));
+
+        // The normals of a scaled mesh are scaled by the inverse scaling.
+        if let Some(normals) = &mut mesh.normals {
+            for n in normals {
+                *n = n.component_div(self.radii()).normalize();
+            }
+        }
+
+        mesh
+    }
+}
@@ -5,6 +5,7 @@ mod capsule_to_trimesh;
 mod cone_to_trimesh;
 mod cuboid_to_trimesh;
 mod cylinder_to_trimesh;
+mod ellipsoid_to_trimesh;
 #[doc(hidden)]
 pub mod to_trimesh;
 // mod mesh_to_trimesh;
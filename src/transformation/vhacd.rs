@@ -0,0 +1,559 @@
+use crate::bounding_volume;
+use crate::procedural::TriMesh;
+use crate::shape::ConvexHull;
+use crate::transformation;
+use crate::utils;
+use na::{self, Point3, RealField, Vector3};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const OUTSIDE: u8 = 1;
+const SURFACE: u8 = 2;
+
+/// Parameters of the volumetric approximate convex decomposition computed by `vhacd`.
+#[derive(Clone, Debug)]
+pub struct VHACDParameters<N: RealField> {
+    /// The number of voxels along the largest side of the bounding box of the mesh.
+    pub resolution: usize,
+    /// The maximum concavity of each part, relative to the volume of the voxelized mesh.
+    ///
+    /// The concavity of a part is the volume of its convex hull not filled by its voxels.
+    pub concavity: N,
+    /// The maximum number of successive splits of a part of the mesh.
+    pub max_depth: usize,
+    /// The number of splitting planes tested along each axis.
+    pub planes_per_axis: usize,
+    /// The maximum number of convex hulls generated.
+    pub max_convex_hulls: usize,
+    /// The maximum number of vertices of each convex hull.
+    pub max_vertices_per_hull: usize,
+}
+
+impl<N: RealField> Default for VHACDParameters<N> {
+    fn default() -> Self {
+        VHACDParameters {
+            resolution: 32,
+            concavity: na::convert(0.01),
+            max_depth: 10,
+            planes_per_axis: 16,
+            max_convex_hulls: 32,
+            max_vertices_per_hull: 64,
+        }
+    }
+}
+
+/// Volumetric approximate convex decomposition of a triangle mesh.
+///
+/// The mesh is voxelized, then its voxels are recursively split by the axis-aligned planes that
+/// minimize the concavity of the resulting parts, until each part is concave enough or the
+/// maximum depth is reached. The parts are then merged pairwise, starting with the ones losing
+/// the least volume, until there are at most `params.max_convex_hulls` of them. Finally, the
+/// convex hull of each part is simplified to at most `params.max_vertices_per_hull` vertices.
+///
+/// The mesh does not need to have normals nor to be manifold. If it is not closed, only the
+/// voxels crossed by its triangles are considered filled. Because each hull encloses the voxels
+/// of its part, it may exceed the mesh by up to the size of one voxel.
+pub fn vhacd<N: RealField>(mesh: &TriMesh<N>, params: &VHACDParameters<N>) -> Vec<ConvexHull<N>> {
+    let grid = match VoxelGrid::new(mesh, params.resolution) {
+        Some(grid) => grid,
+        None => return Vec::new(),
+    };
+
+    let max_concavity = params.concavity * grid.volume(&grid.voxels);
+    let mut to_split = vec![(grid.voxels.clone(), 0)];
+    let mut parts = Vec::new();
+
+    while let Some((part, depth)) = to_split.pop() {
+        if depth >= params.max_depth || part.len() < 2 || grid.concavity(&part) <= max_concavity {
+            parts.push(part);
+            continue;
+        }
+
+        match grid.best_split(&part, params.planes_per_axis) {
+            Some((left, right)) => {
+                to_split.push((left, depth + 1));
+                to_split.push((right, depth + 1));
+            }
+            None => parts.push(part),
+        }
+    }
+
+    let parts = merge_parts(&grid, parts, params.max_convex_hulls.max(1));
+
+    parts
+        .iter()
+        .filter_map(|part| grid.convex_hull(part, params.max_vertices_per_hull))
+        .collect()
+}
+
+// The voxels of a mesh. The voxel `(i, j, k)` spans from the corner `(i, j, k)` to the corner
+// `(i + 1, j + 1, k + 1)` of the grid.
+struct VoxelGrid<N: RealField> {
+    origin: Point3<N>,
+    voxel_size: N,
+    dims: Vector3<usize>,
+    states: Vec<u8>,
+    voxels: Vec<Point3<usize>>,
+}
+
+impl<N: RealField> VoxelGrid<N> {
+    fn new(mesh: &TriMesh<N>, resolution: usize) -> Option<Self> {
+        if mesh.coords.is_empty() || resolution == 0 {
+            return None;
+        }
+
+        let aabb = bounding_volume::local_point_cloud_aabb(&mesh.coords);
+        let voxel_size = aabb.extents().max() / na::convert(resolution as f64);
+
+        if voxel_size.is_zero() {
+            return None;
+        }
+
+        // Keep an empty layer of voxels around the mesh for the flood fill. The grid is shifted
+        // by half a voxel so that axis-aligned faces cross the centers of the voxels instead of
+        // their boundaries.
+        let origin = aabb.mins() - Vector3::repeat(voxel_size * na::convert(1.5));
+        let dims = (aabb.extents() / voxel_size).map(|e| to_index(e) + 4);
+        let id = |v: &Point3<usize>| v.x + v.y * dims.x + v.z * dims.x * dims.y;
+        let mut states = vec![0u8; dims.x * dims.y * dims.z];
+        let half = voxel_size * na::convert(0.5);
+
+        for tri in mesh.flat_indices().chunks(3) {
+            let tri = [
+                &mesh.coords[tri[0] as usize],
+                &mesh.coords[tri[1] as usize],
+                &mesh.coords[tri[2] as usize],
+            ];
+            let mins = na::inf(&na::inf(tri[0], tri[1]), tri[2]);
+            let maxs = na::sup(&na::sup(tri[0], tri[1]), tri[2]);
+            let first = ((mins - origin) / voxel_size).map(to_index);
+            let last = ((maxs - origin) / voxel_size).map(to_index);
+
+            for i in first.x..=last.x.min(dims.x - 1) {
+                for j in first.y..=last.y.min(dims.y - 1) {
+                    for k in first.z..=last.z.min(dims.z - 1) {
+                        let voxel = Point3::new(i, j, k);
+                        let center = origin
+                            + Vector3::new(i, j, k).map(|e| na::convert::<_, N>(e as f64))
+                                * voxel_size
+                            + Vector3::repeat(half);
+
+                        if triangle_intersects_cube(tri, &center, half) {
+                            states[id(&voxel)] = SURFACE;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Flood fill the outside, the remaining voxels are inside of the mesh.
+        let mut stack = vec![Point3::origin()];
+        states[0] = OUTSIDE;
+
+        while let Some(voxel) = stack.pop() {
+            for k in 0..3 {
+                for &shift in &[-1isize, 1] {
+                    let coord = voxel[k] as isize + shift;
+
+                    if coord < 0 || coord as usize >= dims[k] {
+                        continue;
+                    }
+
+                    let mut neighbor = voxel;
+                    neighbor[k] = coord as usize;
+
+                    if states[id(&neighbor)] == 0 {
+                        states[id(&neighbor)] = OUTSIDE;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut voxels = Vec::new();
+
+        for k in 0..dims.z {
+            for j in 0..dims.y {
+                for i in 0..dims.x {
+                    let voxel = Point3::new(i, j, k);
+
+                    if states[id(&voxel)] != OUTSIDE {
+                        voxels.push(voxel);
+                    }
+                }
+            }
+        }
+
+        Some(VoxelGrid {
+            origin,
+            voxel_size,
+            dims,
+            states,
+            voxels,
+        })
+    }
+
+    // The volume of the mesh inside of the voxels of `part`, assuming the voxels crossed by the
+    // surface of the mesh are half-filled.
+    fn volume(&self, part: &[Point3<usize>]) -> N {
+        let num_surface_voxels = part
+            .iter()
+            .filter(|v| {
+                self.states[v.x + v.y * self.dims.x + v.z * self.dims.x * self.dims.y] == SURFACE
+            })
+            .count();
+        let num_voxels = part.len() as f64 - num_surface_voxels as f64 * 0.5;
+
+        na::convert::<_, N>(num_voxels) * self.voxel_size.powi(3)
+    }
+
+    // The points of the voxels of `part` that may be vertices of their convex hull. Those are the
+    // corners (or the centers if `corners` is `false`) of the first and last voxels of each row
+    // along the `x` axis.
+    fn hull_candidates(&self, part: &[Point3<usize>], corners: bool) -> Vec<Point3<N>> {
+        let mut rows = HashMap::new();
+
+        for voxel in part {
+            let range = rows.entry((voxel.y, voxel.z)).or_insert((voxel.x, voxel.x));
+            range.0 = range.0.min(voxel.x);
+            range.1 = range.1.max(voxel.x);
+        }
+
+        // Points as twice their coordinates in the grid, to represent centers exactly.
+        let mut points = HashSet::new();
+
+        for ((y, z), (first, last)) in rows {
+            if corners {
+                for &(dy, dz) in &[(0, 0), (2, 0), (0, 2), (2, 2)] {
+                    let _ = points.insert((first * 2, y * 2 + dy, z * 2 + dz));
+                    let _ = points.insert((last * 2 + 2, y * 2 + dy, z * 2 + dz));
+                }
+            } else {
+                let _ = points.insert((first * 2 + 1, y * 2 + 1, z * 2 + 1));
+                let _ = points.insert((last * 2 + 1, y * 2 + 1, z * 2 + 1));
+            }
+        }
+
+        let mut points: Vec<_> = points.into_iter().collect();
+        points.sort();
+
+        let half = self.voxel_size * na::convert(0.5);
+        points
+            .into_iter()
+            .map(|(x, y, z)| {
+                self.origin + Vector3::new(x, y, z).map(|e| na::convert::<_, N>(e as f64)) * half
+            })
+            .collect()
+    }
+
+    fn hull_volume(&self, part: &[Point3<usize>]) -> N {
+        let points = self.hull_candidates(part, false);
+
+        if points.len() < 4 {
+            return N::zero();
+        }
+
+        let hull = transformation::convex_hull(&points);
+        let center = utils::center(&hull.coords);
+        let mut volume = N::zero();
+
+        // The hull is convex so it is the union of the tetrahedra joining its center to its faces.
+        for tri in hull.flat_indices().chunks(3) {
+            let a = hull.coords[tri[0] as usize] - center;
+            let b = hull.coords[tri[1] as usize] - center;
+            let c = hull.coords[tri[2] as usize] - center;
+            volume += a.dot(&b.cross(&c)).abs();
+        }
+
+        volume / na::convert(6.0)
+    }
+
+    // The volume of the convex hull of the voxel centers of `part` that is not filled by its
+    // voxels. Using the centers ignores the stairs formed by the voxels on slanted surfaces.
+    fn concavity(&self, part: &[Point3<usize>]) -> N {
+        (self.hull_volume(part) - self.volume(part)).max(N::zero())
+    }
+
+    // Splits `part` by the axis-aligned plane minimizing the concavity of the two halves, with a
+    // small penalty for unbalanced splits.
+    fn best_split(
+        &self,
+        part: &[Point3<usize>],
+        planes_per_axis: usize,
+    ) -> Option<(Vec<Point3<usize>>, Vec<Point3<usize>>)> {
+        let mut best: Option<(N, usize, usize)> = None;
+        let balance_weight: N = na::convert(0.05);
+
+        for axis in 0..3 {
+            let first = part.iter().map(|v| v[axis]).min()?;
+            let last = part.iter().map(|v| v[axis]).max()?;
+            // The planes between `first` and `last`, i.e., at the corners `first + 1..=last`.
+            let num_planes = last - first;
+            let num_tested = num_planes.min(planes_per_axis.max(1));
+
+            for s in 0..num_tested {
+                let plane = if num_tested == 1 {
+                    first + 1 + num_planes / 2
+                } else {
+                    first + 1 + s * (num_planes - 1) / (num_tested - 1)
+                };
+                let (left, right): (Vec<_>, Vec<_>) = part.iter().partition(|v| v[axis] < plane);
+
+                if left.is_empty() || right.is_empty() {
+                    continue;
+                }
+
+                let cost = self.concavity(&left)
+                    + self.concavity(&right)
+                    + (self.volume(&left) - self.volume(&right)).abs() * balance_weight;
+
+                if best.map(|best| cost < best.0).unwrap_or(true) {
+                    best = Some((cost, axis, plane));
+                }
+            }
+        }
+
+        let (_, axis, plane) = best?;
+        Some(part.iter().partition(|v| v[axis] < plane))
+    }
+
+    fn convex_hull(&self, part: &[Point3<usize>], max_vertices: usize) -> Option<ConvexHull<N>> {
+        let hull = transformation::convex_hull(&self.hull_candidates(part, true));
+        let mut vertices = hull.coords;
+
+        if vertices.len() > max_vertices {
+            vertices = simplify_hull(&vertices, max_vertices.max(4));
+        }
+
+        ConvexHull::try_from_points(&vertices)
+    }
+}
+
+// Merges the parts pairwise until there are at most `max_parts` of them. The pair merged first
+// is the one which convex hull adds the least volume to the convex hulls of the two parts.
+// Adjacent parts are merged first.
+fn merge_parts<N: RealField>(
+    grid: &VoxelGrid<N>,
+    parts: Vec<Vec<Point3<usize>>>,
+    max_parts: usize,
+) -> Vec<Vec<Point3<usize>>> {
+    if parts.len() <= max_parts {
+        return parts;
+    }
+
+    let mut hull_volumes: Vec<N> = parts.iter().map(|p| grid.hull_volume(p)).collect();
+    let mut bounds: Vec<_> = parts.iter().map(|p| voxel_bounds(p)).collect();
+    let mut parts: Vec<Option<Vec<Point3<usize>>>> = parts.into_iter().map(Some).collect();
+    let mut num_parts = parts.len();
+    let mut costs = BTreeMap::new();
+    let mut adjacent_only = true;
+
+    let merge_cost = |parts: &[Option<Vec<Point3<usize>>>], volumes: &[N], i: usize, j: usize| {
+        let merged = [
+            &parts[i].as_ref().unwrap()[..],
+            &parts[j].as_ref().unwrap()[..],
+        ]
+        .concat();
+        grid.hull_volume(&merged) - volumes[i] - volumes[j]
+    };
+
+    while num_parts > max_parts {
+        if costs.is_empty() {
+            for i in 0..parts.len() {
+                for j in i + 1..parts.len() {
+                    if parts[i].is_some()
+                        && parts[j].is_some()
+                        && (!adjacent_only || bounds_touch(&bounds[i], &bounds[j]))
+                    {
+                        let _ = costs.insert((i, j), merge_cost(&parts, &hull_volumes, i, j));
+                    }
+                }
+            }
+
+            if costs.is_empty() {
+                // The remaining parts are disconnected: merge them anyway.
+                adjacent_only = false;
+                continue;
+            }
+        }
+
+        let (i, j) = *costs
+            .iter()
+            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal))
+            .unwrap()
+            .0;
+
+        let part_j = parts[j].take().unwrap();
+        parts[i].as_mut().unwrap().extend(part_j);
+        hull_volumes[i] = grid.hull_volume(parts[i].as_ref().unwrap());
+        bounds[i] = voxel_bounds(parts[i].as_ref().unwrap());
+        num_parts -= 1;
+
+        let outdated: Vec<_> = costs
+            .keys()
+            .filter(|key| key.0 == i || key.1 == i || key.0 == j || key.1 == j)
+            .cloned()
+            .collect();
+
+        for key in outdated {
+            let _ = costs.remove(&key);
+        }
+
+        for k in 0..parts.len() {
+            if k != i
+                && parts[k].is_some()
+                && (!adjacent_only || bounds_touch(&bounds[i], &bounds[k]))
+            {
+                let key = (i.min(k), i.max(k));
+                let _ = costs.insert(key, merge_cost(&parts, &hull_volumes, key.0, key.1));
+            }
+        }
+    }
+
+    parts.into_iter().filter_map(|p| p).collect()
+}
+
+fn voxel_bounds(part: &[Point3<usize>]) -> (Point3<usize>, Point3<usize>) {
+    let mut mins = part[0];
+    let mut maxs = part[0];
+
+    for voxel in part {
+        mins = na::inf(&mins, voxel);
+        maxs = na::sup(&maxs, voxel);
+    }
+
+    (mins, maxs)
+}
+
+fn bounds_touch(a: &(Point3<usize>, Point3<usize>), b: &(Point3<usize>, Point3<usize>)) -> bool {
+    (0..3).all(|k| a.0[k] <= b.1[k] + 1 && b.0[k] <= a.1[k] + 1)
+}
+
+// Selects at most `max_vertices` vertices of a convex hull, adding first the vertices furthest
+// from the convex hull of the vertices already selected.
+fn simplify_hull<N: RealField>(vertices: &[Point3<N>], max_vertices: usize) -> Vec<Point3<N>> {
+    let cmp = |a: &N, b: &N| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+    let dist_to_line = |p: &Point3<N>, a: &Point3<N>, b: &Point3<N>| (p - a).cross(&(b - a)).norm();
+    let dist_to_plane = |p: &Point3<N>, a: &Point3<N>, b: &Point3<N>, c: &Point3<N>| {
+        (p - a).dot(&(b - a).cross(&(c - a))).abs()
+    };
+
+    // Start with a tetrahedron.
+    let i0 = (0..vertices.len())
+        .min_by(|i, j| cmp(&vertices[*i].x, &vertices[*j].x))
+        .unwrap();
+    let i1 = (0..vertices.len())
+        .max_by(|i, j| {
+            cmp(
+                &na::distance_squared(&vertices[*i], &vertices[i0]),
+                &na::distance_squared(&vertices[*j], &vertices[i0]),
+            )
+        })
+        .unwrap();
+    let (v0, v1) = (&vertices[i0], &vertices[i1]);
+    let i2 = (0..vertices.len())
+        .max_by(|i, j| {
+            cmp(
+                &dist_to_line(&vertices[*i], v0, v1),
+                &dist_to_line(&vertices[*j], v0, v1),
+            )
+        })
+        .unwrap();
+    let v2 = &vertices[i2];
+    let i3 = (0..vertices.len())
+        .max_by(|i, j| {
+            cmp(
+                &dist_to_plane(&vertices[*i], v0, v1, v2),
+                &dist_to_plane(&vertices[*j], v0, v1, v2),
+            )
+        })
+        .unwrap();
+
+    let mut selected = vec![*v0, *v1, *v2, vertices[i3]];
+    let mut remaining: Vec<_> = (0..vertices.len())
+        .filter(|i| ![i0, i1, i2, i3].contains(i))
+        .map(|i| vertices[i])
+        .collect();
+
+    while selected.len() < max_vertices && !remaining.is_empty() {
+        let hull = transformation::convex_hull(&selected);
+        let center = utils::center(&hull.coords);
+        let mut planes = Vec::new();
+
+        for tri in hull.flat_indices().chunks(3) {
+            let a = hull.coords[tri[0] as usize];
+            let b = hull.coords[tri[1] as usize];
+            let c = hull.coords[tri[2] as usize];
+
+            if let Some(mut n) = (b - a).cross(&(c - a)).try_normalize(N::default_epsilon()) {
+                if n.dot(&(a - center)) < N::zero() {
+                    n = -n;
+                }
+
+                planes.push((a, n));
+            }
+        }
+
+        let (best, dist) = remaining
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let dist = planes
+                    .iter()
+                    .map(|(a, n)| n.dot(&(p - a)))
+                    .fold(-N::max_value(), |a, b| a.max(b));
+                (i, dist)
+            })
+            .max_by(|a, b| cmp(&a.1, &b.1))
+            .unwrap();
+
+        if dist <= N::default_epsilon() {
+            break;
+        }
+
+        selected.push(remaining.swap_remove(best));
+    }
+
+    selected
+}
+
+// Tests if a triangle intersects an axis-aligned cube with the separating axis theorem.
+fn triangle_intersects_cube<N: RealField>(
+    tri: [&Point3<N>; 3],
+    center: &Point3<N>,
+    half_size: N,
+) -> bool {
+    let v = [tri[0] - center, tri[1] - center, tri[2] - center];
+    let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+    let mut axes = vec![
+        Vector3::x(),
+        Vector3::y(),
+        Vector3::z(),
+        edges[0].cross(&edges[1]),
+    ];
+
+    for edge in &edges {
+        for k in 0..3 {
+            let mut basis = Vector3::zeros();
+            basis[k] = N::one();
+            axes.push(basis.cross(edge));
+        }
+    }
+
+    for axis in &axes {
+        let p = [axis.dot(&v[0]), axis.dot(&v[1]), axis.dot(&v[2])];
+        let min = p[0].min(p[1]).min(p[2]);
+        let max = p[0].max(p[1]).max(p[2]);
+        let radius = half_size * (axis.x.abs() + axis.y.abs() + axis.z.abs());
+
+        if min > radius || max < -radius {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn to_index<N: RealField>(x: N) -> usize {
+    unsafe { na::convert_unchecked::<N, f64>(x.floor()).max(0.0) as usize }
+}
@@ -0,0 +1,13 @@
+/// A trait implemented by every `Sync` type if the `parallel` feature is enabled, and by every type otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// A trait implemented by every `Sync` type if the `parallel` feature is enabled, and by every type otherwise.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
@@ -9,6 +9,7 @@ pub use self::cleanup::remove_unused_points;
 pub use self::deterministic_state::DeterministicState;
 pub use self::hashable_partial_eq::HashablePartialEq;
 pub use self::isometry_ops::IsometryOps;
+pub use self::maybe_sync::MaybeSync;
 pub use self::median::median;
 pub use self::point_cloud_support_point::{
     point_cloud_support_point, point_cloud_support_point_id,
@@ -34,6 +35,7 @@ mod cleanup;
 mod deterministic_state;
 mod hashable_partial_eq;
 mod isometry_ops;
+mod maybe_sync;
 mod median;
 mod point_cloud_support_point;
 mod point_in_poly2d;